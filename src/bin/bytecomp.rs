@@ -1,8 +1,12 @@
-use lisp_bytecode_vm::{Compiler, bytecode, parser::Parser, optimizer::Optimizer};
+use lisp_bytecode_vm::{Compiler, bytecode, parser::Parser, optimizer::Optimizer, run_with_large_stack};
 use std::env;
 use std::fs;
 
 fn main() {
+    run_with_large_stack(compile_main);
+}
+
+fn compile_main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
@@ -60,13 +64,14 @@ fn main() {
 
     // Parse the source code
     let mut parser = Parser::new_with_file(&source, input_file.clone());
-    let exprs = match parser.parse_all() {
-        Ok(e) => e,
-        Err(msg) => {
-            eprintln!("Parse error: {}", msg);
-            std::process::exit(1);
+    // Report every syntax error in the file, not just the first
+    let (exprs, parse_errors) = parser.parse_all_recovering();
+    if !parse_errors.is_empty() {
+        for error in &parse_errors {
+            eprintln!("Parse error at {}", error);
         }
-    };
+        std::process::exit(1);
+    }
 
     // Compile to bytecode
     let mut compiler = Compiler::new();
@@ -1,16 +1,24 @@
-use lisp_bytecode_vm::{VM, bytecode};
+use lisp_bytecode_vm::{VM, bytecode, run_with_large_stack};
 use std::env;
 
 fn main() {
+    // eval, load and require compile source at runtime
+    run_with_large_stack(vm_main);
+}
+
+fn vm_main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
         eprintln!("Lisp Bytecode VM");
         eprintln!();
-        eprintln!("Usage: {} [--print-result] <bytecode-file>", args[0]);
+        eprintln!("Usage: {} [--print-result] [--verify] [--trace] [--checked-arithmetic] <bytecode-file>", args[0]);
         eprintln!();
         eprintln!("Options:");
         eprintln!("  --print-result    Print the final value on the stack");
+        eprintln!("  --verify          Check the bytecode before running it");
+        eprintln!("  --trace           Log every executed instruction to stderr");
+        eprintln!("  --checked-arithmetic  Make integer overflow an error instead of promoting to a bigint");
         eprintln!();
         eprintln!("Examples:");
         eprintln!("  {} program.bc", args[0]);
@@ -22,6 +30,9 @@ fn main() {
 
     // Parse flags
     let mut print_result = false;
+    let mut verify = false;
+    let mut trace = false;
+    let mut checked_arithmetic = false;
     let mut bytecode_file = "";
     let mut vm_args = Vec::new();
     let mut i = 1;
@@ -30,6 +41,15 @@ fn main() {
         if args[i] == "--print-result" {
             print_result = true;
             i += 1;
+        } else if args[i] == "--verify" {
+            verify = true;
+            i += 1;
+        } else if args[i] == "--trace" {
+            trace = true;
+            i += 1;
+        } else if args[i] == "--checked-arithmetic" {
+            checked_arithmetic = true;
+            i += 1;
         } else if bytecode_file.is_empty() {
             bytecode_file = &args[i];
             i += 1;
@@ -61,6 +81,9 @@ fn main() {
         vm.functions.insert(name, bytecode);
     }
     vm.current_bytecode = main_bytecode;
+    vm.verify_bytecode = verify;
+    vm.trace_bytecode = trace;
+    vm.set_checked_arithmetic(checked_arithmetic);
 
     // Pass command-line arguments to the VM
     vm.args = vm_args;
@@ -82,6 +105,7 @@ fn format_value(value: &lisp_bytecode_vm::Value) -> String {
     use lisp_bytecode_vm::Value;
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             // Always show at least one decimal place for whole numbers
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
@@ -95,16 +119,13 @@ fn format_value(value: &lisp_bytecode_vm::Value) -> String {
         Value::Symbol(s) => s.to_string(),
         Value::List(items) => {
             let formatted: Vec<String> = items.iter().map(|v| format_value(v)).collect();
-            format!("({})", formatted.join(" "))
-        }
-        Value::Function(name) => format!("#<function:{}>", name),
-        Value::Closure(closure_data) => {
-            if closure_data.rest_param.is_some() {
-                format!("#<closure/{} + rest>", closure_data.params.len())
-            } else {
-                format!("#<closure/{}>", closure_data.params.len())
+            match items.dotted_tail() {
+                Some(atom) => format!("({} . {})", formatted.join(" "), format_value(atom)),
+                None => format!("({})", formatted.join(" ")),
             }
         }
+        Value::Function(name) => format!("#<function:{}>", name),
+        Value::Closure(closure_data) => closure_data.describe(),
         Value::HashMap(_) => "#<hashmap>".to_string(),
         Value::Vector(items) => {
             let formatted: Vec<String> = items.iter().map(|v| format_value(v)).collect();
@@ -1,6 +1,20 @@
 use lisp_bytecode_vm::repl::Repl;
+use lisp_bytecode_vm::run_with_large_stack;
+use std::env;
 
 fn main() {
-    let mut repl = Repl::new();
-    repl.run();
+    run_with_large_stack(|| {
+        let mut repl = Repl::new();
+
+        // Files named on the command line are loaded first, like :load;
+        // a .vmc file saved with :save picks up a session without recompiling
+        for path in env::args().skip(1) {
+            match repl.load_file(&path) {
+                Ok(_) => println!("Loaded {}", path),
+                Err(message) => eprintln!("{}", message),
+            }
+        }
+
+        repl.run();
+    });
 }
@@ -1,8 +1,30 @@
+use crate::vm::bigint::BigInt;
 use crate::vm::errors::Location;
+use std::fmt;
+
+/// Deepest expression nesting the parser and compiler accept; deeper input
+/// is reported as an error. Both recurse once per level through
+/// `with_stack_room`, so any thread can reach this depth.
+pub const MAX_NESTING_DEPTH: usize = 1000;
+
+/// Run `f` with room for at least one more level of parsing or compiling.
+/// An unoptimized compile_expr frame alone is tens of kilobytes, so when the
+/// current stack is nearly used up `f` runs on a fresh heap-allocated segment
+/// instead of overflowing a default-size thread.
+pub(crate) fn with_stack_room<R>(f: impl FnOnce() -> R) -> R {
+    const RED_ZONE: usize = 512 * 1024;
+    const SEGMENT_SIZE: usize = 8 * 1024 * 1024;
+    stacker::maybe_grow(RED_ZONE, SEGMENT_SIZE, f)
+}
+
+/// Head of the form an interpolated string literal desugars to. '#' ends a
+/// symbol in the lexer, so no source text can name or rebind it.
+pub const INTERPOLATE: &str = "#interpolate";
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LispExpr {
     Number(i64),
+    BigInt(BigInt), // Integer literal outside i64 range
     Float(f64),
     Boolean(bool),
     Symbol(String),
@@ -30,6 +52,40 @@ impl SourceExpr {
     }
 }
 
+// Source text of an expression, e.g. for assert messages
+impl fmt::Display for LispExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LispExpr::Number(n) => write!(f, "{}", n),
+            LispExpr::BigInt(n) => write!(f, "{}", n),
+            LispExpr::Float(x) if x.fract() == 0.0 && x.is_finite() => write!(f, "{}.0", x),
+            LispExpr::Float(x) => write!(f, "{}", x),
+            LispExpr::Boolean(b) => write!(f, "{}", b),
+            LispExpr::Symbol(s) => match s.strip_prefix("__STRING__") {
+                Some(text) => write!(f, "{:?}", text),
+                None => write!(f, "{}", s),
+            },
+            LispExpr::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item.expr)?;
+                }
+                write!(f, ")")
+            }
+            LispExpr::DottedList(items, rest) => {
+                write!(f, "(")?;
+                for item in items {
+                    write!(f, "{} ", item.expr)?;
+                }
+                write!(f, ". {})", rest.expr)
+            }
+        }
+    }
+}
+
 // Helper functions for creating AST nodes (used in tests)
 #[allow(dead_code)]
 pub fn number(n: i64) -> SourceExpr {
@@ -1,4 +1,4 @@
-// Macro system: defmacro, expand_macro, value_to_expr
+// Macro system: defmacro, expand_macro, expand_all_macros, value_to_expr
 
 use crate::vm::value::Value;
 use crate::vm::instructions::Instruction;
@@ -7,10 +7,14 @@ use crate::vm::vm::VM;
 use crate::vm::stack::Frame;
 use super::Compiler;
 use super::types::MacroDef;
-use super::super::ast::{LispExpr, SourceExpr};
+use super::super::ast::{with_stack_room, LispExpr, SourceExpr, MAX_NESTING_DEPTH};
 
 // ==================== MACRO SYSTEM ====================
 
+// How many expansions macroexpand-all follows along one path before it
+// decides a macro keeps expanding into another macro call forever
+const MAX_EXPANSION_DEPTH: usize = 256;
+
 impl Compiler {
     // Compile defmacro: (defmacro name (params) body)
     pub(super) fn compile_defmacro(&mut self, expr: &SourceExpr) -> Result<(), CompileError> {
@@ -104,6 +108,9 @@ impl Compiler {
         // Create a VM and run the macro
         let mut vm = VM::new();
         vm.current_bytecode = macro_bytecode;
+        // Continue this compiler's gensym numbering so every expansion in
+        // the unit gets fresh names, independent of anything compiled before
+        vm.gensym_counter = self.gensym_counter;
 
         // Create a frame with the quoted arguments
         let mut arg_values = Vec::new();
@@ -125,7 +132,9 @@ impl Compiler {
         vm.call_stack.push(frame);
 
         // Run the VM
-        if let Err(runtime_error) = vm.run() {
+        let outcome = vm.run();
+        self.gensym_counter = vm.gensym_counter;
+        if let Err(runtime_error) = outcome {
             return Err(CompileError::new(
                 format!("Macro expansion failed: {}", runtime_error.message),
                 Location::unknown(),
@@ -142,14 +151,66 @@ impl Compiler {
 
         let result_value = vm.value_stack.pop().unwrap();
 
+        // value_to_expr recurses per level, so check the depth first
+        if nesting_exceeds(&result_value, MAX_NESTING_DEPTH) {
+            return Err(CompileError::new(
+                format!("Macro expansion nests deeper than {} levels", MAX_NESTING_DEPTH),
+                Location::unknown(),
+            ));
+        }
+
         // Convert the result back to a SourceExpr
         self.value_to_expr(&result_value)
     }
 
+    // The macro and arguments if expr is a call to a defined macro
+    pub(super) fn macro_call(&self, expr: &SourceExpr) -> Option<(MacroDef, Vec<SourceExpr>)> {
+        match &expr.expr {
+            LispExpr::List(items) => match items.first().map(|first| &first.expr) {
+                Some(LispExpr::Symbol(name)) => self.macros.get(name).map(|def| (def.clone(), items[1..].to_vec())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // Expand macro calls until none remain: the form itself is expanded until
+    // it is no longer a macro call, then every subform is expanded the same
+    // way. Quoted data is left alone. `depth` counts the expansions that led
+    // here, so a macro that always expands into another macro call fails
+    // instead of looping.
+    pub(super) fn expand_all_macros(&mut self, expr: &SourceExpr, depth: usize) -> Result<SourceExpr, CompileError> {
+        if let Some((macro_def, args)) = self.macro_call(expr) {
+            if depth >= MAX_EXPANSION_DEPTH {
+                return Err(CompileError::new(
+                    format!("macroexpand-all gave up after {} nested expansions; a macro may expand into itself", MAX_EXPANSION_DEPTH),
+                    expr.location.clone(),
+                ));
+            }
+            let expanded = self.expand_macro(&macro_def, &args)?;
+            return self.expand_all_macros(&expanded, depth + 1);
+        }
+
+        match &expr.expr {
+            LispExpr::List(items) if matches!(items.first().map(|first| &first.expr), Some(LispExpr::Symbol(s)) if s == "quote") => {
+                Ok(expr.clone())
+            }
+            LispExpr::List(items) => {
+                let mut expanded = Vec::with_capacity(items.len());
+                for item in items {
+                    expanded.push(self.expand_all_macros(item, depth)?);
+                }
+                Ok(SourceExpr::new(LispExpr::List(expanded), expr.location.clone()))
+            }
+            _ => Ok(expr.clone()),
+        }
+    }
+
     // Convert a Value back to a SourceExpr (inverse of expr_to_value)
     pub(super) fn value_to_expr(&self, value: &Value) -> Result<SourceExpr, CompileError> {
-        match value {
+        with_stack_room(|| match value {
             Value::Integer(n) => Ok(SourceExpr::unknown(LispExpr::Number(*n))),
+            Value::BigInt(n) => Ok(SourceExpr::unknown(LispExpr::BigInt(n.as_ref().clone()))),
             Value::Float(f) => Ok(SourceExpr::unknown(LispExpr::Float(*f))),
             Value::Boolean(b) => Ok(SourceExpr::unknown(LispExpr::Boolean(*b))),
             Value::Symbol(s) => Ok(SourceExpr::unknown(LispExpr::Symbol(s.to_string()))),
@@ -162,7 +223,10 @@ impl Compiler {
                 for item in items.iter() {
                     exprs.push(self.value_to_expr(item)?);
                 }
-                Ok(SourceExpr::unknown(LispExpr::List(exprs)))
+                match items.dotted_tail() {
+                    Some(atom) => Ok(SourceExpr::unknown(LispExpr::DottedList(exprs, Box::new(self.value_to_expr(atom)?)))),
+                    None => Ok(SourceExpr::unknown(LispExpr::List(exprs))),
+                }
             }
             Value::Function(name) => {
                 // Functions become symbols in the macro expansion
@@ -210,6 +274,21 @@ impl Compiler {
                     Location::unknown(),
                 ))
             }
+        })
+    }
+}
+
+// True if lists inside `value` nest more than `limit` levels deep.
+// Iterative, so it is safe on values too deep to walk recursively.
+fn nesting_exceeds(value: &Value, limit: usize) -> bool {
+    let mut pending = vec![(value, 1)];
+    while let Some((value, depth)) = pending.pop() {
+        if let Value::List(items) = value {
+            if depth > limit {
+                return true;
+            }
+            pending.extend(items.iter().map(|item| (item, depth + 1)));
         }
     }
+    false
 }
@@ -4,40 +4,54 @@ mod modules;
 mod utils;
 mod special_forms;
 mod macros;
+mod splat;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::vm::value::{Value, List};
 use crate::vm::instructions::{Instruction, FfiType};
 use crate::vm::ffi::parse_ffi_type;
+use crate::disassembler::Program;
 use crate::vm::errors::{CompileError, Location};
-use super::ast::{LispExpr, SourceExpr};
+use super::ast::{with_stack_room, LispExpr, SourceExpr, INTERPOLATE, MAX_NESTING_DEPTH};
 
 // Re-export types used internally
-pub(self) use types::{ValueLocation, MacroDef, ParsedParams, Pattern, FunctionClause};
+pub(self) use types::{ValueLocation, MacroDef, ParsedParams, KeyParams, Pattern, FunctionClause};
 
 // ==================== COMPILER STRUCT ====================
 
 pub struct Compiler {
     bytecode: Vec<Instruction>,
     pub functions: HashMap<String, Vec<Instruction>>,
+    function_params: HashMap<String, String>, // Function name -> parameter list as written, for Program::disassemble_program
+    function_arities: HashMap<String, usize>, // Function name -> argument slots, for Program::verify
     macros: HashMap<String, MacroDef>, // Macro definitions
+    defined_functions: std::collections::HashSet<String>, // Top-level defun names, which win over inline gcd/lcm
+    key_params: HashMap<String, KeyParams>, // Functions declared with &key, for checking literal keywords at call sites
     global_vars: HashMap<String, bool>, // Track global variables (value is mutable flag)
     known_functions: std::collections::HashSet<String>, // Functions known from runtime context (for eval)
     known_globals: std::collections::HashSet<String>, // Globals known from runtime context (for eval)
+    immutable_globals: std::collections::HashSet<String>, // Known globals that were defined with def (see with_immutable_globals)
     instruction_address: usize,
     param_names: Vec<String>, // Track parameter names for LoadArg
     pattern_bindings: HashMap<String, ValueLocation>, // Track pattern match bindings
     local_bindings: HashMap<String, ValueLocation>, // Track let-bound variables
-    stack_depth: usize, // Track current stack depth for let bindings
+    stack_depth: usize, // Values on the frame's stack at this point; the next let slot
+    assigned_locals: HashSet<usize>, // Let slots of this frame assigned with set!, which closures may not capture
+    captured_locals: HashSet<usize>, // Let slots of this frame captured by a closure, which set! may not assign
     in_tail_position: bool, // Track if current expression is in tail position (for TCO)
     pattern_match_jumps: Vec<usize>, // Temporary storage for pattern match jump indices
+    hoisted_arg_lengths: HashMap<usize, usize>, // Arg index -> stack slot holding its precomputed list length
     // Module system fields
     current_module: Option<String>,                              // Current module being compiled (None = top-level)
     pub module_exports: HashMap<String, std::collections::HashSet<String>>, // Module name -> exported symbols
     imported_symbols: HashMap<String, String>,                   // Alias -> qualified name (e.g., "add" -> "math/add")
     module_functions: std::collections::HashSet<String>,         // Functions declared in current module (for forward references)
+    strict_mode: bool,                                           // Reject calls to unknown functions (see set_strict_mode)
+    called_functions: Vec<(String, Location)>,                   // Named calls to verify at the end of compile_program in strict mode
+    gensym_counter: usize,                                       // Next gensym number during macro expansion (per compiler)
+    depth: usize,                                                // Current compile_expr nesting, bounded by MAX_NESTING_DEPTH
 }
 
 impl Compiler {
@@ -46,25 +60,61 @@ impl Compiler {
         Compiler {
             bytecode: Vec::new(),
             functions: HashMap::new(),
+            function_params: HashMap::new(),
+            function_arities: HashMap::new(),
             macros: HashMap::new(),
+            defined_functions: std::collections::HashSet::new(),
+            key_params: HashMap::new(),
             global_vars: HashMap::new(),
             known_functions: std::collections::HashSet::new(),
             known_globals: std::collections::HashSet::new(),
+            immutable_globals: std::collections::HashSet::new(),
             instruction_address: 0,
             param_names: Vec::new(),
             pattern_bindings: HashMap::new(),
             local_bindings: HashMap::new(),
             stack_depth: 0,
+            assigned_locals: HashSet::new(),
+            captured_locals: HashSet::new(),
             in_tail_position: false,
             pattern_match_jumps: Vec::new(),
+            hoisted_arg_lengths: HashMap::new(),
             // Module system fields
             current_module: None,
             module_exports: HashMap::new(),
             imported_symbols: HashMap::new(),
             module_functions: std::collections::HashSet::new(),
+            strict_mode: false,
+            called_functions: Vec::new(),
+            gensym_counter: 0,
+            depth: 0,
         }
     }
 
+    /// Strict mode trades the friendly defaults for early, explicit errors.
+    /// It changes exactly two things:
+    ///
+    /// - Compiler: calling a function that is neither defined in the program,
+    ///   known from the runtime context, nor a builtin is a compile error from
+    ///   `compile_program` instead of an "Undefined function" error at runtime.
+    /// - VM (`VM::strict_mode`, set separately): `+ - * / %` on one integer and
+    ///   one float is a type error instead of promoting to float; convert
+    ///   explicitly with `int->float` or `float->int`.
+    ///
+    /// Conditionals (`if`, `cond`, `when`, `unless`, `and`, `or`) require
+    /// booleans in both modes, and comparisons such as `<` and `==` still
+    /// compare integers with floats.
+    pub fn set_strict_mode(&mut self, strict: bool) {
+        self.strict_mode = strict;
+    }
+
+    /// Restart macro-time gensym numbering at G__0. Each compiler already
+    /// starts there, so expansion output only depends on the source being
+    /// compiled; call this to get the same guarantee when reusing a compiler.
+    pub fn reset_gensym_counter(&mut self) {
+        self.gensym_counter = 0;
+    }
+
     // Inject known function names from runtime context (for eval)
     // This allows eval'd code to reference functions defined in the parent context
     pub fn with_known_functions<'a, I>(&mut self, function_names: I)
@@ -87,6 +137,22 @@ impl Compiler {
         }
     }
 
+    // Mark known globals that were defined with def rather than def-mutable,
+    // so set! rejects them as it does globals defined in this compilation
+    pub fn with_immutable_globals<'a, I>(&mut self, global_names: I)
+    where
+        I: Iterator<Item = &'a String>,
+    {
+        for name in global_names {
+            self.immutable_globals.insert(name.clone());
+        }
+    }
+
+    // Globals defined by this compiler, with their mutable flag
+    pub fn defined_globals(&self) -> impl Iterator<Item = (&String, bool)> {
+        self.global_vars.iter().map(|(name, mutable)| (name, *mutable))
+    }
+
     // Clear main bytecode (used after loading stdlib to avoid accumulating bytecode)
     pub fn clear_main_bytecode(&mut self) {
         self.bytecode.clear();
@@ -98,10 +164,39 @@ impl Compiler {
         self.instruction_address += 1;
     }
 
+    // Emit an instruction that leaves `consumed` fewer values on the stack.
+    // Arms that compile more subexpressions afterwards use it to keep
+    // stack_depth in step with the runtime stack.
+    fn emit_consuming(&mut self, instruction: Instruction, consumed: usize) {
+        self.emit(instruction);
+        self.stack_depth -= consumed;
+    }
+
     // ==================== EXPRESSION COMPILATION ====================
 
     // Returns the starting address of compiled bytecode
     fn compile_expr(&mut self, expr: &SourceExpr) -> Result<usize, CompileError> {
+        // Source is already limited by the parser, but macro expansions can
+        // keep nesting; stop before the native stack runs out
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(CompileError::with_suggestion(
+                format!("Expression nesting exceeds {} levels", MAX_NESTING_DEPTH),
+                expr.location.clone(),
+                "Check for a macro that expands into itself without a base case".to_string(),
+            ));
+        }
+        self.depth += 1;
+        let stack_depth = self.stack_depth;
+        let result = with_stack_room(|| self.compile_nested_expr(expr));
+        self.depth -= 1;
+        // Every expression leaves exactly one value on the stack. Tracking
+        // that keeps let slots right when a let sits among pending operands,
+        // as in (+ 1 (let ((x 2)) x)).
+        self.stack_depth = stack_depth + 1;
+        result
+    }
+
+    fn compile_nested_expr(&mut self, expr: &SourceExpr) -> Result<usize, CompileError> {
         let start_address = self.instruction_address;
 
         match &expr.expr {
@@ -109,6 +204,9 @@ impl Compiler {
             LispExpr::Number(n) => {
                 self.emit(Instruction::Push(Value::Integer(*n)));
             }
+            LispExpr::BigInt(n) => {
+                self.emit(Instruction::Push(Value::BigInt(Arc::new(n.clone()))));
+            }
             LispExpr::Float(f) => {
                 self.emit(Instruction::Push(Value::Float(*f)));
             }
@@ -131,6 +229,12 @@ impl Compiler {
                 if s.starts_with("__STRING__") {
                     let string_content = s["__STRING__".len()..].to_string();
                     self.emit(Instruction::Push(Value::String(Arc::new(string_content))));
+                } else if s == "nil" {
+                    // nil is the empty list literal, the same value as '()
+                    self.emit(Instruction::Push(Value::List(List::Nil)));
+                } else if s.len() > 1 && s.starts_with(':') {
+                    // Keywords like :width evaluate to themselves, e.g. for &key calls
+                    self.emit(Instruction::Push(Value::Symbol(Arc::new(s.clone()))));
                 } else {
                     // Check local bindings first (let bindings)
                     if let Some(location) = self.local_bindings.get(s) {
@@ -190,6 +294,27 @@ impl Compiler {
                     ));
                 }
 
+                // An interpolated string literal; nothing in scope can shadow it
+                if matches!(&items[0].expr, LispExpr::Symbol(s) if s == INTERPOLATE) {
+                    self.compile_interpolation(items)?;
+                    return Ok(start_address);
+                }
+
+                // (f a ...rest): splice lists into the arguments
+                if self.is_splat_call(items) {
+                    self.compile_splat_call(expr, items)?;
+                    return Ok(start_address);
+                }
+
+                // A local variable shadows special forms and builtins of the same name,
+                // e.g. (let loop ((i 0)) ... (loop (+ i 1)))
+                if let LispExpr::Symbol(operator) = &items[0].expr {
+                    if self.is_local_variable(operator) {
+                        self.compile_local_call(operator, items)?;
+                        return Ok(start_address);
+                    }
+                }
+
                 // Check if operator is a symbol
                 if let LispExpr::Symbol(operator) = &items[0].expr {
                     // Operator is a symbol - might be special form, built-in, or function call
@@ -214,7 +339,7 @@ impl Compiler {
                         // This transforms (+ 1 2 3 4) into (+ 1 (+ 2 (+ 3 4)))
                         for i in 2..items.len() {
                             self.compile_expr(&items[i])?;
-                            self.emit(Instruction::Add);
+                            self.emit_consuming(Instruction::Add, 1);
                         }
 
                         // Restore tail position
@@ -239,7 +364,7 @@ impl Compiler {
                         // This does left-associative subtraction: (- 10 2 3) = (- (- 10 2) 3) = 5
                         for i in 2..items.len() {
                             self.compile_expr(&items[i])?;
-                            self.emit(Instruction::Sub);
+                            self.emit_consuming(Instruction::Sub, 1);
                         }
 
                         self.in_tail_position = saved_tail;
@@ -262,7 +387,7 @@ impl Compiler {
                         // This transforms (* 2 3 4) into (* 2 (* 3 4)) = (* 2 12) = 24
                         for i in 2..items.len() {
                             self.compile_expr(&items[i])?;
-                            self.emit(Instruction::Mul);
+                            self.emit_consuming(Instruction::Mul, 1);
                         }
 
                         self.in_tail_position = saved_tail;
@@ -285,7 +410,7 @@ impl Compiler {
                         // This transforms (/ 20 2 2) into (/ (/ 20 2) 2) = (/ 10 2) = 5
                         for i in 2..items.len() {
                             self.compile_expr(&items[i])?;
-                            self.emit(Instruction::Div);
+                            self.emit_consuming(Instruction::Div, 1);
                         }
 
                         self.in_tail_position = saved_tail;
@@ -308,7 +433,7 @@ impl Compiler {
                         // This transforms (% 10 3 2) into (% (% 10 3) 2) = (% 1 2) = 1
                         for i in 2..items.len() {
                             self.compile_expr(&items[i])?;
-                            self.emit(Instruction::Mod);
+                            self.emit_consuming(Instruction::Mod, 1);
                         }
 
                         self.in_tail_position = saved_tail;
@@ -326,6 +451,87 @@ impl Compiler {
                         self.emit(Instruction::Neg);
                         self.in_tail_position = saved_tail;
                     }
+                    "quotient" | "remainder" | "modulo" | "expt" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 2 arguments", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let instruction = match operator.as_str() {
+                            "quotient" => Instruction::Quotient,
+                            "remainder" => Instruction::Mod,
+                            "expt" => Instruction::IExpt,
+                            _ => Instruction::Modulo,
+                        };
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.compile_expr(&items[2])?;
+                        self.emit(instruction);
+                        self.in_tail_position = saved_tail;
+                    }
+                    // Bitwise operators fold left like +: (bit-or a b c) = (bit-or (bit-or a b) c)
+                    // gcd and lcm fold the same way: (gcd a b c) = (gcd (gcd a b) c)
+                    // A program's own gcd or lcm (e.g. a textbook Euclid defun) is called as written
+                    "bit-and" | "bit-or" | "bit-xor" | "<<" | ">>" | "gcd" | "lcm"
+                        if !self.defined_functions.contains(operator) => {
+                        if items.len() < 3 {
+                            return Err(CompileError::new(
+                                format!("{} expects at least 2 arguments", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let instruction = match operator.as_str() {
+                            "bit-and" => Instruction::BitAnd,
+                            "bit-or" => Instruction::BitOr,
+                            "bit-xor" => Instruction::BitXor,
+                            "<<" => Instruction::Shl,
+                            "gcd" => Instruction::Gcd,
+                            "lcm" => Instruction::Lcm,
+                            _ => Instruction::Shr,
+                        };
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        for arg in &items[2..] {
+                            self.compile_expr(arg)?;
+                            self.emit_consuming(instruction.clone(), 1);
+                        }
+                        self.in_tail_position = saved_tail;
+                    }
+                    "bit-not" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                "bit-not expects exactly 1 argument".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::BitNot);
+                        self.in_tail_position = saved_tail;
+                    }
+                    // (inc x) => (+ x 1), (dec x) => (- x 1)
+                    "inc" | "dec" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 1 argument", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::Push(Value::Integer(1)));
+                        if operator == "inc" {
+                            self.emit(Instruction::Add);
+                        } else {
+                            self.emit(Instruction::Sub);
+                        }
+                        self.in_tail_position = saved_tail;
+                    }
 
                     // Comparison operators
                     "<=" => {
@@ -349,9 +555,12 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
                         self.emit(Instruction::Lt);
+                        self.in_tail_position = saved_tail;
                     }
                     ">" => {
                         if items.len() != 3 {
@@ -360,9 +569,12 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
                         self.emit(Instruction::Gt);
+                        self.in_tail_position = saved_tail;
                     }
                     ">=" => {
                         if items.len() != 3 {
@@ -371,9 +583,12 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
                         self.emit(Instruction::Gte);
+                        self.in_tail_position = saved_tail;
                     }
                     "==" => {
                         if items.len() != 3 {
@@ -382,9 +597,12 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
                         self.emit(Instruction::Eq);
+                        self.in_tail_position = saved_tail;
                     }
                     "!=" => {
                         if items.len() != 3 {
@@ -393,9 +611,26 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
                         self.emit(Instruction::Neq);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "equal?" | "eq?" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 2 arguments", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.compile_expr(&items[2])?;
+                        self.emit(if operator == "eq?" { Instruction::EqIdentity } else { Instruction::Equal });
+                        self.in_tail_position = saved_tail;
                     }
 
                     // Conditional: (if condition then-branch else-branch)
@@ -416,7 +651,8 @@ impl Compiler {
 
                         // Emit JmpIfFalse with placeholder address
                         let jmp_if_false_index = self.bytecode.len();
-                        self.emit(Instruction::JmpIfFalse(0)); // placeholder
+                        self.emit_consuming(Instruction::JmpIfFalse(0), 1); // placeholder
+                        let branch_depth = self.stack_depth;
 
                         // Compile then-branch (inherits tail position from if)
                         self.in_tail_position = saved_tail;
@@ -434,6 +670,7 @@ impl Compiler {
 
                         // Compile else-branch (inherits tail position from if)
                         self.in_tail_position = saved_tail;
+                        self.stack_depth = branch_depth;
                         self.compile_expr(&items[3])?;
 
                         // Record end address
@@ -459,9 +696,8 @@ impl Compiler {
                             // Single expression: just compile it
                             self.compile_expr(&items[1])?;
                         } else {
-                            // Multiple expressions: compile as nested if expressions
-                            // (and a b c) => (if a (if b c false) false)
-                            self.compile_and_helper(&items[1..], expr)?;
+                            // Multiple expressions: stop at the first false one
+                            self.compile_and_helper(&items[1..])?;
                         }
                     }
 
@@ -478,9 +714,8 @@ impl Compiler {
                             // Single expression: just compile it
                             self.compile_expr(&items[1])?;
                         } else {
-                            // Multiple expressions: compile as nested if expressions
-                            // (or a b c) => (if a true (if b true c))
-                            self.compile_or_helper(&items[1..], expr)?;
+                            // Multiple expressions: stop at the first true one
+                            self.compile_or_helper(&items[1..])?;
                         }
                     }
 
@@ -496,6 +731,17 @@ impl Compiler {
                         self.compile_cond(&items[1..], expr)?;
                     }
 
+                    "case" => {
+                        if items.len() < 3 {
+                            return Err(CompileError::new(
+                                "case expects a key expression and at least 1 clause".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+
+                        self.compile_case(&items[1], &items[2..])?;
+                    }
+
                     // When: (when test expr) - syntactic sugar for (if test expr false)
                     "when" => {
                         if items.len() != 3 {
@@ -513,7 +759,7 @@ impl Compiler {
 
                         // Emit JmpIfFalse with placeholder
                         let jmp_if_false_index = self.bytecode.len();
-                        self.emit(Instruction::JmpIfFalse(0));
+                        self.emit_consuming(Instruction::JmpIfFalse(0), 1);
 
                         // Compile then-branch (inherits tail position)
                         self.in_tail_position = saved_tail;
@@ -535,6 +781,90 @@ impl Compiler {
                         self.in_tail_position = saved_tail;
                     }
 
+                    // Assert: (assert expr) or (assert expr "message") - expr's value, or an
+                    // error at this location when it is false
+                    "assert" => {
+                        let message = match &items[..] {
+                            [_, condition] => format!("Assertion failed: {}", condition.expr),
+                            [_, _, message] => match &message.expr {
+                                LispExpr::Symbol(s) if s.starts_with("__STRING__") => {
+                                    format!("Assertion failed: {}", &s["__STRING__".len()..])
+                                }
+                                _ => {
+                                    return Err(CompileError::new(
+                                        "assert message must be a string literal".to_string(),
+                                        message.location.clone(),
+                                    ));
+                                }
+                            },
+                            _ => {
+                                return Err(CompileError::new(
+                                    "assert expects an expression and an optional message".to_string(),
+                                    expr.location.clone(),
+                                ));
+                            }
+                        };
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::Assert(message, expr.location.clone()));
+                        self.in_tail_position = saved_tail;
+                    }
+
+                    // If-let: (if-let (var expr) then else) - binds var only in the then-branch
+                    "if-let" => {
+                        if items.len() != 4 {
+                            return Err(CompileError::new(
+                                "if-let expects exactly 3 arguments ((var expr), then, else)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+
+                        self.compile_if_let(&items[1], &items[2], Some(&items[3]))?;
+                    }
+
+                    // Set!: (set! var value) - assign a let-bound local or a def-mutable global
+                    "set!" => {
+                        self.compile_set(items, expr)?;
+                    }
+
+                    // Incf/decf: (incf var [delta]) - (set! var (+ var delta)), delta defaulting to 1
+                    "incf" | "decf" => {
+                        self.compile_incf(items, expr)?;
+                    }
+
+                    // Dotimes/dolist: (dotimes (i n) body ...) / (dolist (x lst) body ...)
+                    // Run the body for i from 0 to n-1, or for each element x; result is false
+                    "dotimes" | "dolist" => {
+                        self.compile_do_iteration(items, expr)?;
+                    }
+
+                    // Try: (try expr (catch var handler ...))
+                    // Runs expr; if it raises a runtime error, var is bound to the
+                    // error message and the handler expressions run instead
+                    "try" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                "try expects an expression and a (catch var handler ...) clause".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+
+                        self.compile_try(&items[1], &items[2])?;
+                    }
+
+                    // When-let: (when-let (var expr) body) - if-let with false as the else-branch
+                    "when-let" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                "when-let expects exactly 2 arguments ((var expr), body)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+
+                        self.compile_if_let(&items[1], &items[2], None)?;
+                    }
+
                     // Unless: (unless test expr) - syntactic sugar for (if test false expr)
                     "unless" => {
                         if items.len() != 3 {
@@ -592,7 +922,7 @@ impl Compiler {
                             self.in_tail_position = false;
                             self.compile_expr(expr)?;
                             // Pop the result since we don't need it (side effects only)
-                            self.emit(Instruction::PopN(1));
+                            self.emit_consuming(Instruction::PopN(1), 1);
                         }
 
                         // Compile the last expression (inherits tail position)
@@ -602,16 +932,25 @@ impl Compiler {
                         self.in_tail_position = saved_tail;
                     }
 
-                    // Print: (print expr)
-                    "print" => {
-                        if items.len() != 2 {
-                            return Err(CompileError::new(
-                                "print expects exactly 1 argument".to_string(),
-                                expr.location.clone(),
-                            ));
+                    // Print: (print expr ...) / (display expr ...)
+                    // Writes the values separated by single spaces, then a newline, and
+                    // returns the last one ('() with no arguments). print writes strings
+                    // quoted, display writes them as plain text.
+                    "print" | "display" => {
+                        // The arguments are never in tail position: the write comes after them
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        if operator == "print" && items.len() == 2 {
+                            self.compile_expr(&items[1])?;
+                            self.emit(Instruction::Print);
+                        } else {
+                            for arg in &items[1..] {
+                                self.compile_expr(arg)?;
+                            }
+                            self.emit(Instruction::MakeList(items.len() - 1));
+                            self.emit(if operator == "print" { Instruction::PrintList } else { Instruction::DisplayList });
                         }
-                        self.compile_expr(&items[1])?;
-                        self.emit(Instruction::Print);
+                        self.in_tail_position = saved_tail;
                     }
 
                     // Quote: (quote expr) - return expr unevaluated as a list
@@ -628,76 +967,50 @@ impl Compiler {
                     }
 
                     // Macroexpand: (macroexpand '(macro-call ...)) - expand macro once
-                    "macroexpand" => {
+                    // Macroexpand: (macroexpand 'form) expands the outermost macro call once;
+                    // (macroexpand-all 'form) expands every macro call in the form
+                    "macroexpand" | "macroexpand-all" => {
                         if items.len() != 2 {
                             return Err(CompileError::new(
-                                "macroexpand expects exactly 1 argument".to_string(),
+                                format!("{} expects exactly 1 argument", operator),
                                 expr.location.clone(),
                             ));
                         }
 
                         // Extract the form to expand (handling quoted forms)
-                        let form_to_expand = &items[1];
-                        let actual_form = if let LispExpr::List(quoted_items) = &form_to_expand.expr {
-                            // Check if it's a quoted form: (quote expr)
-                            if quoted_items.len() == 2 {
-                                if let LispExpr::Symbol(s) = &quoted_items[0].expr {
-                                    if s == "quote" {
-                                        // Extract the quoted expression
-                                        &quoted_items[1]
-                                    } else {
-                                        form_to_expand
-                                    }
-                                } else {
-                                    form_to_expand
-                                }
-                            } else {
-                                form_to_expand
-                            }
-                        } else {
-                            form_to_expand
+                        let actual_form = match &items[1].expr {
+                            LispExpr::List(quoted_items) if quoted_items.len() == 2
+                                && matches!(&quoted_items[0].expr, LispExpr::Symbol(s) if s == "quote") => &quoted_items[1],
+                            _ => &items[1],
                         };
 
-                        // Now check if actual_form is a macro call
-                        if let LispExpr::List(form_items) = &actual_form.expr {
-                            if let Some(first) = form_items.first() {
-                                if let LispExpr::Symbol(name) = &first.expr {
-                                    if let Some(macro_def) = self.macros.get(name).cloned() {
-                                        // It's a macro - expand it
-                                        let args = &form_items[1..];
-                                        let expanded = self.expand_macro(&macro_def, args)?;
-                                        // Return the expanded form as a value
-                                        let value = self.expr_to_value(&expanded)?;
-                                        self.emit(Instruction::Push(value));
-                                    } else {
-                                        // Not a macro - return the original form
-                                        let value = self.expr_to_value(actual_form)?;
-                                        self.emit(Instruction::Push(value));
-                                    }
-                                } else {
-                                    // First element is not a symbol - return as is
-                                    let value = self.expr_to_value(actual_form)?;
-                                    self.emit(Instruction::Push(value));
-                                }
-                            } else {
-                                // Empty list - return as is
-                                let value = self.expr_to_value(actual_form)?;
-                                self.emit(Instruction::Push(value));
-                            }
+                        let expanded = if operator == "macroexpand-all" {
+                            self.expand_all_macros(actual_form, 0)?
                         } else {
-                            // Not a list - return as is
-                            let value = self.expr_to_value(actual_form)?;
-                            self.emit(Instruction::Push(value));
-                        }
+                            match self.macro_call(actual_form) {
+                                // It's a macro - expand it once
+                                Some((macro_def, args)) => self.expand_macro(&macro_def, &args)?,
+                                // Not a macro call - return the original form
+                                None => actual_form.clone(),
+                            }
+                        };
+
+                        // Return the expanded form as a value
+                        let value = self.expr_to_value(&expanded)?;
+                        self.emit(Instruction::Push(value));
                     }
 
                     "list" => {
                         // list is variadic - compile all arguments and use MakeList
                         let arg_count = items.len() - 1; // Exclude 'list' itself
+                        // Elements are not in tail position
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         for arg in &items[1..] {
                             self.compile_expr(arg)?;
                         }
                         self.emit(Instruction::MakeList(arg_count));
+                        self.in_tail_position = saved_tail;
                     }
 
                     "hash-map" => {
@@ -709,20 +1022,27 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
-                        // Compile all key-value pairs
+                        // Compile all key-value pairs (not in tail position)
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         for arg in &items[1..] {
                             self.compile_expr(arg)?;
                         }
                         self.emit(Instruction::MakeHashMap(arg_count / 2));
+                        self.in_tail_position = saved_tail;
                     }
 
                     "vector" => {
                         // vector is variadic - compile all arguments and use MakeVector
                         let arg_count = items.len() - 1; // Exclude 'vector' itself
+                        // Elements are not in tail position
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         for arg in &items[1..] {
                             self.compile_expr(arg)?;
                         }
                         self.emit(Instruction::MakeVector(arg_count));
+                        self.in_tail_position = saved_tail;
                     }
 
                     // FFI call: (ffi-call func-ptr (arg-types...) return-type arg1 arg2 ...)
@@ -753,6 +1073,10 @@ impl Compiler {
                             ));
                         }
 
+                        // Function pointer and arguments are not in tail position
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+
                         // Compile function pointer expression
                         self.compile_expr(&items[1])?;
 
@@ -763,6 +1087,7 @@ impl Compiler {
 
                         // Emit FFI call instruction with type info
                         self.emit(Instruction::FfiCall(arg_types, return_type));
+                        self.in_tail_position = saved_tail;
                     }
 
                     // Quasiquote: (quasiquote expr) - like quote but allows unquote and unquote-splicing
@@ -773,19 +1098,49 @@ impl Compiler {
                                 expr.location.clone(),
                             ));
                         }
+                        // Unquoted expressions feed the template, so none is in tail position
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
                         self.compile_quasiquote(&items[1])?;
+                        self.in_tail_position = saved_tail;
                     }
 
-                    // Let: (let ((var val) ...) body)
+                    // Let: (let ((var val) ...) body...) - several body forms run as a do
                     "let" => {
-                        if items.len() != 3 {
+                        if items.len() < 3 {
+                            return Err(CompileError::new(
+                                "let expects bindings and at least one body expression".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+
+                        if let LispExpr::Symbol(name) = &items[1].expr {
+                            self.compile_named_let(name, &items[2..], expr)?;
+                        } else if items.len() == 3 {
+                            self.compile_let(&items[1], &items[2])?;
+                        } else {
+                            let mut body = vec![SourceExpr::new(LispExpr::Symbol("do".to_string()), expr.location.clone())];
+                            body.extend_from_slice(&items[2..]);
+                            self.compile_let(&items[1], &SourceExpr::new(LispExpr::List(body), expr.location.clone()))?;
+                        }
+                    }
+
+                    // Letrec: bindings are lambdas that can call each other
+                    "letrec" => {
+                        if items.len() < 3 {
                             return Err(CompileError::new(
-                                "let expects exactly 2 arguments: bindings and body".to_string(),
+                                "letrec expects bindings and at least one body expression".to_string(),
                                 expr.location.clone(),
                             ));
                         }
 
-                        self.compile_let(&items[1], &items[2])?;
+                        if items.len() == 3 {
+                            self.compile_letrec(&items[1], &items[2])?;
+                        } else {
+                            let mut body = vec![SourceExpr::new(LispExpr::Symbol("do".to_string()), expr.location.clone())];
+                            body.extend_from_slice(&items[2..]);
+                            self.compile_letrec(&items[1], &SourceExpr::new(LispExpr::List(body), expr.location.clone()))?;
+                        }
                     }
 
                     // Loop: (loop [bindings] body)
@@ -823,6 +1178,12 @@ impl Compiler {
                         self.compile_lambda(&items[1], &items[2])?;
                     }
 
+                    // Match-lambda: (match-lambda (pattern body) ...) - one-argument closure
+                    // that dispatches on its argument like a multi-clause defun
+                    "match-lambda" => {
+                        self.compile_match_lambda(&items[1..], &expr.location)?;
+                    }
+
                     // List operations
                     "cons" => {
                         if items.len() != 3 {
@@ -838,6 +1199,30 @@ impl Compiler {
                         self.emit(Instruction::Cons);
                         self.in_tail_position = saved_tail;
                     }
+                    "cons*" | "list*" => {
+                        // (cons* 1 2 '(3 4)) -> '(1 2 3 4): cons the leading items onto the last, right to left
+                        if items.len() < 2 {
+                            return Err(CompileError::new(
+                                format!("{} expects at least 1 argument", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        for arg in &items[1..] {
+                            self.compile_expr(arg)?;
+                        }
+                        if items.len() == 2 {
+                            // A lone argument is the tail itself; a non-list still becomes a one-element list like cons
+                            self.emit(Instruction::MakeList(1));
+                            self.emit(Instruction::ListStar);
+                        } else {
+                            for _ in 2..items.len() {
+                                self.emit(Instruction::Cons);
+                            }
+                        }
+                        self.in_tail_position = saved_tail;
+                    }
                     "car" => {
                         if items.len() != 2 {
                             return Err(CompileError::new(
@@ -877,6 +1262,20 @@ impl Compiler {
                         self.emit(Instruction::IsList);
                         self.in_tail_position = saved_tail;
                     }
+                    // nil? is null?: true only for the empty list, never for false
+                    "null?" | "nil?" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 1 argument", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::IsNull);
+                        self.in_tail_position = saved_tail;
+                    }
 
                     // String/Symbol operations
                     "string?" => {
@@ -944,6 +1343,20 @@ impl Compiler {
                         self.emit(Instruction::StringLength);
                         self.in_tail_position = saved_tail;
                     }
+                    "string-ref" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                "string-ref expects exactly 2 arguments (string, index)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?; // string
+                        self.compile_expr(&items[2])?; // index
+                        self.emit(Instruction::StringRef);
+                        self.in_tail_position = saved_tail;
+                    }
                     "substring" => {
                         if items.len() != 4 {
                             return Err(CompileError::new(
@@ -959,18 +1372,44 @@ impl Compiler {
                         self.emit(Instruction::Substring);
                         self.in_tail_position = saved_tail;
                     }
-                    "string-append" => {
+                    "string-contains?" | "string-index-of" | "string-prefix?" | "string-suffix?" => {
                         if items.len() != 3 {
                             return Err(CompileError::new(
-                                "string-append expects exactly 2 arguments".to_string(),
+                                format!("{} expects exactly 2 arguments (string, substring)", operator),
                                 expr.location.clone(),
                             ));
                         }
+                        let instruction = match operator.as_str() {
+                            "string-contains?" => Instruction::StringContains,
+                            "string-index-of" => Instruction::StringIndexOf,
+                            "string-prefix?" => Instruction::StringStartsWith,
+                            _ => Instruction::StringEndsWith,
+                        };
                         let saved_tail = self.in_tail_position;
                         self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
                         self.compile_expr(&items[2])?;
-                        self.emit(Instruction::StringAppend);
+                        self.emit(instruction);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "string-append" => {
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        if items.len() < 3 {
+                            // (string-append) is "" and (string-append s) is s, still type-checked
+                            for item in &items[1..] {
+                                self.compile_expr(item)?;
+                            }
+                            self.emit(Instruction::MakeList(items.len() - 1));
+                            self.emit(Instruction::StringAppendList);
+                        } else {
+                            // Left fold: (string-append a b c) => (string-append (string-append a b) c)
+                            self.compile_expr(&items[1])?;
+                            for item in &items[2..] {
+                                self.compile_expr(item)?;
+                                self.emit_consuming(Instruction::StringAppend, 1);
+                            }
+                        }
                         self.in_tail_position = saved_tail;
                     }
                     "string->list" => {
@@ -1079,6 +1518,19 @@ impl Compiler {
                         self.emit(Instruction::CharCode);
                         self.in_tail_position = saved_tail;
                     }
+                    "integer->char" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                "integer->char expects exactly 1 argument (a codepoint)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::IntToChar);
+                        self.in_tail_position = saved_tail;
+                    }
 
                     // List operations
                     "list-ref" => {
@@ -1095,48 +1547,248 @@ impl Compiler {
                         self.emit(Instruction::ListRef);
                         self.in_tail_position = saved_tail;
                     }
-                    "list-length" => {
+                    "reverse" => {
                         if items.len() != 2 {
                             return Err(CompileError::new(
-                                "list-length expects exactly 1 argument (list)".to_string(),
+                                "reverse expects exactly 1 argument".to_string(),
                                 expr.location.clone(),
                             ));
                         }
                         let saved_tail = self.in_tail_position;
                         self.in_tail_position = false;
                         self.compile_expr(&items[1])?;
-                        self.emit(Instruction::ListLength);
+                        self.emit(Instruction::Reverse);
                         self.in_tail_position = saved_tail;
                     }
-                    "append" => {
+                    // Raise: (raise value) - signal an error that try/catch binds to value
+                    "raise" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                "raise expects exactly 1 argument".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::Raise);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "take" | "drop" => {
+                        if items.len() != 3 {
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 2 arguments (count, list)", operator),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?; // count
+                        self.compile_expr(&items[2])?; // list
+                        self.emit(if operator == "take" { Instruction::Take } else { Instruction::Drop });
+                        self.in_tail_position = saved_tail;
+                    }
+                    "member?" | "assoc" => {
                         if items.len() != 3 {
                             return Err(CompileError::new(
-                                "append expects exactly 2 arguments (list1, list2)".to_string(),
+                                format!("{} expects exactly 2 arguments (item, list)", operator),
                                 expr.location.clone(),
                             ));
                         }
                         let saved_tail = self.in_tail_position;
                         self.in_tail_position = false;
-                        self.compile_expr(&items[1])?; // first list
-                        self.compile_expr(&items[2])?; // second list
-                        self.emit(Instruction::Append);
+                        self.compile_expr(&items[1])?; // item or key
+                        self.compile_expr(&items[2])?; // list
+                        self.emit(if operator == "assoc" { Instruction::Assoc } else { Instruction::Member });
+                        self.in_tail_position = saved_tail;
+                    }
+                    "assoc-set" | "assoc-update" => {
+                        if items.len() != 4 {
+                            let second = if operator == "assoc-set" { "value" } else { "function" };
+                            return Err(CompileError::new(
+                                format!("{} expects exactly 3 arguments (key, {}, alist)", operator, second),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?; // key
+                        self.compile_expr(&items[2])?; // value or function
+                        self.compile_expr(&items[3])?; // alist
+                        self.emit(if operator == "assoc-set" { Instruction::AssocSet } else { Instruction::AssocUpdate });
+                        self.in_tail_position = saved_tail;
+                    }
+                    "list-length" => {
+                        if items.len() != 2 {
+                            return Err(CompileError::new(
+                                "list-length expects exactly 1 argument (list)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        self.emit(Instruction::ListLength);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "append" => {
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        if items.len() < 3 {
+                            // (append) is '() and (append l) is l, still type-checked
+                            for item in &items[1..] {
+                                self.compile_expr(item)?;
+                            }
+                            self.emit(Instruction::MakeList(items.len() - 1));
+                            self.emit(Instruction::AppendList);
+                        } else {
+                            // Left fold, like string-append
+                            self.compile_expr(&items[1])?; // first list
+                            for item in &items[2..] {
+                                self.compile_expr(item)?;
+                                self.emit_consuming(Instruction::Append, 1);
+                            }
+                        }
                         self.in_tail_position = saved_tail;
                     }
 
                     // Number operations
                     "number->string" => {
-                        if items.len() != 2 {
+                        // (number->string n) or (number->string n precision); the precision reaches the VM as a list
+                        if items.len() != 2 && items.len() != 3 {
                             return Err(CompileError::new(
-                                "number->string expects exactly 1 argument (integer)".to_string(),
+                                "number->string expects a number and an optional precision".to_string(),
                                 expr.location.clone(),
                             ));
                         }
                         let saved_tail = self.in_tail_position;
                         self.in_tail_position = false;
-                        self.compile_expr(&items[1])?;
+                        for arg in &items[1..] {
+                            self.compile_expr(arg)?;
+                        }
+                        self.emit(Instruction::MakeList(items.len() - 2));
                         self.emit(Instruction::NumberToString);
                         self.in_tail_position = saved_tail;
                     }
+                    "string->number" => {
+                        // (string->number s) or (string->number s radix); the radix reaches the VM as a list
+                        if items.len() != 2 && items.len() != 3 {
+                            return Err(CompileError::new(
+                                "string->number expects a string and an optional radix".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        for arg in &items[1..] {
+                            self.compile_expr(arg)?;
+                        }
+                        self.emit(Instruction::MakeList(items.len() - 2));
+                        self.emit(Instruction::StringToNumber);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "format-int" => {
+                        // (format-int n :grouping "," :width 10 :pad "0")
+                        if items.len() < 2 || !items.len().is_multiple_of(2) {
+                            return Err(CompileError::new(
+                                "format-int expects an integer followed by :option value pairs".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        // Options go to the VM as a plist; the keywords are pushed as symbols
+                        for pair in items[2..].chunks(2) {
+                            match &pair[0].expr {
+                                LispExpr::Symbol(s) if s.starts_with(':') => {
+                                    self.emit(Instruction::Push(Value::Symbol(Arc::new(s.clone()))));
+                                    self.stack_depth += 1;
+                                }
+                                _ => {
+                                    return Err(CompileError::new(
+                                        "format-int options must be keywords like :grouping, :width or :pad".to_string(),
+                                        pair[0].location.clone(),
+                                    ));
+                                }
+                            }
+                            self.compile_expr(&pair[1])?;
+                        }
+                        self.emit(Instruction::MakeList(items.len() - 2));
+                        self.emit(Instruction::FormatInt);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "range" => {
+                        // (range end), (range start end) or (range start end step); start defaults to 0, step to 1
+                        if items.len() < 2 || items.len() > 4 {
+                            return Err(CompileError::new(
+                                "range expects 1 to 3 arguments: (range end), (range start end) or (range start end step)".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        if items.len() == 2 {
+                            self.emit(Instruction::Push(Value::Integer(0)));
+                            self.stack_depth += 1;
+                        }
+                        for arg in &items[1..] {
+                            self.compile_expr(arg)?;
+                        }
+                        if items.len() < 4 {
+                            self.emit(Instruction::Push(Value::Integer(1)));
+                        }
+                        self.emit(Instruction::Range);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "apply" if items.len() > 3 => {
+                        // (apply f a b rest): the fixed args are prepended to the list at runtime.
+                        // The two-argument form stays a call to the apply builtin
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        for item in &items[1..] {
+                            self.compile_expr(item)?;
+                        }
+                        self.emit(Instruction::ApplyN(items.len() - 2));
+                        self.in_tail_position = saved_tail;
+                    }
+                    "sort" => {
+                        // (sort xs) or (sort xs cmp); '() as the comparator also means numeric order
+                        if items.len() != 2 && items.len() != 3 {
+                            return Err(CompileError::new(
+                                "sort expects a list and an optional comparator".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        self.compile_expr(&items[1])?;
+                        match items.get(2) {
+                            Some(comparator) => {
+                                self.compile_expr(comparator)?;
+                            }
+                            None => self.emit(Instruction::Push(Value::List(List::Nil))),
+                        }
+                        self.emit(Instruction::Sort);
+                        self.in_tail_position = saved_tail;
+                    }
+                    "format" => {
+                        // (format "~a + ~a = ~d~%" 1 2 3): the values reach the VM as one list
+                        if items.len() < 2 {
+                            return Err(CompileError::new(
+                                "format expects a template string followed by its values".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let saved_tail = self.in_tail_position;
+                        self.in_tail_position = false;
+                        for item in &items[1..] {
+                            self.compile_expr(item)?;
+                        }
+                        self.emit(Instruction::MakeList(items.len() - 2));
+                        self.emit(Instruction::Format);
+                        self.in_tail_position = saved_tail;
+                    }
 
                     // User-defined function call or closure variable or macro
                     _ => {
@@ -1149,45 +1801,35 @@ impl Compiler {
                             self.compile_expr(&expanded)?;
                         } else {
                             // Check if operator is a variable (could be a closure)
-                            let is_variable = self.local_bindings.contains_key(operator)
-                                || self.pattern_bindings.contains_key(operator)
-                                || self.param_names.contains(operator);
-
-                            if is_variable {
-                                // It's a variable - load it as a closure and use CallClosure
-                                let saved_tail = self.in_tail_position;
-
-                                // Closure and arguments are not in tail position
-                                self.in_tail_position = false;
-                                self.compile_variable_load(operator)?;
-
-                                // Compile all arguments
-                                let arg_count = items.len() - 1;
-                                for i in 1..items.len() {
-                                    self.compile_expr(&items[i])?;
-                                }
-
-                                // Call the closure
-                                self.emit(Instruction::CallClosure(arg_count));
-
-                                self.in_tail_position = saved_tail;
+                            if self.is_local_variable(operator) {
+                                self.compile_local_call(operator, items)?;
                             } else {
                                 // It's a regular function call
-                                let arg_count = items.len() - 1;
                                 let is_tail_call = self.in_tail_position;
 
-                                // Arguments are not in tail position
-                                self.in_tail_position = false;
-                                for i in 1..items.len() {
-                                    self.compile_expr(&items[i])?;
-                                }
-
                                 // Resolve the function name:
                                 // 1. Check for imported symbol alias
                                 // 2. If in a module and no "/" in name, try module-local first
                                 // 3. Otherwise use the operator as-is (may be qualified like "math/add")
                                 let resolved_name = self.resolve_function_name(operator);
 
+                                // Literal keywords can be checked here; anything else is
+                                // left to the callee's BindKeyArgs
+                                if let Some(spec) = self.key_params.get(&resolved_name) {
+                                    Self::check_key_args(operator, spec, &items[1..])?;
+                                }
+
+                                let arg_count = items.len() - 1;
+
+                                // Arguments are not in tail position
+                                self.in_tail_position = false;
+                                for arg in &items[1..] {
+                                    self.compile_expr(arg)?;
+                                }
+                                if self.strict_mode {
+                                    self.called_functions.push((resolved_name.clone(), items[0].location.clone()));
+                                }
+
                                 // Emit TailCall if in tail position, otherwise Call
                                 if is_tail_call {
                                     self.emit(Instruction::TailCall(resolved_name, arg_count));
@@ -1203,7 +1845,10 @@ impl Compiler {
                 }
             } else {
                 // Non-symbol operator - should be a closure expression
-                // Compile the operator expression (should produce a closure)
+                let is_tail_call = self.in_tail_position;
+
+                // Operator and arguments are not in tail position
+                self.in_tail_position = false;
                 self.compile_expr(&items[0])?;
 
                 // Compile all arguments
@@ -1212,8 +1857,14 @@ impl Compiler {
                     self.compile_expr(&items[i])?;
                 }
 
-                // Call the closure
-                self.emit(Instruction::CallClosure(arg_count));
+                // Call the closure, reusing the frame in tail position
+                if is_tail_call {
+                    self.emit(Instruction::TailCallClosure(arg_count));
+                } else {
+                    self.emit(Instruction::CallClosure(arg_count));
+                }
+
+                self.in_tail_position = is_tail_call;
             }
             }
         }
@@ -1223,14 +1874,17 @@ impl Compiler {
 
     // Convert a SourceExpr to a runtime Value (for quote)
     fn expr_to_value(&self, expr: &SourceExpr) -> Result<Value, CompileError> {
-        match &expr.expr {
+        with_stack_room(|| match &expr.expr {
             LispExpr::Number(n) => Ok(Value::Integer(*n)),
+            LispExpr::BigInt(n) => Ok(Value::BigInt(Arc::new(n.clone()))),
             LispExpr::Float(f) => Ok(Value::Float(*f)),
             LispExpr::Boolean(b) => Ok(Value::Boolean(*b)),
-            LispExpr::Symbol(s) => {
+            LispExpr::Symbol(s) => match s.strip_prefix("__STRING__") {
+                // String literals are symbols in the AST (hack from parser)
+                Some(content) => Ok(Value::String(Arc::new(content.to_string()))),
                 // Symbols in quoted expressions become Symbol values
-                Ok(Value::Symbol(Arc::new(s.clone())))
-            }
+                None => Ok(Value::Symbol(Arc::new(s.clone()))),
+            },
             LispExpr::List(items) => {
                 let mut values = Vec::new();
                 for item in items {
@@ -1240,24 +1894,18 @@ impl Compiler {
             }
             LispExpr::DottedList(items, rest) => {
                 // '(a b . rest) - cons a and b onto rest
-                let rest_value = self.expr_to_value(rest)?;
-
-                // Rest must be a list
-                if let Value::List(rest_list) = rest_value {
-                    // Prepend items to rest_list (from back to front)
-                    let mut result = rest_list;
-                    for item in items.iter().rev() {
-                        result = List::cons(self.expr_to_value(item)?, result);
-                    }
-                    Ok(Value::List(result))
-                } else {
-                    Err(CompileError::new(
-                        "Rest of dotted list must be a list".to_string(),
-                        rest.location.clone(),
-                    ))
+                // Like the Cons instruction, a non-list rest follows the dot: '(k . v) is a pair
+                let mut result = match self.expr_to_value(rest)? {
+                    Value::List(rest_list) => rest_list,
+                    other => List::Dotted(Box::new(other)),
+                };
+                // Prepend items to the rest (from back to front)
+                for item in items.iter().rev() {
+                    result = List::cons(self.expr_to_value(item)?, result);
                 }
+                Ok(Value::List(result))
             }
-        }
+        })
     }
 
     fn compile_def(&mut self, expr: &SourceExpr) -> Result<(), CompileError> {
@@ -1271,10 +1919,14 @@ impl Compiler {
             }
         };
 
+        // def-mutable globals can later be assigned with set!
+        let mutable = matches!(&items[0].expr, LispExpr::Symbol(s) if s == "def-mutable");
+        let form = if mutable { "def-mutable" } else { "def" };
+
         // Check length: (def name value)
         if items.len() != 3 {
             return Err(CompileError::new(
-                "def expects exactly: (def name value)".to_string(),
+                format!("{} expects exactly: ({} name value)", form, form),
                 expr.location.clone(),
             ));
         }
@@ -1284,7 +1936,10 @@ impl Compiler {
 
         // Extract variable name
         let var_name = match &items[1].expr {
-            LispExpr::Symbol(s) => s.clone(),
+            LispExpr::Symbol(s) => {
+                Self::check_binding_name(s, &items[1].location)?;
+                s.clone()
+            }
             _ => {
                 return Err(CompileError::new(
                     "Variable name must be a symbol".to_string(),
@@ -1296,7 +1951,7 @@ impl Compiler {
         // Qualify with module name if in a module
         let qualified_name = self.qualify_name(&var_name);
 
-        // Enforce immutability - no redefinition allowed
+        // Enforce immutability - no redefinition allowed (set! assigns def-mutable globals)
         if self.global_vars.contains_key(&qualified_name) {
             return Err(CompileError::new(
                 format!("Cannot redefine constant '{}' - all bindings are immutable", qualified_name),
@@ -1304,14 +1959,14 @@ impl Compiler {
             ));
         }
 
-        // Register as immutable global variable (false = immutable)
-        self.global_vars.insert(qualified_name.clone(), false);
+        // Register the global with its mutable flag
+        self.global_vars.insert(qualified_name.clone(), mutable);
 
         // Compile the value expression
         self.compile_expr(&items[2])?;
 
         // Emit StoreGlobal to store the value
-        self.emit(Instruction::StoreGlobal(qualified_name));
+        self.emit_consuming(Instruction::StoreGlobal(qualified_name), 1);
 
         Ok(())
     }
@@ -1360,14 +2015,19 @@ impl Compiler {
         // Determine if this is a multi-clause or single-clause defun
         // Multi-clause: (defun name ((pattern) body) ((pattern) body) ...)
         // Single-clause: (defun name (params) body)
+        // Single-clause with contracts: (defun name (params) (pre cond) (post pred) ... body)
         //
         // Heuristic: if items.len() == 4 and items[2] is a list/dotted-list that looks
         // like parameters (only contains symbols), it's single-clause.
         // Otherwise, if items[2] looks like a clause (a list starting with a list), it's multi-clause.
 
-        if items.len() == 4 && self.looks_like_param_list(&items[2]) {
-            // Single-clause defun: (defun name (params) body)
-            self.compile_single_clause_defun(&fn_name, &items[2], &items[3])
+        let body_idx = items.len() - 1;
+        if items.len() >= 4
+            && self.looks_like_param_list(&items[2])
+            && items[3..body_idx].iter().all(|c| Self::contract_kind(c).is_some())
+        {
+            // Single-clause defun: (defun name (params) [contracts...] body)
+            self.compile_single_clause_defun(&fn_name, &items[2], &items[3..body_idx], &items[body_idx])
         } else {
             // Multi-clause defun: (defun name clause1 clause2 ...)
             let clauses = &items[2..];
@@ -1375,6 +2035,22 @@ impl Compiler {
         }
     }
 
+    // Recognise a contract clause: (pre condition) or (post predicate)
+    fn contract_kind(expr: &SourceExpr) -> Option<&'static str> {
+        if let LispExpr::List(items) = &expr.expr {
+            if items.len() == 2 {
+                if let LispExpr::Symbol(s) = &items[0].expr {
+                    match s.as_str() {
+                        "pre" => return Some("pre"),
+                        "post" => return Some("post"),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        None
+    }
+
     // Check if an expression looks like a parameter list (only contains symbols)
     fn looks_like_param_list(&self, expr: &SourceExpr) -> bool {
         match &expr.expr {
@@ -1388,38 +2064,163 @@ impl Compiler {
                     }
                 }
                 // Regular param list: all symbols
-                params.iter().all(|p| matches!(&p.expr, LispExpr::Symbol(_)))
+                Self::looks_like_param_items(params)
             }
             LispExpr::DottedList(head, tail) => {
                 // Dotted list: (a b . rest) - all should be symbols
-                head.iter().all(|p| matches!(&p.expr, LispExpr::Symbol(_)))
+                Self::looks_like_param_items(head)
                     && matches!(&tail.expr, LispExpr::Symbol(_))
             }
             _ => false,
         }
     }
 
+    // Symbols, plus (name default) entries once &optional or &key has been seen
+    fn looks_like_param_items(params: &[SourceExpr]) -> bool {
+        let mut seen_marker = false;
+        params.iter().all(|p| match &p.expr {
+            LispExpr::Symbol(s) => {
+                seen_marker |= s == "&optional" || s == "&key";
+                true
+            }
+            LispExpr::List(entry) => seen_marker && matches!(entry.first().map(|e| &e.expr), Some(LispExpr::Symbol(_))),
+            _ => false,
+        })
+    }
+
+    // nil always evaluates to the empty list, so a variable named nil could be
+    // bound but never read back. Every binding form rejects it up front
+    fn check_binding_name(name: &str, location: &Location) -> Result<(), CompileError> {
+        if name == "nil" {
+            return Err(CompileError::with_suggestion(
+                "Cannot bind 'nil' - it is the empty list literal, not a variable name".to_string(),
+                location.clone(),
+                "Choose another name; nil always evaluates to '()".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Reject a call to a &key function whose keyword arguments are literal and
+    // can't bind: an unknown key, a key without a value, or a repeated key. A
+    // non-literal in a keyword position may still evaluate to a valid keyword,
+    // so checking stops there
+    fn check_key_args(fn_name: &str, spec: &KeyParams, args: &[SourceExpr]) -> Result<(), CompileError> {
+        let mut seen = HashSet::new();
+        let mut rest = args.iter().skip(spec.required);
+        while let Some(keyword) = rest.next() {
+            let key = match &keyword.expr {
+                LispExpr::Symbol(s) if s.len() > 1 && s.starts_with(':') => &s[1..],
+                _ => return Ok(()),
+            };
+            if !spec.keys.iter().any(|name| name == key) {
+                let known: Vec<String> = spec.keys.iter().map(|name| format!(":{}", name)).collect();
+                return Err(CompileError::with_suggestion(
+                    format!("Unknown keyword argument :{} in call to '{}'", key, fn_name),
+                    keyword.location.clone(),
+                    format!("'{}' accepts {}", fn_name, known.join(" ")),
+                ));
+            }
+            if rest.next().is_none() {
+                return Err(CompileError::new(
+                    format!("Keyword argument :{} is missing a value", key),
+                    keyword.location.clone(),
+                ));
+            }
+            if !seen.insert(key) {
+                return Err(CompileError::new(
+                    format!("Keyword argument :{} given more than once", key),
+                    keyword.location.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Remember (or forget, on redefinition) the &key params of a defun
+    fn record_key_params(&mut self, name: String, parsed: &ParsedParams) {
+        if parsed.keys.is_empty() {
+            self.key_params.remove(&name);
+        } else {
+            self.key_params.insert(name, KeyParams {
+                required: parsed.required.len(),
+                keys: parsed.keys.iter().map(|(key, _)| key.clone()).collect(),
+            });
+        }
+    }
+
+    // Parse the symbols of a parameter list: required names, then &optional or
+    // &key entries written as `name` (defaults to nil) or `(name default)`
+    fn parse_param_items(params: &[SourceExpr]) -> Result<ParsedParams, CompileError> {
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+        let mut keys = Vec::new();
+        let mut section: Option<&str> = None; // The marker the current entries follow
+        for param in params {
+            let entry = match &param.expr {
+                LispExpr::Symbol(s) if s == "&optional" || s == "&key" => {
+                    if let Some(previous) = section {
+                        let message = if previous == s {
+                            format!("{} may only appear once in a parameter list", s)
+                        } else {
+                            "&optional and &key cannot be combined in one parameter list".to_string()
+                        };
+                        return Err(CompileError::new(message, param.location.clone()));
+                    }
+                    section = Some(s.as_str());
+                    continue;
+                }
+                LispExpr::Symbol(s) if section.is_none() => {
+                    Self::check_binding_name(s, &param.location)?;
+                    required.push(s.clone());
+                    continue;
+                }
+                LispExpr::Symbol(s) => {
+                    Self::check_binding_name(s, &param.location)?;
+                    (s.clone(), SourceExpr::new(LispExpr::Symbol("nil".to_string()), param.location.clone()))
+                }
+                LispExpr::List(entry) if section.is_some() => match entry.as_slice() {
+                    [SourceExpr { expr: LispExpr::Symbol(s), location }, default] => {
+                        Self::check_binding_name(s, location)?;
+                        (s.clone(), default.clone())
+                    }
+                    _ => {
+                        let kind = if section == Some("&key") { "Keyword" } else { "Optional" };
+                        return Err(CompileError::new(
+                            format!("{} parameter must be a symbol or (name default)", kind),
+                            param.location.clone(),
+                        ));
+                    }
+                },
+                _ => {
+                    return Err(CompileError::new(
+                        "Parameter must be a symbol".to_string(),
+                        param.location.clone(),
+                    ));
+                }
+            };
+            if section == Some("&key") {
+                keys.push(entry);
+            } else {
+                optional.push(entry);
+            }
+        }
+        Ok(ParsedParams { required, optional, keys, rest: None })
+    }
+
     // Parse parameter list, detecting variadic syntax (a b . rest)
     fn parse_params(params_expr: &SourceExpr) -> Result<ParsedParams, CompileError> {
         match &params_expr.expr {
             // Dotted list: (a b . rest) - parser already separated them for us
             LispExpr::DottedList(required_params, rest_param) => {
-                let mut required = Vec::new();
-                for param in required_params {
-                    match &param.expr {
-                        LispExpr::Symbol(s) => required.push(s.clone()),
-                        _ => {
-                            return Err(CompileError::new(
-                                "Parameter must be a symbol".to_string(),
-                                param.location.clone(),
-                            ));
-                        }
-                    }
-                }
+                let parsed = Self::parse_param_items(required_params)?;
 
                 // Extract rest parameter name
                 let rest = match &rest_param.expr {
-                    LispExpr::Symbol(s) => Some(s.clone()),
+                    LispExpr::Symbol(s) => {
+                        Self::check_binding_name(s, &rest_param.location)?;
+                        Some(s.clone())
+                    }
                     _ => {
                         return Err(CompileError::new(
                             "Rest parameter must be a symbol".to_string(),
@@ -1428,7 +2229,14 @@ impl Compiler {
                     }
                 };
 
-                Ok(ParsedParams { required, rest })
+                if !parsed.keys.is_empty() {
+                    return Err(CompileError::new(
+                        "&key cannot be combined with a rest parameter".to_string(),
+                        rest_param.location.clone(),
+                    ));
+                }
+
+                Ok(ParsedParams { rest, ..parsed })
             }
 
             // Regular list: (a b c) or special case (. rest) for zero required params
@@ -1440,8 +2248,11 @@ impl Compiler {
                             // This is (. rest) syntax
                             match &params[1].expr {
                                 LispExpr::Symbol(rest_name) => {
+                                    Self::check_binding_name(rest_name, &params[1].location)?;
                                     return Ok(ParsedParams {
                                         required: Vec::new(),
+                                        optional: Vec::new(),
+                                        keys: Vec::new(),
                                         rest: Some(rest_name.clone()),
                                     });
                                 }
@@ -1457,20 +2268,7 @@ impl Compiler {
                 }
 
                 // Regular parameter list
-                let mut required = Vec::new();
-                for param in params {
-                    match &param.expr {
-                        LispExpr::Symbol(s) => required.push(s.clone()),
-                        _ => {
-                            return Err(CompileError::new(
-                                "Parameter must be a symbol".to_string(),
-                                param.location.clone(),
-                            ));
-                        }
-                    }
-                }
-
-                Ok(ParsedParams { required, rest: None })
+                Self::parse_param_items(params)
             }
 
             _ => {
@@ -1487,13 +2285,31 @@ impl Compiler {
         &mut self,
         fn_name: &str,
         params_expr: &SourceExpr,
+        contracts: &[SourceExpr],
         body_expr: &SourceExpr,
     ) -> Result<(), CompileError> {
         // Parse parameters (handles both regular and variadic)
         let parsed_params = Self::parse_params(params_expr)?;
 
+        // Recorded before the body is compiled, which may call itself with keywords
+        self.record_key_params(self.qualify_name(fn_name), &parsed_params);
+
+        // Split contract clauses into preconditions and postconditions
+        let mut preconditions = Vec::new();
+        let mut postconditions = Vec::new();
+        for contract in contracts {
+            if let LispExpr::List(items) = &contract.expr {
+                match Self::contract_kind(contract) {
+                    Some("pre") => preconditions.push(&items[1]),
+                    _ => postconditions.push(&items[1]),
+                }
+            }
+        }
+
         // Build complete param list for compilation context
         let mut all_params = parsed_params.required.clone();
+        all_params.extend(parsed_params.optional.iter().map(|(name, _)| name.clone()));
+        all_params.extend(parsed_params.keys.iter().map(|(name, _)| name.clone()));
         if let Some(ref rest_name) = parsed_params.rest {
             all_params.push(rest_name.clone());
         }
@@ -1503,27 +2319,113 @@ impl Compiler {
         let saved_params = std::mem::take(&mut self.param_names);
         let saved_address = self.instruction_address;
         let saved_tail_position = self.in_tail_position;
+        let saved_local_bindings = std::mem::take(&mut self.local_bindings);
+        let saved_stack_depth = self.stack_depth;
+        let saved_assigned_locals = std::mem::take(&mut self.assigned_locals);
+        let saved_captured_locals = std::mem::take(&mut self.captured_locals);
 
         // Set up new context for function
+        // BindKeyArgs keeps its supplied flags in one extra slot after the keys
+        let arity = all_params.len() + usize::from(!parsed_params.keys.is_empty());
         self.bytecode = Vec::new();
         self.param_names = all_params;
         self.instruction_address = 0;
-        self.in_tail_position = true; // Function body is in tail position
+        self.stack_depth = 0;
+
+        // Without a rest param there is nowhere for surplus arguments to go
+        if !parsed_params.optional.is_empty() && parsed_params.rest.is_none() {
+            self.emit(Instruction::CheckMaxArgs(parsed_params.required.len() + parsed_params.optional.len()));
+        }
+
+        // Fill each missing optional argument with its default, in order, so a
+        // default can refer to the parameters before it
+        self.in_tail_position = false;
+        for (i, (_, default)) in parsed_params.optional.iter().enumerate() {
+            let arg_idx = parsed_params.required.len() + i;
+            let skip_idx = self.bytecode.len();
+            self.emit(Instruction::JmpIfArgSupplied(arg_idx, 0)); // placeholder jump address
+            self.compile_expr(default)?;
+            self.emit_consuming(Instruction::BindArg(arg_idx), 1);
+            let after_default = self.bytecode.len();
+            self.patch_jump(skip_idx, after_default);
+        }
+
+        // Sort the :key value pairs after the required args into one slot per
+        // key, then fill each key that wasn't passed with its default, in
+        // order, as for &optional
+        if !parsed_params.keys.is_empty() {
+            let first_key = parsed_params.required.len();
+            let names = parsed_params.keys.iter().map(|(name, _)| name.clone()).collect();
+            self.emit(Instruction::BindKeyArgs(first_key, names));
+            for (i, (_, default)) in parsed_params.keys.iter().enumerate() {
+                let arg_idx = first_key + i;
+                let skip_idx = self.bytecode.len();
+                self.emit(Instruction::JmpIfKeySupplied(arg_idx, 0)); // placeholder jump address
+                self.compile_expr(default)?;
+                self.emit_consuming(Instruction::BindArg(arg_idx), 1);
+                let after_default = self.bytecode.len();
+                self.patch_jump(skip_idx, after_default);
+            }
+        }
 
         // If variadic, emit PackRestArgs at the start of function
         if parsed_params.rest.is_some() {
-            self.emit(Instruction::PackRestArgs(parsed_params.required.len()));
+            self.emit(Instruction::PackRestArgs(parsed_params.required.len() + parsed_params.optional.len()));
+        }
+
+        // Prologue: check each precondition against the arguments
+        for condition in &preconditions {
+            self.compile_expr(condition)?;
+            self.emit_consuming(Instruction::CheckContract(format!(
+                "Precondition failed in '{}': {}",
+                fn_name,
+                Self::expr_to_source(&condition.expr)
+            )), 1);
         }
 
         // Compile function body
+        // With postconditions the body can't be a tail call: the epilogue needs its result
+        self.in_tail_position = postconditions.is_empty();
         self.compile_expr(body_expr)?;
 
+        // Epilogue: call each postcondition predicate with the result, which stays on the stack
+        if !postconditions.is_empty() {
+            self.in_tail_position = false;
+            let result_slot = self.stack_depth - 1;
+            for predicate in &postconditions {
+                self.compile_expr(predicate)?;
+                self.emit(Instruction::GetLocal(result_slot));
+                self.emit(Instruction::CallClosure(1));
+                self.emit_consuming(Instruction::CheckContract(format!(
+                    "Postcondition failed in '{}': {}",
+                    fn_name,
+                    Self::expr_to_source(&predicate.expr)
+                )), 1);
+            }
+        }
+
         // Emit return instruction
         self.emit(Instruction::Ret);
 
         // Store compiled function (qualified with module name if in a module)
         let fn_bytecode = std::mem::take(&mut self.bytecode);
         let qualified_name = self.qualify_name(fn_name);
+        let mut params = parsed_params.required.join(" ");
+        if !parsed_params.optional.is_empty() {
+            let optional: Vec<&str> = parsed_params.optional.iter().map(|(name, _)| name.as_str()).collect();
+            let optional = format!("&optional {}", optional.join(" "));
+            params = if params.is_empty() { optional } else { format!("{} {}", params, optional) };
+        }
+        if !parsed_params.keys.is_empty() {
+            let keys: Vec<&str> = parsed_params.keys.iter().map(|(name, _)| name.as_str()).collect();
+            let keys = format!("&key {}", keys.join(" "));
+            params = if params.is_empty() { keys } else { format!("{} {}", params, keys) };
+        }
+        if let Some(ref rest_name) = parsed_params.rest {
+            params = if params.is_empty() { format!(". {}", rest_name) } else { format!("{} . {}", params, rest_name) };
+        }
+        self.function_params.insert(qualified_name.clone(), format!("({})", params));
+        self.function_arities.insert(qualified_name.clone(), arity);
         self.functions.insert(qualified_name, fn_bytecode);
 
         // Restore context
@@ -1531,6 +2433,10 @@ impl Compiler {
         self.param_names = saved_params;
         self.instruction_address = saved_address;
         self.in_tail_position = saved_tail_position;
+        self.local_bindings = saved_local_bindings;
+        self.assigned_locals = saved_assigned_locals;
+        self.captured_locals = saved_captured_locals;
+        self.stack_depth = saved_stack_depth;
 
         Ok(())
     }
@@ -1576,6 +2482,9 @@ impl Compiler {
         let saved_tail_position = self.in_tail_position;
         let saved_local_bindings = std::mem::take(&mut self.local_bindings);
         let saved_stack_depth = self.stack_depth;
+        let saved_assigned_locals = std::mem::take(&mut self.assigned_locals);
+        let saved_captured_locals = std::mem::take(&mut self.captured_locals);
+        let saved_hoisted = std::mem::take(&mut self.hoisted_arg_lengths);
 
         // Set up new context for function
         self.bytecode = Vec::new();
@@ -1583,8 +2492,60 @@ impl Compiler {
         self.instruction_address = 0;
         self.stack_depth = 0;
 
+        self.compile_clause_dispatch(&parsed_clauses, max_arity, &format!("No matching clause in function '{}'", fn_name))?;
+
+        // Store compiled function (qualified with module name if in a module)
+        let fn_bytecode = std::mem::take(&mut self.bytecode);
+        let qualified_name = self.qualify_name(fn_name);
+        self.key_params.remove(&qualified_name);
+        let params: Vec<String> = (0..max_arity).map(|i| format!("__arg{}", i)).collect();
+        self.function_params.insert(qualified_name.clone(), format!("({})", params.join(" ")));
+        self.function_arities.insert(qualified_name.clone(), max_arity);
+        self.functions.insert(qualified_name, fn_bytecode);
+
+        // Restore context
+        self.bytecode = saved_bytecode;
+        self.param_names = saved_params;
+        self.instruction_address = saved_address;
+        self.in_tail_position = saved_tail_position;
+        self.local_bindings = saved_local_bindings;
+        self.assigned_locals = saved_assigned_locals;
+        self.captured_locals = saved_captured_locals;
+        self.stack_depth = saved_stack_depth;
+        self.hoisted_arg_lengths = saved_hoisted;
+
+        Ok(())
+    }
+
+    // Emit the pattern dispatch shared by multi-clause defun and match-lambda.
+    // Expects a fresh function context (bytecode, params, stack depth) to be set up;
+    // each clause returns from the function when its patterns match.
+    fn compile_clause_dispatch(
+        &mut self,
+        parsed_clauses: &[FunctionClause],
+        max_arity: usize,
+        no_match_message: &str,
+    ) -> Result<(), CompileError> {
+        // Hoist list-length checks: every argument matched against a list pattern
+        // in some clause gets its length computed once, at function entry, into a
+        // stack slot. Clauses then compare against that slot instead of re-checking
+        // IsList and ListLength themselves. The slots sit below each clause's
+        // bindings, so the Slide before Ret cleans them up as well.
+        for arg_idx in 0..max_arity {
+            let has_list_pattern = parsed_clauses.iter().any(|clause| {
+                matches!(clause.patterns.get(arg_idx), Some(Pattern::List(_)) | Some(Pattern::DottedList(_, _)))
+            });
+            if has_list_pattern {
+                self.emit(Instruction::ArgListLength(arg_idx));
+                self.hoisted_arg_lengths.insert(arg_idx, self.stack_depth);
+                self.stack_depth += 1;
+            }
+        }
+        let hoisted_depth = self.stack_depth;
+
         // Compile pattern matching dispatch
         // Structure:
+        //   ArgListLength(i) for each hoisted argument
         // clause_0:
         //   CheckArity(expected_arity_0, clause_1)  # Jump if arg count doesn't match
         //   <pattern checks for clause 0>
@@ -1608,7 +2569,7 @@ impl Compiler {
 
             // Save bindings for this clause
             self.local_bindings.clear();
-            self.stack_depth = 0;
+            self.stack_depth = hoisted_depth;
 
             // Get the arity for this specific clause
             let clause_arity = clause.patterns.len();
@@ -1629,19 +2590,40 @@ impl Compiler {
 
             // All patterns matched! Bind variables from patterns
             self.bind_pattern_variables(&clause.patterns, clause_arity)?;
+            let bindings_depth = self.stack_depth;
+
+            // The guard sees the bindings; if it fails they are popped on the way to the next clause
+            let guard_jump = match &clause.guard {
+                Some(guard) => {
+                    self.in_tail_position = false;
+                    self.compile_expr(guard)?;
+                    let jump_idx = self.bytecode.len();
+                    self.emit_consuming(Instruction::JmpIfFalse(0), 1); // placeholder jump address
+                    Some(jump_idx)
+                }
+                None => None,
+            };
 
             // Compile the body in tail position
             self.in_tail_position = true;
             self.compile_expr(&clause.body)?;
 
             // Clean up any stack values from pattern bindings
-            if self.stack_depth > 0 {
-                self.emit(Instruction::Slide(self.stack_depth));
+            if bindings_depth > 0 {
+                self.emit(Instruction::Slide(bindings_depth));
             }
 
             // Return
             self.emit(Instruction::Ret);
 
+            if let Some(jump_idx) = guard_jump {
+                let cleanup = self.instruction_address;
+                self.patch_jump(jump_idx, cleanup);
+                if bindings_depth > hoisted_depth {
+                    self.emit(Instruction::PopN(bindings_depth - hoisted_depth));
+                }
+            }
+
             // Patch all jump addresses to point to the next clause (or error)
             let target = self.instruction_address;
             for jump_idx in jumps_to_patch {
@@ -1650,29 +2632,47 @@ impl Compiler {
 
             // If this is the last clause, emit error handler
             if clause_idx == num_clauses - 1 {
-                // Emit error for no matching clause
-                self.emit(Instruction::Push(Value::String(Arc::new(
-                    format!("No matching clause in function '{}'", fn_name)
-                ))));
-                self.emit(Instruction::Print);
-                self.emit(Instruction::Halt);
+                // Raise a runtime error for no matching clause, so callers can catch it
+                self.emit(Instruction::NoMatchingClause(no_match_message.to_string()));
             }
         }
 
-        // Store compiled function (qualified with module name if in a module)
-        let fn_bytecode = std::mem::take(&mut self.bytecode);
-        let qualified_name = self.qualify_name(fn_name);
-        self.functions.insert(qualified_name, fn_bytecode);
+        Ok(())
+    }
 
-        // Restore context
-        self.bytecode = saved_bytecode;
-        self.param_names = saved_params;
-        self.instruction_address = saved_address;
-        self.in_tail_position = saved_tail_position;
-        self.local_bindings = saved_local_bindings;
-        self.stack_depth = saved_stack_depth;
+    // Emit a length check for a list pattern argument, jumping to the next clause on failure.
+    // Uses the hoisted length slot when there is one (-1 there means "not a list", which
+    // fails both checks); otherwise falls back to IsList followed by ListLength.
+    fn compile_list_length_check(&mut self, arg_idx: usize, len: usize, at_least: bool) {
+        let cmp = if at_least { Instruction::Gte } else { Instruction::Eq };
+        if let Some(&slot) = self.hoisted_arg_lengths.get(&arg_idx) {
+            self.emit(Instruction::GetLocal(slot));
+            self.emit(Instruction::Push(Value::Integer(len as i64)));
+            self.emit(cmp);
+            let jump_idx = self.instruction_address;
+            self.emit(Instruction::JmpIfFalse(0));
+            self.pattern_match_jumps.push(jump_idx);
+            return;
+        }
 
-        Ok(())
+        // 1. Check it's a list
+        self.emit(Instruction::LoadArg(arg_idx));
+        self.emit(Instruction::IsList);
+        let not_list_jump = self.instruction_address;
+        self.emit(Instruction::JmpIfFalse(0));
+        self.pattern_match_jumps.push(not_list_jump);
+
+        // 2. Check length (a minimum of zero is implied by being a list)
+        if at_least && len == 0 {
+            return;
+        }
+        self.emit(Instruction::LoadArg(arg_idx));
+        self.emit(Instruction::ListLength);
+        self.emit(Instruction::Push(Value::Integer(len as i64)));
+        self.emit(cmp);
+        let wrong_len_jump = self.instruction_address;
+        self.emit(Instruction::JmpIfFalse(0));
+        self.pattern_match_jumps.push(wrong_len_jump);
     }
 
     // Compile pattern checks for all patterns in a clause
@@ -1718,62 +2718,32 @@ impl Compiler {
             }
             Pattern::EmptyList => {
                 // Load argument and check equality with empty list
-                self.emit(Instruction::LoadArg(arg_idx));
-                self.emit(Instruction::Push(Value::List(List::Nil)));
-                self.emit(Instruction::Eq);
-                let jump_idx = self.instruction_address;
-                self.emit(Instruction::JmpIfFalse(0));
-                self.pattern_match_jumps.push(jump_idx);
-            }
-            Pattern::List(sub_patterns) => {
-                // Check if it's a list with the right length
-                // 1. Check it's a list
-                self.emit(Instruction::LoadArg(arg_idx));
-                self.emit(Instruction::IsList);
-                let not_list_jump = self.instruction_address;
-                self.emit(Instruction::JmpIfFalse(0));
-                self.pattern_match_jumps.push(not_list_jump);
-
-                // 2. Check length
-                self.emit(Instruction::LoadArg(arg_idx));
-                self.emit(Instruction::ListLength);
-                self.emit(Instruction::Push(Value::Integer(sub_patterns.len() as i64)));
+                self.emit(Instruction::LoadArg(arg_idx));
+                self.emit(Instruction::Push(Value::List(List::Nil)));
                 self.emit(Instruction::Eq);
-                let wrong_len_jump = self.instruction_address;
+                let jump_idx = self.instruction_address;
                 self.emit(Instruction::JmpIfFalse(0));
-                self.pattern_match_jumps.push(wrong_len_jump);
+                self.pattern_match_jumps.push(jump_idx);
+            }
+            Pattern::List(sub_patterns) => {
+                // Check it's a list with exactly the right length
+                self.compile_list_length_check(arg_idx, sub_patterns.len(), false);
 
-                // 3. Check each element
+                // Check each element
                 for (elem_idx, sub_pattern) in sub_patterns.iter().enumerate() {
                     self.compile_pattern_check_for_list_element(sub_pattern, arg_idx, elem_idx)?;
                 }
             }
             Pattern::DottedList(head_patterns, tail_pattern) => {
-                // Check if it's a list with at least head_patterns.len() elements
-                // 1. Check it's a list
-                self.emit(Instruction::LoadArg(arg_idx));
-                self.emit(Instruction::IsList);
-                let not_list_jump = self.instruction_address;
-                self.emit(Instruction::JmpIfFalse(0));
-                self.pattern_match_jumps.push(not_list_jump);
-
-                // 2. Check length is at least head_patterns.len()
-                if !head_patterns.is_empty() {
-                    self.emit(Instruction::LoadArg(arg_idx));
-                    self.emit(Instruction::ListLength);
-                    self.emit(Instruction::Push(Value::Integer(head_patterns.len() as i64)));
-                    self.emit(Instruction::Gte); // length >= required
-                    let too_short_jump = self.instruction_address;
-                    self.emit(Instruction::JmpIfFalse(0));
-                    self.pattern_match_jumps.push(too_short_jump);
-                }
+                // Check it's a list with at least head_patterns.len() elements
+                self.compile_list_length_check(arg_idx, head_patterns.len(), true);
 
-                // 3. Check each head element
+                // Check each head element
                 for (elem_idx, sub_pattern) in head_patterns.iter().enumerate() {
                     self.compile_pattern_check_for_list_element(sub_pattern, arg_idx, elem_idx)?;
                 }
 
-                // 4. Check the tail pattern (rest of the list)
+                // Check the tail pattern (rest of the list)
                 self.compile_pattern_check_for_list_tail(tail_pattern, arg_idx, head_patterns.len())?;
             }
         }
@@ -1824,14 +2794,73 @@ impl Compiler {
                 self.pattern_match_jumps.push(jump_idx);
             }
             Pattern::List(_) | Pattern::DottedList(_, _) => {
-                // Nested complex patterns - would need recursive handling
-                // For now, skip the check (will always succeed structurally)
-                // The binding phase will still extract the values
+                // Nested list: check its shape (and its elements) where it sits
+                let mut path = vec![Instruction::Cdr; elem_idx];
+                path.push(Instruction::Car);
+                self.compile_pattern_check_at_path(pattern, arg_idx, &path)?;
             }
         }
         Ok(())
     }
 
+    // Check a pattern against the value reached from argument arg_idx by the
+    // Car/Cdr instructions in path, recursing into nested list patterns. Each
+    // check navigates from the argument again; earlier checks have already
+    // confirmed that the enclosing lists are long enough for the path
+    fn compile_pattern_check_at_path(&mut self, pattern: &Pattern, arg_idx: usize, path: &[Instruction]) -> Result<(), CompileError> {
+        let load = |this: &mut Self| {
+            this.emit(Instruction::LoadArg(arg_idx));
+            for step in path {
+                this.emit(step.clone());
+            }
+        };
+        let constant = match pattern {
+            Pattern::Variable(_) | Pattern::Wildcard => return Ok(()),
+            Pattern::Literal(value) => value.clone(),
+            Pattern::QuotedSymbol(s) => Value::Symbol(Arc::new(s.clone())),
+            Pattern::EmptyList => Value::List(List::Nil),
+            Pattern::List(sub_patterns) | Pattern::DottedList(sub_patterns, _) => {
+                let at_least = matches!(pattern, Pattern::DottedList(_, _));
+
+                // It must be a list, of the right length
+                load(self);
+                self.emit(Instruction::IsList);
+                let not_list_jump = self.instruction_address;
+                self.emit(Instruction::JmpIfFalse(0));
+                self.pattern_match_jumps.push(not_list_jump);
+
+                load(self);
+                self.emit(Instruction::ListLength);
+                self.emit(Instruction::Push(Value::Integer(sub_patterns.len() as i64)));
+                self.emit(if at_least { Instruction::Gte } else { Instruction::Eq });
+                let wrong_len_jump = self.instruction_address;
+                self.emit(Instruction::JmpIfFalse(0));
+                self.pattern_match_jumps.push(wrong_len_jump);
+
+                for (elem_idx, sub_pattern) in sub_patterns.iter().enumerate() {
+                    let mut elem_path = path.to_vec();
+                    elem_path.extend(std::iter::repeat_n(Instruction::Cdr, elem_idx));
+                    elem_path.push(Instruction::Car);
+                    with_stack_room(|| self.compile_pattern_check_at_path(sub_pattern, arg_idx, &elem_path))?;
+                }
+                if let Pattern::DottedList(_, tail_pattern) = pattern {
+                    let mut tail_path = path.to_vec();
+                    tail_path.extend(std::iter::repeat_n(Instruction::Cdr, sub_patterns.len()));
+                    with_stack_room(|| self.compile_pattern_check_at_path(tail_pattern, arg_idx, &tail_path))?;
+                }
+                return Ok(());
+            }
+        };
+
+        load(self);
+        self.emit(Instruction::Push(constant));
+        self.emit(Instruction::Eq);
+        let jump_idx = self.instruction_address;
+        self.emit(Instruction::JmpIfFalse(0));
+        self.pattern_match_jumps.push(jump_idx);
+        Ok(())
+    }
+
     // Compile check for a pattern against a list tail (rest after skipping elements)
     fn compile_pattern_check_for_list_tail(&mut self, pattern: &Pattern, arg_idx: usize, skip_count: usize) -> Result<(), CompileError> {
         match pattern {
@@ -1850,19 +2879,35 @@ impl Compiler {
                 self.emit(Instruction::JmpIfFalse(0));
                 self.pattern_match_jumps.push(jump_idx);
             }
+            Pattern::Literal(value) => {
+                // Constant in tail position: (x . '(2 3))
+                self.emit(Instruction::LoadArg(arg_idx));
+                for _ in 0..skip_count {
+                    self.emit(Instruction::Cdr);
+                }
+                self.emit(Instruction::Push(value.clone()));
+                self.emit(Instruction::Eq);
+                let jump_idx = self.instruction_address;
+                self.emit(Instruction::JmpIfFalse(0));
+                self.pattern_match_jumps.push(jump_idx);
+            }
             _ => {
-                // Other patterns as tail - skip for now
+                // Nested list or quoted symbol as the tail
+                let path = vec![Instruction::Cdr; skip_count];
+                self.compile_pattern_check_at_path(pattern, arg_idx, &path)?;
             }
         }
         Ok(())
     }
 
-    // Patch a JmpIfFalse, Jmp, or CheckArity instruction with the correct target address
+    // Patch a JmpIfFalse, JmpIfTrue, Jmp, CheckArity, PushHandler, JmpIfArgSupplied, or JmpIfKeySupplied instruction with the correct target address
     fn patch_jump(&mut self, idx: usize, target: usize) {
         match &mut self.bytecode[idx] {
-            Instruction::JmpIfFalse(addr) => *addr = target,
+            Instruction::JmpIfFalse(addr) | Instruction::JmpIfTrue(addr) => *addr = target,
             Instruction::Jmp(addr) => *addr = target,
             Instruction::CheckArity(_, addr) => *addr = target,
+            Instruction::PushHandler(addr) => *addr = target,
+            Instruction::JmpIfArgSupplied(_, addr) | Instruction::JmpIfKeySupplied(_, addr) => *addr = target,
             _ => panic!("Expected jump instruction at index {}", idx),
         }
     }
@@ -1888,7 +2933,7 @@ impl Compiler {
                 self.emit(Instruction::LoadArg(arg_idx));
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::Literal(_) | Pattern::QuotedSymbol(_) | Pattern::EmptyList => {
                 // No binding needed
@@ -1925,7 +2970,7 @@ impl Compiler {
                 self.emit(Instruction::Car);
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::Literal(_) | Pattern::QuotedSymbol(_) | Pattern::EmptyList => {
                 // No binding needed
@@ -1961,7 +3006,7 @@ impl Compiler {
                 }
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::EmptyList => {
                 // No binding needed
@@ -1983,7 +3028,7 @@ impl Compiler {
                             self.emit(Instruction::Car);
                             let stack_pos = self.stack_depth;
                             self.stack_depth += 1;
-                            self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                            self.bind_local(name.clone(), stack_pos);
                         }
                         _ => {
                             // More complex nested patterns - skip for now
@@ -2008,7 +3053,7 @@ impl Compiler {
                             self.emit(Instruction::Car);
                             let stack_pos = self.stack_depth;
                             self.stack_depth += 1;
-                            self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                            self.bind_local(name.clone(), stack_pos);
                         }
                         _ => {
                             // More complex nested patterns - skip for now
@@ -2029,7 +3074,7 @@ impl Compiler {
                         }
                         let stack_pos = self.stack_depth;
                         self.stack_depth += 1;
-                        self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                        self.bind_local(name.clone(), stack_pos);
                     }
                     _ => {
                         // Complex tail patterns - skip for now
@@ -2063,7 +3108,7 @@ impl Compiler {
                 self.emit(Instruction::Car);
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::Literal(_) | Pattern::QuotedSymbol(_) | Pattern::EmptyList => {
                 // No binding needed
@@ -2102,7 +3147,7 @@ impl Compiler {
                 }
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::EmptyList => {
                 // No binding needed
@@ -2138,7 +3183,7 @@ impl Compiler {
                 self.emit(Instruction::Car);
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             Pattern::Wildcard | Pattern::Literal(_) | Pattern::QuotedSymbol(_) | Pattern::EmptyList => {
                 // No binding needed
@@ -2173,7 +3218,7 @@ impl Compiler {
                 }
                 let stack_pos = self.stack_depth;
                 self.stack_depth += 1;
-                self.local_bindings.insert(name.clone(), ValueLocation::Local(stack_pos));
+                self.bind_local(name.clone(), stack_pos);
             }
             _ => {
                 // No binding needed for other patterns
@@ -2194,18 +3239,32 @@ impl Compiler {
             }
         };
 
-        if items.len() != 2 {
-            return Err(CompileError::new(
-                "Clause must have exactly 2 elements: ((patterns...) body)".to_string(),
-                expr.location.clone(),
-            ));
-        }
+        // An optional guard sits between the patterns and the body: ((patterns...) (when guard) body)
+        let guard = match items.as_slice() {
+            [_, _] => None,
+            [_, guard, _] => match &guard.expr {
+                LispExpr::List(parts) if parts.len() == 2
+                    && matches!(&parts[0].expr, LispExpr::Symbol(s) if s == "when") => Some(parts[1].clone()),
+                _ => {
+                    return Err(CompileError::new(
+                        "Clause guard must be (when condition)".to_string(),
+                        guard.location.clone(),
+                    ));
+                }
+            },
+            _ => {
+                return Err(CompileError::new(
+                    "Clause must be ((patterns...) body) or ((patterns...) (when guard) body)".to_string(),
+                    expr.location.clone(),
+                ));
+            }
+        };
 
         // Parse patterns from first element
         let patterns = self.parse_patterns(&items[0])?;
-        let body = items[1].clone();
+        let body = items[items.len() - 1].clone();
 
-        Ok(FunctionClause { patterns, body })
+        Ok(FunctionClause { patterns, guard, body })
     }
 
     // Parse patterns list: (pattern1 pattern2 ...)
@@ -2242,11 +3301,18 @@ impl Compiler {
 
     // Parse a single pattern
     fn parse_pattern(&self, expr: &SourceExpr) -> Result<Pattern, CompileError> {
-        match &expr.expr {
+        with_stack_room(|| match &expr.expr {
+            // String literal (hack from parser)
+            LispExpr::Symbol(s) if s.starts_with("__STRING__") => {
+                Ok(Pattern::Literal(self.expr_to_value(expr)?))
+            }
             // Simple symbol: variable or wildcard
             LispExpr::Symbol(s) => {
                 if s == "_" {
                     Ok(Pattern::Wildcard)
+                } else if s == "nil" {
+                    // Like true and false, nil in a pattern is its literal value
+                    Ok(Pattern::EmptyList)
                 } else {
                     Ok(Pattern::Variable(s.clone()))
                 }
@@ -2255,6 +3321,9 @@ impl Compiler {
             LispExpr::Number(n) => {
                 Ok(Pattern::Literal(Value::Integer(*n)))
             }
+            LispExpr::BigInt(n) => {
+                Ok(Pattern::Literal(Value::BigInt(Arc::new(n.clone()))))
+            }
             // Float literal
             LispExpr::Float(f) => {
                 Ok(Pattern::Literal(Value::Float(*f)))
@@ -2273,6 +3342,10 @@ impl Compiler {
                         }
                     }
                 }
+                // Constant vector or hash-map: (vector 1 2), (hash-map "k" 1)
+                if matches!(&items.first().map(|i| &i.expr), Some(LispExpr::Symbol(s)) if s == "vector" || s == "hash-map") {
+                    return Ok(Pattern::Literal(self.constant_pattern_value(expr)?));
+                }
                 // Regular list pattern: (a b c)
                 let sub_patterns: Vec<Pattern> = items
                     .iter()
@@ -2289,63 +3362,78 @@ impl Compiler {
                 let tail_pattern = self.parse_pattern(tail)?;
                 Ok(Pattern::DottedList(head_patterns, Box::new(tail_pattern)))
             }
-        }
+        })
     }
 
-    // Parse a quoted pattern: 'symbol or '()
+    // Parse a quoted pattern: 'symbol, '() or quoted data such as '(1 (2 "x"))
     fn parse_quoted_pattern(&self, expr: &SourceExpr) -> Result<Pattern, CompileError> {
         match &expr.expr {
             // 'symbol - quoted symbol
-            LispExpr::Symbol(s) => {
+            LispExpr::Symbol(s) if !s.starts_with("__STRING__") => {
                 Ok(Pattern::QuotedSymbol(s.clone()))
             }
             // '() - empty list
             LispExpr::List(items) if items.is_empty() => {
                 Ok(Pattern::EmptyList)
             }
-            // '(1 2 3) - quoted list (treat as literal list pattern)
-            LispExpr::List(items) => {
-                let sub_patterns: Vec<Pattern> = items
-                    .iter()
-                    .map(|p| self.parse_quoted_list_element(p))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Pattern::List(sub_patterns))
-            }
-            _ => {
-                Err(CompileError::new(
-                    format!("Unsupported quoted pattern: {:?}", expr.expr),
-                    expr.location.clone(),
-                ))
-            }
+            // Any other quoted datum binds nothing, so it matches by equality as a whole
+            _ => Ok(Pattern::Literal(self.expr_to_value(expr)?)),
         }
     }
 
-    // Parse elements within a quoted list (they're all literals)
-    fn parse_quoted_list_element(&self, expr: &SourceExpr) -> Result<Pattern, CompileError> {
+    // Evaluate a constant used as a pattern literal: numbers, booleans, strings,
+    // quoted data, and vector/hash-map forms built from those
+    fn constant_pattern_value(&self, expr: &SourceExpr) -> Result<Value, CompileError> {
         match &expr.expr {
-            LispExpr::Symbol(s) => Ok(Pattern::QuotedSymbol(s.clone())),
-            LispExpr::Number(n) => Ok(Pattern::Literal(Value::Integer(*n))),
-            LispExpr::Float(f) => Ok(Pattern::Literal(Value::Float(*f))),
-            LispExpr::Boolean(b) => Ok(Pattern::Literal(Value::Boolean(*b))),
-            LispExpr::List(items) if items.is_empty() => Ok(Pattern::EmptyList),
-            LispExpr::List(items) => {
-                let sub_patterns: Vec<Pattern> = items
-                    .iter()
-                    .map(|p| self.parse_quoted_list_element(p))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Pattern::List(sub_patterns))
-            }
-            LispExpr::DottedList(head, tail) => {
-                let head_patterns: Vec<Pattern> = head
+            LispExpr::Number(_) | LispExpr::BigInt(_) | LispExpr::Float(_) | LispExpr::Boolean(_) => self.expr_to_value(expr),
+            LispExpr::Symbol(s) if s.starts_with("__STRING__") => self.expr_to_value(expr),
+            LispExpr::List(items) if !items.is_empty() => {
+                let args = items[1..]
                     .iter()
-                    .map(|p| self.parse_quoted_list_element(p))
-                    .collect::<Result<Vec<_>, _>>()?;
-                let tail_pattern = self.parse_quoted_list_element(tail)?;
-                Ok(Pattern::DottedList(head_patterns, Box::new(tail_pattern)))
+                    .map(|item| self.constant_pattern_value(item))
+                    .collect::<Result<Vec<_>, _>>();
+                match &items[0].expr {
+                    LispExpr::Symbol(s) if s == "quote" && items.len() == 2 => self.expr_to_value(&items[1]),
+                    LispExpr::Symbol(s) if s == "vector" => Ok(Value::Vector(args?.into())),
+                    LispExpr::Symbol(s) if s == "hash-map" => {
+                        let args = args?;
+                        if !args.len().is_multiple_of(2) {
+                            return Err(CompileError::new(
+                                "hash-map pattern expects key/value pairs".to_string(),
+                                expr.location.clone(),
+                            ));
+                        }
+                        let mut map = HashMap::new();
+                        for pair in args.chunks(2) {
+                            match &pair[0] {
+                                Value::String(key) => {
+                                    map.insert(key.to_string(), pair[1].clone());
+                                }
+                                _ => {
+                                    return Err(CompileError::new(
+                                        "hash-map pattern keys must be strings".to_string(),
+                                        expr.location.clone(),
+                                    ));
+                                }
+                            }
+                        }
+                        Ok(Value::HashMap(map.into()))
+                    }
+                    _ => Err(self.non_constant_pattern_error(expr)),
+                }
             }
+            _ => Err(self.non_constant_pattern_error(expr)),
         }
     }
 
+    fn non_constant_pattern_error(&self, expr: &SourceExpr) -> CompileError {
+        CompileError::with_suggestion(
+            format!("Expected a constant in vector/hash-map pattern, got {}", Self::expr_to_source(&expr.expr)),
+            expr.location.clone(),
+            "Vector and hash-map patterns match a fixed value and cannot bind variables. Bind the whole argument and check it in the body instead.".to_string(),
+        )
+    }
+
     // Helper to bind a pattern to a local stack position
     fn bind_pattern_to_local(
         &mut self,
@@ -2355,7 +3443,8 @@ impl Compiler {
         match &pattern.expr {
             // Simple variable binding
             LispExpr::Symbol(s) if s != "_" => {
-                self.local_bindings.insert(s.clone(), ValueLocation::Local(stack_pos));
+                Self::check_binding_name(s, &pattern.location)?;
+                self.bind_local(s.clone(), stack_pos);
             }
 
             // Wildcard - no binding needed
@@ -2428,9 +3517,27 @@ impl Compiler {
         &mut self,
         params_expr: &SourceExpr,
         body_expr: &SourceExpr,
+    ) -> Result<(), CompileError> {
+        self.compile_group_lambda(params_expr, body_expr, &[])
+    }
+
+    // Compile a lambda that belongs to a letrec group. Every member captures the
+    // whole group first, in group order, as placeholders; LetrecFill and
+    // LoadLetrec later tie those slots to the members' closures
+    pub(super) fn compile_group_lambda(
+        &mut self,
+        params_expr: &SourceExpr,
+        body_expr: &SourceExpr,
+        group: &[String],
     ) -> Result<(), CompileError> {
         // Parse parameters (handles both regular and variadic)
         let parsed_params = Self::parse_params(params_expr)?;
+        if !parsed_params.optional.is_empty() || !parsed_params.keys.is_empty() {
+            return Err(CompileError::new(
+                "&optional and &key parameters are only supported in defun".to_string(),
+                params_expr.location.clone(),
+            ));
+        }
 
         // Build complete param list for compilation context
         let mut all_params = parsed_params.required.clone();
@@ -2439,12 +3546,18 @@ impl Compiler {
         }
 
         // Find free variables in body (variables not in all_params)
-        let free_vars = self.find_free_variables(body_expr, &all_params);
+        let mut free_vars = self.find_free_variables(body_expr, &all_params);
+        if !group.is_empty() {
+            free_vars.retain(|v| !group.contains(v));
+            free_vars.splice(0..0, group.iter().cloned());
+        }
 
         // Save current compilation context
         let saved_bytecode = std::mem::take(&mut self.bytecode);
         let saved_params = std::mem::take(&mut self.param_names);
         let saved_local_bindings = self.local_bindings.clone();
+        let saved_assigned_locals = std::mem::take(&mut self.assigned_locals);
+        let saved_captured_locals = std::mem::take(&mut self.captured_locals);
         let saved_pattern_bindings = self.pattern_bindings.clone();
         let saved_address = self.instruction_address;
         let saved_stack_depth = self.stack_depth;
@@ -2461,7 +3574,14 @@ impl Compiler {
 
         // Set up captured variables as "LoadCaptured" locations
         for (i, var_name) in free_vars.iter().enumerate() {
-            self.pattern_bindings.insert(var_name.clone(), ValueLocation::Captured(i));
+            if i < group.len() {
+                // A parameter of the same name shadows the sibling
+                if !all_params.contains(var_name) {
+                    self.pattern_bindings.insert(var_name.clone(), ValueLocation::LetrecCaptured(i, group.len()));
+                }
+            } else {
+                self.pattern_bindings.insert(var_name.clone(), ValueLocation::Captured(i));
+            }
         }
 
         // Compile body
@@ -2475,15 +3595,22 @@ impl Compiler {
         self.bytecode = saved_bytecode;
         self.param_names = saved_params;
         self.local_bindings = saved_local_bindings;
+        self.assigned_locals = saved_assigned_locals;
+        self.captured_locals = saved_captured_locals;
         self.pattern_bindings = saved_pattern_bindings;
         self.instruction_address = saved_address;
         self.stack_depth = saved_stack_depth;
         self.in_tail_position = saved_tail_position;
 
         // Emit code to push captured variable values onto stack
-        for var_name in &free_vars {
-            // Load the value of this free variable
-            self.compile_variable_load(var_name)?;
+        for (i, var_name) in free_vars.iter().enumerate() {
+            if i < group.len() {
+                // Group slots are filled in by LetrecFill
+                self.emit(Instruction::Push(Value::Boolean(false)));
+            } else {
+                // Load the value of this free variable
+                self.capture_variable(var_name, &body_expr.location)?;
+            }
         }
 
         // Emit appropriate closure instruction based on whether it's variadic
@@ -2501,6 +3628,89 @@ impl Compiler {
         Ok(())
     }
 
+    // Compile (match-lambda (pattern body) ...) into a closure over a single argument
+    fn compile_match_lambda(&mut self, clauses: &[SourceExpr], location: &Location) -> Result<(), CompileError> {
+        if clauses.is_empty() {
+            return Err(CompileError::new(
+                "match-lambda requires at least one clause: (match-lambda (pattern body) ...)".to_string(),
+                location.clone(),
+            ));
+        }
+
+        // Each clause is (pattern body): the single argument's pattern, not an argument list
+        let mut parsed_clauses = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            match &clause.expr {
+                LispExpr::List(items) if items.len() == 2 => {
+                    let pattern = self.parse_pattern(&items[0])?;
+                    parsed_clauses.push(FunctionClause { patterns: vec![pattern], guard: None, body: items[1].clone() });
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        "match-lambda clause must have exactly 2 elements: (pattern body)".to_string(),
+                        clause.location.clone(),
+                    ));
+                }
+            }
+        }
+
+        // Free variables of every body, excluding what its own pattern binds
+        let mut free_vars = Vec::new();
+        for clause in &parsed_clauses {
+            let mut bound = Vec::new();
+            clause.patterns[0].collect_variables(&mut bound);
+            for var in self.find_free_variables(&clause.body, &bound) {
+                if !free_vars.contains(&var) {
+                    free_vars.push(var);
+                }
+            }
+        }
+
+        // Save current compilation context
+        let saved_bytecode = std::mem::take(&mut self.bytecode);
+        let saved_params = std::mem::take(&mut self.param_names);
+        let saved_local_bindings = std::mem::take(&mut self.local_bindings);
+        let saved_pattern_bindings = std::mem::take(&mut self.pattern_bindings);
+        let saved_assigned_locals = std::mem::take(&mut self.assigned_locals);
+        let saved_captured_locals = std::mem::take(&mut self.captured_locals);
+        let saved_address = self.instruction_address;
+        let saved_stack_depth = self.stack_depth;
+        let saved_tail_position = self.in_tail_position;
+        let saved_hoisted = std::mem::take(&mut self.hoisted_arg_lengths);
+
+        // Set up new context for closure body
+        let params = vec!["__arg0".to_string()];
+        self.param_names = params.clone();
+        self.instruction_address = 0;
+        self.stack_depth = 0;
+        for (i, var_name) in free_vars.iter().enumerate() {
+            self.pattern_bindings.insert(var_name.clone(), ValueLocation::Captured(i));
+        }
+
+        let dispatch = self.compile_clause_dispatch(&parsed_clauses, 1, "No matching clause in match-lambda");
+        let body_bytecode = std::mem::take(&mut self.bytecode);
+
+        // Restore context
+        self.bytecode = saved_bytecode;
+        self.param_names = saved_params;
+        self.local_bindings = saved_local_bindings;
+        self.assigned_locals = saved_assigned_locals;
+        self.captured_locals = saved_captured_locals;
+        self.pattern_bindings = saved_pattern_bindings;
+        self.instruction_address = saved_address;
+        self.stack_depth = saved_stack_depth;
+        self.in_tail_position = saved_tail_position;
+        self.hoisted_arg_lengths = saved_hoisted;
+        dispatch?;
+
+        for var_name in &free_vars {
+            self.capture_variable(var_name, location)?;
+        }
+        self.emit(Instruction::MakeClosure(params, body_bytecode, free_vars.len()));
+
+        Ok(())
+    }
+
     // Find free variables in an expression (variables not in bound_vars)
     fn find_free_variables(&self, expr: &SourceExpr, bound_vars: &[String]) -> Vec<String> {
         let mut free_vars = Vec::new();
@@ -2518,7 +3728,7 @@ impl Compiler {
         bound_vars: &[String],
         free_vars: &mut Vec<String>,
     ) {
-        match &expr.expr {
+        with_stack_room(|| match &expr.expr {
             LispExpr::Symbol(s) => {
                 // Check if it's a variable (not a string literal, not bound)
                 if !s.starts_with("__STRING__") && !bound_vars.contains(s) {
@@ -2560,6 +3770,79 @@ impl Compiler {
                                 return;
                             }
                         }
+                        "letrec" if items.len() >= 3 => {
+                            // letrec names are bound in every value and in the body
+                            if let LispExpr::List(bindings) = &items[1].expr {
+                                let mut new_bound = bound_vars.to_vec();
+                                for binding in bindings {
+                                    if let LispExpr::List(pair) = &binding.expr {
+                                        if let Some(LispExpr::Symbol(var)) = pair.first().map(|p| &p.expr) {
+                                            new_bound.push(var.clone());
+                                        }
+                                    }
+                                }
+                                for binding in bindings {
+                                    if let LispExpr::List(pair) = &binding.expr {
+                                        for part in pair.iter().skip(1) {
+                                            self.collect_free_variables(part, &new_bound, free_vars);
+                                        }
+                                    }
+                                }
+                                for body in &items[2..] {
+                                    self.collect_free_variables(body, &new_bound, free_vars);
+                                }
+                                return;
+                            }
+                        }
+                        "if-let" | "when-let" if items.len() >= 3 => {
+                            // The variable is bound only in the then-branch
+                            if let LispExpr::List(binding) = &items[1].expr {
+                                if let [var, value] = binding.as_slice() {
+                                    self.collect_free_variables(value, bound_vars, free_vars);
+                                    let mut new_bound = bound_vars.to_vec();
+                                    if let LispExpr::Symbol(name) = &var.expr {
+                                        new_bound.push(name.clone());
+                                    }
+                                    self.collect_free_variables(&items[2], &new_bound, free_vars);
+                                    for other in &items[3..] {
+                                        self.collect_free_variables(other, bound_vars, free_vars);
+                                    }
+                                    return;
+                                }
+                            }
+                        }
+                        "dotimes" | "dolist" if items.len() >= 2 => {
+                            // The iteration variable is bound only in the body
+                            if let LispExpr::List(spec) = &items[1].expr {
+                                if let [var, source] = spec.as_slice() {
+                                    self.collect_free_variables(source, bound_vars, free_vars);
+                                    let mut new_bound = bound_vars.to_vec();
+                                    if let LispExpr::Symbol(name) = &var.expr {
+                                        new_bound.push(name.clone());
+                                    }
+                                    for body in &items[2..] {
+                                        self.collect_free_variables(body, &new_bound, free_vars);
+                                    }
+                                    return;
+                                }
+                            }
+                        }
+                        "try" if items.len() == 3 => {
+                            // The catch variable is bound only in the handler expressions
+                            self.collect_free_variables(&items[1], bound_vars, free_vars);
+                            if let LispExpr::List(clause) = &items[2].expr {
+                                if let [_, var, handlers @ ..] = clause.as_slice() {
+                                    let mut new_bound = bound_vars.to_vec();
+                                    if let LispExpr::Symbol(name) = &var.expr {
+                                        new_bound.push(name.clone());
+                                    }
+                                    for handler in handlers {
+                                        self.collect_free_variables(handler, &new_bound, free_vars);
+                                    }
+                                }
+                            }
+                            return;
+                        }
                         "lambda" if items.len() == 3 => {
                             // lambda introduces new parameters
                             if let LispExpr::List(params) = &items[1].expr {
@@ -2573,10 +3856,39 @@ impl Compiler {
                                 return;
                             }
                         }
+                        "match-lambda" => {
+                            // Each clause's pattern binds variables for its body
+                            for clause in &items[1..] {
+                                match &clause.expr {
+                                    LispExpr::List(parts) if parts.len() == 2 => {
+                                        if let Ok(pattern) = self.parse_pattern(&parts[0]) {
+                                            let mut new_bound = bound_vars.to_vec();
+                                            pattern.collect_variables(&mut new_bound);
+                                            self.collect_free_variables(&parts[1], &new_bound, free_vars);
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            return;
+                        }
                         "quote" => {
                             // Quoted expressions don't have free variables
                             return;
                         }
+                        "case" if items.len() >= 2 => {
+                            // Clause data are literals; only the key and bodies reference variables
+                            self.collect_free_variables(&items[1], bound_vars, free_vars);
+                            for clause in &items[2..] {
+                                match &clause.expr {
+                                    LispExpr::List(parts) if parts.len() == 2 => {
+                                        self.collect_free_variables(&parts[1], bound_vars, free_vars);
+                                    }
+                                    _ => self.collect_free_variables(clause, bound_vars, free_vars),
+                                }
+                            }
+                            return;
+                        }
                         _ => {}
                     }
                 }
@@ -2593,7 +3905,65 @@ impl Compiler {
                 self.collect_free_variables(rest, bound_vars, free_vars);
             }
             _ => {}
+        })
+    }
+
+    // Let bindings, pattern bindings, captures and parameters
+    fn is_local_variable(&self, name: &str) -> bool {
+        self.local_bindings.contains_key(name)
+            || self.pattern_bindings.contains_key(name)
+            || self.param_names.iter().any(|p| p == name)
+    }
+
+    // Call the closure held in a local variable: (f args...)
+    fn compile_local_call(&mut self, operator: &str, items: &[SourceExpr]) -> Result<(), CompileError> {
+        let is_tail_call = self.in_tail_position;
+
+        // Closure and arguments are not in tail position
+        self.in_tail_position = false;
+        self.compile_variable_load(operator)?;
+        self.stack_depth += 1;
+
+        // Compile all arguments
+        let arg_count = items.len() - 1;
+        for item in &items[1..] {
+            self.compile_expr(item)?;
+        }
+
+        // Call the closure, reusing the frame in tail position
+        if is_tail_call {
+            self.emit(Instruction::TailCallClosure(arg_count));
+        } else {
+            self.emit(Instruction::CallClosure(arg_count));
+        }
+
+        self.in_tail_position = is_tail_call;
+        Ok(())
+    }
+
+    // Load a free variable for a closure being created. The closure keeps a
+    // copy, so a let local that set! assigns can't be captured (and vice versa,
+    // see compile_set): the closure would go on seeing the old value
+    fn capture_variable(&mut self, var_name: &str, location: &Location) -> Result<(), CompileError> {
+        if let Some(ValueLocation::Local(slot)) = self.local_bindings.get(var_name) {
+            let slot = *slot;
+            if self.assigned_locals.contains(&slot) {
+                return Err(CompileError::new(
+                    format!("Cannot capture '{}' in a closure - it is assigned with set!, and the closure would keep a stale copy", var_name),
+                    location.clone(),
+                ));
+            }
+            self.captured_locals.insert(slot);
         }
+        self.compile_variable_load(var_name)
+    }
+
+    // Bind a name to a let slot of the current frame. Anything recorded for
+    // that slot or above belonged to bindings that are now out of scope
+    pub(super) fn bind_local(&mut self, name: String, slot: usize) {
+        self.assigned_locals.retain(|assigned| *assigned < slot);
+        self.captured_locals.retain(|captured| *captured < slot);
+        self.local_bindings.insert(name, ValueLocation::Local(slot));
     }
 
     // Helper to load a variable (for capturing)
@@ -2624,6 +3994,7 @@ impl Compiler {
     ) -> Result<(), CompileError> {
         match &pattern.expr {
             LispExpr::Symbol(s) if s != "_" => {
+                Self::check_binding_name(s, &pattern.location)?;
                 self.local_bindings.insert(s.clone(), location);
             }
             LispExpr::Symbol(s) if s == "_" => {
@@ -2646,6 +4017,14 @@ impl Compiler {
     // Compile quasiquote expression
     // Quasiquote is like quote, but allows unquote (,) and unquote-splicing (,@)
     fn compile_quasiquote(&mut self, expr: &SourceExpr) -> Result<(), CompileError> {
+        // Like compile_expr, leaves one value however the list is built
+        let stack_depth = self.stack_depth;
+        with_stack_room(|| self.compile_quasiquote_value(expr))?;
+        self.stack_depth = stack_depth + 1;
+        Ok(())
+    }
+
+    fn compile_quasiquote_value(&mut self, expr: &SourceExpr) -> Result<(), CompileError> {
         match &expr.expr {
             // Check for unquote: (unquote expr)
             LispExpr::List(items) if items.len() == 2 => {
@@ -2743,6 +4122,7 @@ impl Compiler {
 
             // Build forward: start with list containing all non-splice elements and splice points
             self.emit(Instruction::Push(Value::List(List::Nil)));
+            self.stack_depth += 1;
 
             for item in items.iter() {
                 if let LispExpr::List(inner) = &item.expr {
@@ -2754,6 +4134,7 @@ impl Compiler {
                                 // Stack: [accumulator, splice_list]
                                 // We want: [accumulator..., splice_list...]
                                 self.emit_append()?;
+                                self.stack_depth -= 1;
                                 continue;
                             } else if s == "unquote" {
                                 // Regular unquote - cons the element
@@ -2762,6 +4143,7 @@ impl Compiler {
                                 // We need to make a single-element list and append
                                 self.emit(Instruction::MakeList(1));
                                 self.emit_append()?;
+                                self.stack_depth -= 1;
                                 continue;
                             }
                         }
@@ -2771,6 +4153,7 @@ impl Compiler {
                 self.compile_quasiquote(item)?;
                 self.emit(Instruction::MakeList(1));
                 self.emit_append()?;
+                self.stack_depth -= 1;
             }
         } else {
             // No splicing - simpler case
@@ -2828,8 +4211,45 @@ impl Compiler {
 
 
     pub fn compile_program(&mut self, exprs: &[SourceExpr]) -> Result<(HashMap<String, Vec<Instruction>>, Vec<Instruction>), CompileError> {
-        // First pass: compile all defun, defmacro, def, module, and import expressions
+        // Modules defined in this unit, so an import can initialize its target first
+        let mut unit_modules: HashMap<String, usize> = HashMap::new();
+        for (i, expr) in exprs.iter().enumerate() {
+            if let LispExpr::List(items) = &expr.expr {
+                if let (Some(LispExpr::Symbol(head)), Some(LispExpr::Symbol(name))) =
+                    (items.first().map(|e| &e.expr), items.get(1).map(|e| &e.expr))
+                {
+                    if head == "module" {
+                        unit_modules.entry(name.clone()).or_insert(i);
+                    }
+                }
+            }
+        }
+        let mut done_modules = HashSet::new();
+        let mut module_chain = Vec::new();
+        let mut provided = Vec::new();
+
+        // Record defun names and &key functions up front, so a defun can call one defined after it
         for expr in exprs {
+            if let LispExpr::List(items) = &expr.expr {
+                if let [head, name, rest @ ..] = items.as_slice() {
+                    if let (LispExpr::Symbol(head), LispExpr::Symbol(name)) = (&head.expr, &name.expr) {
+                        if head == "defun" {
+                            self.defined_functions.insert(name.clone());
+                            if let Some(params) = rest.first().filter(|params| self.looks_like_param_list(params)) {
+                                if let Ok(parsed) = Self::parse_params(params) {
+                                    self.record_key_params(name.clone(), &parsed);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // First pass: compile all defun, defmacro, def, module, and import expressions.
+        // Definitions run in source order, except that an imported module is always
+        // initialized before whatever imports it (see compile_module_in_order).
+        for (i, expr) in exprs.iter().enumerate() {
             if let LispExpr::List(items) = &expr.expr {
                 if let Some(first) = items.first() {
                     if let LispExpr::Symbol(s) = &first.expr {
@@ -2837,12 +4257,17 @@ impl Compiler {
                             self.compile_defun(expr)?;
                         } else if s == "defmacro" {
                             self.compile_defmacro(expr)?;
-                        } else if s == "def" {
+                        } else if s == "def" || s == "def-mutable" {
                             self.compile_def(expr)?;
                         } else if s == "module" {
-                            self.compile_module(expr)?;
+                            self.compile_module_in_order(i, exprs, &unit_modules, &mut done_modules, &mut module_chain)?;
                         } else if s == "import" {
+                            if let Some(&target) = Self::import_target(expr).and_then(|(name, _)| unit_modules.get(&name)) {
+                                self.compile_module_in_order(target, exprs, &unit_modules, &mut done_modules, &mut module_chain)?;
+                            }
                             self.compile_import(expr)?;
+                        } else if s == "provide" {
+                            provided.extend(self.compile_provide(expr, &unit_modules)?);
                         } else if s == "defvar" {
                             // defvar has been removed - provide helpful error
                             return Err(CompileError::new(
@@ -2861,13 +4286,18 @@ impl Compiler {
             }
         }
 
+        // Every module is registered by now, so the file can claim the ones it provides
+        if !provided.is_empty() {
+            self.emit(Instruction::ProvideModules(provided));
+        }
+
         // Second pass: compile non-definition expressions into main bytecode
         // We need to track and pop intermediate results
         let non_def_exprs: Vec<_> = exprs.iter().enumerate().filter_map(|(i, expr)| {
             let is_definition = if let LispExpr::List(items) = &expr.expr {
                 if let Some(first) = items.first() {
                     if let LispExpr::Symbol(s) = &first.expr {
-                        s == "defun" || s == "defmacro" || s == "def" || s == "module" || s == "import"
+                        s == "defun" || s == "defmacro" || s == "def" || s == "def-mutable" || s == "module" || s == "import" || s == "provide"
                     } else {
                         false
                     }
@@ -2891,17 +4321,32 @@ impl Compiler {
             // Pop the result if it's not the last expression
             // This prevents values from accumulating on the stack between top-level expressions
             if idx < num_non_defs - 1 {
-                self.emit(Instruction::PopN(1));
+                self.emit_consuming(Instruction::PopN(1), 1);
             }
         }
 
         // Emit Halt at end of main bytecode
         self.emit(Instruction::Halt);
 
+        // Every function is defined by now, so forward references resolve
+        if self.strict_mode {
+            self.check_called_functions_exist()?;
+        }
+
         // Return (functions, main bytecode)
         Ok((self.functions.clone(), self.bytecode.clone()))
     }
 
+    /// Compile a program into a `Program`, which also carries each function's
+    /// parameter list and arity so `Program::disassemble_program` can show
+    /// them and `Program::verify` can check against them.
+    pub fn compile_to_program(&mut self, exprs: &[SourceExpr]) -> Result<Program, CompileError> {
+        let (functions, main) = self.compile_program(exprs)?;
+        Ok(Program::new(functions, main)
+            .with_params(self.function_params.clone())
+            .with_arities(self.function_arities.clone()))
+    }
+
     // ==================== FFI TYPE PARSING ====================
 
     /// Parse FFI argument types from a list expression
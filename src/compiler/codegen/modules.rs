@@ -1,9 +1,10 @@
 // Module system: module definitions, exports, imports
 
 use crate::vm::instructions::Instruction;
-use crate::vm::errors::CompileError;
+use crate::vm::errors::{CompileError, Location};
 use super::Compiler;
 use super::super::ast::{LispExpr, SourceExpr};
+use std::collections::{HashMap, HashSet};
 
 // ==================== MODULE SYSTEM ====================
 
@@ -22,6 +23,87 @@ impl Compiler {
         self.module_exports.insert(module.to_string(), exports.clone());
     }
 
+    /// Compile a module declaration after the modules it imports.
+    ///
+    /// Initialization order within one compilation unit is:
+    /// 1. Every module imported by a module (or by a top-level `import`) runs
+    ///    its top-level forms before the importer, even if it appears later
+    ///    in the source.
+    /// 2. Everything else runs in source order, and each module runs once.
+    ///
+    /// `unit_modules` maps module names to their definitions in this unit,
+    /// `done` holds the indices of modules already compiled, and `in_progress`
+    /// is the current import chain, used to report circular imports.
+    pub(super) fn compile_module_in_order(
+        &mut self,
+        index: usize,
+        exprs: &[SourceExpr],
+        unit_modules: &HashMap<String, usize>,
+        done: &mut HashSet<usize>,
+        in_progress: &mut Vec<String>,
+    ) -> Result<(), CompileError> {
+        if done.contains(&index) {
+            return Ok(());
+        }
+        let expr = &exprs[index];
+        let name = match &expr.expr {
+            LispExpr::List(items) => match items.get(1).map(|i| &i.expr) {
+                Some(LispExpr::Symbol(s)) => s.clone(),
+                _ => return self.compile_module(expr),
+            },
+            _ => return self.compile_module(expr),
+        };
+
+        in_progress.push(name.clone());
+        for (dep, location) in Self::module_imports(expr) {
+            if dep == name {
+                continue;
+            }
+            if in_progress.contains(&dep) {
+                let mut chain = in_progress.clone();
+                chain.push(dep);
+                return Err(CompileError::new(
+                    format!("Circular import between modules: {}", chain.join(" -> ")),
+                    location,
+                ));
+            }
+            if let Some(&dep_index) = unit_modules.get(&dep) {
+                self.compile_module_in_order(dep_index, exprs, unit_modules, done, in_progress)?;
+            }
+        }
+        in_progress.pop();
+
+        done.insert(index);
+        self.compile_module(expr)
+    }
+
+    /// Names of the modules imported by a module body, with their locations
+    fn module_imports(expr: &SourceExpr) -> Vec<(String, Location)> {
+        let mut imports = Vec::new();
+        if let LispExpr::List(items) = &expr.expr {
+            for item in items.iter().skip(2) {
+                if let Some(import) = Self::import_target(item) {
+                    imports.push(import);
+                }
+            }
+        }
+        imports
+    }
+
+    /// The module named by an `(import name ...)` form, if `expr` is one
+    pub(super) fn import_target(expr: &SourceExpr) -> Option<(String, Location)> {
+        if let LispExpr::List(inner) = &expr.expr {
+            if let (Some(LispExpr::Symbol(head)), Some(target)) = (inner.first().map(|e| &e.expr), inner.get(1)) {
+                if head == "import" {
+                    if let LispExpr::Symbol(name) = &target.expr {
+                        return Some((name.clone(), target.location.clone()));
+                    }
+                }
+            }
+        }
+        None
+    }
+
     /// Compile a module declaration
     /// (module name (export sym1 sym2 ...) body...)
     pub(super) fn compile_module(&mut self, expr: &SourceExpr) -> Result<(), CompileError> {
@@ -117,13 +199,18 @@ impl Compiler {
                             "export" | "import" => {
                                 // Already processed
                             }
+                            "provide" => return Err(CompileError::with_suggestion(
+                                "provide must appear at the top level of a file".to_string(),
+                                item.location.clone(),
+                                format!("Move (provide {}) outside the module", module_name),
+                            )),
                             "defun" => self.compile_defun(item)?,
                             "defmacro" => self.compile_defmacro(item)?,
-                            "def" => self.compile_def(item)?,
+                            "def" | "def-mutable" => self.compile_def(item)?,
                             _ => {
                                 // Other expressions in module body - compile as main code
                                 self.compile_expr(item)?;
-                                self.emit(Instruction::PopN(1));
+                                self.emit_consuming(Instruction::PopN(1), 1);
                             }
                         }
                         continue;
@@ -132,16 +219,70 @@ impl Compiler {
             }
             // Compile other expressions in module
             self.compile_expr(item)?;
-            self.emit(Instruction::PopN(1));
+            self.emit_consuming(Instruction::PopN(1), 1);
         }
 
         // Exit module context and clear module-local state
         self.current_module = None;
         self.module_functions.clear();
 
+        // Carry the export list into the VM so it can be inspected at runtime
+        let mut exports: Vec<String> = self.module_exports
+            .get(&module_name)
+            .map(|e| e.iter().cloned().collect())
+            .unwrap_or_default();
+        exports.sort();
+        self.emit(Instruction::RegisterModule(module_name, exports));
+
         Ok(())
     }
 
+    /// Compile a provide declaration
+    /// (provide module-name ...) - declare the modules this file defines
+    ///
+    /// Modules and imports are resolved at compile time, while require runs a
+    /// file at runtime. provide is checked on both sides: each named module
+    /// must be defined in the same file, and the VM rejects a module provided
+    /// by two different files.
+    pub(super) fn compile_provide(&self, expr: &SourceExpr, unit_modules: &HashMap<String, usize>) -> Result<Vec<String>, CompileError> {
+        let items = match &expr.expr {
+            LispExpr::List(items) => items,
+            _ => return Err(CompileError::new(
+                "provide expects a list".to_string(),
+                expr.location.clone(),
+            )),
+        };
+
+        if items.len() < 2 {
+            return Err(CompileError::with_suggestion(
+                "provide expects at least one module name".to_string(),
+                expr.location.clone(),
+                "Use: (provide math)".to_string(),
+            ));
+        }
+
+        let mut modules = Vec::new();
+        for item in items.iter().skip(1) {
+            let name = match &item.expr {
+                LispExpr::Symbol(s) => s.clone(),
+                _ => return Err(CompileError::new(
+                    "Provided module names must be symbols".to_string(),
+                    item.location.clone(),
+                )),
+            };
+            if !unit_modules.contains_key(&name) {
+                return Err(CompileError::with_suggestion(
+                    format!("File provides module '{}' but does not define it", name),
+                    item.location.clone(),
+                    format!("Add (module {} (export ...) ...) to this file", name),
+                ));
+            }
+            modules.push(name);
+        }
+
+        Ok(modules)
+    }
+
     /// Compile an export declaration
     /// (export sym1 sym2 ...) or (export (sym1 sym2 ...))
     pub(super) fn compile_export(&mut self, expr: &SourceExpr, module_name: &str) -> Result<(), CompileError> {
@@ -230,7 +371,10 @@ impl Compiler {
                 return Err(CompileError::with_suggestion(
                     format!("Unknown module '{}'", module_name),
                     items[1].location.clone(),
-                    format!("Make sure module '{}' is defined before importing it.", module_name),
+                    format!(
+                        "Define module '{}' in this file. A module from a required file can only be imported by files required after it; elsewhere, call its functions by qualified name ({}/name).",
+                        module_name, module_name
+                    ),
                 ));
             }
         } else {
@@ -1,5 +1,6 @@
-// Special forms: let, loop, recur, cond, and, or
+// Special forms: let, letrec, if-let, loop, recur, cond, case, and, or
 
+use std::sync::Arc;
 use crate::vm::value::Value;
 use crate::vm::instructions::Instruction;
 use crate::vm::errors::CompileError;
@@ -7,7 +8,7 @@ use super::Compiler;
 use super::types::ValueLocation;
 use super::super::ast::{LispExpr, SourceExpr};
 
-// ==================== SPECIAL FORMS (LET, LOOP, RECUR, COND, AND, OR) ====================
+// ==================== SPECIAL FORMS (LET, LETREC, IF-LET, LOOP, RECUR, COND, CASE, AND, OR) ====================
 
 impl Compiler {
     // Compile let expression: (let ((pattern value) ...) body)
@@ -60,14 +61,28 @@ impl Compiler {
             self.in_tail_position = false;
 
             // Compile the value expression (pushes result onto stack)
+            let value_position = self.stack_depth;
             self.compile_expr(value_expr)?;
 
             // Restore tail position
             self.in_tail_position = saved_tail;
 
-            // The value is now on the stack at position stack_depth
-            let value_position = self.stack_depth;
-            self.stack_depth += 1;
+            // Fast path for (h . t): split the list once with Uncons and keep
+            // head and tail in their own slots instead of re-walking on every use
+            if let Some((head_name, tail_name)) = Self::simple_uncons_pattern(pattern) {
+                self.emit(Instruction::Uncons);
+                self.stack_depth += 1;
+                for (offset, name) in [head_name, tail_name].into_iter().enumerate() {
+                    Self::check_binding_name(name, &pattern.location)?;
+                    if name != "_" {
+                        self.bind_local(name.to_string(), value_position + offset);
+                    }
+                    num_bindings += 1;
+                }
+                continue;
+            }
+
+            // The value is now on the stack at position value_position
             num_bindings += 1;
 
             // Bind the pattern to this stack position
@@ -92,6 +107,548 @@ impl Compiler {
         Ok(())
     }
 
+    // Compile letrec: (letrec ((name (lambda ...)) ...) body)
+    // Every binding must be a lambda. Closures capture by value, so each one is
+    // first built with placeholder slots for the whole group; LetrecFill then
+    // points those slots at the other members, and a member loading a sibling
+    // re-ties it with LoadLetrec, so all of them see the final closures
+    pub(super) fn compile_letrec(
+        &mut self,
+        bindings_expr: &SourceExpr,
+        body_expr: &SourceExpr,
+    ) -> Result<(), CompileError> {
+        let bindings = match &bindings_expr.expr {
+            LispExpr::List(b) => b,
+            _ => {
+                return Err(CompileError::new(
+                    "letrec bindings must be a list".to_string(),
+                    bindings_expr.location.clone(),
+                ));
+            }
+        };
+
+        let mut names = Vec::new();
+        let mut lambdas = Vec::new();
+        for binding in bindings {
+            let (name, value) = match &binding.expr {
+                LispExpr::List(pair) if pair.len() == 2 => match &pair[0].expr {
+                    LispExpr::Symbol(name) => {
+                        Self::check_binding_name(name, &pair[0].location)?;
+                        (name, &pair[1])
+                    }
+                    _ => {
+                        return Err(CompileError::new(
+                            "letrec binding name must be a symbol".to_string(),
+                            pair[0].location.clone(),
+                        ));
+                    }
+                },
+                _ => {
+                    return Err(CompileError::new(
+                        "Each letrec binding must be a list (name (lambda ...))".to_string(),
+                        binding.location.clone(),
+                    ));
+                }
+            };
+            match &value.expr {
+                LispExpr::List(parts) if parts.len() == 3 && matches!(&parts[0].expr, LispExpr::Symbol(s) if s == "lambda") => {
+                    names.push(name.clone());
+                    lambdas.push((&parts[1], &parts[2]));
+                }
+                _ => {
+                    return Err(CompileError::with_suggestion(
+                        format!("letrec binding '{}' must be a lambda", name),
+                        value.location.clone(),
+                        "Only functions can refer to each other before they exist; bind other values with let".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let saved_bindings = self.local_bindings.clone();
+        let saved_stack_depth = self.stack_depth;
+        let saved_tail = self.in_tail_position;
+        self.in_tail_position = false;
+
+        for (params, body) in &lambdas {
+            self.compile_group_lambda(params, body, &names)?;
+            self.stack_depth += 1;
+        }
+        if !names.is_empty() {
+            self.emit(Instruction::LetrecFill(names.len()));
+        }
+        for (i, name) in names.iter().enumerate() {
+            self.bind_local(name.clone(), saved_stack_depth + i);
+        }
+
+        // Body inherits tail position from letrec
+        self.in_tail_position = saved_tail;
+        self.compile_expr(body_expr)?;
+
+        if !names.is_empty() {
+            self.emit(Instruction::Slide(names.len()));
+        }
+
+        self.local_bindings = saved_bindings;
+        self.stack_depth = saved_stack_depth;
+
+        Ok(())
+    }
+
+    // Compile if-let / when-let: (if-let (var expr) then else)
+    // expr goes into a let slot; var is bound to it in the then-branch when the
+    // value is truthy (anything but false and the empty list). Both branches
+    // meet before one Slide drops the slot
+    pub(super) fn compile_if_let(
+        &mut self,
+        binding: &SourceExpr,
+        then_expr: &SourceExpr,
+        else_expr: Option<&SourceExpr>,
+    ) -> Result<(), CompileError> {
+        let (name, value_expr) = match &binding.expr {
+            LispExpr::List(pair) if pair.len() == 2 => match &pair[0].expr {
+                LispExpr::Symbol(name) => {
+                    Self::check_binding_name(name, &pair[0].location)?;
+                    (name.clone(), &pair[1])
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        "if-let binding name must be a symbol".to_string(),
+                        pair[0].location.clone(),
+                    ));
+                }
+            },
+            _ => {
+                return Err(CompileError::new(
+                    "if-let binding must be a list (var expr)".to_string(),
+                    binding.location.clone(),
+                ));
+            }
+        };
+
+        let saved_bindings = self.local_bindings.clone();
+        let saved_stack_depth = self.stack_depth;
+        let saved_tail = self.in_tail_position;
+
+        self.in_tail_position = false;
+        let slot = self.stack_depth;
+        self.compile_expr(value_expr)?;
+
+        // false and '() both take the else-branch
+        let mut else_jumps = Vec::new();
+        self.emit(Instruction::GetLocal(slot));
+        self.emit(Instruction::Push(Value::Boolean(false)));
+        self.emit(Instruction::Eq);
+        else_jumps.push(self.bytecode.len());
+        self.emit(Instruction::JmpIfTrue(0));
+        self.emit(Instruction::GetLocal(slot));
+        self.emit(Instruction::IsNull);
+        else_jumps.push(self.bytecode.len());
+        self.emit(Instruction::JmpIfTrue(0));
+        let branch_depth = self.stack_depth;
+
+        // Then-branch sees var (inherits tail position)
+        self.bind_local(name, slot);
+        self.in_tail_position = saved_tail;
+        self.compile_expr(then_expr)?;
+        self.local_bindings = saved_bindings.clone();
+
+        let jmp_to_end_index = self.bytecode.len();
+        self.emit(Instruction::Jmp(0));
+
+        // Else-branch runs without var
+        let else_addr = self.instruction_address;
+        for index in else_jumps {
+            self.bytecode[index] = Instruction::JmpIfTrue(else_addr);
+        }
+        self.stack_depth = branch_depth;
+        match else_expr {
+            Some(else_expr) => {
+                self.compile_expr(else_expr)?;
+            }
+            None => self.emit(Instruction::Push(Value::Boolean(false))),
+        }
+
+        let end_addr = self.instruction_address;
+        self.bytecode[jmp_to_end_index] = Instruction::Jmp(end_addr);
+        self.emit(Instruction::Slide(1));
+
+        self.local_bindings = saved_bindings;
+        self.stack_depth = saved_stack_depth;
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    // Compile set!: (set! var value)
+    // Assigns a let-bound local (SetLocal into its stack slot) or a global
+    // defined with def-mutable, and evaluates to the new value. Closures
+    // capture values, so a variable that a closure captures, inside it or in
+    // the scope that created it, cannot be assigned.
+    pub(super) fn compile_set(&mut self, items: &[SourceExpr], expr: &SourceExpr) -> Result<(), CompileError> {
+        if items.len() != 3 {
+            return Err(CompileError::new(
+                "set! expects exactly: (set! var value)".to_string(),
+                expr.location.clone(),
+            ));
+        }
+        let name = match &items[1].expr {
+            LispExpr::Symbol(name) if !name.starts_with("__STRING__") => name.clone(),
+            _ => {
+                return Err(CompileError::new(
+                    "set! target must be a variable name".to_string(),
+                    items[1].location.clone(),
+                ));
+            }
+        };
+
+        let saved_tail = self.in_tail_position;
+        self.in_tail_position = false;
+
+        let location = self.local_bindings.get(&name).or_else(|| self.pattern_bindings.get(&name));
+        if let Some(ValueLocation::Local(slot)) = location {
+            let slot = *slot;
+            if self.captured_locals.contains(&slot) {
+                return Err(CompileError::new(
+                    format!("Cannot set! '{}' - a closure has captured it and would keep the old value", name),
+                    items[1].location.clone(),
+                ));
+            }
+            self.assigned_locals.insert(slot);
+            self.compile_expr(&items[2])?;
+            self.emit_consuming(Instruction::SetLocal(slot), 1);
+            self.emit(Instruction::GetLocal(slot));
+        } else if let Some(ValueLocation::Captured(_) | ValueLocation::LetrecCaptured(..)) = location {
+            return Err(CompileError::new(
+                format!("Cannot set! '{}' - it is captured by this closure, which holds a copy", name),
+                items[1].location.clone(),
+            ));
+        } else if location.is_some() || self.param_names.contains(&name) {
+            return Err(CompileError::new(
+                format!("Cannot set! parameter '{}' - only let-bound locals and def-mutable globals can be assigned", name),
+                items[1].location.clone(),
+            ));
+        } else {
+            let resolved = self.resolve_global_name(&name);
+            // Globals from an earlier compilation (e.g. a previous REPL line) are
+            // mutable unless the caller marked them with with_immutable_globals
+            let global = [&resolved, &name].into_iter()
+                .find_map(|candidate| self.global_vars.get(candidate).map(|mutable| (candidate.clone(), *mutable)))
+                .or_else(|| [&resolved, &name].into_iter()
+                    .find(|candidate| self.known_globals.contains(*candidate))
+                    .map(|candidate| (candidate.clone(), !self.immutable_globals.contains(candidate))));
+            let target = match global {
+                Some((target, true)) => target,
+                Some((_, false)) => {
+                    return Err(CompileError::with_suggestion(
+                        format!("Cannot set! '{}' - it was defined with def and is immutable", name),
+                        items[1].location.clone(),
+                        format!("Define it with (def-mutable {} ...) to allow assignment", name),
+                    ));
+                }
+                None => {
+                    return Err(CompileError::new(
+                        format!("Cannot set! '{}' - it is not bound", name),
+                        items[1].location.clone(),
+                    ));
+                }
+            };
+            self.compile_expr(&items[2])?;
+            self.emit_consuming(Instruction::StoreGlobal(target.clone()), 1);
+            self.emit(Instruction::LoadGlobal(target));
+        }
+
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    // Compile an interpolated string, (#interpolate "a " x " b"): each part
+    // is pushed and Interpolate joins their display forms
+    pub(super) fn compile_interpolation(&mut self, items: &[SourceExpr]) -> Result<(), CompileError> {
+        let saved_tail = self.in_tail_position;
+        self.in_tail_position = false;
+        for item in &items[1..] {
+            self.compile_expr(item)?;
+        }
+        self.emit(Instruction::Interpolate(items.len() - 1));
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    // Compile incf/decf: (incf var) / (incf var delta)
+    // Rewritten to (set! var (+ var delta)), or - for decf; delta defaults to 1
+    pub(super) fn compile_incf(&mut self, items: &[SourceExpr], expr: &SourceExpr) -> Result<(), CompileError> {
+        let form = match &items[0].expr {
+            LispExpr::Symbol(s) => s.clone(),
+            _ => unreachable!("incf/decf dispatched on a symbol"),
+        };
+        if items.len() != 2 && items.len() != 3 {
+            return Err(CompileError::new(
+                format!("{} expects a variable and an optional amount", form),
+                expr.location.clone(),
+            ));
+        }
+
+        let location = expr.location.clone();
+        let node = |e: LispExpr| SourceExpr::new(e, location.clone());
+        let operator = if form == "incf" { "+" } else { "-" };
+        let delta = items.get(2).cloned().unwrap_or_else(|| node(LispExpr::Number(1)));
+        let update = node(LispExpr::List(vec![
+            node(LispExpr::Symbol(operator.to_string())),
+            items[1].clone(),
+            delta,
+        ]));
+        let rewritten = [node(LispExpr::Symbol("set!".to_string())), items[1].clone(), update];
+        self.compile_set(&rewritten, expr)
+    }
+
+    // Compile dotimes/dolist: (dotimes (var count) body ...) / (dolist (var list) body ...)
+    //   dotimes: [count] [var=0]  TOP: var < count? else END; body...; var += 1; Jmp TOP
+    //   dolist:  [rest]  [var]    TOP: rest empty? then END; var = car, rest = cdr; body...; Jmp TOP
+    //   END: Push(false); Slide(2)
+    // dotimes checks its count first: anything but an integer raises a type error
+    // The body is never in tail position (the loop continues after it), and
+    // both slots are dropped when the loop ends; the result is false
+    pub(super) fn compile_do_iteration(&mut self, items: &[SourceExpr], expr: &SourceExpr) -> Result<(), CompileError> {
+        let form = match &items[0].expr {
+            LispExpr::Symbol(s) => s.clone(),
+            _ => unreachable!("dotimes/dolist dispatched on a symbol"),
+        };
+        let what = if form == "dotimes" { "count" } else { "list" };
+        let (name, source) = match items.get(1).map(|spec| &spec.expr) {
+            Some(LispExpr::List(spec)) if spec.len() == 2 && items.len() >= 3 => match &spec[0].expr {
+                LispExpr::Symbol(name) if !name.starts_with("__STRING__") => {
+                    Self::check_binding_name(name, &spec[0].location)?;
+                    (name.clone(), &spec[1])
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        format!("{} variable must be a symbol", form),
+                        spec[0].location.clone(),
+                    ));
+                }
+            },
+            _ => {
+                return Err(CompileError::new(
+                    format!("{} expects ({} (var {}) body ...)", form, form, what),
+                    expr.location.clone(),
+                ));
+            }
+        };
+
+        let saved_bindings = self.local_bindings.clone();
+        let saved_stack_depth = self.stack_depth;
+        let saved_tail = self.in_tail_position;
+        self.in_tail_position = false;
+
+        // Hidden slot: the count, or the part of the list not visited yet
+        let source_slot = self.stack_depth;
+        self.compile_expr(source)?;
+        if form == "dotimes" {
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::IsInteger);
+            let ok_index = self.bytecode.len();
+            self.emit(Instruction::JmpIfTrue(0));
+            self.emit(Instruction::Push(Value::String(Arc::new("Type error: 'dotimes' expects an integer count, got ~a".to_string()))));
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::TypeOf);
+            self.emit(Instruction::MakeList(1));
+            self.emit(Instruction::Format);
+            self.emit(Instruction::Raise);
+            let ok_addr = self.instruction_address;
+            self.patch_jump(ok_index, ok_addr);
+        }
+        let var_slot = self.stack_depth;
+        self.emit(Instruction::Push(if form == "dotimes" { Value::Integer(0) } else { Value::Boolean(false) }));
+        self.stack_depth += 1;
+
+        let top_addr = self.instruction_address;
+        let exit_index;
+        if form == "dotimes" {
+            self.emit(Instruction::GetLocal(var_slot));
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::Lt);
+            exit_index = self.bytecode.len();
+            self.emit(Instruction::JmpIfFalse(0));
+        } else {
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::IsNull);
+            exit_index = self.bytecode.len();
+            self.emit(Instruction::JmpIfTrue(0));
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::Car);
+            self.emit(Instruction::SetLocal(var_slot));
+            self.emit(Instruction::GetLocal(source_slot));
+            self.emit(Instruction::Cdr);
+            self.emit(Instruction::SetLocal(source_slot));
+        }
+
+        self.bind_local(name, var_slot);
+        for body in &items[2..] {
+            self.compile_expr(body)?;
+            self.emit_consuming(Instruction::PopN(1), 1);
+        }
+
+        if form == "dotimes" {
+            self.emit(Instruction::GetLocal(var_slot));
+            self.emit(Instruction::Push(Value::Integer(1)));
+            self.emit(Instruction::Add);
+            self.emit(Instruction::SetLocal(var_slot));
+        }
+        self.emit(Instruction::Jmp(top_addr));
+
+        let end_addr = self.instruction_address;
+        self.patch_jump(exit_index, end_addr);
+        self.emit(Instruction::Push(Value::Boolean(false)));
+        self.emit(Instruction::Slide(2));
+
+        self.local_bindings = saved_bindings;
+        self.stack_depth = saved_stack_depth;
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    // Compile try: (try expr (catch var handler ...))
+    //   PushHandler(C); expr; PopHandler; Jmp(END); C: handler ...; Slide(1); END:
+    // On an error the VM restores the stack to where PushHandler ran and pushes
+    // the message, which becomes var's slot for the handler expressions
+    pub(super) fn compile_try(&mut self, body: &SourceExpr, clause: &SourceExpr) -> Result<(), CompileError> {
+        let (name, handlers) = match &clause.expr {
+            LispExpr::List(parts) if parts.len() >= 3 && matches!(&parts[0].expr, LispExpr::Symbol(s) if s == "catch") => {
+                match &parts[1].expr {
+                    LispExpr::Symbol(name) => {
+                        Self::check_binding_name(name, &parts[1].location)?;
+                        (name.clone(), &parts[2..])
+                    }
+                    _ => {
+                        return Err(CompileError::new(
+                            "catch variable must be a symbol".to_string(),
+                            parts[1].location.clone(),
+                        ));
+                    }
+                }
+            }
+            _ => {
+                return Err(CompileError::new(
+                    "try clause must be (catch var handler ...)".to_string(),
+                    clause.location.clone(),
+                ));
+            }
+        };
+
+        let saved_bindings = self.local_bindings.clone();
+        let saved_stack_depth = self.stack_depth;
+        let saved_tail = self.in_tail_position;
+
+        // Neither part is in tail position: the handler must stay installed
+        // while expr runs, and the catch variable is dropped after the handler
+        self.in_tail_position = false;
+        let push_handler_index = self.bytecode.len();
+        self.emit(Instruction::PushHandler(0));
+        self.compile_expr(body)?;
+        self.emit(Instruction::PopHandler);
+        let jmp_to_end_index = self.bytecode.len();
+        self.emit(Instruction::Jmp(0));
+
+        let catch_addr = self.instruction_address;
+        self.patch_jump(push_handler_index, catch_addr);
+        self.stack_depth = saved_stack_depth;
+        let slot = self.stack_depth;
+        self.stack_depth += 1;
+        self.bind_local(name, slot);
+
+        for handler in &handlers[..handlers.len() - 1] {
+            self.compile_expr(handler)?;
+            self.emit_consuming(Instruction::PopN(1), 1);
+        }
+        self.compile_expr(&handlers[handlers.len() - 1])?;
+        self.emit(Instruction::Slide(1));
+
+        let end_addr = self.instruction_address;
+        self.patch_jump(jmp_to_end_index, end_addr);
+
+        self.local_bindings = saved_bindings;
+        self.stack_depth = saved_stack_depth;
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    // Compile named let: (let name ((var init) ...) body ...)
+    // Same as ((letrec ((name (lambda (var ...) body ...))) name) init ...): the
+    // inits are evaluated outside the loop, and calling name in tail position
+    // reuses the frame like any closure tail call
+    pub(super) fn compile_named_let(
+        &mut self,
+        name: &str,
+        rest: &[SourceExpr],
+        expr: &SourceExpr,
+    ) -> Result<(), CompileError> {
+        let location = &expr.location;
+        if rest.len() < 2 {
+            return Err(CompileError::new(
+                format!("named let '{}' expects bindings and at least one body expression", name),
+                location.clone(),
+            ));
+        }
+        let bindings = match &rest[0].expr {
+            LispExpr::List(b) => b,
+            _ => {
+                return Err(CompileError::new(
+                    "let bindings must be a list".to_string(),
+                    rest[0].location.clone(),
+                ));
+            }
+        };
+
+        let mut params = Vec::new();
+        let mut inits = Vec::new();
+        for binding in bindings {
+            match &binding.expr {
+                LispExpr::List(pair) if pair.len() == 2 && matches!(pair[0].expr, LispExpr::Symbol(_)) => {
+                    params.push(pair[0].clone());
+                    inits.push(pair[1].clone());
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        "Each named let binding must be (variable value)".to_string(),
+                        binding.location.clone(),
+                    ));
+                }
+            }
+        }
+
+        let node = |e: LispExpr| SourceExpr::new(e, location.clone());
+        let symbol = |s: &str| node(LispExpr::Symbol(s.to_string()));
+        let body = if rest.len() == 2 {
+            rest[1].clone()
+        } else {
+            let mut forms = vec![symbol("do")];
+            forms.extend_from_slice(&rest[1..]);
+            node(LispExpr::List(forms))
+        };
+        let lambda = node(LispExpr::List(vec![symbol("lambda"), node(LispExpr::List(params)), body]));
+        let letrec = node(LispExpr::List(vec![
+            symbol("letrec"),
+            node(LispExpr::List(vec![node(LispExpr::List(vec![symbol(name), lambda]))])),
+            symbol(name),
+        ]));
+        let mut call = vec![letrec];
+        call.extend(inits);
+        self.compile_expr(&node(LispExpr::List(call)))?;
+        Ok(())
+    }
+
+    // Match a (head . tail) pattern whose parts are both plain symbols
+    fn simple_uncons_pattern(pattern: &SourceExpr) -> Option<(&str, &str)> {
+        if let LispExpr::DottedList(items, rest) = &pattern.expr {
+            if let ([head], LispExpr::Symbol(tail)) = (items.as_slice(), &rest.expr) {
+                if let LispExpr::Symbol(head) = &head.expr {
+                    return Some((head.as_str(), tail.as_str()));
+                }
+            }
+        }
+        None
+    }
+
     pub(super) fn compile_loop(
         &mut self,
         bindings_expr: &SourceExpr,
@@ -139,7 +696,10 @@ impl Compiler {
 
             // Get binding name (must be a symbol)
             let name = match &name_expr.expr {
-                LispExpr::Symbol(s) => s.clone(),
+                LispExpr::Symbol(s) => {
+                    Self::check_binding_name(s, &name_expr.location)?;
+                    s.clone()
+                }
                 _ => {
                     return Err(CompileError::new(
                         "loop binding name must be a symbol".to_string(),
@@ -151,16 +711,15 @@ impl Compiler {
             // Compile the value expression (pushes result onto stack)
             let saved_tail = self.in_tail_position;
             self.in_tail_position = false;
+            let value_position = self.stack_depth;
             self.compile_expr(value_expr)?;
             self.in_tail_position = saved_tail;
 
-            // The value is now on the stack at position stack_depth
-            let value_position = self.stack_depth;
-            self.stack_depth += 1;
+            // The value is now on the stack at position value_position
             num_bindings += 1;
 
             // Create local binding
-            self.local_bindings.insert(name.clone(), ValueLocation::Local(value_position));
+            self.bind_local(name.clone(), value_position);
             _binding_names.push(name);
         }
 
@@ -202,96 +761,63 @@ impl Compiler {
         Ok(())
     }
 
-    // Helper for compiling and: (and a b c) => (if a (if b c false) false)
-    pub(super) fn compile_and_helper(&mut self, exprs: &[SourceExpr], context: &SourceExpr) -> Result<(), CompileError> {
-        if exprs.is_empty() {
-            // Empty and is true
-            self.emit(Instruction::Push(Value::Boolean(true)));
-            return Ok(());
-        }
+    // Helper for compiling and: (and a b c) tests a and b in turn, jumping
+    // to one shared `false` as soon as one fails; c is the result otherwise
+    //
+    //   a; JmpIfFalse(F); b; JmpIfFalse(F); c; Jmp(END); F: Push(false); END:
+    pub(super) fn compile_and_helper(&mut self, exprs: &[SourceExpr]) -> Result<(), CompileError> {
+        self.compile_short_circuit(exprs, false)
+    }
 
-        if exprs.len() == 1 {
-            // Last expression - just compile it
-            self.compile_expr(&exprs[0])?;
+    // Helper for compiling or: (or a b c) tests a and b in turn, jumping to
+    // one shared `true` as soon as one holds; c is the result otherwise
+    //
+    //   a; JmpIfTrue(T); b; JmpIfTrue(T); c; Jmp(END); T: Push(true); END:
+    pub(super) fn compile_or_helper(&mut self, exprs: &[SourceExpr]) -> Result<(), CompileError> {
+        self.compile_short_circuit(exprs, true)
+    }
+
+    // Shared body of and/or. `stop_on` is the condition value that ends the
+    // form early; since conditions are booleans, pushing it again at the
+    // exit gives back exactly the value that was tested.
+    fn compile_short_circuit(&mut self, exprs: &[SourceExpr], stop_on: bool) -> Result<(), CompileError> {
+        let Some((last, tests)) = exprs.split_last() else {
+            // Empty and is true, empty or is false
+            self.emit(Instruction::Push(Value::Boolean(!stop_on)));
             return Ok(());
-        }
+        };
 
-        // Multiple expressions: if first then (and rest...) else false
         let saved_tail = self.in_tail_position;
 
-        // Compile first expression (not in tail position)
+        // Tested expressions are never in tail position
         self.in_tail_position = false;
-        self.compile_expr(&exprs[0])?;
-
-        // Emit JmpIfFalse with placeholder
-        let jmp_if_false_index = self.bytecode.len();
-        self.emit(Instruction::JmpIfFalse(0));
-
-        // Compile rest (inherits tail position)
-        self.in_tail_position = saved_tail;
-        self.compile_and_helper(&exprs[1..], context)?;
-
-        // Emit Jmp to skip false branch
-        let jmp_to_end_index = self.bytecode.len();
-        self.emit(Instruction::Jmp(0));
-
-        // False branch
-        let false_addr = self.instruction_address;
-        self.bytecode[jmp_if_false_index] = Instruction::JmpIfFalse(false_addr);
-        self.emit(Instruction::Push(Value::Boolean(false)));
-
-        // End
-        let end_addr = self.instruction_address;
-        self.bytecode[jmp_to_end_index] = Instruction::Jmp(end_addr);
-
-        self.in_tail_position = saved_tail;
-        Ok(())
-    }
-
-    // Helper for compiling or: (or a b c) => (if a true (if b true c))
-    pub(super) fn compile_or_helper(&mut self, exprs: &[SourceExpr], context: &SourceExpr) -> Result<(), CompileError> {
-        if exprs.is_empty() {
-            // Empty or is false
-            self.emit(Instruction::Push(Value::Boolean(false)));
-            return Ok(());
+        let mut exit_jumps = Vec::new();
+        for test in tests {
+            self.compile_expr(test)?;
+            exit_jumps.push(self.bytecode.len());
+            self.emit_consuming(if stop_on { Instruction::JmpIfTrue(0) } else { Instruction::JmpIfFalse(0) }, 1);
         }
 
-        if exprs.len() == 1 {
-            // Last expression - just compile it
-            self.compile_expr(&exprs[0])?;
+        // The last expression is the result and inherits tail position
+        self.in_tail_position = saved_tail;
+        self.compile_expr(last)?;
+        if exit_jumps.is_empty() {
             return Ok(());
         }
-
-        // Multiple expressions: if first then true else (or rest...)
-        let saved_tail = self.in_tail_position;
-
-        // Compile first expression (not in tail position)
-        self.in_tail_position = false;
-        self.compile_expr(&exprs[0])?;
-
-        // Emit JmpIfFalse with placeholder
-        let jmp_if_false_index = self.bytecode.len();
-        self.emit(Instruction::JmpIfFalse(0));
-
-        // True branch
-        self.emit(Instruction::Push(Value::Boolean(true)));
-
-        // Emit Jmp to skip rest
         let jmp_to_end_index = self.bytecode.len();
         self.emit(Instruction::Jmp(0));
 
-        // Rest branch
-        let rest_addr = self.instruction_address;
-        self.bytecode[jmp_if_false_index] = Instruction::JmpIfFalse(rest_addr);
-
-        self.in_tail_position = saved_tail;
-        self.compile_or_helper(&exprs[1..], context)?;
+        // Short-circuit exit
+        let exit_addr = self.instruction_address;
+        for index in exit_jumps {
+            self.patch_jump(index, exit_addr);
+        }
+        self.emit(Instruction::Push(Value::Boolean(stop_on)));
 
         // End
         let end_addr = self.instruction_address;
-        self.bytecode[jmp_to_end_index] = Instruction::Jmp(end_addr);
+        self.patch_jump(jmp_to_end_index, end_addr);
 
-        self.in_tail_position = saved_tail;
         Ok(())
     }
 
@@ -334,7 +860,8 @@ impl Compiler {
 
                         // Emit JmpIfFalse with placeholder
                         let jmp_if_false_index = self.bytecode.len();
-                        self.emit(Instruction::JmpIfFalse(0));
+                        self.emit_consuming(Instruction::JmpIfFalse(0), 1);
+                        let branch_depth = self.stack_depth;
 
                         // Compile then branch (inherits tail position)
                         self.in_tail_position = saved_tail;
@@ -350,6 +877,7 @@ impl Compiler {
 
                         // If this is the last clause and not else, compile remaining clauses
                         if !is_last {
+                            self.stack_depth = branch_depth;
                             self.compile_cond(&clauses[i + 1..], context)?;
                         } else {
                             // Last clause without else - push false
@@ -376,4 +904,107 @@ impl Compiler {
         self.in_tail_position = saved_tail;
         Ok(())
     }
+
+    // Compile case: (case key (datum body) ((d1 d2) body) ... (else body))
+    // The key is evaluated once into a temporary slot; each clause compares it
+    // against its literal data with Eq and jumps to the body on the first match
+    pub(super) fn compile_case(&mut self, key: &SourceExpr, clauses: &[SourceExpr]) -> Result<(), CompileError> {
+        let saved_tail = self.in_tail_position;
+        let saved_stack_depth = self.stack_depth;
+
+        self.in_tail_position = false;
+        let key_slot = self.stack_depth;
+        self.compile_expr(key)?;
+        let body_depth = self.stack_depth;
+
+        let mut end_jumps = Vec::new();
+        let mut has_else = false;
+
+        for (i, clause) in clauses.iter().enumerate() {
+            let (data_expr, body) = match &clause.expr {
+                LispExpr::List(items) if items.len() == 2 => (&items[0], &items[1]),
+                _ => {
+                    return Err(CompileError::new(
+                        "case clause must be a list of (datum expr) or ((datum ...) expr)".to_string(),
+                        clause.location.clone(),
+                    ));
+                }
+            };
+
+            if matches!(&data_expr.expr, LispExpr::Symbol(s) if s == "else") {
+                if i != clauses.len() - 1 {
+                    return Err(CompileError::new(
+                        "else clause must be the last clause in case".to_string(),
+                        clause.location.clone(),
+                    ));
+                }
+                self.in_tail_position = saved_tail;
+                self.compile_expr(body)?;
+                has_else = true;
+                break;
+            }
+
+            // A bare datum is shorthand for a one-element list of data
+            let data = match &data_expr.expr {
+                LispExpr::List(items) if items.is_empty() => {
+                    return Err(CompileError::new(
+                        "case clause needs at least one datum".to_string(),
+                        data_expr.location.clone(),
+                    ));
+                }
+                LispExpr::List(items) => items.as_slice(),
+                _ => std::slice::from_ref(data_expr),
+            };
+
+            // Any datum matching jumps to the body; the last one falls through to the next clause
+            let mut body_jumps = Vec::new();
+            let mut next_clause_jump = 0;
+            for (j, datum) in data.iter().enumerate() {
+                let value = self.expr_to_value(datum)?;
+                self.emit(Instruction::GetLocal(key_slot));
+                self.emit(Instruction::Push(value));
+                self.emit(Instruction::Eq);
+                if j == data.len() - 1 {
+                    next_clause_jump = self.bytecode.len();
+                    self.emit(Instruction::JmpIfFalse(0));
+                } else {
+                    body_jumps.push(self.bytecode.len());
+                    self.emit(Instruction::JmpIfTrue(0));
+                }
+            }
+
+            let body_addr = self.instruction_address;
+            for index in body_jumps {
+                self.bytecode[index] = Instruction::JmpIfTrue(body_addr);
+            }
+
+            self.in_tail_position = saved_tail;
+            self.stack_depth = body_depth;
+            self.compile_expr(body)?;
+            self.stack_depth = body_depth;
+
+            end_jumps.push(self.bytecode.len());
+            self.emit(Instruction::Jmp(0));
+
+            let next_addr = self.instruction_address;
+            self.bytecode[next_clause_jump] = Instruction::JmpIfFalse(next_addr);
+        }
+
+        if !has_else {
+            // No clause matched and there is no else
+            self.emit(Instruction::Push(Value::Boolean(false)));
+        }
+
+        let end_addr = self.instruction_address;
+        for index in end_jumps {
+            self.bytecode[index] = Instruction::Jmp(end_addr);
+        }
+
+        // Drop the key slot from under the result
+        self.emit(Instruction::Slide(1));
+
+        self.in_tail_position = saved_tail;
+        self.stack_depth = saved_stack_depth;
+        Ok(())
+    }
 }
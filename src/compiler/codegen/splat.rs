@@ -0,0 +1,187 @@
+// Call-site splat: (f a ...rest b) splices the list `rest` into the arguments
+
+use crate::vm::instructions::Instruction;
+use crate::vm::errors::CompileError;
+use super::Compiler;
+use super::utils::SPECIAL_FORMS;
+use super::super::ast::{LispExpr, SourceExpr};
+
+// ==================== SPLAT ====================
+
+/// One argument of a call after splats are resolved
+enum CallArg<'a> {
+    /// A plain argument
+    Single(&'a SourceExpr),
+    /// An expression that evaluates to a list of arguments
+    Spread(&'a SourceExpr),
+}
+
+/// Builtins that are expanded inline for any number of arguments but have
+/// no variadic runtime version, so a runtime list cannot be spliced into them
+const INLINE_VARIADIC_BUILTINS: &[&str] = &[
+    "%", "hash-map",
+];
+
+impl Compiler {
+    /// Whether `(op args...)` uses splat syntax and should be compiled by
+    /// compile_splat_call. Special forms and macros see their arguments
+    /// unevaluated, so a `...x` there is left alone.
+    pub(super) fn is_splat_call(&self, items: &[SourceExpr]) -> bool {
+        let has_splat = items[1..].iter().any(|item| {
+            matches!(&item.expr, LispExpr::Symbol(s) if s.starts_with("..."))
+        });
+        match &items[0].expr {
+            LispExpr::Symbol(op) => {
+                has_splat && !SPECIAL_FORMS.contains(&op.as_str()) && !self.macros.contains_key(op)
+            }
+            _ => has_splat,
+        }
+    }
+
+    /// Compile a call with splat arguments. `...name` splices the list bound
+    /// to `name`; a bare `...` splices the value of the expression after it,
+    /// as in `(f ...(cdr xs))`. Any number of splats may appear, in any
+    /// position, and each is spliced where it stands:
+    /// `(f 1 ...xs 2 ...ys)` calls f with 1, the items of xs, 2, the items of ys.
+    ///
+    /// A splat of a `(list ...)` form is spliced at compile time, so
+    /// `(f ...(list a b))` is exactly `(f a b)`. Otherwise the arguments are
+    /// gathered into one list at runtime and passed with Apply, which has
+    /// `apply`'s semantics (and is not a tail call).
+    pub(super) fn compile_splat_call(&mut self, expr: &SourceExpr, items: &[SourceExpr]) -> Result<(), CompileError> {
+        let mut args = Vec::new();
+        let mut rest = items[1..].iter();
+        while let Some(item) = rest.next() {
+            let operand = match &item.expr {
+                LispExpr::Symbol(s) if s == "..." => rest.next().ok_or_else(|| CompileError::new(
+                    "Splat '...' must be followed by the expression to splice".to_string(),
+                    item.location.clone(),
+                ))?,
+                LispExpr::Symbol(s) if s.starts_with("...") => {
+                    // `...name` is sugar for `... name`
+                    args.push(CallArg::Spread(item));
+                    continue;
+                }
+                _ => {
+                    args.push(CallArg::Single(item));
+                    continue;
+                }
+            };
+            match &operand.expr {
+                LispExpr::List(elements) if self.is_list_constructor(elements) => {
+                    args.extend(elements[1..].iter().map(CallArg::Single));
+                }
+                _ => args.push(CallArg::Spread(operand)),
+            }
+        }
+
+        // Everything spliced at compile time: an ordinary call
+        if args.iter().all(|arg| matches!(arg, CallArg::Single(_))) {
+            let mut call = vec![items[0].clone()];
+            call.extend(args.iter().map(|arg| match arg {
+                CallArg::Single(e) | CallArg::Spread(e) => (*e).clone(),
+            }));
+            return self.compile_expr(&SourceExpr::new(LispExpr::List(call), expr.location.clone())).map(|_| ());
+        }
+
+        let saved_tail = self.in_tail_position;
+        self.in_tail_position = false;
+
+        let operator = match &items[0].expr {
+            LispExpr::Symbol(op) if !self.is_local_name(op) && !self.functions.contains_key(op) => Some(op.as_str()),
+            _ => None,
+        };
+        match operator {
+            // list and vector build their result straight from the spliced arguments
+            Some("list") => self.compile_splat_arg_list(&args)?,
+            Some("vector") => {
+                self.compile_splat_arg_list(&args)?;
+                self.emit(Instruction::ListToVector);
+            }
+            Some(op) if INLINE_VARIADIC_BUILTINS.contains(&op) => {
+                return Err(CompileError::with_suggestion(
+                    format!("Cannot splat a runtime list into '{}'", op),
+                    expr.location.clone(),
+                    format!(
+                        "'{}' is expanded inline for a fixed number of arguments. Splat a (list ...) form instead, or fold over the list with loop/recur.",
+                        op
+                    ),
+                ));
+            }
+            _ => {
+                self.compile_expr(&items[0])?;
+                self.compile_splat_arg_list(&args)?;
+                self.emit(Instruction::Apply);
+            }
+        }
+
+        self.in_tail_position = saved_tail;
+        Ok(())
+    }
+
+    /// Push one list holding all arguments: each run of plain arguments is
+    /// gathered with MakeList and joined to the spliced lists with Append
+    fn compile_splat_arg_list(&mut self, args: &[CallArg]) -> Result<(), CompileError> {
+        let mut segments = 0;
+        let mut pending = 0;
+        for arg in args {
+            match arg {
+                CallArg::Single(e) => {
+                    self.compile_expr(e)?;
+                    pending += 1;
+                }
+                CallArg::Spread(e) => {
+                    if pending > 0 || segments == 0 {
+                        // A leading splat is appended to '() so a non-list is still reported
+                        self.emit(Instruction::MakeList(pending));
+                        self.stack_depth = self.stack_depth + 1 - pending;
+                        segments = self.join_segment(segments);
+                        pending = 0;
+                    }
+                    match &e.expr {
+                        LispExpr::Symbol(s) if s.len() > 3 && s.starts_with("...") => {
+                            let name = SourceExpr::new(LispExpr::Symbol(s[3..].to_string()), e.location.clone());
+                            self.compile_expr(&name)?;
+                        }
+                        _ => {
+                            self.compile_expr(e)?;
+                        }
+                    }
+                    segments = self.join_segment(segments);
+                }
+            }
+        }
+        if pending > 0 {
+            self.emit(Instruction::MakeList(pending));
+            self.join_segment(segments);
+        }
+        Ok(())
+    }
+
+    /// Append the segment just pushed to the list built so far
+    fn join_segment(&mut self, segments: usize) -> usize {
+        if segments > 0 {
+            self.emit_consuming(Instruction::Append, 1);
+        }
+        segments + 1
+    }
+
+    /// Whether `(head ...)` is a call to the builtin `list`
+    fn is_list_constructor(&self, elements: &[SourceExpr]) -> bool {
+        match elements.first().map(|e| &e.expr) {
+            Some(LispExpr::Symbol(op)) if op == "list" => {
+                !self.is_local_name(op)
+                    && !self.functions.contains_key(op)
+                    && !elements[1..].iter().any(|e| matches!(&e.expr, LispExpr::Symbol(s) if s.starts_with("...")))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `name` is bound as a parameter, let binding or pattern variable
+    fn is_local_name(&self, name: &str) -> bool {
+        self.local_bindings.contains_key(name)
+            || self.pattern_bindings.contains_key(name)
+            || self.param_names.iter().any(|p| p == name)
+    }
+}
@@ -11,6 +11,7 @@ use super::super::ast::SourceExpr;
 pub(super) enum ValueLocation {
     Local(usize),                                  // Local variable on value stack
     Captured(usize),                               // Captured variable in closure
+    LetrecCaptured(usize, usize),                  // Captured letrec sibling (index, group size), re-tied on load
     ListElement(Box<ValueLocation>, usize),        // i-th element of a list
     ListRest(Box<ValueLocation>, usize),           // Rest after skipping n elements
 }
@@ -25,6 +26,9 @@ impl ValueLocation {
             ValueLocation::Captured(idx) => {
                 compiler.emit(Instruction::LoadCaptured(*idx));
             }
+            ValueLocation::LetrecCaptured(idx, group_size) => {
+                compiler.emit(Instruction::LoadLetrec(*idx, *group_size));
+            }
             ValueLocation::ListElement(list_loc, idx) => {
                 // Load the list
                 list_loc.emit_load(compiler);
@@ -53,9 +57,17 @@ pub(super) struct MacroDef {
     pub body: SourceExpr,
 }
 
-// Helper struct for parsed parameters (supports variadic syntax)
+// The &key parameters of a defun, for checking keyword calls at compile time
+pub(super) struct KeyParams {
+    pub required: usize,   // Positional params before the keys
+    pub keys: Vec<String>, // Key names without the colon, in parameter order
+}
+
+// Helper struct for parsed parameters (supports variadic, &optional and &key syntax)
 pub(super) struct ParsedParams {
     pub required: Vec<String>,
+    pub optional: Vec<(String, SourceExpr)>, // &optional params with their default expressions
+    pub keys: Vec<(String, SourceExpr)>,     // &key params with their default expressions
     pub rest: Option<String>,
 }
 
@@ -64,16 +76,38 @@ pub(super) struct ParsedParams {
 pub(super) enum Pattern {
     Variable(String),           // Matches anything, binds to name
     Wildcard,                   // Matches anything, no binding
-    Literal(Value),             // Matches specific value (number, boolean)
+    Literal(Value),             // Matches a constant by equality (numbers, strings, quoted data, vectors, maps)
     QuotedSymbol(String),       // Matches quoted symbol: 'foo
     EmptyList,                  // Matches empty list: '()
     List(Vec<Pattern>),         // Matches fixed-length list: (a b c)
     DottedList(Vec<Pattern>, Box<Pattern>), // Matches cons pattern: (h . t)
 }
 
+impl Pattern {
+    // Append the names this pattern binds, in order of appearance
+    pub fn collect_variables(&self, names: &mut Vec<String>) {
+        match self {
+            Pattern::Variable(name) => names.push(name.clone()),
+            Pattern::List(items) => {
+                for item in items {
+                    item.collect_variables(names);
+                }
+            }
+            Pattern::DottedList(head, tail) => {
+                for item in head {
+                    item.collect_variables(names);
+                }
+                tail.collect_variables(names);
+            }
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::QuotedSymbol(_) | Pattern::EmptyList => {}
+        }
+    }
+}
+
 // A single clause in a multi-clause function definition
 #[derive(Debug)]
 pub(super) struct FunctionClause {
     pub patterns: Vec<Pattern>,     // Patterns for each argument
+    pub guard: Option<SourceExpr>,  // (when condition) checked after binding, before the body
     pub body: SourceExpr,           // Body to execute if patterns match
 }
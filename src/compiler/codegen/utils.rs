@@ -1,42 +1,70 @@
 // Utility functions for the compiler
 
 use super::Compiler;
+use crate::compiler::ast::LispExpr;
+use crate::vm::errors::CompileError;
+use crate::vm::builtins::BUILTIN_NAMES;
+
+// Special forms and definition keywords handled directly by the compiler
+pub(super) const SPECIAL_FORMS: &[&str] = &[
+    "def", "def-mutable", "defun", "defmacro", "module", "import", "export", "provide",
+    "if", "and", "or", "cond", "case", "when", "unless", "if-let", "when-let", "assert", "try", "set!", "incf", "decf", "do", "begin",
+    "quote", "quasiquote", "macroexpand", "macroexpand-all", "let", "letrec", "loop", "recur", "dotimes", "dolist", "lambda", "match-lambda",
+];
 
 impl Compiler {
-    // Check if a name is a builtin function
+    /// All names the compiler currently knows about, for editor/REPL completion:
+    /// special forms, functions, macros, globals, and any parameters or locals
+    /// in scope at the current compilation point. Sorted and deduplicated.
+    pub fn known_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = SPECIAL_FORMS.iter().map(|s| s.to_string()).collect();
+        names.extend(self.functions.keys().cloned());
+        names.extend(self.macros.keys().cloned());
+        names.extend(self.global_vars.keys().cloned());
+        names.extend(self.known_functions.iter().cloned());
+        names.extend(self.known_globals.iter().cloned());
+        names.extend(self.param_names.iter().cloned());
+        names.extend(self.local_bindings.keys().cloned());
+        names.extend(self.pattern_bindings.keys().cloned());
+        // Synthetic pattern-dispatch parameters are not user-visible
+        names.retain(|n| !n.starts_with("__"));
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    // Check if a name is a builtin function: one the VM registers, or a
+    // constructor the compiler always expands inline
     pub(super) fn is_builtin_function(name: &str) -> bool {
-        matches!(name,
-            // Arithmetic
-            "+" | "-" | "*" | "/" | "%" | "neg" |
-            // Comparison
-            "<=" | "<" | ">" | ">=" | "==" | "!=" |
-            // List operations
-            "cons" | "car" | "cdr" | "list?" | "append" | "list-ref" | "list-length" | "null?" | "list" |
-            // Type predicates
-            "integer?" | "boolean?" | "function?" | "closure?" | "procedure?" | "number?" |
-            // String operations
-            "string?" | "symbol?" | "symbol->string" | "string->symbol" |
-            "string-length" | "substring" | "string-append" | "string->list" |
-            "list->string" | "char-code" | "number->string" | "string->number" |
-            "string-split" | "string-join" | "string-trim" | "string-replace" |
-            "string-starts-with?" | "string-ends-with?" | "string-contains?" |
-            "string-upcase" | "string-downcase" |
-            // File I/O
-            "read-file" | "write-file" | "file-exists?" | "write-binary-file" | "load" | "require" |
-            // HashMap operations
-            "hashmap?" | "hashmap-get" | "hashmap-set" | "hashmap-keys" |
-            "hashmap-values" | "hashmap-contains-key?" | "hash-map" |
-            // Vector operations
-            "vector?" | "vector-ref" | "vector-set" | "vector-push" | "vector-pop" |
-            "vector-length" | "vector" |
-            // Type conversions
-            "list->vector" | "vector->list" |
-            // Metaprogramming & Reflection
-            "eval" |
-            "function-arity" | "function-params" | "closure-captured" | "function-name" |
-            // Other
-            "get-args" | "print"
-        )
+        BUILTIN_NAMES.contains(&name) || matches!(name, "list" | "vector" | "hash-map")
+    }
+
+    /// Strict mode: every named call must reach a defined, known, or builtin function
+    pub(super) fn check_called_functions_exist(&mut self) -> Result<(), CompileError> {
+        for (name, location) in std::mem::take(&mut self.called_functions) {
+            let known = self.functions.contains_key(&name)
+                || self.known_functions.contains(&name)
+                || Self::is_builtin_function(&name);
+            if !known {
+                let closest = self.functions.keys()
+                    .map(String::as_str)
+                    .chain(self.known_functions.iter().map(String::as_str))
+                    .chain(BUILTIN_NAMES.iter().copied())
+                    .map(|candidate| (Self::levenshtein_distance(&name, candidate), candidate))
+                    .filter(|(distance, _)| *distance <= 3)
+                    .min();
+                let suggestion = match closest {
+                    Some((_, similar)) => format!("Did you mean '{}'? Strict mode requires every called function to be defined.", similar),
+                    None => "Define the function with 'defun' before running, or disable strict mode.".to_string(),
+                };
+                return Err(CompileError::with_suggestion(
+                    format!("Unknown function '{}' (strict mode)", name),
+                    location,
+                    suggestion,
+                ));
+            }
+        }
+        Ok(())
     }
 
     /// Generate a helpful suggestion for an undefined variable name
@@ -73,6 +101,28 @@ impl Compiler {
         }
     }
 
+    /// Render an expression back to Lisp source text (used in error messages)
+    pub(super) fn expr_to_source(expr: &LispExpr) -> String {
+        match expr {
+            LispExpr::Number(n) => n.to_string(),
+            LispExpr::BigInt(n) => n.to_string(),
+            LispExpr::Float(f) => f.to_string(),
+            LispExpr::Boolean(b) => b.to_string(),
+            LispExpr::Symbol(s) => match s.strip_prefix("__STRING__") {
+                Some(content) => format!("\"{}\"", content),
+                None => s.clone(),
+            },
+            LispExpr::List(items) => {
+                let parts: Vec<String> = items.iter().map(|i| Self::expr_to_source(&i.expr)).collect();
+                format!("({})", parts.join(" "))
+            }
+            LispExpr::DottedList(head, tail) => {
+                let parts: Vec<String> = head.iter().map(|i| Self::expr_to_source(&i.expr)).collect();
+                format!("({} . {})", parts.join(" "), Self::expr_to_source(&tail.expr))
+            }
+        }
+    }
+
     /// Calculate Levenshtein distance between two strings
     /// This measures the minimum number of single-character edits needed to transform one string into another
     pub(super) fn levenshtein_distance(s1: &str, s2: &str) -> usize {
@@ -4,5 +4,5 @@ pub mod ast;
 mod codegen;
 
 // Re-export
-pub use ast::{LispExpr, SourceExpr};
+pub use ast::{LispExpr, SourceExpr, MAX_NESTING_DEPTH};
 pub use codegen::Compiler;
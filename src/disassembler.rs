@@ -1,4 +1,6 @@
 use crate::Instruction;
+use crate::vm::errors::RuntimeError;
+use crate::vm::verifier;
 use std::collections::HashMap;
 
 pub struct DisassemblerStats {
@@ -8,6 +10,187 @@ pub struct DisassemblerStats {
     pub main_instruction_count: usize,
 }
 
+/// A compiled program: named functions plus main bytecode, with each
+/// function's parameter list and arity when the compiler recorded them.
+pub struct Program {
+    pub functions: HashMap<String, Vec<Instruction>>,
+    pub main: Vec<Instruction>,
+    pub params: HashMap<String, String>, // Function name -> parameter list, e.g. "(x y . rest)"
+    pub arities: HashMap<String, usize>, // Function name -> argument slots (a rest parameter counts as one)
+}
+
+impl Program {
+    pub fn new(functions: HashMap<String, Vec<Instruction>>, main: Vec<Instruction>) -> Self {
+        Program { functions, main, params: HashMap::new(), arities: HashMap::new() }
+    }
+
+    pub fn with_params(mut self, params: HashMap<String, String>) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn with_arities(mut self, arities: HashMap<String, usize>) -> Self {
+        self.arities = arities;
+        self
+    }
+
+    /// Run the bytecode verifier over the program, using the recorded
+    /// arities as each function's declared arity.
+    pub fn verify(&self) -> Result<(), RuntimeError> {
+        verifier::verify_program(&self.functions, &self.main, 0, &self.arities)
+    }
+
+    /// Render the program grouped by function (sorted by name), then main.
+    /// Each function gets a header with its parameters; each closure is
+    /// expanded in place with its parameters, the instructions that load its
+    /// captured values, and its body. Calls are marked as tail or non-tail,
+    /// and jumps are annotated with their target (see `render_block`).
+    /// The output is deterministic, so it can be compared against a snapshot.
+    pub fn disassemble_program(&self) -> String {
+        let mut sorted_names: Vec<_> = self.functions.keys().collect();
+        sorted_names.sort();
+
+        let mut output = String::new();
+        for name in sorted_names {
+            output.push_str(&self.render_function(name, &self.functions[name]));
+            output.push('\n');
+        }
+
+        output.push_str("=== Main ===\n");
+        render_block(&self.main, 1, &mut output);
+        output
+    }
+
+    /// Render one function the way `disassemble_program` does, or None if
+    /// the program has no function by that name
+    pub fn disassemble_function(&self, name: &str) -> Option<String> {
+        self.functions.get(name).map(|bytecode| self.render_function(name, bytecode))
+    }
+
+    fn render_function(&self, name: &str, bytecode: &[Instruction]) -> String {
+        let params = self.params.get(name).map(|p| p.as_str()).unwrap_or("(?)");
+        let mut output = format!("=== Function: {} {} ===\n", name, params);
+        render_block(bytecode, 1, &mut output);
+        output
+    }
+}
+
+/// Format a closure parameter list the way it is written in source
+fn format_params(required: &[String], rest: Option<&str>) -> String {
+    let mut params = required.join(" ");
+    if let Some(rest) = rest {
+        if !params.is_empty() {
+            params.push(' ');
+        }
+        params.push_str(". ");
+        params.push_str(rest);
+    }
+    format!("({})", params)
+}
+
+/// The address an instruction may jump to, if it has one
+fn jump_target(instr: &Instruction) -> Option<usize> {
+    match instr {
+        Instruction::Jmp(target)
+        | Instruction::JmpIfFalse(target)
+        | Instruction::JmpIfTrue(target)
+        | Instruction::PushHandler(target)
+        | Instruction::CheckArity(_, target)
+        | Instruction::JmpIfArgSupplied(_, target)
+        | Instruction::JmpIfKeySupplied(_, target) => Some(*target),
+        _ => None,
+    }
+}
+
+/// Name the jump targets of a block that have an obvious role. An `if`
+/// compiles to `JmpIfFalse else` ... `Jmp end` with the Jmp just before
+/// `else`, so that shape labels both targets; a handler is the code that
+/// PushHandler installs.
+fn jump_labels(bytecode: &[Instruction]) -> HashMap<usize, &'static str> {
+    let mut labels = HashMap::new();
+    for instr in bytecode {
+        match instr {
+            Instruction::JmpIfFalse(else_addr) if *else_addr > 0 => {
+                if let Some(Instruction::Jmp(end_addr)) = bytecode.get(else_addr - 1) {
+                    if end_addr >= else_addr {
+                        labels.insert(*else_addr, "else");
+                        labels.insert(*end_addr, "end");
+                    }
+                }
+            }
+            Instruction::PushHandler(handler_addr) => {
+                labels.insert(*handler_addr, "handler");
+            }
+            _ => {}
+        }
+    }
+    labels
+}
+
+fn render_block(bytecode: &[Instruction], depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    let labels = jump_labels(bytecode);
+
+    for (addr, instr) in bytecode.iter().enumerate() {
+        let line = instr.to_string();
+        if let Some(target) = jump_target(instr) {
+            let line = match labels.get(&target) {
+                Some(label) => format!("{}  ; -> {} ({})", line, target, label),
+                None => format!("{}  ; -> {}", line, target),
+            };
+            output.push_str(&format!("{}{:4}: {}\n", indent, addr, line));
+            continue;
+        }
+        match instr {
+            Instruction::Call(..) | Instruction::CallClosure(..) => {
+                output.push_str(&format!("{}{:4}: {}  ; call\n", indent, addr, line));
+            }
+            Instruction::TailCall(..) | Instruction::TailCallClosure(..) => {
+                output.push_str(&format!("{}{:4}: {}  ; tail call\n", indent, addr, line));
+            }
+            Instruction::MakeClosure(params, body, num_captured) => {
+                output.push_str(&format!("{}{:4}: {}\n", indent, addr, line));
+                render_closure(bytecode, addr, &format_params(params, None), body, *num_captured, depth, output);
+            }
+            Instruction::MakeVariadicClosure(params, rest, body, num_captured) => {
+                output.push_str(&format!("{}{:4}: {}\n", indent, addr, line));
+                render_closure(bytecode, addr, &format_params(params, Some(rest)), body, *num_captured, depth, output);
+            }
+            _ => output.push_str(&format!("{}{:4}: {}\n", indent, addr, line)),
+        }
+    }
+}
+
+/// Render a closure created at `addr`. The compiler pushes one value per
+/// captured variable right before the closure instruction, in capture order,
+/// so those instructions say where each capture slot comes from.
+fn render_closure(
+    bytecode: &[Instruction],
+    addr: usize,
+    params: &str,
+    body: &[Instruction],
+    num_captured: usize,
+    depth: usize,
+    output: &mut String,
+) {
+    let indent = "  ".repeat(depth + 3);
+    output.push_str(&format!("{}closure {}\n", indent, params));
+
+    if num_captured == 0 {
+        output.push_str(&format!("{}captures: none\n", indent));
+    } else if addr >= num_captured {
+        output.push_str(&format!("{}captures:\n", indent));
+        for (slot, source) in bytecode[addr - num_captured..addr].iter().enumerate() {
+            output.push_str(&format!("{}  [{}] <- {}\n", indent, slot, source));
+        }
+    } else {
+        output.push_str(&format!("{}captures: {} (sources not in this block)\n", indent, num_captured));
+    }
+
+    output.push_str(&format!("{}body:\n", indent));
+    render_block(body, depth + 4, output);
+}
+
 pub fn disassemble_bytecode(
     functions: &HashMap<String, Vec<Instruction>>,
     main: &[Instruction],
@@ -45,192 +228,12 @@ fn disassemble_instructions(bytecode: &[Instruction]) -> String {
     let mut output = String::new();
 
     for (addr, instr) in bytecode.iter().enumerate() {
-        output.push_str(&format!("  {:4}: {}\n", addr, format_instruction(instr)));
+        output.push_str(&format!("  {:4}: {}\n", addr, instr));
     }
 
     output
 }
 
-fn format_instruction(instr: &Instruction) -> String {
-    match instr {
-        Instruction::Push(val) => format!("Push({:?})", val),
-        Instruction::Add => "Add".to_string(),
-        Instruction::Sub => "Sub".to_string(),
-        Instruction::Mul => "Mul".to_string(),
-        Instruction::Div => "Div".to_string(),
-        Instruction::Mod => "Mod".to_string(),
-        Instruction::Neg => "Neg".to_string(),
-        Instruction::Leq => "Leq".to_string(),
-        Instruction::Lt => "Lt".to_string(),
-        Instruction::Gt => "Gt".to_string(),
-        Instruction::Gte => "Gte".to_string(),
-        Instruction::Eq => "Eq".to_string(),
-        Instruction::Neq => "Neq".to_string(),
-        Instruction::JmpIfFalse(addr) => format!("JmpIfFalse({})", addr),
-        Instruction::Jmp(addr) => format!("Jmp({})", addr),
-        Instruction::Call(name, argc) => format!("Call(\"{}\", {})", name, argc),
-        Instruction::TailCall(name, argc) => format!("TailCall(\"{}\", {})", name, argc),
-        Instruction::Ret => "Ret".to_string(),
-        Instruction::LoadArg(idx) => format!("LoadArg({})", idx),
-        Instruction::Print => "Print".to_string(),
-        Instruction::Halt => "Halt".to_string(),
-        Instruction::Cons => "Cons".to_string(),
-        Instruction::Car => "Car".to_string(),
-        Instruction::Cdr => "Cdr".to_string(),
-        Instruction::IsList => "IsList".to_string(),
-        Instruction::IsString => "IsString".to_string(),
-        Instruction::IsSymbol => "IsSymbol".to_string(),
-        Instruction::SymbolToString => "SymbolToString".to_string(),
-        Instruction::StringToSymbol => "StringToSymbol".to_string(),
-        Instruction::GetLocal(pos) => format!("GetLocal({})", pos),
-        Instruction::SetLocal(pos) => format!("SetLocal({})", pos),
-        Instruction::BeginLoop(count) => format!("BeginLoop({})", count),
-        Instruction::Recur(count) => format!("Recur({})", count),
-        Instruction::PopN(n) => format!("PopN({})", n),
-        Instruction::Slide(n) => format!("Slide({})", n),
-        Instruction::CheckArity(arity, addr) => format!("CheckArity({}, {})", arity, addr),
-        Instruction::MakeClosure(params, body, num_captured) => {
-            format!("MakeClosure({:?}, {} instructions, {} captured)", params, body.len(), num_captured)
-        }
-        Instruction::CallClosure(argc) => format!("CallClosure({})", argc),
-        Instruction::Apply => "Apply".to_string(),
-        Instruction::LoadCaptured(idx) => format!("LoadCaptured({})", idx),
-        Instruction::Append => "Append".to_string(),
-        Instruction::MakeList(n) => format!("MakeList({})", n),
-        Instruction::LoadGlobal(name) => format!("LoadGlobal(\"{}\")", name),
-        Instruction::StoreGlobal(name) => format!("StoreGlobal(\"{}\")", name),
-        Instruction::StringLength => "StringLength".to_string(),
-        Instruction::Substring => "Substring".to_string(),
-        Instruction::StringAppend => "StringAppend".to_string(),
-        Instruction::StringToList => "StringToList".to_string(),
-        Instruction::ListToString => "ListToString".to_string(),
-        Instruction::CharCode => "CharCode".to_string(),
-        Instruction::ReadFile => "ReadFile".to_string(),
-        Instruction::WriteFile => "WriteFile".to_string(),
-        Instruction::FileExists => "FileExists".to_string(),
-        Instruction::GetArgs => "GetArgs".to_string(),
-        Instruction::WriteBinaryFile => "WriteBinaryFile".to_string(),
-        Instruction::LoadFile => "LoadFile".to_string(),
-        Instruction::RequireFile => "RequireFile".to_string(),
-        Instruction::ListRef => "ListRef".to_string(),
-        Instruction::ListLength => "ListLength".to_string(),
-        Instruction::NumberToString => "NumberToString".to_string(),
-        // HashMap operations
-        Instruction::MakeHashMap(n) => format!("MakeHashMap({})", n),
-        Instruction::HashMapGet => "HashMapGet".to_string(),
-        Instruction::HashMapSet => "HashMapSet".to_string(),
-        Instruction::HashMapKeys => "HashMapKeys".to_string(),
-        Instruction::HashMapValues => "HashMapValues".to_string(),
-        Instruction::HashMapContainsKey => "HashMapContainsKey".to_string(),
-        Instruction::IsHashMap => "IsHashMap".to_string(),
-        // Vector operations
-        Instruction::MakeVector(n) => format!("MakeVector({})", n),
-        Instruction::VectorGet => "VectorGet".to_string(),
-        Instruction::VectorSet => "VectorSet".to_string(),
-        Instruction::VectorPush => "VectorPush".to_string(),
-        Instruction::VectorPop => "VectorPop".to_string(),
-        Instruction::VectorLength => "VectorLength".to_string(),
-        Instruction::IsVector => "IsVector".to_string(),
-        // Type predicates
-        Instruction::IsInteger => "IsInteger".to_string(),
-        Instruction::IsBoolean => "IsBoolean".to_string(),
-        Instruction::IsFunction => "IsFunction".to_string(),
-        Instruction::IsClosure => "IsClosure".to_string(),
-        Instruction::IsProcedure => "IsProcedure".to_string(),
-        // Type conversions
-        Instruction::StringToNumber => "StringToNumber".to_string(),
-        Instruction::ListToVector => "ListToVector".to_string(),
-        Instruction::VectorToList => "VectorToList".to_string(),
-        // Variadic function support
-        Instruction::PackRestArgs(n) => format!("PackRestArgs({})", n),
-        Instruction::MakeVariadicClosure(params, rest_param, body, num_captured) => {
-            format!("MakeVariadicClosure({:?} . {}, {} instrs, {} captured)",
-                    params, rest_param, body.len(), num_captured)
-        }
-        // Float type predicates and conversions
-        Instruction::IsFloat => "IsFloat".to_string(),
-        Instruction::IsNumber => "IsNumber".to_string(),
-        Instruction::IntToFloat => "IntToFloat".to_string(),
-        Instruction::FloatToInt => "FloatToInt".to_string(),
-        // Math functions
-        Instruction::Sqrt => "Sqrt".to_string(),
-        Instruction::Sin => "Sin".to_string(),
-        Instruction::Cos => "Cos".to_string(),
-        Instruction::Tan => "Tan".to_string(),
-        Instruction::Atan => "Atan".to_string(),
-        Instruction::Atan2 => "Atan2".to_string(),
-        Instruction::Log => "Log".to_string(),
-        Instruction::Exp => "Exp".to_string(),
-        Instruction::Floor => "Floor".to_string(),
-        Instruction::Ceil => "Ceil".to_string(),
-        Instruction::Abs => "Abs".to_string(),
-        Instruction::Pow => "Pow".to_string(),
-        Instruction::Random => "Random".to_string(),
-        Instruction::RandomInt => "RandomInt".to_string(),
-        Instruction::SeedRandom => "SeedRandom".to_string(),
-        // String operations
-        Instruction::StringSplit => "StringSplit".to_string(),
-        Instruction::StringJoin => "StringJoin".to_string(),
-        Instruction::StringTrim => "StringTrim".to_string(),
-        Instruction::StringReplace => "StringReplace".to_string(),
-        // Date/Time operations
-        Instruction::CurrentTimestamp => "CurrentTimestamp".to_string(),
-        Instruction::FormatTimestamp => "FormatTimestamp".to_string(),
-        // Metaprogramming
-        Instruction::Eval => "Eval".to_string(),
-        // Reflection
-        Instruction::FunctionArity => "FunctionArity".to_string(),
-        Instruction::FunctionParams => "FunctionParams".to_string(),
-        Instruction::ClosureCaptured => "ClosureCaptured".to_string(),
-        Instruction::FunctionName => "FunctionName".to_string(),
-        // Type inspection and symbol generation
-        Instruction::TypeOf => "TypeOf".to_string(),
-        Instruction::GenSym => "GenSym".to_string(),
-        // Parallel Collections
-        Instruction::PMap => "PMap".to_string(),
-        Instruction::PFilter => "PFilter".to_string(),
-        Instruction::PReduce => "PReduce".to_string(),
-        // HTTP/Networking
-        Instruction::HttpListen => "HttpListen".to_string(),
-        Instruction::HttpAccept => "HttpAccept".to_string(),
-        Instruction::HttpReadRequest => "HttpReadRequest".to_string(),
-        Instruction::HttpSendResponse => "HttpSendResponse".to_string(),
-        Instruction::HttpClose => "HttpClose".to_string(),
-        // Multi-threaded HTTP
-        Instruction::HttpListenShared => "HttpListenShared".to_string(),
-        Instruction::HttpServeParallel => "HttpServeParallel".to_string(),
-        // String predicates and utilities
-        Instruction::StringStartsWith => "StringStartsWith".to_string(),
-        Instruction::StringEndsWith => "StringEndsWith".to_string(),
-        Instruction::StringContains => "StringContains".to_string(),
-        Instruction::StringUpcase => "StringUpcase".to_string(),
-        Instruction::StringDowncase => "StringDowncase".to_string(),
-        Instruction::Format => "Format".to_string(),
-        // FFI instructions
-        Instruction::FfiLoadLibrary => "FfiLoadLibrary".to_string(),
-        Instruction::FfiGetSymbol => "FfiGetSymbol".to_string(),
-        Instruction::FfiCall(ref arg_types, ref return_type) => {
-            format!("FfiCall({:?}, {:?})", arg_types, return_type)
-        }
-        Instruction::FfiPointerToString => "FfiPointerToString".to_string(),
-        Instruction::FfiStringToPointer => "FfiStringToPointer".to_string(),
-        Instruction::FfiFreeString => "FfiFreeString".to_string(),
-        Instruction::FfiNullPointer => "FfiNullPointer".to_string(),
-        Instruction::FfiPointerNull => "FfiPointerNull".to_string(),
-        Instruction::IsPointer => "IsPointer".to_string(),
-        Instruction::FfiPointerAdd => "FfiPointerAdd".to_string(),
-        Instruction::FfiReadInt => "FfiReadInt".to_string(),
-        Instruction::FfiWriteInt => "FfiWriteInt".to_string(),
-        Instruction::FfiReadFloat => "FfiReadFloat".to_string(),
-        Instruction::FfiWriteFloat => "FfiWriteFloat".to_string(),
-        Instruction::FfiReadByte => "FfiReadByte".to_string(),
-        Instruction::FfiWriteByte => "FfiWriteByte".to_string(),
-        Instruction::FfiAllocate => "FfiAllocate".to_string(),
-        Instruction::FfiFree => "FfiFree".to_string(),
-        Instruction::FfiSizeOf(ref ffi_type) => format!("FfiSizeOf({:?})", ffi_type),
-    }
-}
-
 fn format_statistics(
     functions: &HashMap<String, Vec<Instruction>>,
     main: &[Instruction],
@@ -0,0 +1,359 @@
+// Streaming tokenizer shared by the parser and by tools (syntax highlighting,
+// partial-input analysis) that need tokens with their source spans.
+
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::vm::bigint::BigInt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    LParen,              // (
+    RParen,              // )
+    Quote,               // '
+    Quasiquote,          // `
+    Unquote,             // ,
+    UnquoteSplicing,     // ,@
+    Hash,                // # (reader macro dispatch: #t, #f, #(...), #'f)
+    DatumComment,        // #; (comments out the next expression)
+    Number,              // 42, -7, 3.14, 1e10
+    String,              // "text", quotes included
+    Symbol,              // any other atom, including true/false
+    Comment,             // ; to end of line, or a #! shebang line at the start
+    UnterminatedString,  // " with no closing quote before end of input
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str, // Exact source text of the token
+}
+
+/// Where a token sits in the source: byte offsets for slicing, plus the
+/// 1-based line and column of its first character for diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Yields `(Token, Span)` pairs lazily; whitespace is skipped, comments are
+/// reported as tokens so callers can decide whether to keep them
+pub struct Lexer<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lexer {
+            input,
+            chars: input.char_indices().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, ch) = self.chars.next()?;
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|&(_, ch)| ch)
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |&(i, _)| i)
+    }
+
+    /// Consume characters up to (not including) the first one matching `stop`
+    fn bump_until(&mut self, stop: impl Fn(char) -> bool) {
+        while let Some(ch) = self.peek() {
+            if stop(ch) {
+                break;
+            }
+            self.bump();
+        }
+    }
+}
+
+fn is_whitespace(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\r' | '\n')
+}
+
+/// Characters that end a symbol or number
+fn is_delimiter(ch: char) -> bool {
+    is_whitespace(ch) || matches!(ch, '(' | ')' | '\'' | '`' | ',' | '#' | '"' | ';')
+}
+
+/// Length of a string literal's body: the text after the opening quote, up to
+/// the closing one. Quotes inside a #{...} interpolation belong to string
+/// literals of its own, and \#{ is not an interpolation. None if unterminated.
+pub fn string_body_len(text: &str) -> Option<usize> {
+    let mut pos = 0;
+    while let Some(ch) = text[pos..].chars().next() {
+        let after = pos + ch.len_utf8();
+        pos = match ch {
+            '"' => return Some(pos),
+            '\\' if text[after..].starts_with('#') => after + 1,
+            '#' if text[after..].starts_with('{') => {
+                let start = after + 1;
+                start + interpolation_len(&text[start..])? + 1
+            }
+            _ => after,
+        };
+    }
+    None
+}
+
+/// Length of the expression in a #{...} interpolation: the text after the #{,
+/// up to its matching }. Braces inside nested string literals don't count.
+/// None if the closing } is missing.
+pub fn interpolation_len(text: &str) -> Option<usize> {
+    let mut depth = 1;
+    let mut pos = 0;
+    while let Some(ch) = text[pos..].chars().next() {
+        let after = pos + ch.len_utf8();
+        pos = match ch {
+            '{' => {
+                depth += 1;
+                after
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(pos);
+                }
+                after
+            }
+            '"' => after + string_body_len(&text[after..])? + 1,
+            _ => after,
+        };
+    }
+    None
+}
+
+fn is_number(text: &str) -> bool {
+    BigInt::parse(text).is_some()
+        || ((text.contains('.') || text.contains('e') || text.contains('E'))
+            && text.parse::<f64>().is_ok())
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = (Token<'a>, Span);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.bump_until(|ch| !is_whitespace(ch));
+
+        let start = self.offset();
+        let (line, column) = (self.line, self.column);
+        let kind = match self.bump()? {
+            // A shebang line is only recognised at the very start of the input
+            '#' if start == 0 && self.peek() == Some('!') => {
+                self.bump_until(|ch| ch == '\n');
+                TokenKind::Comment
+            }
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '\'' => TokenKind::Quote,
+            '`' => TokenKind::Quasiquote,
+            ',' if self.peek() == Some('@') => {
+                self.bump();
+                TokenKind::UnquoteSplicing
+            }
+            ',' => TokenKind::Unquote,
+            '#' if self.peek() == Some(';') => {
+                self.bump();
+                TokenKind::DatumComment
+            }
+            '#' => TokenKind::Hash,
+            ';' => {
+                self.bump_until(|ch| ch == '\n');
+                TokenKind::Comment
+            }
+            '"' => {
+                // Escapes are kept verbatim; the compiler interprets them.
+                // If a #{ never closes, the first quote ends the string so the
+                // parser can point at the unterminated #{
+                let body = &self.input[self.offset()..];
+                match string_body_len(body).or_else(|| body.find('"')) {
+                    Some(len) => {
+                        let end = self.offset() + len + 1;
+                        while self.offset() < end {
+                            self.bump();
+                        }
+                        TokenKind::String
+                    }
+                    None => {
+                        self.bump_until(|_| false);
+                        TokenKind::UnterminatedString
+                    }
+                }
+            }
+            _ => {
+                self.bump_until(is_delimiter);
+                if is_number(&self.input[start..self.offset()]) {
+                    TokenKind::Number
+                } else {
+                    TokenKind::Symbol
+                }
+            }
+        };
+
+        let end = self.offset();
+        Some((
+            Token { kind, text: &self.input[start..end] },
+            Span { start, end, line, column },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<(TokenKind, &str)> {
+        Lexer::new(input).map(|(t, _)| (t.kind, t.text)).collect()
+    }
+
+    #[test]
+    fn test_lex_simple_list() {
+        assert_eq!(
+            kinds("(+ 1 2.5)"),
+            vec![
+                (TokenKind::LParen, "("),
+                (TokenKind::Symbol, "+"),
+                (TokenKind::Number, "1"),
+                (TokenKind::Number, "2.5"),
+                (TokenKind::RParen, ")"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_numbers_and_symbols() {
+        assert_eq!(
+            kinds("-7 1e3 . - e list->vector"),
+            vec![
+                (TokenKind::Number, "-7"),
+                (TokenKind::Number, "1e3"),
+                (TokenKind::Symbol, "."),
+                (TokenKind::Symbol, "-"),
+                (TokenKind::Symbol, "e"),
+                (TokenKind::Symbol, "list->vector"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_strings_and_comments() {
+        assert_eq!(
+            kinds("\"a b\" ; note\nx"),
+            vec![
+                (TokenKind::String, "\"a b\""),
+                (TokenKind::Comment, "; note"),
+                (TokenKind::Symbol, "x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_unterminated_string() {
+        assert_eq!(
+            kinds("(f \"abc"),
+            vec![
+                (TokenKind::LParen, "("),
+                (TokenKind::Symbol, "f"),
+                (TokenKind::UnterminatedString, "\"abc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_string_with_nested_interpolated_strings() {
+        assert_eq!(
+            kinds(r#""a #{(f "}" "b")} c" x"#),
+            vec![
+                (TokenKind::String, r#""a #{(f "}" "b")} c""#),
+                (TokenKind::Symbol, "x"),
+            ]
+        );
+        // A #{ that never closes leaves the string ending at the first quote
+        assert_eq!(
+            kinds(r#""a #{b" c"#),
+            vec![
+                (TokenKind::String, r#""a #{b""#),
+                (TokenKind::Symbol, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_reader_macros() {
+        assert_eq!(
+            kinds("`(a ,b ,@c) #t #;x #'f"),
+            vec![
+                (TokenKind::Quasiquote, "`"),
+                (TokenKind::LParen, "("),
+                (TokenKind::Symbol, "a"),
+                (TokenKind::Unquote, ","),
+                (TokenKind::Symbol, "b"),
+                (TokenKind::UnquoteSplicing, ",@"),
+                (TokenKind::Symbol, "c"),
+                (TokenKind::RParen, ")"),
+                (TokenKind::Hash, "#"),
+                (TokenKind::Symbol, "t"),
+                (TokenKind::DatumComment, "#;"),
+                (TokenKind::Symbol, "x"),
+                (TokenKind::Hash, "#"),
+                (TokenKind::Quote, "'"),
+                (TokenKind::Symbol, "f"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_shebang() {
+        assert_eq!(
+            kinds("#!/usr/bin/env vm\n(f)"),
+            vec![
+                (TokenKind::Comment, "#!/usr/bin/env vm"),
+                (TokenKind::LParen, "("),
+                (TokenKind::Symbol, "f"),
+                (TokenKind::RParen, ")"),
+            ]
+        );
+        assert_eq!(kinds("x #!y")[1], (TokenKind::Hash, "#"));
+    }
+
+    #[test]
+    fn test_lex_spans() {
+        let input = "(def x\n  \"héllo\")";
+        let spans: Vec<Span> = Lexer::new(input).map(|(_, s)| s).collect();
+        assert_eq!(spans[0], Span { start: 0, end: 1, line: 1, column: 1 });
+        assert_eq!(spans[2], Span { start: 5, end: 6, line: 1, column: 6 });
+        assert_eq!(spans[3], Span { start: 9, end: 17, line: 2, column: 3 });
+        assert_eq!(&input[spans[3].start..spans[3].end], "\"héllo\"");
+        assert_eq!(spans[4], Span { start: 17, end: 18, line: 2, column: 10 });
+    }
+
+    #[test]
+    fn test_lex_is_lazy() {
+        let mut lexer = Lexer::new("a (b");
+        assert_eq!(lexer.next().map(|(t, _)| t.text), Some("a"));
+        assert_eq!(lexer.next().map(|(t, _)| t.kind), Some(TokenKind::LParen));
+        assert_eq!(lexer.next().map(|(t, _)| t.text), Some("b"));
+        assert!(lexer.next().is_none());
+    }
+}
@@ -3,15 +3,42 @@ pub mod vm;
 pub mod compiler;
 
 // Utility modules
+pub mod lexer;
 pub mod parser;
 pub mod disassembler;
 pub mod repl;
 pub mod optimizer;
 
 // Re-export commonly used types for backward compatibility
-pub use vm::{VM, Value, Instruction, List, FfiType};
+pub use vm::{VM, DEFAULT_MAX_CALL_DEPTH, Value, Instruction, List, BigInt, FfiType, LogLevel, StepInfo, StopReason};
 pub use vm::errors::{CompileError, RuntimeError, Location};
 pub use vm::stack::Frame;
 pub use vm::bytecode;
+pub use disassembler::Program;
 
-pub use compiler::{Compiler, LispExpr, SourceExpr};
+pub use compiler::{Compiler, LispExpr, SourceExpr, MAX_NESTING_DEPTH};
+
+/// Stack size for `run_with_large_stack`. Parsing and compiling grow their
+/// own stack as needed, but callbacks such as the function passed to map run
+/// in a nested VM loop on the native stack, and unoptimized builds need
+/// hundreds of kilobytes per level, so deep recursion through them outgrows
+/// the default main-thread stack.
+pub const LARGE_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Run `f` on a thread with a LARGE_STACK_SIZE stack and return its result.
+/// The command-line tools wrap their `main` in this; a panic in `f` is
+/// propagated to the caller.
+pub fn run_with_large_stack<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let handle = std::thread::Builder::new()
+        .stack_size(LARGE_STACK_SIZE)
+        .spawn(f)
+        .expect("failed to spawn thread for the Lisp runtime");
+    match handle.join() {
+        Ok(value) => value,
+        Err(panic) => std::panic::resume_unwind(panic),
+    }
+}
@@ -114,7 +114,7 @@ impl Optimizer {
             if i + 1 < bytecode.len() {
                 let folded = match (&bytecode[i], &bytecode[i + 1]) {
                     (Instruction::Push(Value::Integer(n)), Instruction::Neg) => {
-                        Some(Value::Integer(-n))
+                        n.checked_neg().map(Value::Integer)
                     }
                     (Instruction::Push(Value::Float(f)), Instruction::Neg) => {
                         Some(Value::Float(-f))
@@ -139,11 +139,16 @@ impl Optimizer {
 
     fn fold_int_int(&self, a: i64, b: i64, op: &Instruction) -> Option<Value> {
         match op {
-            Instruction::Add => Some(Value::Integer(a + b)),
-            Instruction::Sub => Some(Value::Integer(a - b)),
-            Instruction::Mul => Some(Value::Integer(a * b)),
-            Instruction::Div if b != 0 => Some(Value::Integer(a / b)),
-            Instruction::Mod if b != 0 => Some(Value::Integer(a % b)),
+            // Overflowing results are left unfolded so the VM decides whether
+            // to promote or report them (see VM::set_checked_arithmetic).
+            // checked_div/checked_rem also leave division by zero and
+            // i64::MIN / -1 unfolded: the VM raises the former and promotes
+            // the latter to a bigint (or reports it in checked mode)
+            Instruction::Add => a.checked_add(b).map(Value::Integer),
+            Instruction::Sub => a.checked_sub(b).map(Value::Integer),
+            Instruction::Mul => a.checked_mul(b).map(Value::Integer),
+            Instruction::Div => a.checked_div(b).map(Value::Integer),
+            Instruction::Mod => a.checked_rem(b).map(Value::Integer),
             Instruction::Leq => Some(Value::Boolean(a <= b)),
             Instruction::Lt => Some(Value::Boolean(a < b)),
             Instruction::Gt => Some(Value::Boolean(a > b)),
@@ -229,21 +234,11 @@ impl Optimizer {
                     Instruction::Neg,
                 ])
             }
-            (_, Instruction::Push(Value::Float(f)), Instruction::Mul) if *f == -1.0 => {
-                Some(vec![
-                    instr1.clone(),
-                    Instruction::Neg,
-                ])
-            }
 
-            // x * 0 → 0 (eliminate multiplication, just pop x and push 0)
-            (_, Instruction::Push(Value::Integer(0)), Instruction::Mul) => {
-                Some(vec![
-                    instr1.clone(),
-                    Instruction::PopN(1),
-                    Instruction::Push(Value::Integer(0)),
-                ])
-            }
+            // x * 0.0 → 0.0 (eliminate multiplication, just pop x and push 0.0).
+            // There is no integer counterpart: a float x times 0 yields 0.0,
+            // and the operand's type is unknown here. Likewise x * -1.0 is
+            // left alone because an integer x must still be promoted.
             (_, Instruction::Push(Value::Float(f)), Instruction::Mul) if *f == 0.0 => {
                 Some(vec![
                     instr1.clone(),
@@ -257,38 +252,29 @@ impl Optimizer {
     }
 
     fn try_peephole_2(&self, instr1: &Instruction, instr2: &Instruction) -> Option<Vec<Instruction>> {
+        // Only integer identities are removed: with a float constant the
+        // result is a float even when x is an integer, so e.g. x + 0.0
+        // is not a no-op.
         match (instr1, instr2) {
             // Algebraic simplifications: x + 0 = x
             (Instruction::Push(Value::Integer(0)), Instruction::Add) => {
                 Some(vec![]) // Remove both: pushing 0 and adding it is a no-op
             }
-            (Instruction::Push(Value::Float(f)), Instruction::Add) if *f == 0.0 => {
-                Some(vec![]) // Remove both: pushing 0.0 and adding it is a no-op
-            }
 
             // Algebraic simplifications: x - 0 = x
             (Instruction::Push(Value::Integer(0)), Instruction::Sub) => {
                 Some(vec![]) // Remove both: subtracting 0 is a no-op
             }
-            (Instruction::Push(Value::Float(f)), Instruction::Sub) if *f == 0.0 => {
-                Some(vec![]) // Remove both: subtracting 0.0 is a no-op
-            }
 
             // Algebraic simplifications: x * 1 = x
             (Instruction::Push(Value::Integer(1)), Instruction::Mul) => {
                 Some(vec![]) // Remove both: multiplying by 1 is a no-op
             }
-            (Instruction::Push(Value::Float(f)), Instruction::Mul) if *f == 1.0 => {
-                Some(vec![]) // Remove both: multiplying by 1.0 is a no-op
-            }
 
             // Algebraic simplifications: x / 1 = x
             (Instruction::Push(Value::Integer(1)), Instruction::Div) => {
                 Some(vec![]) // Remove both: dividing by 1 is a no-op
             }
-            (Instruction::Push(Value::Float(f)), Instruction::Div) if *f == 1.0 => {
-                Some(vec![]) // Remove both: dividing by 1.0 is a no-op
-            }
 
             // Double negation: -(-(x)) = x
             (Instruction::Neg, Instruction::Neg) => {
@@ -329,13 +315,14 @@ impl Optimizer {
                 Instruction::Jmp(target) => {
                     to_visit.push(*target);
                 }
-                Instruction::JmpIfFalse(target) => {
+                Instruction::JmpIfFalse(target) | Instruction::JmpIfTrue(target) | Instruction::PushHandler(target)
+                | Instruction::JmpIfArgSupplied(_, target) | Instruction::JmpIfKeySupplied(_, target) => {
                     to_visit.push(*target);
                     if addr + 1 < bytecode.len() {
                         to_visit.push(addr + 1);
                     }
                 }
-                Instruction::Halt | Instruction::Ret => {
+                Instruction::Halt | Instruction::Ret | Instruction::NoMatchingClause(_) => {
                 }
                 _ => {
                     if addr + 1 < bytecode.len() {
@@ -373,6 +360,16 @@ impl Optimizer {
                         Instruction::JmpIfFalse(target)
                     }
                 }
+                Instruction::JmpIfTrue(target) => {
+                    if let Some(&final_target) = jump_targets.get(&target) {
+                        if final_target != target {
+                            self.stats.jump_chains_simplified += 1;
+                        }
+                        Instruction::JmpIfTrue(final_target)
+                    } else {
+                        Instruction::JmpIfTrue(target)
+                    }
+                }
                 other => other,
             }
         }).collect()
@@ -385,7 +382,7 @@ impl Optimizer {
             if let Instruction::Jmp(target) = instr {
                 let final_target = self.follow_jump_chain(bytecode, *target, 100);
                 resolved.insert(i, final_target);
-            } else if let Instruction::JmpIfFalse(target) = instr {
+            } else if let Instruction::JmpIfFalse(target) | Instruction::JmpIfTrue(target) = instr {
                 let final_target = self.follow_jump_chain(bytecode, *target, 100);
                 resolved.insert(i, final_target);
             }
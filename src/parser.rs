@@ -1,16 +1,34 @@
-use crate::{LispExpr, Location, SourceExpr};
+use crate::compiler::ast::{with_stack_room, INTERPOLATE};
+use crate::lexer::{interpolation_len, Lexer, TokenKind};
+use crate::vm::bigint::BigInt;
+use crate::{LispExpr, Location, SourceExpr, MAX_NESTING_DEPTH};
 
 #[derive(Debug, Clone)]
 struct Token {
+    kind: TokenKind,
     text: String,
     line: usize,
     column: usize,
 }
 
+/// A syntax error and where it was detected
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location.format(), self.message)
+    }
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
     file: String,
+    depth: usize, // Current parse_expr nesting, bounded by MAX_NESTING_DEPTH
 }
 
 impl Parser {
@@ -20,20 +38,111 @@ impl Parser {
 
     pub fn new_with_file(input: &str, file: String) -> Self {
         let tokens = tokenize(input);
-        Parser { tokens, pos: 0, file }
+        Parser { tokens, pos: 0, file, depth: 0 }
     }
 
     pub fn parse_all(&mut self) -> Result<Vec<SourceExpr>, String> {
         let mut exprs = Vec::new();
         while self.pos < self.tokens.len() {
-            exprs.push(self.parse_expr()?);
+            if let Some(expr) = self.parse_top_level().map_err(|e| e.message)? {
+                exprs.push(expr);
+            }
         }
         Ok(exprs)
     }
 
-    fn parse_expr(&mut self) -> Result<SourceExpr, String> {
+    /// Parse the whole input without stopping at the first syntax error.
+    /// After an error the parser skips to the next top-level form and
+    /// carries on, so every form that parses is returned along with all
+    /// of the errors found.
+    pub fn parse_all_recovering(&mut self) -> (Vec<SourceExpr>, Vec<ParseError>) {
+        let mut exprs = Vec::new();
+        let mut errors = Vec::new();
+        while self.pos < self.tokens.len() {
+            let start = self.pos;
+            match self.parse_top_level() {
+                Ok(Some(expr)) => exprs.push(expr),
+                Ok(None) => {}
+                Err(error) => {
+                    errors.push(error);
+                    self.skip_to_next_form(start);
+                }
+            }
+        }
+        (exprs, errors)
+    }
+
+    /// Parse the next top-level form; None when only datum comments remain
+    fn parse_top_level(&mut self) -> Result<Option<SourceExpr>, ParseError> {
+        self.skip_datum_comments()?;
+        if self.pos >= self.tokens.len() {
+            return Ok(None);
+        }
+        self.parse_expr().map(Some)
+    }
+
+    /// Skip `#;expr` datum comments where a form or a closing paren may follow,
+    /// so `(f x #;y)` and a trailing `#;(...)` need no expression after them
+    fn skip_datum_comments(&mut self) -> Result<(), ParseError> {
+        while self.pos < self.tokens.len() && self.tokens[self.pos].text == "#;" {
+            self.pos += 1;
+            let _discarded = self.parse_expr()?;
+        }
+        Ok(())
+    }
+
+    /// Move past a form that failed to parse. Resumes after the form's
+    /// closing paren, at the next '(' in column 1 (a new top-level form
+    /// when the broken one was never closed), or at the first token past
+    /// the error that is outside any list, whichever comes first.
+    fn skip_to_next_form(&mut self, start: usize) {
+        let error_pos = self.pos;
+        let mut depth = 0i32;
+        let mut i = start;
+        while i < self.tokens.len() {
+            let token = &self.tokens[i];
+            if i > start && token.text == "(" && token.column == 1 {
+                break;
+            }
+            if i > error_pos && depth == 0 {
+                break;
+            }
+            if token.text == "(" {
+                depth += 1;
+            } else if token.text == ")" {
+                depth -= 1;
+                if depth <= 0 {
+                    i += 1;
+                    break;
+                }
+            }
+            i += 1;
+        }
+        self.pos = i.max(start + 1);
+    }
+
+    /// Build an error at the current token, or at the last one at end of input
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let location = self.tokens.get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|token| Location::new(token.line, token.column, self.file.clone()))
+            .unwrap_or_else(|| Location::new(1, 1, self.file.clone()));
+        ParseError { message: message.into(), location }
+    }
+
+    fn parse_expr(&mut self) -> Result<SourceExpr, ParseError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(self.error(format!("Expression nesting exceeds {} levels", MAX_NESTING_DEPTH)));
+        }
+        self.depth += 1;
+        let result = with_stack_room(|| self.parse_nested_expr());
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_nested_expr(&mut self) -> Result<SourceExpr, ParseError> {
         if self.pos >= self.tokens.len() {
-            return Err("Unexpected end of input".to_string());
+            return Err(self.error("Unexpected end of input"));
         }
 
         let token = &self.tokens[self.pos];
@@ -42,7 +151,15 @@ impl Parser {
         if token.text == "(" {
             self.parse_list()
         } else if token.text == ")" {
-            Err("Unexpected closing parenthesis".to_string())
+            Err(self.error(format!(
+                "Unexpected closing parenthesis at line {}, column {} - there is no '(' for it to close",
+                token.line, token.column
+            )))
+        } else if token.kind == TokenKind::UnterminatedString {
+            Err(self.error(format!(
+                "Unterminated string literal started at line {}, column {} - missing closing '\"'",
+                token.line, token.column
+            )))
         } else if token.text == "'" {
             // Quote syntax: 'expr → (quote expr)
             self.pos += 1;
@@ -57,34 +174,38 @@ impl Parser {
             let quasiquote_symbol = SourceExpr::new(LispExpr::Symbol("quasiquote".to_string()), location.clone());
             let quoted_list = vec![quasiquote_symbol, quoted_expr];
             Ok(SourceExpr::new(LispExpr::List(quoted_list), location))
+        } else if token.text == ",@" {
+            // Unquote-splicing: ,@expr → (unquote-splicing expr)
+            self.pos += 1;
+            let unquoted_expr = self.parse_expr()?;
+            let unquote_splicing_symbol = SourceExpr::new(
+                LispExpr::Symbol("unquote-splicing".to_string()),
+                location.clone()
+            );
+            let unquoted_list = vec![unquote_splicing_symbol, unquoted_expr];
+            Ok(SourceExpr::new(LispExpr::List(unquoted_list), location))
         } else if token.text == "," {
-            // Unquote or unquote-splicing
+            // Unquote: ,expr → (unquote expr)
+            self.pos += 1;
+            let unquoted_expr = self.parse_expr()?;
+            let unquote_symbol = SourceExpr::new(LispExpr::Symbol("unquote".to_string()), location.clone());
+            let unquoted_list = vec![unquote_symbol, unquoted_expr];
+            Ok(SourceExpr::new(LispExpr::List(unquoted_list), location))
+        } else if token.text == "#;" {
+            // Comment out next expression: #;expr → (nothing)
             self.pos += 1;
 
-            // Check if next token is @ for unquote-splicing
-            if self.pos < self.tokens.len() && self.tokens[self.pos].text == "@" {
-                // Unquote-splicing: ,@expr → (unquote-splicing expr)
-                self.pos += 1;
-                let unquoted_expr = self.parse_expr()?;
-                let unquote_splicing_symbol = SourceExpr::new(
-                    LispExpr::Symbol("unquote-splicing".to_string()),
-                    location.clone()
-                );
-                let unquoted_list = vec![unquote_splicing_symbol, unquoted_expr];
-                Ok(SourceExpr::new(LispExpr::List(unquoted_list), location))
-            } else {
-                // Unquote: ,expr → (unquote expr)
-                let unquoted_expr = self.parse_expr()?;
-                let unquote_symbol = SourceExpr::new(LispExpr::Symbol("unquote".to_string()), location.clone());
-                let unquoted_list = vec![unquote_symbol, unquoted_expr];
-                Ok(SourceExpr::new(LispExpr::List(unquoted_list), location))
-            }
+            // Parse and discard the next expression
+            let _discarded = self.parse_expr()?;
+
+            // Now parse and return the expression after the discarded one
+            self.parse_expr()
         } else if token.text == "#" {
             // Reader macro dispatch character
             self.pos += 1;
 
             if self.pos >= self.tokens.len() {
-                return Err("Unexpected end of input after '#'".to_string());
+                return Err(self.error("Unexpected end of input after '#'"));
             }
 
             let dispatch_char = &self.tokens[self.pos].text;
@@ -102,7 +223,7 @@ impl Parser {
                         vector_call.extend(elements);
                         Ok(SourceExpr::new(LispExpr::List(vector_call), location))
                     }
-                    _ => Err("Expected list after #(".to_string()),
+                    _ => Err(self.error("Expected list after #(")),
                 }
             } else if dispatch_char == "t" {
                 // Boolean true: #t → true
@@ -112,22 +233,13 @@ impl Parser {
                 // Boolean false: #f → false
                 self.pos += 1;
                 Ok(SourceExpr::new(LispExpr::Boolean(false), location))
-            } else if dispatch_char == ";" {
-                // Comment out next expression: #;expr → (nothing)
-                self.pos += 1; // consume ';'
-
-                // Parse and discard the next expression
-                let _discarded = self.parse_expr()?;
-
-                // Now parse and return the expression after the discarded one
-                self.parse_expr()
             } else if dispatch_char == "'" {
                 // Function quote: #'symbol → symbol
                 // In our Lisp, function names are already first-class values
                 self.pos += 1; // consume '
                 self.parse_expr()
             } else {
-                Err(format!("Unknown reader macro: #{}", dispatch_char))
+                Err(self.error(format!("Unknown reader macro: #{}", dispatch_char)))
             }
         } else if token.text == "true" {
             self.pos += 1;
@@ -135,10 +247,13 @@ impl Parser {
         } else if token.text == "false" {
             self.pos += 1;
             Ok(SourceExpr::new(LispExpr::Boolean(false), location))
-        } else if token.text.starts_with('"') && token.text.ends_with('"') {
+        } else if token.kind == TokenKind::String {
             // String literal
             self.pos += 1;
             let string_content = token.text[1..token.text.len()-1].to_string();
+            if string_content.contains("#{") {
+                return self.parse_interpolated_string(&string_content, location);
+            }
             // @TODO: for now, represents strings as symbols prefixed with "str:"
             // This is a temporary hack, there should be a String variant to LispExpr
             // for simplicity, just a special symbol so the compiler can recognise
@@ -148,18 +263,18 @@ impl Parser {
             if let Ok(f) = token.text.parse::<f64>() {
                 self.pos += 1;
                 Ok(SourceExpr::new(LispExpr::Float(f), location))
-            } else if let Ok(n) = token.text.parse::<i64>() {
+            } else if let Some(n) = BigInt::parse(&token.text) {
                 // Fallback to integer if float parsing fails
                 self.pos += 1;
-                Ok(SourceExpr::new(LispExpr::Number(n), location))
+                Ok(SourceExpr::new(Self::integer_literal(n), location))
             } else {
                 let symbol = token.text.clone();
                 self.pos += 1;
                 Ok(SourceExpr::new(LispExpr::Symbol(symbol), location))
             }
-        } else if let Ok(n) = token.text.parse::<i64>() {
+        } else if let Some(n) = BigInt::parse(&token.text) {
             self.pos += 1;
-            Ok(SourceExpr::new(LispExpr::Number(n), location))
+            Ok(SourceExpr::new(Self::integer_literal(n), location))
         } else {
             let symbol = token.text.clone();
             self.pos += 1;
@@ -167,7 +282,74 @@ impl Parser {
         }
     }
 
-    fn parse_list(&mut self) -> Result<SourceExpr, String> {
+    /// An integer literal: a Number if it fits in an i64, a BigInt otherwise
+    fn integer_literal(n: BigInt) -> LispExpr {
+        match n.to_i64() {
+            Some(n) => LispExpr::Number(n),
+            None => LispExpr::BigInt(n),
+        }
+    }
+
+    /// Interpolated string: "Hi #{name}!" → (#interpolate "Hi " name "!"),
+    /// which compiles to one Interpolate instruction (see INTERPOLATE).
+    /// Each #{...} holds exactly one expression, which may contain string
+    /// literals of its own; write \#{ for a literal #{.
+    /// A string whose only #{ is escaped stays a plain string literal.
+    fn parse_interpolated_string(&self, content: &str, location: Location) -> Result<SourceExpr, ParseError> {
+        let string_literal = |text: &str| {
+            SourceExpr::new(LispExpr::Symbol(format!("__STRING__{}", text)), location.clone())
+        };
+        let error = |message: String| ParseError { message, location: location.clone() };
+
+        let mut items = vec![SourceExpr::new(LispExpr::Symbol(INTERPOLATE.to_string()), location.clone())];
+        let mut text = String::new();
+        let mut rest = content;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("\\#{") {
+                text.push_str("#{");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("#{") {
+                let end = interpolation_len(after)
+                    .ok_or_else(|| error("Unterminated #{ in string literal - missing closing '}'".to_string()))?;
+
+                let source = &after[..end];
+                let mut exprs = Parser::new_with_file(source, self.file.clone())
+                    .parse_all()
+                    .map_err(|e| error(format!("In string interpolation #{{{}}}: {}", source, e)))?;
+                if exprs.len() != 1 {
+                    return Err(error(format!(
+                        "String interpolation #{{{}}} must contain exactly one expression, found {}",
+                        source,
+                        exprs.len()
+                    )));
+                }
+
+                let mut expr = exprs.remove(0);
+                relocate(&mut expr, &location);
+                if !text.is_empty() {
+                    items.push(string_literal(&std::mem::take(&mut text)));
+                }
+                items.push(expr);
+                rest = &after[end + 1..];
+            } else {
+                let ch = rest.chars().next().unwrap();
+                text.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+
+        if items.len() == 1 {
+            // Nothing but escaped #{ - still a plain string
+            return Ok(string_literal(&text));
+        }
+        if !text.is_empty() {
+            items.push(string_literal(&text));
+        }
+        Ok(SourceExpr::new(LispExpr::List(items), location))
+    }
+
+    fn parse_list(&mut self) -> Result<SourceExpr, ParseError> {
         let start_token = &self.tokens[self.pos];
         let location = Location::new(start_token.line, start_token.column, self.file.clone());
 
@@ -175,7 +357,11 @@ impl Parser {
 
         let mut items = Vec::new();
 
-        while self.pos < self.tokens.len() {
+        loop {
+            self.skip_datum_comments()?;
+            if self.pos >= self.tokens.len() {
+                break;
+            }
             if self.tokens[self.pos].text == ")" {
                 self.pos += 1; // consume ')'
                 return Ok(SourceExpr::new(LispExpr::List(items), location));
@@ -189,10 +375,11 @@ impl Parser {
 
                 // Parse the rest expression
                 let rest = self.parse_expr()?;
+                self.skip_datum_comments()?;
 
                 // Expect closing paren
                 if self.pos >= self.tokens.len() || self.tokens[self.pos].text != ")" {
-                    return Err("Expected ')' after dotted pair".to_string());
+                    return Err(self.error("Expected ')' after dotted pair"));
                 }
                 self.pos += 1; // consume ')'
 
@@ -203,172 +390,40 @@ impl Parser {
             }
         }
 
-        Err("Unclosed list - missing closing parenthesis".to_string())
+        // Point at the '(' that was never closed rather than at end of input
+        Err(ParseError {
+            message: format!(
+                "Unterminated list started at line {}, column {} - missing closing parenthesis",
+                location.line, location.column
+            ),
+            location,
+        })
     }
 }
 
-fn tokenize(input: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut current = String::new();
-    let mut line = 1;
-    let mut column = 1;
-    let mut token_start_column = 1;
-    let mut in_string = false;
-    let mut string_content = String::new();
-    let mut string_start_line = 1;
-    let mut string_start_column = 1;
-    let mut in_comment = false;
-
-    for ch in input.chars() {
-        if in_comment {
-            // Skip everything until newline
-            if ch == '\n' {
-                in_comment = false;
-                line += 1;
-                column = 1;
-                token_start_column = 1;
-            } else {
-                column += 1;
-            }
-        } else if in_string {
-            if ch == '"' {
-                // End of string
-                tokens.push(Token {
-                    text: format!("\"{}\"", string_content),
-                    line: string_start_line,
-                    column: string_start_column,
-                });
-                string_content.clear();
-                in_string = false;
-                column += 1;
-                token_start_column = column;
-            } else if ch == '\\' {
-                // Handle escape sequences (basic support)
-                string_content.push(ch);
-                column += 1;
-            } else {
-                string_content.push(ch);
-                if ch == '\n' {
-                    line += 1;
-                    column = 1;
-                } else {
-                    column += 1;
-                }
-            }
-        } else {
-            match ch {
-                '"' => {
-                    // Start of string
-                    if !current.is_empty() {
-                        tokens.push(Token {
-                            text: current.clone(),
-                            line,
-                            column: token_start_column,
-                        });
-                        current.clear();
-                    }
-                    in_string = true;
-                    string_start_line = line;
-                    string_start_column = column;
-                    column += 1;
-                }
-                ';' => {
-                    // Check if previous token was '#' - if so, this is part of #; reader macro
-                    let is_reader_macro = !tokens.is_empty() && tokens.last().unwrap().text == "#";
-
-                    if is_reader_macro {
-                        // Treat ';' as a special token (part of #; reader macro)
-                        if !current.is_empty() {
-                            tokens.push(Token {
-                                text: current.clone(),
-                                line,
-                                column: token_start_column,
-                            });
-                            current.clear();
-                        }
-                        tokens.push(Token {
-                            text: ";".to_string(),
-                            line,
-                            column,
-                        });
-                        column += 1;
-                        token_start_column = column;
-                    } else {
-                        // Start of comment - skip until end of line
-                        if !current.is_empty() {
-                            tokens.push(Token {
-                                text: current.clone(),
-                                line,
-                                column: token_start_column,
-                            });
-                            current.clear();
-                        }
-                        in_comment = true;
-                        column += 1;
-                    }
-                }
-                '(' | ')' | '\'' | '`' | ',' | '@' | '#' => {
-                    if !current.is_empty() {
-                        tokens.push(Token {
-                            text: current.clone(),
-                            line,
-                            column: token_start_column,
-                        });
-                        current.clear();
-                    }
-                    tokens.push(Token {
-                        text: ch.to_string(),
-                        line,
-                        column,
-                    });
-                    column += 1;
-                    token_start_column = column;
-                }
-                '\n' => {
-                    if !current.is_empty() {
-                        tokens.push(Token {
-                            text: current.clone(),
-                            line,
-                            column: token_start_column,
-                        });
-                        current.clear();
-                    }
-                    line += 1;
-                    column = 1;
-                    token_start_column = 1;
-                }
-                ' ' | '\t' | '\r' => {
-                    if !current.is_empty() {
-                        tokens.push(Token {
-                            text: current.clone(),
-                            line,
-                            column: token_start_column,
-                        });
-                        current.clear();
-                    }
-                    column += 1;
-                    token_start_column = column;
-                }
-                _ => {
-                    if current.is_empty() {
-                        token_start_column = column;
-                    }
-                    current.push(ch);
-                    column += 1;
-                }
-            }
+/// Give an expression parsed out of a string literal the literal's location
+fn relocate(expr: &mut SourceExpr, location: &Location) {
+    expr.location = location.clone();
+    match &mut expr.expr {
+        LispExpr::List(items) => items.iter_mut().for_each(|item| relocate(item, location)),
+        LispExpr::DottedList(items, rest) => {
+            items.iter_mut().for_each(|item| relocate(item, location));
+            relocate(rest, location);
         }
+        _ => {}
     }
+}
 
-    if !current.is_empty() {
-        tokens.push(Token {
-            text: current,
-            line,
-            column: token_start_column,
-        });
-    }
-
-    tokens
+fn tokenize(input: &str) -> Vec<Token> {
+    Lexer::new(input)
+        .filter(|(token, _)| token.kind != TokenKind::Comment)
+        .map(|(token, span)| Token {
+            kind: token.kind,
+            text: token.text.to_string(),
+            line: span.line,
+            column: span.column,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -765,4 +820,138 @@ mod tests {
             _ => panic!("Expected List"),
         }
     }
+
+    #[test]
+    fn test_recovering_parse_without_errors() {
+        let mut parser = Parser::new("(def x 1) (+ x 2)");
+        let (exprs, errors) = parser.parse_all_recovering();
+        assert_eq!(exprs.len(), 2);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_recovering_parse_collects_multiple_errors() {
+        let source = "(def a 1)\n(foo #z)\n(def b 2)\n)\n(bar #q 3)\n(def c 3)";
+        let mut parser = Parser::new_with_file(source, "test.lisp".to_string());
+        let (exprs, errors) = parser.parse_all_recovering();
+
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown reader macro: #z",
+                "Unexpected closing parenthesis at line 4, column 1 - there is no '(' for it to close",
+                "Unknown reader macro: #q",
+            ]
+        );
+        let lines: Vec<usize> = errors.iter().map(|e| e.location.line).collect();
+        assert_eq!(lines, vec![2, 4, 5]);
+        assert!(errors[1].to_string().starts_with("test.lisp:4:1: Unexpected closing parenthesis"));
+
+        // Every well-formed form survives
+        let heads: Vec<&LispExpr> = exprs.iter()
+            .map(|e| match &e.expr {
+                LispExpr::List(items) => &items[1].expr,
+                other => panic!("Expected list, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            heads,
+            vec![
+                &LispExpr::Symbol("a".to_string()),
+                &LispExpr::Symbol("b".to_string()),
+                &LispExpr::Symbol("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovering_parse_resumes_after_unclosed_form() {
+        // The unclosed defun swallows nothing past the next column-1 form
+        let source = "(defun f (x)\n  (+ x 1)\n(def y 2)\n(def z 3)";
+        let mut parser = Parser::new(source);
+        let (exprs, errors) = parser.parse_all_recovering();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("missing closing parenthesis"));
+        assert_eq!(exprs.len(), 2);
+    }
+
+    #[test]
+    fn test_recovering_parse_skips_bad_atom() {
+        let mut parser = Parser::new("#q (+ 1 2)");
+        let (exprs, errors) = parser.parse_all_recovering();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(exprs.len(), 1);
+        assert!(matches!(&exprs[0].expr, LispExpr::List(items) if items.len() == 3));
+    }
+
+    #[test]
+    fn test_unterminated_list_points_at_opening_paren() {
+        let source = "(def a 1)\n(defun f (x)\n  (+ x 1)";
+        let mut parser = Parser::new_with_file(source, "test.lisp".to_string());
+        let (_, errors) = parser.parse_all_recovering();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Unterminated list started at line 2, column 1 - missing closing parenthesis"
+        );
+        assert_eq!(errors[0].location, Location::new(2, 1, "test.lisp".to_string()));
+    }
+
+    #[test]
+    fn test_unterminated_inner_list() {
+        let mut parser = Parser::new("(list (+ 1 2)\n      (* 3 4");
+        let err = parser.parse_all().unwrap_err();
+        assert_eq!(err, "Unterminated list started at line 2, column 7 - missing closing parenthesis");
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        let mut parser = Parser::new("(print \"hello)\n(+ 1 2)");
+        let (_, errors) = parser.parse_all_recovering();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Unterminated string literal started at line 1, column 8 - missing closing '\"'"
+        );
+        assert_eq!((errors[0].location.line, errors[0].location.column), (1, 8));
+    }
+
+    #[test]
+    fn test_lone_quote_is_unterminated_string() {
+        let mut parser = Parser::new("\"");
+        let err = parser.parse_all().unwrap_err();
+        assert!(err.starts_with("Unterminated string literal started at line 1, column 1"));
+    }
+
+    #[test]
+    fn test_extra_closing_paren() {
+        let mut parser = Parser::new("(+ 1 2))");
+        let (exprs, errors) = parser.parse_all_recovering();
+        assert_eq!(exprs.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Unexpected closing parenthesis at line 1, column 8 - there is no '(' for it to close"
+        );
+    }
+
+    #[test]
+    fn test_parse_interpolated_string_desugars_to_interpolate() {
+        let mut parser = Parser::new("\"a #{x} b\"");
+        let exprs = parser.parse_all().unwrap();
+        let mut expected = Parser::new("(head \"a \" x \" b\")").parse_all().unwrap();
+        // The whole desugared form carries the string literal's location
+        relocate(&mut expected[0], &exprs[0].location);
+        if let LispExpr::List(items) = &mut expected[0].expr {
+            items[0].expr = LispExpr::Symbol(INTERPOLATE.to_string());
+        }
+        assert_eq!(exprs[0], expected[0]);
+    }
+
+    #[test]
+    fn test_interpolation_head_cannot_be_written() {
+        let exprs = Parser::new(INTERPOLATE).parse_all().unwrap_or_default();
+        assert!(!exprs.iter().any(|e| e.expr == LispExpr::Symbol(INTERPOLATE.to_string())));
+    }
 }
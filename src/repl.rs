@@ -1,4 +1,6 @@
-use crate::{Compiler, VM, parser::Parser, disassembler, Value};
+use crate::{bytecode, Compiler, CompileError, Instruction, Program, VM, parser::Parser, Value};
+use crate::lexer::{Lexer, TokenKind};
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
 use std::sync::Arc;
 
@@ -6,6 +8,12 @@ pub struct Repl {
     compiler: Compiler,
     vm: VM,
     pub input_buffer: String,
+    /// File from the most recent :load, re-run by :reload
+    last_loaded: Option<String>,
+    /// Parameter lists of functions defined in the session, for :disasm-fn
+    function_params: HashMap<String, String>,
+    /// Globals defined with def (not def-mutable), which set! must reject on later lines
+    immutable_globals: HashSet<String>,
 }
 
 impl Repl {
@@ -15,11 +23,18 @@ impl Repl {
 
         // Auto-load stdlib.lisp if it exists
         Self::load_stdlib(&mut compiler, &mut vm);
+        let immutable_globals = compiler.defined_globals()
+            .filter(|(_, mutable)| !mutable)
+            .map(|(name, _)| name.clone())
+            .collect();
 
         Repl {
             compiler,
             vm,
             input_buffer: String::new(),
+            last_loaded: None,
+            function_params: HashMap::new(),
+            immutable_globals,
         }
     }
 
@@ -102,37 +117,44 @@ impl Repl {
         println!("Goodbye!");
     }
 
+    /// Input is complete once every list and string is closed. Extra
+    /// closing parens also count as complete so the parser can report them.
     pub fn is_complete_input(&self) -> bool {
         let mut depth = 0;
-        let mut in_string = false;
-        let mut chars = self.input_buffer.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            match ch {
-                '"' => in_string = !in_string,
-                '(' if !in_string => depth += 1,
-                ')' if !in_string => depth -= 1,
+
+        for (token, _) in Lexer::new(&self.input_buffer) {
+            match token.kind {
+                TokenKind::LParen => depth += 1,
+                TokenKind::RParen => depth -= 1,
+                TokenKind::UnterminatedString => return false,
                 _ => {}
             }
         }
 
-        depth == 0 && !self.input_buffer.trim().is_empty()
+        depth <= 0 && !self.input_buffer.trim().is_empty()
     }
 
     fn eval_and_print(&mut self) {
-        let input = self.input_buffer.trim();
-
-        let mut parser = Parser::new(input);
-        let exprs = match parser.parse_all() {
-            Ok(exprs) => exprs,
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
-                return;
-            }
+        let input = self.input_buffer.trim().to_string();
+        match self.eval_source(&input, None) {
+            Ok(Some(result)) => println!("=> {}", self.format_value(&result)),
+            Ok(None) => {}
+            Err(message) => eprintln!("{}", message),
+        }
+    }
+
+    /// Compile and run `source` in the live session, keeping its definitions.
+    /// Returns the value left on the stack, or the formatted parse, compile or
+    /// runtime error.
+    pub fn eval_source(&mut self, source: &str, file: Option<&str>) -> Result<Option<Value>, String> {
+        let mut parser = match file {
+            Some(path) => Parser::new_with_file(source, path.to_string()),
+            None => Parser::new(source),
         };
+        let exprs = parser.parse_all().map_err(|e| format!("Parse error: {}", e))?;
 
         if exprs.is_empty() {
-            return;
+            return Ok(None);
         }
 
         // Create a fresh compiler with runtime context from the VM
@@ -140,25 +162,30 @@ impl Repl {
         let mut fresh_compiler = Compiler::new();
         fresh_compiler.with_known_functions(self.vm.functions.keys());
         fresh_compiler.with_known_globals(self.vm.global_vars.keys());
+        fresh_compiler.with_immutable_globals(self.immutable_globals.iter());
 
-        let (new_functions, main_bytecode) = match fresh_compiler.compile_program(&exprs) {
-            Ok(result) => result,
-            Err(e) => {
-                let source_lines: Vec<&str> = input.lines().collect();
-                let source_line = if e.location.line > 0 && e.location.line <= source_lines.len() {
-                    Some(source_lines[e.location.line - 1])
-                } else {
-                    None
-                };
-                eprintln!("{}", e.format(source_line));
-                return;
+        let program = fresh_compiler.compile_to_program(&exprs)
+            .map_err(|e| Self::format_compile_error(&e, source))?;
+
+        // A later def or def-mutable of the same name replaces its mutability
+        for (name, mutable) in fresh_compiler.defined_globals() {
+            if mutable {
+                self.immutable_globals.remove(name);
+            } else {
+                self.immutable_globals.insert(name.clone());
             }
-        };
+        }
 
-        for (name, bytecode) in new_functions {
+        self.function_params.extend(program.params);
+        for (name, bytecode) in program.functions {
             self.vm.functions.insert(name, bytecode);
         }
 
+        self.run_main(program.main)
+    }
+
+    /// Run top-level bytecode in the session and return the value it leaves
+    fn run_main(&mut self, main_bytecode: Vec<Instruction>) -> Result<Option<Value>, String> {
         self.vm.current_bytecode = main_bytecode;
         self.vm.value_stack.clear();
         self.vm.call_stack.clear();
@@ -166,20 +193,71 @@ impl Repl {
         self.vm.halted = false;
 
         match self.vm.run() {
-            Ok(_) => {
-                if let Some(result) = self.vm.value_stack.last() {
-                    println!("=> {}", self.format_value(result));
-                }
-            }
-            Err(runtime_error) => {
-                eprintln!("{}", runtime_error.format());
+            Ok(_) => Ok(self.vm.value_stack.last().cloned()),
+            Err(runtime_error) => Err(runtime_error.format()),
+        }
+    }
+
+    /// Read `path` and evaluate it into the session; :reload runs it again.
+    /// A `.vmc` or `.bc` file is compiled bytecode (see `save_bytecode` and the
+    /// bytecomp tool), which is loaded without recompiling.
+    /// A file that fails to compile or run is still remembered so it can be
+    /// fixed and reloaded.
+    pub fn load_file(&mut self, path: &str) -> Result<Option<Value>, String> {
+        if path.ends_with(".vmc") || path.ends_with(".bc") {
+            self.last_loaded = Some(path.to_string());
+            return self.load_bytecode(path);
+        }
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+        self.last_loaded = Some(path.to_string());
+        self.eval_source(&source, Some(path))
+    }
+
+    /// Add the functions from a bytecode file to the session and run its main code
+    pub fn load_bytecode(&mut self, path: &str) -> Result<Option<Value>, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+        let (functions, main_bytecode) = bytecode::deserialize_bytecode(&bytes)
+            .map_err(|e| format!("Cannot load '{}': {}", path, e))?;
+        self.vm.functions.extend(functions);
+        self.run_main(main_bytecode)
+    }
+
+    /// Write the session to a bytecode file: every function, plus main code
+    /// that restores the current globals. Loading it starts a session where
+    /// it left off without recompiling anything. Network handles can't be
+    /// saved, so globals holding one are skipped.
+    pub fn save_bytecode(&self, path: &str) -> Result<(), String> {
+        let mut names: Vec<_> = self.vm.global_vars.keys().collect();
+        names.sort();
+
+        let mut main_bytecode = Vec::new();
+        for name in names {
+            let value = &self.vm.global_vars[name];
+            if matches!(value, Value::TcpListener(_) | Value::TcpStream(_) | Value::SharedTcpListener(_)) {
+                continue;
             }
+            main_bytecode.push(Instruction::Push(value.clone()));
+            main_bytecode.push(Instruction::StoreGlobal(name.clone()));
+        }
+        main_bytecode.push(Instruction::Halt);
+
+        bytecode::save_bytecode_file(path, &self.vm.functions, &main_bytecode)
+            .map_err(|e| format!("Cannot save '{}': {}", path, e))
+    }
+
+    /// Load the file from the most recent :load again
+    pub fn reload(&mut self) -> Result<Option<Value>, String> {
+        match self.last_loaded.clone() {
+            Some(path) => self.load_file(&path),
+            None => Err("No file loaded yet; use :load <file> first".to_string()),
         }
     }
 
     pub fn format_value(&self, value: &Value) -> String {
         match value {
             Value::Integer(n) => n.to_string(),
+            Value::BigInt(n) => n.to_string(),
             Value::Float(f) => {
                 // Format float nicely - show decimal point even for whole numbers
                 if f.fract() == 0.0 && f.is_finite() {
@@ -194,14 +272,15 @@ impl Repl {
                     .iter()
                     .map(|v| self.format_value(v))
                     .collect();
-                format!("({})", formatted_items.join(" "))
+                match items.dotted_tail() {
+                    Some(atom) => format!("({} . {})", formatted_items.join(" "), self.format_value(atom)),
+                    None => format!("({})", formatted_items.join(" ")),
+                }
             }
             Value::Symbol(s) => s.to_string(),
             Value::String(s) => format!("\"{}\"", s),
             Value::Function(name) => format!("<function {}>", name),
-            Value::Closure(closure_data) => {
-                format!("<closure ({})>", closure_data.params.join(" "))
-            }
+            Value::Closure(closure_data) => closure_data.describe(),
             Value::HashMap(map) => {
                 let mut items: Vec<String> = map.iter()
                     .map(|(k, v)| format!("{} {}", self.format_value(&Value::String(Arc::new(k.clone()))), self.format_value(v)))
@@ -242,12 +321,63 @@ impl Repl {
             ":clear" | ":c" => {
                 self.clear_state();
             }
-            ":bytecode" | ":bc" => {
+            ":complete" => {
+                let prefix = parts.get(1).copied().unwrap_or("");
+                let matches = self.completions(prefix);
+                if matches.is_empty() {
+                    println!("No matches");
+                } else {
+                    for name in matches {
+                        println!("  {}", name);
+                    }
+                }
+            }
+            ":load" | ":l" => {
+                // Everything after the command is the path; quotes are optional
+                let path = cmd[parts[0].len()..].trim().trim_matches('"');
+                if path.is_empty() {
+                    eprintln!("Usage: :load <file>");
+                } else {
+                    match self.load_file(path) {
+                        Ok(_) => println!("Loaded {}", path),
+                        Err(message) => eprintln!("{}", message),
+                    }
+                }
+            }
+            ":save" => {
+                let path = cmd[parts[0].len()..].trim().trim_matches('"');
+                if path.is_empty() {
+                    eprintln!("Usage: :save <file.vmc>");
+                } else {
+                    match self.save_bytecode(path) {
+                        Ok(_) => println!("Saved {}", path),
+                        Err(message) => eprintln!("{}", message),
+                    }
+                }
+            }
+            ":reload" | ":r" => {
+                match self.reload() {
+                    Ok(_) => println!("Reloaded {}", self.last_loaded.as_deref().unwrap_or_default()),
+                    Err(message) => eprintln!("{}", message),
+                }
+            }
+            ":disasm" | ":bytecode" | ":bc" => {
                 if parts.len() < 2 {
-                    eprintln!("Usage: :bytecode <expression>");
+                    eprintln!("Usage: {} <expression or function name>", parts[0]);
                 } else {
-                    let expr = parts[1..].join(" ");
-                    self.show_bytecode(&expr);
+                    match self.disassemble_source(&parts[1..].join(" ")) {
+                        Ok(listing) => println!("{}", listing),
+                        Err(message) => eprintln!("{}", message),
+                    }
+                }
+            }
+            ":disasm-fn" => {
+                match parts.get(1) {
+                    None => eprintln!("Usage: :disasm-fn <name>"),
+                    Some(name) => match self.disassemble_function(name) {
+                        Ok(listing) => println!("{}", listing),
+                        Err(message) => eprintln!("{}", message),
+                    },
                 }
             }
             _ => {
@@ -265,7 +395,13 @@ impl Repl {
         println!("  :quit, :exit, :q    - Exit the REPL");
         println!("  :functions, :f      - List all defined functions");
         println!("  :clear, :c          - Clear all state (reset VM and compiler)");
-        println!("  :bytecode <expr>    - Show bytecode for an expression");
+        println!("  :load, :l <file>    - Evaluate a file into the session (.vmc/.bc: load bytecode)");
+        println!("  :save <file.vmc>    - Save the session's functions and globals as bytecode");
+        println!("  :reload, :r         - Load the last loaded file again");
+        println!("  :disasm <expr>      - Show bytecode for an expression or defun, without running it");
+        println!("                        (a bare function name shows that function; alias :bytecode)");
+        println!("  :disasm-fn <name>   - Show the bytecode of a defined function");
+        println!("  :complete <prefix>  - List known names starting with prefix");
         println!();
         println!("Examples:");
         println!("  (+ 2 3)");
@@ -273,6 +409,17 @@ impl Repl {
         println!("  (square 5)");
     }
 
+    /// Known names starting with `prefix`: special forms, builtins, user functions,
+    /// macros and globals from both the compiler and the VM. Sorted, no duplicates.
+    pub fn completions(&self, prefix: &str) -> Vec<String> {
+        let mut names = self.compiler.known_names();
+        names.extend(self.vm.known_names());
+        names.retain(|n| n.starts_with(prefix));
+        names.sort();
+        names.dedup();
+        names
+    }
+
     fn list_functions(&self) {
         if self.vm.functions.is_empty() {
             println!("No functions defined");
@@ -291,38 +438,47 @@ impl Repl {
         self.compiler = Compiler::new();
         self.vm = VM::new();
         self.input_buffer.clear();
+        self.immutable_globals.clear();
         println!("State cleared");
     }
 
-    fn show_bytecode(&mut self, expr: &str) {
-        let mut parser = Parser::new(expr);
-        let exprs = match parser.parse_all() {
-            Ok(exprs) => exprs,
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
-                return;
-            }
-        };
+    /// Disassembly for :disasm. A bare name of a defined function shows that
+    /// function; anything else is compiled against the session (so it can call
+    /// functions defined earlier) and shown without being run or defined.
+    pub fn disassemble_source(&self, source: &str) -> Result<String, String> {
+        let trimmed = source.trim();
+        if self.vm.functions.contains_key(trimmed) {
+            return self.disassemble_function(trimmed);
+        }
+
+        let exprs = Parser::new(source).parse_all().map_err(|e| format!("Parse error: {}", e))?;
 
         let mut temp_compiler = Compiler::new();
-        for (name, bytecode) in &self.vm.functions {
-            temp_compiler.functions.insert(name.clone(), bytecode.clone());
-        }
+        temp_compiler.with_known_functions(self.vm.functions.keys());
+        temp_compiler.with_known_globals(self.vm.global_vars.keys());
+        temp_compiler.with_immutable_globals(self.immutable_globals.iter());
+        let program = temp_compiler.compile_to_program(&exprs)
+            .map_err(|e| Self::format_compile_error(&e, source))?;
 
-        let (functions, main) = match temp_compiler.compile_program(&exprs) {
-            Ok(result) => result,
-            Err(e) => {
-                let source_lines: Vec<&str> = expr.lines().collect();
-                let source_line = if e.location.line > 0 && e.location.line <= source_lines.len() {
-                    Some(source_lines[e.location.line - 1])
-                } else {
-                    None
-                };
-                eprintln!("{}", e.format(source_line));
-                return;
-            }
-        };
+        Ok(program.disassemble_program())
+    }
 
-        println!("{}", disassembler::disassemble_bytecode(&functions, &main));
+    /// Disassembly of a function already defined in the session, for :disasm-fn
+    pub fn disassemble_function(&self, name: &str) -> Result<String, String> {
+        self.vm.functions.get(name)
+            .map(|bytecode| Program::new(HashMap::from([(name.to_string(), bytecode.clone())]), Vec::new())
+                .with_params(self.function_params.clone()))
+            .and_then(|program| program.disassemble_function(name))
+            .ok_or_else(|| format!("Unknown function: {}", name))
+    }
+
+    fn format_compile_error(e: &CompileError, source: &str) -> String {
+        let source_lines: Vec<&str> = source.lines().collect();
+        let source_line = if e.location.line > 0 && e.location.line <= source_lines.len() {
+            Some(source_lines[e.location.line - 1])
+        } else {
+            None
+        };
+        e.format(source_line)
     }
 }
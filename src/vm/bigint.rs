@@ -0,0 +1,479 @@
+// Arbitrary-precision integers
+// Integer arithmetic that overflows i64 is promoted to a BigInt instead of
+// wrapping. Results that fit back into an i64 are turned into plain integers
+// again (see Value::from_bigint), so a BigInt value is always outside i64 range.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Sub};
+
+/// Sign and magnitude integer. The magnitude is stored in base 2^32 digits,
+/// least significant first, with no leading zero digits; zero is an empty
+/// magnitude and is never negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt { negative: false, magnitude: Vec::new() }
+    }
+
+    pub fn from_i64(n: i64) -> Self {
+        let abs = n.unsigned_abs();
+        Self::from_parts(n < 0, vec![abs as u32, (abs >> 32) as u32])
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        let negative = negative && !magnitude.is_empty();
+        BigInt { negative, magnitude }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The value as an i64, or None if it doesn't fit
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let abs = self.magnitude.iter().rev().fold(0u64, |acc, &d| (acc << 32) | d as u64);
+        if self.negative {
+            0i64.checked_sub_unsigned(abs)
+        } else {
+            i64::try_from(abs).ok()
+        }
+    }
+
+    /// Nearest float (infinite if the value is beyond f64 range)
+    pub fn to_f64(&self) -> f64 {
+        let abs = self.magnitude.iter().rev().fold(0.0, |acc, &d| acc * 4294967296.0 + d as f64);
+        if self.negative { -abs } else { abs }
+    }
+
+    /// The exact value of a finite float with no fractional part, or None
+    /// for NaN, infinities and non-integral floats
+    pub fn from_f64(f: f64) -> Option<Self> {
+        if !f.is_finite() || f.fract() != 0.0 {
+            return None;
+        }
+        if f.abs() < 9223372036854775808.0 {
+            return Some(Self::from_i64(f as i64));
+        }
+        // At or above 2^63 the float is its 53-bit significand shifted left
+        let bits = f.to_bits();
+        let shift = ((bits >> 52) & 0x7ff) as usize - 1075;
+        let significand = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let wide = (significand as u128) << (shift % 32);
+        let mut magnitude = vec![0u32; shift / 32];
+        magnitude.extend([wide as u32, (wide >> 32) as u32, (wide >> 64) as u32]);
+        Some(Self::from_parts(f < 0.0, magnitude))
+    }
+
+    /// Parse an optionally signed decimal integer of any length
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::parse_radix(text, 10)
+    }
+
+    /// Parse an optionally signed integer of any length in the given radix
+    /// (2 to 36), accepting the same text as i64::from_str_radix
+    pub fn parse_radix(text: &str, radix: u32) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() {
+            return None;
+        }
+        let mut magnitude = Vec::new();
+        for c in digits.chars() {
+            mul_small_add(&mut magnitude, radix, c.to_digit(radix)?);
+        }
+        Some(Self::from_parts(negative, magnitude))
+    }
+
+    /// Raise to a power by repeated squaring
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = BigInt::from_i64(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Truncating division: the quotient rounds toward zero and the remainder
+    /// takes the sign of the dividend, like i64 `/` and `%`. None for a zero divisor.
+    pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitudes(&self.magnitude, &other.magnitude);
+        Some((
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// Multiply by 2^bits
+    pub fn shl(&self, bits: u32) -> BigInt {
+        let mut magnitude = vec![0u32; bits as usize / 32];
+        magnitude.extend(shift_digits_left(&self.magnitude, bits % 32));
+        Self::from_parts(self.negative, magnitude)
+    }
+
+    /// Divide by 2^bits, rounding toward negative infinity like i64 `>>`
+    pub fn shr(&self, bits: u32) -> BigInt {
+        let skip = bits as usize / 32;
+        if skip >= self.magnitude.len() {
+            return if self.negative { Self::from_i64(-1) } else { Self::zero() };
+        }
+        let mut magnitude = shift_digits_right(&self.magnitude[skip..], bits % 32);
+        // A negative value whose shifted-out bits aren't all zero rounds down
+        let low_bits = bits % 32;
+        let lost = self.magnitude[..skip].iter().any(|&d| d != 0)
+            || (low_bits > 0 && self.magnitude[skip] & ((1 << low_bits) - 1) != 0);
+        if self.negative && lost {
+            mul_small_add(&mut magnitude, 1, 1);
+        }
+        Self::from_parts(self.negative, magnitude)
+    }
+
+    /// The lowest `len` digits of the two's complement form
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut digits = self.magnitude.clone();
+        digits.resize(len, 0);
+        if self.negative {
+            negate_digits(&mut digits);
+        }
+        digits
+    }
+
+    /// Bitwise `op` on the two's complement forms, extended with sign bits,
+    /// which is what i64 bit operations do within 64 bits
+    fn bitwise(&self, other: &BigInt, op: impl Fn(u32, u32) -> u32) -> BigInt {
+        // One extra digit leaves room for the sign
+        let len = self.magnitude.len().max(other.magnitude.len()) + 1;
+        let mut digits: Vec<u32> = self.twos_complement(len).into_iter()
+            .zip(other.twos_complement(len))
+            .map(|(a, b)| op(a, b))
+            .collect();
+        let negative = digits[len - 1] >> 31 == 1;
+        if negative {
+            negate_digits(&mut digits);
+        }
+        Self::from_parts(negative, digits)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitudes(&self.magnitude, &other.magnitude));
+        }
+        // Opposite signs: subtract the smaller magnitude from the larger
+        match compare_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_magnitudes(&other.magnitude, &self.magnitude)),
+            _ => BigInt::from_parts(self.negative, sub_magnitudes(&self.magnitude, &other.magnitude)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut product = vec![0u32; self.magnitude.len() + other.magnitude.len()];
+        for (i, &a) in self.magnitude.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.magnitude.iter().enumerate() {
+                let t = product[i + j] as u64 + a as u64 * b as u64 + carry;
+                product[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            product[i + other.magnitude.len()] = carry as u32;
+        }
+        BigInt::from_parts(self.negative != other.negative, product)
+    }
+}
+
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    // !x is -x - 1 in two's complement
+    fn not(self) -> BigInt {
+        &(-self) - &BigInt::from_i64(1)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        BigInt::from_i64(n)
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(n: u128) -> Self {
+        BigInt::from_parts(false, vec![n as u32, (n >> 32) as u32, (n >> 64) as u32, (n >> 96) as u32])
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Peel off nine decimal digits at a time, least significant first
+        let mut magnitude = self.magnitude.clone();
+        let mut chunks = Vec::new();
+        while !magnitude.is_empty() {
+            chunks.push(div_small(&mut magnitude, 1_000_000_000));
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{}", first)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &digit) in long.iter().enumerate() {
+        let t = digit as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(t as u32);
+        carry = t >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+// a - b, where a's magnitude is at least b's
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut t = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = if t < 0 { t += 1 << 32; 1 } else { 0 };
+        difference.push(t as u32);
+    }
+    while difference.last() == Some(&0) {
+        difference.pop();
+    }
+    difference
+}
+
+// Long division of magnitudes one base 2^32 digit at a time (Knuth's
+// Algorithm D), returning (quotient, remainder). b must not be zero.
+fn div_rem_magnitudes(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_magnitudes(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if let [divisor] = b {
+        let mut quotient = a.to_vec();
+        let remainder = div_small(&mut quotient, *divisor);
+        return (quotient, if remainder == 0 { Vec::new() } else { vec![remainder] });
+    }
+
+    // Shift both so the divisor's top digit has its high bit set, which keeps
+    // each estimated quotient digit at most two too large
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut v = shift_digits_left(b, shift);
+    v.pop();
+    let mut u = shift_digits_left(a, shift);
+    let n = v.len();
+    let base = 1u64 << 32;
+
+    let mut quotient = vec![0u32; a.len() - n + 1];
+    for j in (0..quotient.len()).rev() {
+        // Estimate the digit from the top two digits of the running remainder
+        let top = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+        let mut q = top / v[n - 1] as u64;
+        let mut r = top % v[n - 1] as u64;
+        while q >= base || q * v[n - 2] as u64 > ((r << 32) | u[j + n - 2] as u64) {
+            q -= 1;
+            r += v[n - 1] as u64;
+            if r >= base {
+                break;
+            }
+        }
+
+        // u[j..=j+n] -= q * v
+        let mut carry = 0u64;
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let product = q * v[i] as u64 + carry;
+            carry = product >> 32;
+            let t = u[i + j] as i64 - borrow - (product as u32) as i64;
+            u[i + j] = t as u32;
+            borrow = i64::from(t < 0);
+        }
+        let t = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = t as u32;
+
+        // The estimate was still one too large: add the divisor back
+        if t < 0 {
+            q -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = q as u32;
+    }
+
+    let mut remainder = shift_digits_right(&u[..n], shift);
+    while remainder.last() == Some(&0) {
+        remainder.pop();
+    }
+    (quotient, remainder)
+}
+
+// The digits shifted left by fewer than 32 bits, with one extra top digit
+fn shift_digits_left(digits: &[u32], bits: u32) -> Vec<u32> {
+    let mut shifted = Vec::with_capacity(digits.len() + 1);
+    let mut carry = 0u32;
+    for &digit in digits {
+        shifted.push((((digit as u64) << bits) as u32) | carry);
+        carry = ((digit as u64) << bits >> 32) as u32;
+    }
+    shifted.push(carry);
+    shifted
+}
+
+// The digits shifted right by fewer than 32 bits
+fn shift_digits_right(digits: &[u32], bits: u32) -> Vec<u32> {
+    (0..digits.len())
+        .map(|i| {
+            let high = digits.get(i + 1).copied().unwrap_or(0) as u64;
+            (((high << 32) | digits[i] as u64) >> bits) as u32
+        })
+        .collect()
+}
+
+// Two's complement negation in place: invert and add one
+fn negate_digits(digits: &mut [u32]) {
+    let mut carry = 1u64;
+    for digit in digits.iter_mut() {
+        let t = (!*digit) as u64 + carry;
+        *digit = t as u32;
+        carry = t >> 32;
+    }
+}
+
+// magnitude = magnitude * factor + addend
+fn mul_small_add(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for digit in magnitude.iter_mut() {
+        let t = *digit as u64 * factor as u64 + carry;
+        *digit = t as u32;
+        carry = t >> 32;
+    }
+    if carry != 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+// Divide the magnitude in place by a small divisor, returning the remainder
+fn div_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for digit in magnitude.iter_mut().rev() {
+        let t = (remainder << 32) | *digit as u64;
+        *digit = (t / divisor as u64) as u32;
+        remainder = t % divisor as u64;
+    }
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    remainder as u32
+}
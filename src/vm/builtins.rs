@@ -6,3 +6,71 @@ use super::errors::RuntimeError;
 
 // Placeholder for future built-in function registry
 pub type BuiltinFn = fn(&[Value]) -> Result<Value, RuntimeError>;
+
+/// Every function a new VM registers. The compiler consults this list to
+/// know which names are builtins without building a VM.
+pub const BUILTIN_NAMES: &[&str] = &[
+    // Arithmetic operations
+    "+", "-", "*", "/", "%", "quotient", "remainder", "modulo", "gcd", "lcm", "neg", "inc", "dec",
+    // Bitwise operations
+    "bit-and", "bit-or", "bit-xor", "<<", ">>", "bit-not",
+    // Comparison operations
+    "<=", "<", ">", ">=", "==", "!=", "equal?", "eq?",
+    // List operations
+    "cons", "car", "cdr", "uncons", "list?", "append", "list-ref", "list-length", "list-copy",
+    "shares-structure?", "null?", "nil?", "map", "filter", "reduce", "foldr", "sort", "member?",
+    "assoc", "assoc-set", "assoc-update", "reverse", "take", "drop", "zip", "unzip", "enumerate",
+    "flatten", "remove-duplicates", "cons*", "list*", "range",
+    // Type predicates
+    "integer?", "bigint?", "float?", "nan?", "infinite?", "number?", "boolean?", "function?",
+    "closure?", "procedure?",
+    // String operations
+    "string?", "symbol?", "symbol->string", "string->symbol", "symbol-namespace", "symbol-name",
+    "qualified-symbol?", "make-qualified-symbol", "string-length", "string-ref", "substring",
+    "string-append", "string->list", "list->string", "for-each-char", "string-map", "string-filter",
+    "char-code", "integer->char", "char-alphabetic?", "char-numeric?", "char-whitespace?",
+    "char-upcase", "char-downcase", "number->string", "format-int", "string->number",
+    "string-split", "string-join", "string-trim", "string-replace", "string-starts-with?",
+    "string-ends-with?", "string-contains?", "string-index-of", "string-prefix?", "string-suffix?",
+    "string-upcase", "string-downcase", "format",
+    // File I/O operations
+    "read-file", "write-file", "file-exists?", "write-binary-file", "load", "require",
+    // Date/Time operations
+    "current-timestamp", "format-timestamp",
+    // Logging
+    "log-debug", "log-info", "log-warn", "log-error", "set-log-level",
+    // Other operations
+    "get-args", "print", "display", "raise", "apply",
+    // HashMap operations
+    "hashmap?", "hashmap-get", "hashmap-set", "hashmap-keys", "hashmap-values",
+    "hashmap-contains-key?",
+    // Vector operations
+    "vector?", "vector-ref", "vector-set", "vector-push", "vector-pop", "vector-length",
+    "vector-map", "vector-for-each",
+    // Type conversions
+    "list->vector", "vector->list", "deep-list->vector", "deep-vector->list", "int->float",
+    "float->int",
+    // Math functions
+    "sqrt", "sin", "cos", "tan", "atan", "atan2", "log", "exp", "floor", "ceil", "round", "abs",
+    "pow", "expt", "random", "random-int", "seed-random",
+    // Metaprogramming
+    "eval",
+    // Reflection - Function Introspection
+    "function-arity", "function-params", "closure-captured", "function-name", "module-exports",
+    "module-list",
+    // Type inspection
+    "type-of",
+    // Symbol generation
+    "gensym",
+    // Parallel Collections (Phase 12a)
+    "pmap", "pfilter", "preduce",
+    // HTTP/Networking (Phase 14)
+    "http-listen", "http-accept", "http-read-request", "http-send-response", "http-close",
+    // Multi-threaded HTTP (Phase 14b)
+    "http-listen-shared", "http-serve-parallel",
+    // FFI (Foreign Function Interface) - Phase 21
+    "ffi-load", "ffi-symbol", "ffi-pointer->string", "ffi-string->pointer", "ffi-free-string",
+    "ffi-null", "ffi-null?", "pointer?", "ffi-pointer+", "ffi-read-int", "ffi-write-int",
+    "ffi-read-float", "ffi-write-float", "ffi-read-byte", "ffi-write-byte", "ffi-allocate",
+    "ffi-free",
+];
@@ -3,8 +3,10 @@ use std::fs::File;
 use std::io::{Read, Write};
 use std::sync::Arc;
 
-use super::instructions::{Instruction, FfiType};
+use super::instructions::{Instruction, FfiType, LogLevel};
 use super::value::{Value, List, ClosureData};
+use super::bigint::BigInt;
+use super::errors::Location;
 
 // FFI type serialization helpers
 fn ffi_type_to_byte(ffi_type: &FfiType) -> u8 {
@@ -44,6 +46,28 @@ fn byte_to_ffi_type(byte: u8) -> Result<FfiType, String> {
     }
 }
 
+// Log level serialization helpers
+fn log_level_to_byte(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Debug => 0,
+        LogLevel::Info => 1,
+        LogLevel::Warn => 2,
+        LogLevel::Error => 3,
+        LogLevel::Off => 4,
+    }
+}
+
+fn byte_to_log_level(byte: u8) -> Result<LogLevel, String> {
+    match byte {
+        0 => Ok(LogLevel::Debug),
+        1 => Ok(LogLevel::Info),
+        2 => Ok(LogLevel::Warn),
+        3 => Ok(LogLevel::Error),
+        4 => Ok(LogLevel::Off),
+        _ => Err(format!("Invalid log level byte: {}", byte)),
+    }
+}
+
 // Bytecode file format serialization
 
 pub fn serialize_bytecode(
@@ -382,6 +406,168 @@ fn write_instruction(bytes: &mut Vec<u8>, instr: &Instruction) {
         Instruction::StringUpcase => bytes.push(129),
         Instruction::StringDowncase => bytes.push(130),
         Instruction::Format => bytes.push(131),
+        Instruction::IsNull => bytes.push(132),
+        Instruction::Uncons => bytes.push(133),
+        Instruction::ArgListLength(idx) => {
+            bytes.push(134);
+            write_u32(bytes, *idx as u32);
+        }
+        Instruction::LogMessage(level) => {
+            bytes.push(135);
+            bytes.push(log_level_to_byte(*level));
+        }
+        Instruction::SetLogLevel => bytes.push(136),
+        Instruction::CheckContract(message) => {
+            bytes.push(137);
+            write_string(bytes, message);
+        }
+        Instruction::RegisterModule(name, exports) => {
+            bytes.push(142);
+            write_string(bytes, name);
+            write_u32(bytes, exports.len() as u32);
+            for export in exports {
+                write_string(bytes, export);
+            }
+        }
+        Instruction::IsNaN => bytes.push(145),
+        Instruction::IsInfinite => bytes.push(146),
+        Instruction::ListCopy => bytes.push(147),
+        Instruction::SharesStructure => bytes.push(148),
+        Instruction::ForEachChar => bytes.push(149),
+        Instruction::StringMap => bytes.push(170),
+        Instruction::StringFilter => bytes.push(171),
+        Instruction::PrintList => bytes.push(173),
+        Instruction::DisplayList => bytes.push(174),
+        Instruction::DeepListToVector => bytes.push(175),
+        Instruction::DeepVectorToList => bytes.push(176),
+        Instruction::BitAnd => bytes.push(177),
+        Instruction::BitOr => bytes.push(178),
+        Instruction::BitXor => bytes.push(179),
+        Instruction::BitNot => bytes.push(180),
+        Instruction::Shl => bytes.push(181),
+        Instruction::Shr => bytes.push(182),
+        Instruction::Quotient => bytes.push(184),
+        Instruction::Modulo => bytes.push(185),
+        Instruction::FormatInt => bytes.push(186),
+        Instruction::StringIndexOf => bytes.push(187),
+        Instruction::JmpIfTrue(addr) => {
+            bytes.push(188);
+            write_u32(bytes, *addr as u32);
+        }
+        Instruction::Map => bytes.push(189),
+        Instruction::Filter => bytes.push(190),
+        Instruction::Reduce => bytes.push(191),
+        Instruction::Sort => bytes.push(192),
+        Instruction::Range => bytes.push(193),
+        Instruction::ListStar => bytes.push(194),
+        Instruction::Reverse => bytes.push(195),
+        Instruction::Take => bytes.push(196),
+        Instruction::Drop => bytes.push(197),
+        Instruction::Member => bytes.push(198),
+        Instruction::Assoc => bytes.push(199),
+        Instruction::StringRef => bytes.push(200),
+        Instruction::LetrecFill(n) => {
+            bytes.push(201);
+            write_u32(bytes, *n as u32);
+        }
+        Instruction::LoadLetrec(idx, group_size) => {
+            bytes.push(202);
+            write_u32(bytes, *idx as u32);
+            write_u32(bytes, *group_size as u32);
+        }
+        Instruction::Assert(message, location) => {
+            bytes.push(203);
+            write_string(bytes, message);
+            write_u32(bytes, location.line as u32);
+            write_u32(bytes, location.column as u32);
+            write_string(bytes, &location.file);
+        }
+        Instruction::PushHandler(addr) => {
+            bytes.push(204);
+            write_u32(bytes, *addr as u32);
+        }
+        Instruction::PopHandler => bytes.push(205),
+        Instruction::Raise => bytes.push(206),
+        Instruction::VectorMap => bytes.push(207),
+        Instruction::VectorForEach => bytes.push(208),
+        Instruction::JmpIfArgSupplied(idx, addr) => {
+            bytes.push(209);
+            write_u32(bytes, *idx as u32);
+            write_u32(bytes, *addr as u32);
+        }
+        Instruction::BindArg(idx) => {
+            bytes.push(210);
+            write_u32(bytes, *idx as u32);
+        }
+        Instruction::NoMatchingClause(message) => {
+            bytes.push(211);
+            write_string(bytes, message);
+        }
+        Instruction::ApplyN(count) => {
+            bytes.push(212);
+            write_u32(bytes, *count as u32);
+        }
+        Instruction::FoldRight => bytes.push(213),
+        Instruction::Zip => bytes.push(214),
+        Instruction::Unzip => bytes.push(215),
+        Instruction::Enumerate => bytes.push(216),
+        Instruction::Flatten => bytes.push(217),
+        Instruction::RemoveDuplicates => bytes.push(218),
+        Instruction::IsBigInt => bytes.push(219),
+        Instruction::Round => bytes.push(220),
+        Instruction::IExpt => bytes.push(221),
+        Instruction::Gcd => bytes.push(222),
+        Instruction::Lcm => bytes.push(223),
+        Instruction::CharAlphabetic => bytes.push(224),
+        Instruction::CharNumeric => bytes.push(225),
+        Instruction::CharWhitespace => bytes.push(226),
+        Instruction::CharUpcase => bytes.push(227),
+        Instruction::CharDowncase => bytes.push(228),
+        Instruction::IntToChar => bytes.push(229),
+        Instruction::AssocSet => bytes.push(230),
+        Instruction::AssocUpdate => bytes.push(231),
+        Instruction::Equal => bytes.push(232),
+        Instruction::EqIdentity => bytes.push(233),
+        Instruction::CheckMaxArgs(max) => {
+            bytes.push(234);
+            write_u32(bytes, *max as u32);
+        }
+        Instruction::BindKeyArgs(first, keys) => {
+            bytes.push(235);
+            write_u32(bytes, *first as u32);
+            write_u32(bytes, keys.len() as u32);
+            for key in keys {
+                write_string(bytes, key);
+            }
+        }
+        Instruction::JmpIfKeySupplied(idx, addr) => {
+            bytes.push(236);
+            write_u32(bytes, *idx as u32);
+            write_u32(bytes, *addr as u32);
+        }
+        Instruction::StringAppendList => bytes.push(237),
+        Instruction::AppendList => bytes.push(238),
+        Instruction::Interpolate(n) => {
+            bytes.push(239);
+            write_u32(bytes, *n as u32);
+        }
+        Instruction::TailCallClosure(argc) => {
+            bytes.push(183);
+            write_u32(bytes, *argc as u32);
+        }
+        Instruction::ProvideModules(modules) => {
+            bytes.push(172);
+            write_u32(bytes, modules.len() as u32);
+            for module in modules {
+                write_string(bytes, module);
+            }
+        }
+        Instruction::ModuleExports => bytes.push(143),
+        Instruction::ModuleList => bytes.push(144),
+        Instruction::SymbolNamespace => bytes.push(138),
+        Instruction::SymbolName => bytes.push(139),
+        Instruction::IsQualifiedSymbol => bytes.push(140),
+        Instruction::MakeQualifiedSymbol => bytes.push(141),
         // Date/Time operations (109-110)
         Instruction::CurrentTimestamp => bytes.push(109),
         Instruction::FormatTimestamp => bytes.push(110),
@@ -654,6 +840,142 @@ fn read_instruction(bytes: &[u8], pos: &mut usize) -> Result<Instruction, String
         129 => Ok(Instruction::StringUpcase),
         130 => Ok(Instruction::StringDowncase),
         131 => Ok(Instruction::Format),
+        132 => Ok(Instruction::IsNull),
+        133 => Ok(Instruction::Uncons),
+        134 => Ok(Instruction::ArgListLength(read_u32(bytes, pos)? as usize)),
+        135 => {
+            if *pos >= bytes.len() {
+                return Err("Unexpected end of bytecode reading log level".to_string());
+            }
+            let level = byte_to_log_level(bytes[*pos])?;
+            *pos += 1;
+            Ok(Instruction::LogMessage(level))
+        }
+        136 => Ok(Instruction::SetLogLevel),
+        137 => Ok(Instruction::CheckContract(read_string(bytes, pos)?)),
+        142 => {
+            let name = read_string(bytes, pos)?;
+            let exports_len = read_u32(bytes, pos)? as usize;
+            let mut exports = Vec::new();
+            for _ in 0..exports_len {
+                exports.push(read_string(bytes, pos)?);
+            }
+            Ok(Instruction::RegisterModule(name, exports))
+        }
+        172 => {
+            let modules_len = read_u32(bytes, pos)? as usize;
+            let mut modules = Vec::new();
+            for _ in 0..modules_len {
+                modules.push(read_string(bytes, pos)?);
+            }
+            Ok(Instruction::ProvideModules(modules))
+        }
+        173 => Ok(Instruction::PrintList),
+        174 => Ok(Instruction::DisplayList),
+        175 => Ok(Instruction::DeepListToVector),
+        176 => Ok(Instruction::DeepVectorToList),
+        177 => Ok(Instruction::BitAnd),
+        178 => Ok(Instruction::BitOr),
+        179 => Ok(Instruction::BitXor),
+        180 => Ok(Instruction::BitNot),
+        181 => Ok(Instruction::Shl),
+        182 => Ok(Instruction::Shr),
+        183 => Ok(Instruction::TailCallClosure(read_u32(bytes, pos)? as usize)),
+        184 => Ok(Instruction::Quotient),
+        185 => Ok(Instruction::Modulo),
+        186 => Ok(Instruction::FormatInt),
+        187 => Ok(Instruction::StringIndexOf),
+        188 => Ok(Instruction::JmpIfTrue(read_u32(bytes, pos)? as usize)),
+        189 => Ok(Instruction::Map),
+        190 => Ok(Instruction::Filter),
+        191 => Ok(Instruction::Reduce),
+        192 => Ok(Instruction::Sort),
+        193 => Ok(Instruction::Range),
+        194 => Ok(Instruction::ListStar),
+        195 => Ok(Instruction::Reverse),
+        196 => Ok(Instruction::Take),
+        197 => Ok(Instruction::Drop),
+        198 => Ok(Instruction::Member),
+        199 => Ok(Instruction::Assoc),
+        200 => Ok(Instruction::StringRef),
+        201 => Ok(Instruction::LetrecFill(read_u32(bytes, pos)? as usize)),
+        202 => {
+            let idx = read_u32(bytes, pos)? as usize;
+            let group_size = read_u32(bytes, pos)? as usize;
+            Ok(Instruction::LoadLetrec(idx, group_size))
+        }
+        203 => {
+            let message = read_string(bytes, pos)?;
+            let line = read_u32(bytes, pos)? as usize;
+            let column = read_u32(bytes, pos)? as usize;
+            let file = read_string(bytes, pos)?;
+            Ok(Instruction::Assert(message, Location::new(line, column, file)))
+        }
+        204 => Ok(Instruction::PushHandler(read_u32(bytes, pos)? as usize)),
+        205 => Ok(Instruction::PopHandler),
+        206 => Ok(Instruction::Raise),
+        207 => Ok(Instruction::VectorMap),
+        208 => Ok(Instruction::VectorForEach),
+        209 => {
+            let idx = read_u32(bytes, pos)? as usize;
+            let addr = read_u32(bytes, pos)? as usize;
+            Ok(Instruction::JmpIfArgSupplied(idx, addr))
+        }
+        210 => Ok(Instruction::BindArg(read_u32(bytes, pos)? as usize)),
+        211 => Ok(Instruction::NoMatchingClause(read_string(bytes, pos)?)),
+        212 => Ok(Instruction::ApplyN(read_u32(bytes, pos)? as usize)),
+        213 => Ok(Instruction::FoldRight),
+        214 => Ok(Instruction::Zip),
+        215 => Ok(Instruction::Unzip),
+        216 => Ok(Instruction::Enumerate),
+        217 => Ok(Instruction::Flatten),
+        218 => Ok(Instruction::RemoveDuplicates),
+        219 => Ok(Instruction::IsBigInt),
+        220 => Ok(Instruction::Round),
+        221 => Ok(Instruction::IExpt),
+        222 => Ok(Instruction::Gcd),
+        223 => Ok(Instruction::Lcm),
+        224 => Ok(Instruction::CharAlphabetic),
+        225 => Ok(Instruction::CharNumeric),
+        226 => Ok(Instruction::CharWhitespace),
+        227 => Ok(Instruction::CharUpcase),
+        228 => Ok(Instruction::CharDowncase),
+        229 => Ok(Instruction::IntToChar),
+        230 => Ok(Instruction::AssocSet),
+        231 => Ok(Instruction::AssocUpdate),
+        232 => Ok(Instruction::Equal),
+        233 => Ok(Instruction::EqIdentity),
+        234 => Ok(Instruction::CheckMaxArgs(read_u32(bytes, pos)? as usize)),
+        235 => {
+            let first = read_u32(bytes, pos)? as usize;
+            let keys_len = read_u32(bytes, pos)? as usize;
+            let mut keys = Vec::new();
+            for _ in 0..keys_len {
+                keys.push(read_string(bytes, pos)?);
+            }
+            Ok(Instruction::BindKeyArgs(first, keys))
+        }
+        236 => {
+            let idx = read_u32(bytes, pos)? as usize;
+            let addr = read_u32(bytes, pos)? as usize;
+            Ok(Instruction::JmpIfKeySupplied(idx, addr))
+        }
+        237 => Ok(Instruction::StringAppendList),
+        238 => Ok(Instruction::AppendList),
+        239 => Ok(Instruction::Interpolate(read_u32(bytes, pos)? as usize)),
+        145 => Ok(Instruction::IsNaN),
+        146 => Ok(Instruction::IsInfinite),
+        147 => Ok(Instruction::ListCopy),
+        148 => Ok(Instruction::SharesStructure),
+        149 => Ok(Instruction::ForEachChar),
+        170 => Ok(Instruction::StringMap),
+        171 => Ok(Instruction::StringFilter),
+        143 => Ok(Instruction::ModuleExports),
+        144 => Ok(Instruction::ModuleList),
+        138 => Ok(Instruction::SymbolNamespace),
+        139 => Ok(Instruction::SymbolName),
+        140 => Ok(Instruction::IsQualifiedSymbol),
+        141 => Ok(Instruction::MakeQualifiedSymbol),
         // FFI instructions (150-169)
         150 => Ok(Instruction::FfiLoadLibrary),
         151 => Ok(Instruction::FfiGetSymbol),
@@ -716,11 +1038,16 @@ fn write_value(bytes: &mut Vec<u8>, value: &Value) {
             bytes.extend_from_slice(&f.to_le_bytes());
         }
         Value::List(list) => {
-            bytes.push(2);
+            // Improper lists get their own tag, with the dotted atom written last
+            let dotted_tail = list.dotted_tail();
+            bytes.push(if dotted_tail.is_some() { 11 } else { 2 });
             write_u32(bytes, list.len() as u32);
             for item in list.iter() {
                 write_value(bytes, item);
             }
+            if let Some(atom) = dotted_tail {
+                write_value(bytes, atom);
+            }
         }
         Value::Symbol(s) => {
             bytes.push(3);
@@ -793,6 +1120,10 @@ fn write_value(bytes: &mut Vec<u8>, value: &Value) {
             bytes.push(10);  // Tag 10 for Pointer
             bytes.extend_from_slice(&p.to_le_bytes());
         }
+        Value::BigInt(n) => {
+            bytes.push(12);  // Tag 12 for BigInt, stored as its decimal digits
+            write_string(bytes, &n.to_string());
+        }
     }
 }
 
@@ -927,6 +1258,24 @@ fn read_value(bytes: &[u8], pos: &mut usize) -> Result<Value, String> {
             *pos += 8;
             Ok(Value::Pointer(p))
         }
+        11 => {
+            // Read improper list: the elements, then the atom after the dot
+            let len = read_u32(bytes, pos)? as usize;
+            let mut items = Vec::new();
+            for _ in 0..len {
+                items.push(read_value(bytes, pos)?);
+            }
+            let mut list = List::Dotted(Box::new(read_value(bytes, pos)?));
+            for item in items.into_iter().rev() {
+                list = List::cons(item, list);
+            }
+            Ok(Value::List(list))
+        }
+        12 => {
+            let digits = read_string(bytes, pos)?;
+            let n = BigInt::parse(&digits).ok_or_else(|| format!("Invalid bigint constant: {}", digits))?;
+            Ok(Value::from_bigint(n))
+        }
         _ => Err(format!("Unknown value tag: {}", tag)),
     }
 }
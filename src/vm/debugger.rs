@@ -0,0 +1,22 @@
+// Step debugger support: what VM::step reports, and why VM::run_with_hook stopped
+
+use super::instructions::Instruction;
+use super::value::Value;
+
+/// One instruction executed by `VM::step`
+#[derive(Debug, Clone)]
+pub struct StepInfo {
+    pub instruction: Instruction, // The instruction that just ran
+    pub ip: usize,                // Its address within the bytecode it ran in
+    pub instruction_pointer: usize, // Where execution continues (in the bytecode that is now current)
+    pub function: String,         // Function it ran in, "<main>" at top level
+    pub depth: usize,             // Call frames active when it ran
+    pub stack_top: Vec<Value>,    // Up to the top three stack values afterwards, top last
+}
+
+/// Why `VM::run_with_hook` returned
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    Halted,             // The program ran to completion
+    Breakpoint(String), // Paused before a call into this function; run again to continue
+}
@@ -1,3 +1,5 @@
+use super::value::Value;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Location {
     pub line: usize,
@@ -157,6 +159,7 @@ pub struct RuntimeError {
     pub call_stack: Vec<String>,
     pub location: Option<Location>,
     pub suggestion: Option<String>,
+    pub raised: Option<Box<Value>>, // Value passed to raise; a catch clause binds it instead of the message
 }
 
 impl RuntimeError {
@@ -166,6 +169,7 @@ impl RuntimeError {
             call_stack: Vec::new(),
             location: None,
             suggestion: None,
+            raised: None,
         }
     }
 
@@ -175,6 +179,7 @@ impl RuntimeError {
             call_stack: Vec::new(),
             location: None,
             suggestion: Some(suggestion),
+            raised: None,
         }
     }
 
@@ -184,6 +189,7 @@ impl RuntimeError {
             call_stack,
             location: None,
             suggestion: None,
+            raised: None,
         }
     }
 
@@ -193,6 +199,7 @@ impl RuntimeError {
             call_stack: Vec::new(),
             location: Some(location),
             suggestion: None,
+            raised: None,
         }
     }
 
@@ -206,6 +213,19 @@ impl RuntimeError {
             call_stack,
             location,
             suggestion: None,
+            raised: None,
+        }
+    }
+
+    /// An error signalled by user code with (raise value). The message is
+    /// the value's display text, used when nothing catches it.
+    pub fn raised(value: Value, message: String) -> Self {
+        RuntimeError {
+            message,
+            call_stack: Vec::new(),
+            location: None,
+            suggestion: None,
+            raised: Some(Box::new(value)),
         }
     }
 
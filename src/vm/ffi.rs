@@ -502,6 +502,7 @@ pub fn ffi_type_size(ffi_type: &FfiType) -> usize {
 fn value_type_name(value: &Value) -> &'static str {
     match value {
         Value::Integer(_) => "integer",
+        Value::BigInt(_) => "bigint",
         Value::Float(_) => "float",
         Value::Boolean(_) => "boolean",
         Value::List(_) => "list",
@@ -1,4 +1,7 @@
+use std::fmt;
+
 use super::value::Value;
+use super::errors::Location;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Instruction {
@@ -9,6 +12,14 @@ pub enum Instruction {
     Div,
     Mod,
     Neg,
+    BitAnd,  // Pop two integers, push a & b
+    BitOr,   // Pop two integers, push a | b
+    BitXor,  // Pop two integers, push a ^ b
+    BitNot,  // Pop integer, push !a
+    Shl,     // Pop integer and shift amount (0-63), push a << n
+    Shr,     // Pop integer and shift amount (0-63), push a >> n (arithmetic, keeps the sign)
+    Quotient, // Pop two integers, push a / b truncated toward zero
+    Modulo,  // Pop two integers, push a mod b with the sign of the divisor (floored)
     Leq,
     Lt,
     Gt,
@@ -16,6 +27,7 @@ pub enum Instruction {
     Eq,
     Neq,
     JmpIfFalse(usize),
+    JmpIfTrue(usize),  // Pop a boolean and jump if it is true (used by or)
     Jmp(usize),
     Call(String, usize),
     TailCall(String, usize), // Tail call: reuse current frame instead of pushing new one
@@ -25,26 +37,47 @@ pub enum Instruction {
     PopN(usize),     // Pop N values from the stack
     Slide(usize),    // Pop top value, pop N values, push top value back (cleanup let bindings)
     CheckArity(usize, usize), // Check if frame.locals.len() == expected_arity, jump to addr if not
+    CheckContract(String), // Pop boolean, raise a runtime error with the message if it's false
+    Assert(String, Location), // Raise the message at the source location if the top value is false, else leave it
+    PushHandler(usize), // Install a try handler: an error unwinds to here, pushes its message and jumps to addr
+    PopHandler,         // Remove the innermost try handler (the protected expression finished normally)
+    Raise,              // Pop a value and raise it as a runtime error (catch binds the value itself)
+    ArgListLength(usize), // Push list length of arg N, or -1 if it's missing or not a proper list (pattern dispatch)
     PackRestArgs(usize), // Collect args from index N onwards into a list, replace them with the list in frame.locals
+    JmpIfArgSupplied(usize, usize), // Jump to addr if arg N was passed; error if an earlier required arg is missing (&optional)
+    BindArg(usize),      // Pop a value and store it as arg N in frame.locals (fills a missing &optional arg)
+    CheckMaxArgs(usize), // Raise an error if more than N args were passed (&optional without a rest param)
+    BindKeyArgs(usize, Vec<String>), // Sort the :key value args from index N into one arg per named key (nil if absent), then a slot of supplied flags (&key)
+    JmpIfKeySupplied(usize, usize), // Jump to addr if the key in arg N was passed, per BindKeyArgs' flags (&key)
+    NoMatchingClause(String), // Raise the message with the current frame's arguments (pattern dispatch fell through)
     MakeClosure(Vec<String>, Vec<Instruction>, usize), // Create closure: (params, body, num_captured_vars)
     MakeVariadicClosure(Vec<String>, String, Vec<Instruction>, usize), // Variadic closure: (required_params, rest_param, body, num_captured)
     CallClosure(usize), // Call closure with N arguments (pops closure + args from stack)
+    TailCallClosure(usize), // CallClosure in tail position: reuse the current frame like TailCall
     Apply,              // Apply function to list of arguments: pop list, pop function/closure, call with list elements as args
+    ApplyN(usize),      // Apply with leading args: pop list, pop N-1 fixed args, pop function/closure, call with fixed ++ list
     LoadCaptured(usize), // Load captured variable at index from current closure's environment
     SetLocal(usize),    // Set local variable at position on value stack
     BeginLoop(usize),   // Mark loop start with N bindings
     Recur(usize),       // Recur with N new values: update loop bindings and jump back
     Print,
+    PrintList,      // Pop list, print its values space-separated plus newline, push the last value (or '())
+    DisplayList,    // Like PrintList, but strings and symbols are written without quotes
     Halt,
     // List operations
     Cons,    // Pop two values, push cons cell (list)
     Car,     // Pop list, push first element
     Cdr,     // Pop list, push rest of list
+    Uncons,  // Pop non-empty list, push head then tail (car and cdr in one step)
     IsList,  // Pop value, push boolean indicating if it's a list
+    IsNull,  // Pop value, push true only if it's the empty list (never errors)
     // Type predicates
-    IsInteger,      // Pop value, push boolean indicating if it's an integer
+    IsInteger,      // Pop value, push boolean indicating if it's an integer (fixnum or bigint)
+    IsBigInt,       // Pop value, push true only if it's an integer too large for i64
     IsFloat,        // Pop value, push boolean indicating if it's a float
-    IsNumber,       // Pop value, push boolean indicating if it's a number (int or float)
+    IsNaN,          // Pop value, push true only if it's a NaN float (never errors)
+    IsInfinite,     // Pop value, push true only if it's a +/- infinite float (never errors)
+    IsNumber,       // Pop value, push boolean indicating if it's a number (int, bigint or float)
     IsBoolean,      // Pop value, push boolean indicating if it's a boolean
     IsFunction,     // Pop value, push boolean indicating if it's a function
     IsClosure,      // Pop value, push boolean indicating if it's a closure
@@ -54,11 +87,19 @@ pub enum Instruction {
     IsSymbol,       // Pop value, push boolean indicating if it's a symbol
     SymbolToString, // Pop symbol, push string
     StringToSymbol, // Pop string, push symbol
+    SymbolNamespace,       // Pop symbol, push namespace string of a qualified symbol, or '() if unqualified
+    SymbolName,            // Pop symbol, push its name without the namespace as a string
+    IsQualifiedSymbol,     // Pop value, push boolean indicating if it's a namespace-qualified symbol
+    MakeQualifiedSymbol,   // Pop namespace and name (strings or symbols), push qualified symbol namespace/name
     StringLength,   // Pop string, push integer length
     Substring,      // Pop string, start, end; push substring
     StringAppend,   // Pop two strings, push concatenation
+    StringAppendList, // Pop list of strings, push their concatenation ("" for an empty list)
     StringToList,   // Pop string, push list of single-char strings
     ListToString,   // Pop list of strings/chars, push concatenated string
+    ForEachChar,    // Pop string and callable, call it on each one-char string, push '()
+    StringMap,      // Pop string and callable, push concatenation of the callable's string results
+    StringFilter,   // Pop string and predicate, push string of the chars it returned true for
     CharCode,       // Pop single-char string, push ASCII code as integer
     StringSplit,    // Pop string and delimiter, push list of substrings
     StringJoin,     // Pop list of strings and delimiter, push joined string
@@ -68,17 +109,43 @@ pub enum Instruction {
     StringStartsWith, // Pop string and prefix, push boolean
     StringEndsWith,   // Pop string and suffix, push boolean
     StringContains,   // Pop string and substring, push boolean
+    StringIndexOf,    // Pop string and substring, push char index of first match (or -1)
     StringUpcase,     // Pop string, push uppercase version
     StringDowncase,   // Pop string, push lowercase version
     Format,           // Pop format string and N arguments, push formatted string
+    Interpolate(usize), // Pop N values, push their display forms (strings unquoted) concatenated
     // List manipulation
     Append,         // Pop two lists, push their concatenation (second appended to first)
+    AppendList,     // Pop list of lists, push their concatenation ('() for an empty list)
     MakeList(usize), // Pop N values from stack and create a list from them (in order)
     ListRef,        // Pop list and index, push element at that index (0-based)
     ListLength,     // Pop list, push its length as integer
+    ListCopy,       // Pop list, push a copy built from fresh cons cells
+    SharesStructure, // Pop two lists, push true if they have any cons cell in common
+    Map,            // Pop extra lists, list and callable, push the list of its results
+    Filter,         // Pop list and predicate, push the elements it returned true for
+    Reduce,         // Pop list, initial value and binary callable, push the folded result
+    FoldRight,      // Like Reduce, but folds from the last element inward, calling (f item acc)
+    Sort,           // Pop comparator (or '() for numeric order) and list, push the stably sorted list
+    Range,          // Pop step, end and start, push the integers from start up to (not including) end
+    ListStar,       // Pop a list of arguments, push all but the last consed onto the last (cons*)
+    Reverse,        // Pop list, push it reversed
+    Take,           // Pop list and count, push the first count elements
+    Drop,           // Pop list and count, push the list without its first count elements
+    Zip,            // Pop two lists, push a list of (a b) pairs, stopping at the shorter list
+    Unzip,          // Pop a list of (a b) pairs, push a list of the two lists (as bs)
+    Enumerate,      // Pop list, push a list of (index item) pairs counting from 0
+    Flatten,        // Pop list, push the atoms of it and every nested list, in order
+    RemoveDuplicates, // Pop list, push it keeping only the first of each group of equal (==) elements
+    Member,         // Pop list and item, push the sublist starting at the first equal element, or false
+    Assoc,          // Pop association list and key, push the first pair whose car equals key, or false
+    StringRef,      // Pop index and string, push the character at that index as a one-character string
+    LetrecFill(usize),         // Pop n letrec closures, push them with their first n captures pointing at each other
+    LoadLetrec(usize, usize),  // Load captured letrec sibling (index, group size), tied to this closure's group
     // Number operations
-    NumberToString, // Pop integer, push string representation
-    StringToNumber, // Pop string, push integer (or error if not a valid number)
+    NumberToString, // Pop options list (empty or (precision)) and number, push string representation
+    FormatInt,      // Pop integer and option plist (:grouping, :width, :pad), push formatted string
+    StringToNumber, // Pop options list (empty or (radix)) and string, push the number or false if unparseable
     // File I/O operations
     ReadFile,       // Pop string path, push file contents as string (or error)
     WriteFile,      // Pop string path, string content; push boolean success
@@ -107,9 +174,13 @@ pub enum Instruction {
     VectorPop,           // Pop vector, push vector without last element and the last element
     VectorLength,        // Pop vector, push its length as integer
     IsVector,            // Pop value, push boolean indicating if it's a vector
+    VectorMap,           // Pop vector and callable, push the vector of its results
+    VectorForEach,       // Pop vector and callable, call it on each element for side effects, push '()
     // Type conversions
     ListToVector,        // Pop list, push vector with same elements
     VectorToList,        // Pop vector, push list with same elements
+    DeepListToVector,    // Pop value, push it with every nested list turned into a vector
+    DeepVectorToList,    // Pop value, push it with every nested vector turned into a list
     IntToFloat,          // Pop integer, push float
     FloatToInt,          // Pop float, push integer (truncate towards zero)
     // Math functions
@@ -121,8 +192,22 @@ pub enum Instruction {
     Atan2,               // Pop y and x, push atan2(y, x) as float
     Floor,               // Pop number, push floor as integer
     Ceil,                // Pop number, push ceiling as integer
+    Round,               // Pop number, push it rounded to the nearest integer (ties to even)
     Abs,                 // Pop number, push absolute value (same type)
     Pow,                 // Pop base and exponent, push power as float
+    IExpt,               // Pop base and exponent, push the exact power of two integers (else a float power)
+    Gcd,                 // Pop two integers, push their greatest common divisor (non-negative)
+    Lcm,                 // Pop two integers, push their least common multiple (non-negative, 0 if either is 0)
+    CharAlphabetic,      // Pop a single-character string, push whether it is alphabetic
+    CharNumeric,         // Pop a single-character string, push whether it is numeric
+    CharWhitespace,      // Pop a single-character string, push whether it is whitespace
+    CharUpcase,          // Pop a single-character string, push it in upper case
+    CharDowncase,        // Pop a single-character string, push it in lower case
+    IntToChar,           // Pop an integer codepoint, push the single-character string for it (inverse of CharCode)
+    AssocSet,            // Pop alist, value, key; push a copy with key's entry set to value (appended as (key . value) if missing)
+    AssocUpdate,         // Pop alist, closure, key; push a copy with key's value replaced by (closure value)
+    Equal,               // Pop two values, push whether they are structurally equal (no int/float coercion, unlike Eq)
+    EqIdentity,          // Pop two values, push whether they are the same object (symbols/immediates by value)
     Log,                 // Pop number, push natural logarithm as float
     Exp,                 // Pop number, push e^x as float
     Random,              // Push random float in [0.0, 1.0)
@@ -131,6 +216,9 @@ pub enum Instruction {
     // Date/Time operations
     CurrentTimestamp,    // Push current Unix timestamp as integer (seconds since epoch)
     FormatTimestamp,     // Pop timestamp and format string, push formatted date string
+    // Logging
+    LogMessage(LogLevel), // Pop message, write it to the VM log writer if level >= min log level, push message back
+    SetLogLevel,         // Pop level name (symbol or string), set VM min log level, push previous level as symbol
     // Metaprogramming
     Eval,                // Pop string, parse and evaluate as Lisp code, push result
     // Reflection - Function Introspection
@@ -138,10 +226,15 @@ pub enum Instruction {
     FunctionParams,      // Pop closure, push list of parameter names as strings
     ClosureCaptured,     // Pop closure, push list of (name, value) pairs for captured variables
     FunctionName,        // Pop function, push name as string (error if closure)
+    // Reflection - Module Introspection
+    RegisterModule(String, Vec<String>), // Record a module's exported names in the VM (emitted by module declarations)
+    ProvideModules(Vec<String>), // Record that the file being run provides these modules (emitted by provide)
+    ModuleExports,       // Pop module name (symbol or string), push list of its exported symbols
+    ModuleList,          // Push list of known module names as symbols
     // Type inspection
     TypeOf,              // Pop value, push symbol representing its type
     // Symbol generation
-    GenSym,              // Push a unique symbol
+    GenSym,              // Pop an options list (empty or a prefix string), push a unique symbol
     // Parallel Collections (Phase 12a)
     PMap,                // Pop list and function, parallel map, push result list
     PFilter,             // Pop list and predicate, parallel filter, push result list
@@ -177,6 +270,312 @@ pub enum Instruction {
     FfiSizeOf(FfiType),  // Push size of FFI type in bytes
 }
 
+/// How instructions appear in disassembly listings and execution traces
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Push(val) => write!(f, "Push({:?})", val),
+            Instruction::Add => f.write_str("Add"),
+            Instruction::Sub => f.write_str("Sub"),
+            Instruction::Mul => f.write_str("Mul"),
+            Instruction::Div => f.write_str("Div"),
+            Instruction::Mod => f.write_str("Mod"),
+            Instruction::Neg => f.write_str("Neg"),
+            Instruction::BitAnd => f.write_str("BitAnd"),
+            Instruction::BitOr => f.write_str("BitOr"),
+            Instruction::BitXor => f.write_str("BitXor"),
+            Instruction::BitNot => f.write_str("BitNot"),
+            Instruction::Shl => f.write_str("Shl"),
+            Instruction::Shr => f.write_str("Shr"),
+            Instruction::Quotient => f.write_str("Quotient"),
+            Instruction::Modulo => f.write_str("Modulo"),
+            Instruction::Leq => f.write_str("Leq"),
+            Instruction::Lt => f.write_str("Lt"),
+            Instruction::Gt => f.write_str("Gt"),
+            Instruction::Gte => f.write_str("Gte"),
+            Instruction::Eq => f.write_str("Eq"),
+            Instruction::Neq => f.write_str("Neq"),
+            Instruction::JmpIfFalse(addr) => write!(f, "JmpIfFalse({})", addr),
+            Instruction::JmpIfTrue(addr) => write!(f, "JmpIfTrue({})", addr),
+            Instruction::Jmp(addr) => write!(f, "Jmp({})", addr),
+            Instruction::Call(name, argc) => write!(f, "Call(\"{}\", {})", name, argc),
+            Instruction::TailCall(name, argc) => write!(f, "TailCall(\"{}\", {})", name, argc),
+            Instruction::Ret => f.write_str("Ret"),
+            Instruction::LoadArg(idx) => write!(f, "LoadArg({})", idx),
+            Instruction::ArgListLength(idx) => write!(f, "ArgListLength({})", idx),
+            Instruction::CheckContract(message) => write!(f, "CheckContract({:?})", message),
+            Instruction::Assert(message, location) => write!(f, "Assert({:?}, {})", message, location.format()),
+            Instruction::PushHandler(addr) => write!(f, "PushHandler({})", addr),
+            Instruction::PopHandler => f.write_str("PopHandler"),
+            Instruction::Raise => f.write_str("Raise"),
+            Instruction::Print => f.write_str("Print"),
+            Instruction::PrintList => f.write_str("PrintList"),
+            Instruction::DeepListToVector => f.write_str("DeepListToVector"),
+            Instruction::DeepVectorToList => f.write_str("DeepVectorToList"),
+            Instruction::DisplayList => f.write_str("DisplayList"),
+            Instruction::Halt => f.write_str("Halt"),
+            Instruction::Cons => f.write_str("Cons"),
+            Instruction::Car => f.write_str("Car"),
+            Instruction::Cdr => f.write_str("Cdr"),
+            Instruction::Uncons => f.write_str("Uncons"),
+            Instruction::IsList => f.write_str("IsList"),
+            Instruction::IsNull => f.write_str("IsNull"),
+            Instruction::IsString => f.write_str("IsString"),
+            Instruction::IsSymbol => f.write_str("IsSymbol"),
+            Instruction::SymbolToString => f.write_str("SymbolToString"),
+            Instruction::StringToSymbol => f.write_str("StringToSymbol"),
+            Instruction::SymbolNamespace => f.write_str("SymbolNamespace"),
+            Instruction::SymbolName => f.write_str("SymbolName"),
+            Instruction::IsQualifiedSymbol => f.write_str("IsQualifiedSymbol"),
+            Instruction::MakeQualifiedSymbol => f.write_str("MakeQualifiedSymbol"),
+            Instruction::GetLocal(pos) => write!(f, "GetLocal({})", pos),
+            Instruction::SetLocal(pos) => write!(f, "SetLocal({})", pos),
+            Instruction::BeginLoop(count) => write!(f, "BeginLoop({})", count),
+            Instruction::Recur(count) => write!(f, "Recur({})", count),
+            Instruction::PopN(n) => write!(f, "PopN({})", n),
+            Instruction::Slide(n) => write!(f, "Slide({})", n),
+            Instruction::CheckArity(arity, addr) => write!(f, "CheckArity({}, {})", arity, addr),
+            Instruction::MakeClosure(params, body, num_captured) => {
+                write!(f, "MakeClosure({:?}, {} instructions, {} captured)", params, body.len(), num_captured)
+            }
+            Instruction::CallClosure(argc) => write!(f, "CallClosure({})", argc),
+            Instruction::TailCallClosure(argc) => write!(f, "TailCallClosure({})", argc),
+            Instruction::Apply => f.write_str("Apply"),
+            Instruction::ApplyN(count) => write!(f, "ApplyN({})", count),
+            Instruction::LoadCaptured(idx) => write!(f, "LoadCaptured({})", idx),
+            Instruction::Append => f.write_str("Append"),
+            Instruction::AppendList => f.write_str("AppendList"),
+            Instruction::MakeList(n) => write!(f, "MakeList({})", n),
+            Instruction::LoadGlobal(name) => write!(f, "LoadGlobal(\"{}\")", name),
+            Instruction::StoreGlobal(name) => write!(f, "StoreGlobal(\"{}\")", name),
+            Instruction::StringLength => f.write_str("StringLength"),
+            Instruction::Substring => f.write_str("Substring"),
+            Instruction::StringAppend => f.write_str("StringAppend"),
+            Instruction::StringAppendList => f.write_str("StringAppendList"),
+            Instruction::StringToList => f.write_str("StringToList"),
+            Instruction::ListToString => f.write_str("ListToString"),
+            Instruction::ForEachChar => f.write_str("ForEachChar"),
+            Instruction::StringMap => f.write_str("StringMap"),
+            Instruction::StringFilter => f.write_str("StringFilter"),
+            Instruction::CharCode => f.write_str("CharCode"),
+            Instruction::CharAlphabetic => f.write_str("CharAlphabetic"),
+            Instruction::CharNumeric => f.write_str("CharNumeric"),
+            Instruction::CharWhitespace => f.write_str("CharWhitespace"),
+            Instruction::CharUpcase => f.write_str("CharUpcase"),
+            Instruction::CharDowncase => f.write_str("CharDowncase"),
+            Instruction::IntToChar => f.write_str("IntToChar"),
+            Instruction::AssocSet => f.write_str("AssocSet"),
+            Instruction::AssocUpdate => f.write_str("AssocUpdate"),
+            Instruction::Equal => f.write_str("Equal"),
+            Instruction::EqIdentity => f.write_str("EqIdentity"),
+            Instruction::ReadFile => f.write_str("ReadFile"),
+            Instruction::WriteFile => f.write_str("WriteFile"),
+            Instruction::FileExists => f.write_str("FileExists"),
+            Instruction::GetArgs => f.write_str("GetArgs"),
+            Instruction::WriteBinaryFile => f.write_str("WriteBinaryFile"),
+            Instruction::LoadFile => f.write_str("LoadFile"),
+            Instruction::RequireFile => f.write_str("RequireFile"),
+            Instruction::ListRef => f.write_str("ListRef"),
+            Instruction::ListLength => f.write_str("ListLength"),
+            Instruction::Map => f.write_str("Map"),
+            Instruction::Filter => f.write_str("Filter"),
+            Instruction::Reduce => f.write_str("Reduce"),
+            Instruction::FoldRight => f.write_str("FoldRight"),
+            Instruction::Sort => f.write_str("Sort"),
+            Instruction::Range => f.write_str("Range"),
+            Instruction::ListStar => f.write_str("ListStar"),
+            Instruction::Reverse => f.write_str("Reverse"),
+            Instruction::Take => f.write_str("Take"),
+            Instruction::Zip => f.write_str("Zip"),
+            Instruction::Unzip => f.write_str("Unzip"),
+            Instruction::Enumerate => f.write_str("Enumerate"),
+            Instruction::Flatten => f.write_str("Flatten"),
+            Instruction::RemoveDuplicates => f.write_str("RemoveDuplicates"),
+            Instruction::Drop => f.write_str("Drop"),
+            Instruction::Member => f.write_str("Member"),
+            Instruction::Assoc => f.write_str("Assoc"),
+            Instruction::StringRef => f.write_str("StringRef"),
+            Instruction::LetrecFill(n) => write!(f, "LetrecFill({})", n),
+            Instruction::LoadLetrec(idx, group_size) => write!(f, "LoadLetrec({}, {})", idx, group_size),
+            Instruction::ListCopy => f.write_str("ListCopy"),
+            Instruction::SharesStructure => f.write_str("SharesStructure"),
+            Instruction::NumberToString => f.write_str("NumberToString"),
+            Instruction::FormatInt => f.write_str("FormatInt"),
+            // HashMap operations
+            Instruction::MakeHashMap(n) => write!(f, "MakeHashMap({})", n),
+            Instruction::HashMapGet => f.write_str("HashMapGet"),
+            Instruction::HashMapSet => f.write_str("HashMapSet"),
+            Instruction::HashMapKeys => f.write_str("HashMapKeys"),
+            Instruction::HashMapValues => f.write_str("HashMapValues"),
+            Instruction::HashMapContainsKey => f.write_str("HashMapContainsKey"),
+            Instruction::IsHashMap => f.write_str("IsHashMap"),
+            // Vector operations
+            Instruction::MakeVector(n) => write!(f, "MakeVector({})", n),
+            Instruction::VectorGet => f.write_str("VectorGet"),
+            Instruction::VectorSet => f.write_str("VectorSet"),
+            Instruction::VectorPush => f.write_str("VectorPush"),
+            Instruction::VectorPop => f.write_str("VectorPop"),
+            Instruction::VectorLength => f.write_str("VectorLength"),
+            Instruction::IsVector => f.write_str("IsVector"),
+            // Type predicates
+            Instruction::IsInteger => f.write_str("IsInteger"),
+            Instruction::IsBigInt => f.write_str("IsBigInt"),
+            Instruction::IsBoolean => f.write_str("IsBoolean"),
+            Instruction::IsFunction => f.write_str("IsFunction"),
+            Instruction::IsClosure => f.write_str("IsClosure"),
+            Instruction::IsProcedure => f.write_str("IsProcedure"),
+            // Type conversions
+            Instruction::StringToNumber => f.write_str("StringToNumber"),
+            Instruction::ListToVector => f.write_str("ListToVector"),
+            Instruction::VectorToList => f.write_str("VectorToList"),
+            Instruction::VectorMap => f.write_str("VectorMap"),
+            Instruction::VectorForEach => f.write_str("VectorForEach"),
+            // Variadic function support
+            Instruction::PackRestArgs(n) => write!(f, "PackRestArgs({})", n),
+            Instruction::JmpIfArgSupplied(idx, addr) => write!(f, "JmpIfArgSupplied({}, {})", idx, addr),
+            Instruction::BindArg(idx) => write!(f, "BindArg({})", idx),
+            Instruction::CheckMaxArgs(max) => write!(f, "CheckMaxArgs({})", max),
+            Instruction::BindKeyArgs(first, keys) => write!(f, "BindKeyArgs({}, {:?})", first, keys),
+            Instruction::JmpIfKeySupplied(idx, addr) => write!(f, "JmpIfKeySupplied({}, {})", idx, addr),
+            Instruction::NoMatchingClause(message) => write!(f, "NoMatchingClause({:?})", message),
+            Instruction::MakeVariadicClosure(params, rest_param, body, num_captured) => {
+                write!(f, "MakeVariadicClosure({:?} . {}, {} instrs, {} captured)",
+                        params, rest_param, body.len(), num_captured)
+            }
+            // Float type predicates and conversions
+            Instruction::IsFloat => f.write_str("IsFloat"),
+            Instruction::IsNaN => f.write_str("IsNaN"),
+            Instruction::IsInfinite => f.write_str("IsInfinite"),
+            Instruction::IsNumber => f.write_str("IsNumber"),
+            Instruction::IntToFloat => f.write_str("IntToFloat"),
+            Instruction::FloatToInt => f.write_str("FloatToInt"),
+            // Math functions
+            Instruction::Sqrt => f.write_str("Sqrt"),
+            Instruction::Sin => f.write_str("Sin"),
+            Instruction::Cos => f.write_str("Cos"),
+            Instruction::Tan => f.write_str("Tan"),
+            Instruction::Atan => f.write_str("Atan"),
+            Instruction::Atan2 => f.write_str("Atan2"),
+            Instruction::Log => f.write_str("Log"),
+            Instruction::Exp => f.write_str("Exp"),
+            Instruction::Floor => f.write_str("Floor"),
+            Instruction::Ceil => f.write_str("Ceil"),
+            Instruction::Round => f.write_str("Round"),
+            Instruction::IExpt => f.write_str("IExpt"),
+            Instruction::Gcd => f.write_str("Gcd"),
+            Instruction::Lcm => f.write_str("Lcm"),
+            Instruction::Abs => f.write_str("Abs"),
+            Instruction::Pow => f.write_str("Pow"),
+            Instruction::Random => f.write_str("Random"),
+            Instruction::RandomInt => f.write_str("RandomInt"),
+            Instruction::SeedRandom => f.write_str("SeedRandom"),
+            // String operations
+            Instruction::StringSplit => f.write_str("StringSplit"),
+            Instruction::StringJoin => f.write_str("StringJoin"),
+            Instruction::StringTrim => f.write_str("StringTrim"),
+            Instruction::StringReplace => f.write_str("StringReplace"),
+            // Date/Time operations
+            Instruction::CurrentTimestamp => f.write_str("CurrentTimestamp"),
+            Instruction::FormatTimestamp => f.write_str("FormatTimestamp"),
+            Instruction::LogMessage(level) => write!(f, "LogMessage({:?})", level),
+            Instruction::SetLogLevel => f.write_str("SetLogLevel"),
+            // Metaprogramming
+            Instruction::Eval => f.write_str("Eval"),
+            // Reflection
+            Instruction::FunctionArity => f.write_str("FunctionArity"),
+            Instruction::FunctionParams => f.write_str("FunctionParams"),
+            Instruction::ClosureCaptured => f.write_str("ClosureCaptured"),
+            Instruction::FunctionName => f.write_str("FunctionName"),
+            Instruction::RegisterModule(name, exports) => write!(f, "RegisterModule(\"{}\", {:?})", name, exports),
+            Instruction::ProvideModules(modules) => write!(f, "ProvideModules({:?})", modules),
+            Instruction::ModuleExports => f.write_str("ModuleExports"),
+            Instruction::ModuleList => f.write_str("ModuleList"),
+            // Type inspection and symbol generation
+            Instruction::TypeOf => f.write_str("TypeOf"),
+            Instruction::GenSym => f.write_str("GenSym"),
+            // Parallel Collections
+            Instruction::PMap => f.write_str("PMap"),
+            Instruction::PFilter => f.write_str("PFilter"),
+            Instruction::PReduce => f.write_str("PReduce"),
+            // HTTP/Networking
+            Instruction::HttpListen => f.write_str("HttpListen"),
+            Instruction::HttpAccept => f.write_str("HttpAccept"),
+            Instruction::HttpReadRequest => f.write_str("HttpReadRequest"),
+            Instruction::HttpSendResponse => f.write_str("HttpSendResponse"),
+            Instruction::HttpClose => f.write_str("HttpClose"),
+            // Multi-threaded HTTP
+            Instruction::HttpListenShared => f.write_str("HttpListenShared"),
+            Instruction::HttpServeParallel => f.write_str("HttpServeParallel"),
+            // String predicates and utilities
+            Instruction::StringStartsWith => f.write_str("StringStartsWith"),
+            Instruction::StringEndsWith => f.write_str("StringEndsWith"),
+            Instruction::StringContains => f.write_str("StringContains"),
+            Instruction::StringIndexOf => f.write_str("StringIndexOf"),
+            Instruction::StringUpcase => f.write_str("StringUpcase"),
+            Instruction::StringDowncase => f.write_str("StringDowncase"),
+            Instruction::Format => f.write_str("Format"),
+            Instruction::Interpolate(n) => write!(f, "Interpolate({})", n),
+            // FFI instructions
+            Instruction::FfiLoadLibrary => f.write_str("FfiLoadLibrary"),
+            Instruction::FfiGetSymbol => f.write_str("FfiGetSymbol"),
+            Instruction::FfiCall(ref arg_types, ref return_type) => {
+                write!(f, "FfiCall({:?}, {:?})", arg_types, return_type)
+            }
+            Instruction::FfiPointerToString => f.write_str("FfiPointerToString"),
+            Instruction::FfiStringToPointer => f.write_str("FfiStringToPointer"),
+            Instruction::FfiFreeString => f.write_str("FfiFreeString"),
+            Instruction::FfiNullPointer => f.write_str("FfiNullPointer"),
+            Instruction::FfiPointerNull => f.write_str("FfiPointerNull"),
+            Instruction::IsPointer => f.write_str("IsPointer"),
+            Instruction::FfiPointerAdd => f.write_str("FfiPointerAdd"),
+            Instruction::FfiReadInt => f.write_str("FfiReadInt"),
+            Instruction::FfiWriteInt => f.write_str("FfiWriteInt"),
+            Instruction::FfiReadFloat => f.write_str("FfiReadFloat"),
+            Instruction::FfiWriteFloat => f.write_str("FfiWriteFloat"),
+            Instruction::FfiReadByte => f.write_str("FfiReadByte"),
+            Instruction::FfiWriteByte => f.write_str("FfiWriteByte"),
+            Instruction::FfiAllocate => f.write_str("FfiAllocate"),
+            Instruction::FfiFree => f.write_str("FfiFree"),
+            Instruction::FfiSizeOf(ref ffi_type) => write!(f, "FfiSizeOf({:?})", ffi_type),
+        }
+    }
+}
+
+/// Severity levels for the log-* builtins, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Off, // Only meaningful as a threshold: suppresses all log output
+}
+
+impl LogLevel {
+    /// Parse a level name as accepted by set-log-level (case-insensitive)
+    pub fn from_name(name: &str) -> Option<LogLevel> {
+        match name.to_ascii_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            "off" => Some(LogLevel::Off),
+            _ => None,
+        }
+    }
+
+    /// Lowercase level name, as returned by set-log-level
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Off => "off",
+        }
+    }
+}
+
 /// FFI type descriptors for marshalling between Lisp and C
 #[derive(Debug, Clone, PartialEq)]
 pub enum FfiType {
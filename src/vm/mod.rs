@@ -2,6 +2,7 @@
 // This module contains all the runtime execution components
 
 pub mod value;
+pub mod bigint;
 pub mod instructions;
 pub mod bytecode;
 pub mod stack;
@@ -11,9 +12,13 @@ pub mod builtins;
 pub mod errors;
 pub mod object;
 pub mod ffi;
+pub mod verifier;
+pub mod debugger;
 
 // Re-export commonly used types for convenience
 pub use value::{Value, List};
-pub use instructions::{Instruction, FfiType};
-pub use vm::VM;
+pub use bigint::BigInt;
+pub use instructions::{Instruction, FfiType, LogLevel};
+pub use vm::{VM, DEFAULT_MAX_CALL_DEPTH};
 pub use ffi::FfiState;
+pub use debugger::{StepInfo, StopReason};
@@ -34,3 +34,13 @@ impl Frame {
         }
     }
 }
+
+/// An active try handler, installed by PushHandler. An error raised while it
+/// is active unwinds the VM back to this state and continues at the catch code.
+#[derive(Debug)]
+pub struct Handler {
+    pub catch_address: usize,
+    pub bytecode: Vec<Instruction>, // Bytecode containing the catch code
+    pub call_depth: usize,          // Frames on the call stack when the handler was installed
+    pub stack_depth: usize,         // Values on the value stack when the handler was installed
+}
@@ -1,3 +1,5 @@
+use super::bigint::BigInt;
+use super::errors::RuntimeError;
 use super::instructions::Instruction;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -7,19 +9,26 @@ use std::net::{TcpListener, TcpStream};
 
 /// Cons-cell based list structure for O(1) cons/car/cdr operations.
 /// Uses Arc for structural sharing - cdr returns a reference to existing tail.
+/// Each cell records the length of the list it starts, so `len()` is O(1),
+/// and whether that list ends in '(), so `is_proper()` is O(1) too.
 #[derive(Debug, Clone)]
 pub struct ConsCell {
     pub head: Value,
     pub tail: List,
+    pub len: usize,
+    pub proper: bool,
 }
 
 /// List type using cons cells with Arc for efficient structural sharing.
 /// - Nil represents empty list '()
 /// - Cons(Arc<ConsCell>) wraps a cons cell in Arc for O(1) sharing
+/// - Dotted(Box<Value>) ends an improper list: the tail of (1 . 2) is Dotted(2).
+///   It only ever appears as the tail of a cons cell; `cdr` unwraps it to the atom.
 #[derive(Debug, Clone)]
 pub enum List {
     Nil,
     Cons(Arc<ConsCell>),
+    Dotted(Box<Value>),
 }
 
 impl List {
@@ -30,7 +39,9 @@ impl List {
 
     /// Create a cons cell (prepend element to list)
     pub fn cons(head: Value, tail: List) -> Self {
-        List::Cons(Arc::new(ConsCell { head, tail }))
+        let len = tail.len() + 1;
+        let proper = tail.is_proper();
+        List::Cons(Arc::new(ConsCell { head, tail, len, proper }))
     }
 
     /// Check if list is empty
@@ -41,28 +52,63 @@ impl List {
     /// Get the head (car) of the list
     pub fn car(&self) -> Option<&Value> {
         match self {
-            List::Nil => None,
             List::Cons(cell) => Some(&cell.head),
+            List::Nil | List::Dotted(_) => None,
         }
     }
 
     /// Get the tail (cdr) of the list - O(1) operation via Arc clone
     pub fn cdr(&self) -> Option<List> {
         match self {
-            List::Nil => None,
             List::Cons(cell) => Some(cell.tail.clone()),
+            List::Nil | List::Dotted(_) => None,
         }
     }
 
-    /// Get the length of the list
+    /// Get the tail (cdr) as a value: the rest of the list, or the atom after the dot
+    pub fn cdr_value(&self) -> Option<Value> {
+        match self {
+            List::Cons(cell) => Some(cell.tail.to_value()),
+            List::Nil | List::Dotted(_) => None,
+        }
+    }
+
+    /// Wrap the list as a value; a bare dotted tail becomes the atom it holds
+    pub fn to_value(&self) -> Value {
+        match self {
+            List::Dotted(atom) => (**atom).clone(),
+            list => Value::List(list.clone()),
+        }
+    }
+
+    /// Get the length of the list - O(1), read from the cached length in the head cell.
+    /// For an improper list this counts the elements before the dot.
     pub fn len(&self) -> usize {
-        let mut count = 0;
+        match self {
+            List::Cons(cell) => cell.len,
+            List::Nil | List::Dotted(_) => 0,
+        }
+    }
+
+    /// The atom after the dot if this is an improper list like (1 2 . 3)
+    pub fn dotted_tail(&self) -> Option<&Value> {
         let mut current = self;
         while let List::Cons(cell) = current {
-            count += 1;
             current = &cell.tail;
         }
-        count
+        match current {
+            List::Dotted(atom) => Some(atom),
+            _ => None,
+        }
+    }
+
+    /// True for '() and for lists whose spine ends in '() rather than a dotted atom
+    pub fn is_proper(&self) -> bool {
+        match self {
+            List::Nil => true,
+            List::Cons(cell) => cell.proper,
+            List::Dotted(_) => false,
+        }
     }
 
     /// Check if the list is empty
@@ -79,7 +125,7 @@ impl List {
         list
     }
 
-    /// Convert to Vec<Value>
+    /// Convert to Vec<Value> (an improper list's dotted atom is left out)
     pub fn to_vec(&self) -> Vec<Value> {
         let mut result = Vec::new();
         let mut current = self;
@@ -94,6 +140,50 @@ impl List {
     pub fn iter(&self) -> ListIter<'_> {
         ListIter { current: self }
     }
+
+    /// Copy the spine into fresh cons cells; the elements themselves are shared.
+    /// An improper list keeps the atom after its dot.
+    pub fn copy(&self) -> List {
+        let mut list = match self.dotted_tail() {
+            Some(atom) => List::Dotted(Box::new(atom.clone())),
+            None => List::Nil,
+        };
+        for item in self.to_vec().into_iter().rev() {
+            list = List::cons(item, list);
+        }
+        list
+    }
+
+    /// True if the two lists have at least one cons cell in common.
+    /// Cells are immutable, so once two lists meet they share the rest of
+    /// their tails: skipping the longer list down to the shorter one's length
+    /// lines up the only cells that could be shared. O(n), no allocation.
+    pub fn shares_structure(&self, other: &List) -> bool {
+        let (mut a, mut b) = (self, other);
+        while a.len() > b.len() {
+            a = match a { List::Cons(cell) => &cell.tail, _ => unreachable!() };
+        }
+        while b.len() > a.len() {
+            b = match b { List::Cons(cell) => &cell.tail, _ => unreachable!() };
+        }
+        while let (List::Cons(x), List::Cons(y)) = (a, b) {
+            if Arc::ptr_eq(x, y) {
+                return true;
+            }
+            a = &x.tail;
+            b = &y.tail;
+        }
+        false
+    }
+
+    /// True if both lists are the same cons cell (or both empty)
+    pub fn ptr_eq(&self, other: &List) -> bool {
+        match (self, other) {
+            (List::Nil, List::Nil) => true,
+            (List::Cons(a), List::Cons(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
 }
 
 /// Iterator over List elements
@@ -106,23 +196,28 @@ impl<'a> Iterator for ListIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.current {
-            List::Nil => None,
             List::Cons(cell) => {
                 let head = &cell.head;
                 self.current = &cell.tail;
                 Some(head)
             }
+            List::Nil | List::Dotted(_) => None,
         }
     }
 }
 
 impl PartialEq for List {
     fn eq(&self, other: &Self) -> bool {
+        // Lists of different lengths can never be equal
+        if self.len() != other.len() {
+            return false;
+        }
         let mut a = self;
         let mut b = other;
         loop {
             match (a, b) {
                 (List::Nil, List::Nil) => return true,
+                (List::Dotted(atom_a), List::Dotted(atom_b)) => return atom_a == atom_b,
                 (List::Cons(cell_a), List::Cons(cell_b)) => {
                     if cell_a.head != cell_b.head {
                         return false;
@@ -159,6 +254,14 @@ impl Drop for List {
                     std::mem::forget(current);
                     return;
                 }
+                List::Dotted(atom) => {
+                    // Same trick as below: move the box out, then forget current
+                    // so this Drop isn't re-entered for it
+                    let atom = unsafe { std::ptr::read(atom as *const Box<Value>) };
+                    std::mem::forget(current);
+                    drop(atom);
+                    return;
+                }
                 List::Cons(arc) => arc as *const Arc<ConsCell>,
             };
 
@@ -193,9 +296,34 @@ pub struct ClosureData {
     pub captured: Vec<(String, Value)>,
 }
 
+impl ClosureData {
+    /// Human-readable signature, e.g. `#<closure (x y)>` or `#<closure (x . rest) variadic>`
+    pub fn describe(&self) -> String {
+        match &self.rest_param {
+            Some(rest) if self.params.is_empty() => format!("#<closure (. {}) variadic>", rest),
+            Some(rest) => format!("#<closure ({} . {}) variadic>", self.params.join(" "), rest),
+            None => format!("#<closure ({})>", self.params.join(" ")),
+        }
+    }
+}
+
+/// Split a module-qualified symbol name into (namespace, name) at the last `/`,
+/// matching how the compiler qualifies module members (`math/add`, `net/http/get`).
+/// Plain symbols, and ones with an empty side such as `/` itself, are unqualified.
+pub fn split_qualified_symbol(symbol: &str) -> Option<(&str, &str)> {
+    let idx = symbol.rfind('/')?;
+    let (namespace, name) = (&symbol[..idx], &symbol[idx + 1..]);
+    if namespace.is_empty() || name.is_empty() {
+        None
+    } else {
+        Some((namespace, name))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Integer(i64),
+    BigInt(Arc<BigInt>), // Integer outside i64 range, produced when arithmetic overflows
     Float(f64),
     Boolean(bool),
     List(List),
@@ -216,6 +344,7 @@ impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
             (Value::Float(a), Value::Float(b)) => {
                 // NaN != NaN, but we treat them as equal for Value comparison
                 if a.is_nan() && b.is_nan() {
@@ -231,7 +360,8 @@ impl PartialEq for Value {
             (Value::Function(a), Value::Function(b)) => a == b,
             (Value::HashMap(a), Value::HashMap(b)) => a == b,
             (Value::Vector(a), Value::Vector(b)) => a == b,
-            (Value::Closure(a), Value::Closure(b)) => a == b,
+            // Closures compare by identity: two lambdas with identical code are still distinct
+            (Value::Closure(a), Value::Closure(b)) => Arc::ptr_eq(a, b),
             (Value::Pointer(a), Value::Pointer(b)) => a == b,
             _ => false,
         }
@@ -239,6 +369,32 @@ impl PartialEq for Value {
 }
 
 impl Value {
+    /// Identity comparison behind eq?. Symbols and function references compare
+    /// by name and immediates by value; heap values (cons cells, strings,
+    /// bigints, vectors, hashmaps, closures, sockets) are identical only if
+    /// they are the same allocation, so two freshly built lists are not eq?
+    /// even when they are equal?
+    pub fn is_identical(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Integer(_), Value::Integer(_))
+            | (Value::Float(_), Value::Float(_))
+            | (Value::Boolean(_), Value::Boolean(_))
+            | (Value::Pointer(_), Value::Pointer(_))
+            | (Value::Symbol(_), Value::Symbol(_))
+            | (Value::Function(_), Value::Function(_)) => self == other,
+            (Value::List(a), Value::List(b)) => a.ptr_eq(b),
+            (Value::BigInt(a), Value::BigInt(b)) => Arc::ptr_eq(a, b),
+            (Value::String(a), Value::String(b)) => Arc::ptr_eq(a, b),
+            (Value::Closure(a), Value::Closure(b)) => Arc::ptr_eq(a, b),
+            (Value::HashMap(a), Value::HashMap(b)) => Arc::ptr_eq(a, b),
+            (Value::Vector(a), Value::Vector(b)) => Arc::ptr_eq(a, b),
+            (Value::TcpListener(a), Value::TcpListener(b)) => Rc::ptr_eq(a, b),
+            (Value::TcpStream(a), Value::TcpStream(b)) => Rc::ptr_eq(a, b),
+            (Value::SharedTcpListener(a), Value::SharedTcpListener(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
     pub fn is_int(&self) -> bool {
         matches!(self, Value::Integer(_))
     }
@@ -248,7 +404,11 @@ impl Value {
     }
 
     pub fn is_number(&self) -> bool {
-        matches!(self, Value::Integer(_) | Value::Float(_))
+        matches!(self, Value::Integer(_) | Value::BigInt(_) | Value::Float(_))
+    }
+
+    pub fn is_bigint(&self) -> bool {
+        matches!(self, Value::BigInt(_))
     }
 
     pub fn is_bool(&self) -> bool {
@@ -275,6 +435,27 @@ impl Value {
         matches!(self, Value::Function(_))
     }
 
+    /// Lisp-facing name of this value's type, as used in error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "integer",
+            Value::BigInt(_) => "bigint",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+            Value::List(_) => "list",
+            Value::Symbol(_) => "symbol",
+            Value::String(_) => "string",
+            Value::Function(_) => "function",
+            Value::Closure { .. } => "closure",
+            Value::HashMap(_) => "hashmap",
+            Value::Vector(_) => "vector",
+            Value::TcpListener(_) => "tcp-listener",
+            Value::TcpStream(_) => "tcp-stream",
+            Value::SharedTcpListener(_) => "shared-tcp-listener",
+            Value::Pointer(_) => "pointer",
+        }
+    }
+
     pub fn as_int(&self) -> Option<i64> {
         if let Value::Integer(n) = self {
             Some(*n)
@@ -299,14 +480,28 @@ impl Value {
         }
     }
 
-    pub fn as_string(&self) -> Option<&str> {
+    /// Integer accessor for embedders; fails with a type error otherwise
+    pub fn as_integer(&self) -> Result<i64, RuntimeError> {
+        self.as_int().ok_or_else(|| self.conversion_error("integer"))
+    }
+
+    /// String accessor for embedders; fails with a type error otherwise
+    pub fn as_string(&self) -> Result<&str, RuntimeError> {
         if let Value::String(s) = self {
-            Some(s)
+            Ok(s)
         } else {
-            None
+            Err(self.conversion_error("string"))
         }
     }
 
+    fn conversion_error(&self, expected: &str) -> RuntimeError {
+        RuntimeError::new(format!(
+            "Type error: expected {}, got {}",
+            expected,
+            self.type_name()
+        ))
+    }
+
     pub fn as_symbol(&self) -> Option<&str> {
         if let Value::Symbol(s) = self {
             Some(s)
@@ -315,6 +510,17 @@ impl Value {
         }
     }
 
+    /// Namespace part of a module-qualified symbol (`math` for `math/add`)
+    pub fn symbol_namespace(&self) -> Option<&str> {
+        self.as_symbol().and_then(split_qualified_symbol).map(|(ns, _)| ns)
+    }
+
+    /// Name part of a symbol: `add` for both `math/add` and `add`
+    pub fn symbol_name(&self) -> Option<&str> {
+        self.as_symbol()
+            .map(|s| split_qualified_symbol(s).map_or(s, |(_, name)| name))
+    }
+
     pub fn as_list(&self) -> Option<&List> {
         if let Value::List(lst) = self {
             Some(lst)
@@ -375,6 +581,14 @@ impl Value {
         Value::List(List::Nil)
     }
 
+    /// Integer value of a bignum result: a plain Integer when it fits in an i64
+    pub fn from_bigint(n: BigInt) -> Self {
+        match n.to_i64() {
+            Some(small) => Value::Integer(small),
+            None => Value::BigInt(Arc::new(n)),
+        }
+    }
+
     /// Helper to create a list from a vector
     pub fn list_from_vec(items: Vec<Value>) -> Self {
         Value::List(List::from_vec(items))
@@ -401,4 +615,144 @@ impl Value {
     pub fn pointer(addr: i64) -> Self {
         Value::Pointer(addr)
     }
+
+    /// Turn every list nested anywhere in lists or vectors into a vector.
+    /// Anything else (including hash maps and their contents) is kept as is.
+    pub fn deep_list_to_vector(&self) -> Value {
+        self.deep_convert(true)
+    }
+
+    /// Turn every vector nested anywhere in lists or vectors into a list.
+    /// Anything else (including hash maps and their contents) is kept as is.
+    pub fn deep_vector_to_list(&self) -> Value {
+        self.deep_convert(false)
+    }
+
+    /// Rebuild nested lists and vectors bottom-up with an explicit work stack,
+    /// so arbitrarily deep data can't overflow the native stack
+    fn deep_convert(&self, to_vector: bool) -> Value {
+        enum Task {
+            Visit(Value),
+            Build(usize), // Collect this many converted children into one sequence
+        }
+
+        let mut tasks = vec![Task::Visit(self.clone())];
+        let mut converted: Vec<Value> = Vec::new();
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(value) => {
+                    let children = match &value {
+                        // An improper list has no vector form, so it is kept as is
+                        Value::List(list) if list.is_proper() => list.to_vec(),
+                        Value::Vector(items) => items.as_ref().clone(),
+                        _ => {
+                            converted.push(value);
+                            continue;
+                        }
+                    };
+                    tasks.push(Task::Build(children.len()));
+                    // Reversed, so children are converted (and land in `converted`) in order
+                    tasks.extend(children.into_iter().rev().map(Task::Visit));
+                }
+                Task::Build(len) => {
+                    let items = converted.split_off(converted.len() - len);
+                    converted.push(if to_vector {
+                        Value::Vector(items.into())
+                    } else {
+                        Value::List(List::from_vec(items))
+                    });
+                }
+            }
+        }
+
+        converted.pop().unwrap_or(Value::List(List::Nil))
+    }
+}
+
+// Conversions between Rust types and Value, for embedding the VM.
+// Vec<Value> maps to a Lisp list; use Value::Vector for vectors.
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Integer(n)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::string(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::string(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(items: Vec<Value>) -> Self {
+        Value::list_from_vec(items)
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value.as_integer()
+    }
+}
+
+/// Integers are promoted, matching the arithmetic rules
+impl TryFrom<Value> for f64 {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(f) => Ok(f),
+            Value::Integer(n) => Ok(n as f64),
+            other => Err(other.conversion_error("number")),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or_else(|| value.conversion_error("boolean"))
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value.as_string().map(str::to_string)
+    }
+}
+
+impl TryFrom<Value> for Vec<Value> {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::List(list) => Ok(list.to_vec()),
+            other => Err(other.conversion_error("list")),
+        }
+    }
 }
@@ -0,0 +1,200 @@
+// Bytecode verifier: static checks run before execution (opt-in)
+//
+// The compiler should never produce bytecode that fails these checks, so a
+// failure points at a codegen bug (or a corrupted bytecode file) and is
+// reported up front instead of as a stack underflow somewhere later.
+
+use std::collections::HashMap;
+use super::instructions::Instruction;
+use super::errors::RuntimeError;
+
+/// How many arguments a block of bytecode can see through LoadArg
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    /// Exactly this many arguments (closures, main, functions with known params)
+    Exact(usize),
+    /// Not recorded; only limits implied by the bytecode itself are checked
+    Unknown,
+}
+
+/// Verify a whole program: main and every function. Main sees `main_args`
+/// arguments (zero unless the host pushed a frame, as macro expansion does).
+/// `params` gives the number of parameters of functions whose signature is
+/// known (a rest parameter counts as one).
+pub fn verify_program(
+    functions: &HashMap<String, Vec<Instruction>>,
+    main: &[Instruction],
+    main_args: usize,
+    params: &HashMap<String, usize>,
+) -> Result<(), RuntimeError> {
+    let mut names: Vec<&String> = functions.keys().collect();
+    names.sort();
+    for name in names {
+        let arity = params.get(name).map_or(Arity::Unknown, |n| Arity::Exact(*n));
+        verify_function(name, &functions[name], arity)?;
+    }
+    verify_block("main", main, Arity::Exact(main_args), true)
+}
+
+/// Verify one function's bytecode, including the bodies of closures it creates
+pub fn verify_function(name: &str, bytecode: &[Instruction], arity: Arity) -> Result<(), RuntimeError> {
+    verify_block(name, bytecode, arity, false)
+}
+
+fn fail(name: &str, addr: usize, message: String) -> RuntimeError {
+    RuntimeError::new(format!(
+        "Bytecode verification failed in '{}' at {}: {}",
+        name, addr, message
+    ))
+}
+
+fn verify_block(name: &str, bytecode: &[Instruction], arity: Arity, is_main: bool) -> Result<(), RuntimeError> {
+    if bytecode.is_empty() {
+        return Err(RuntimeError::new(format!(
+            "Bytecode verification failed in '{}': no instructions",
+            name
+        )));
+    }
+    let len = bytecode.len();
+
+    // Arguments visible to LoadArg. PackRestArgs, CheckArity, CheckMaxArgs and BindKeyArgs imply
+    // a limit even when the declared arity is unknown.
+    let max_args = match arity {
+        Arity::Exact(n) => Some(n),
+        Arity::Unknown => bytecode.iter().filter_map(|instr| match instr {
+            Instruction::PackRestArgs(n) => Some(n + 1),
+            Instruction::CheckArity(n, _) | Instruction::CheckMaxArgs(n) => Some(*n),
+            Instruction::BindKeyArgs(first, keys) => Some(first + keys.len() + 1),
+            _ => None,
+        }).max(),
+    };
+
+    let mut has_loop = false;
+    for (addr, instr) in bytecode.iter().enumerate() {
+        match instr {
+            Instruction::Jmp(target) | Instruction::JmpIfFalse(target) | Instruction::JmpIfTrue(target)
+            | Instruction::CheckArity(_, target) | Instruction::PushHandler(target)
+            | Instruction::JmpIfArgSupplied(_, target) | Instruction::JmpIfKeySupplied(_, target)
+                if *target >= len =>
+            {
+                return Err(fail(name, addr, format!(
+                    "jump target {} is out of range ({} instructions)",
+                    target, len
+                )));
+            }
+            _ => {}
+        }
+
+        match instr {
+            Instruction::LoadArg(idx) | Instruction::ArgListLength(idx) | Instruction::BindArg(idx)
+            | Instruction::JmpIfKeySupplied(idx, _) => {
+                if let Some(max) = max_args {
+                    if *idx >= max {
+                        return Err(fail(name, addr, format!(
+                            "argument {} is out of range (arity {})",
+                            idx, max
+                        )));
+                    }
+                }
+            }
+            Instruction::CheckArity(n, _) => {
+                if let Arity::Exact(declared) = arity {
+                    if *n > declared {
+                        return Err(fail(name, addr, format!(
+                            "CheckArity({}) exceeds the declared arity {}",
+                            n, declared
+                        )));
+                    }
+                }
+            }
+            Instruction::BindKeyArgs(first, keys) => {
+                if let Arity::Exact(declared) = arity {
+                    if first + keys.len() >= declared {
+                        return Err(fail(name, addr, format!(
+                            "BindKeyArgs({}, ..) leaves no slot for {} key(s) and their flags (arity {})",
+                            first, keys.len(), declared
+                        )));
+                    }
+                }
+            }
+            Instruction::PackRestArgs(n) => {
+                if let Arity::Exact(declared) = arity {
+                    if *n >= declared {
+                        return Err(fail(name, addr, format!(
+                            "PackRestArgs({}) leaves no slot for the rest parameter (arity {})",
+                            n, declared
+                        )));
+                    }
+                }
+            }
+            // Every value removed or gathered must have been pushed by an earlier instruction
+            Instruction::PopN(n) | Instruction::Slide(n) | Instruction::MakeList(n) | Instruction::MakeVector(n)
+            | Instruction::Interpolate(n)
+                if *n > addr =>
+            {
+                return Err(fail(name, addr, format!(
+                    "{:?} consumes more values than the {} instruction(s) before it can push",
+                    instr, addr
+                )));
+            }
+            Instruction::BeginLoop(_) => has_loop = true,
+            Instruction::Recur(_) if !has_loop => {
+                return Err(fail(name, addr, "recur without a preceding BeginLoop".to_string()));
+            }
+            Instruction::MakeClosure(params, body, _) => {
+                let closure_name = format!("{} (closure at {})", name, addr);
+                verify_block(&closure_name, body, Arity::Exact(params.len()), false)?;
+            }
+            Instruction::MakeVariadicClosure(params, _, body, _) => {
+                let closure_name = format!("{} (closure at {})", name, addr);
+                verify_block(&closure_name, body, Arity::Exact(params.len() + 1), false)?;
+            }
+            _ => {}
+        }
+    }
+
+    check_no_fall_through(name, bytecode, is_main)
+}
+
+/// Walk every reachable instruction and make sure none of them continues
+/// past the end: each path has to finish in Ret (functions), Halt (main),
+/// a tail call, or jump somewhere else.
+fn check_no_fall_through(name: &str, bytecode: &[Instruction], is_main: bool) -> Result<(), RuntimeError> {
+    let len = bytecode.len();
+    let mut visited = vec![false; len];
+    let mut pending = vec![0];
+
+    while let Some(addr) = pending.pop() {
+        if visited[addr] {
+            continue;
+        }
+        visited[addr] = true;
+
+        let (falls_through, target) = match &bytecode[addr] {
+            Instruction::Ret | Instruction::Halt | Instruction::TailCall(..) | Instruction::TailCallClosure(_) | Instruction::Recur(_)
+            | Instruction::NoMatchingClause(_) => (false, None),
+            Instruction::Jmp(target) => (false, Some(*target)),
+            Instruction::JmpIfFalse(target) | Instruction::JmpIfTrue(target) | Instruction::CheckArity(_, target)
+            | Instruction::JmpIfArgSupplied(_, target) | Instruction::JmpIfKeySupplied(_, target) => (true, Some(*target)),
+            // The catch code is only entered by unwinding, but it must end properly too
+            Instruction::PushHandler(target) => (true, Some(*target)),
+            _ => (true, None),
+        };
+
+        if let Some(target) = target {
+            pending.push(target);
+        }
+        if falls_through {
+            if addr + 1 == len {
+                let expected = if is_main { "Halt" } else { "Ret" };
+                return Err(fail(name, addr, format!(
+                    "execution can run past the last instruction; expected {}",
+                    expected
+                )));
+            }
+            pending.push(addr + 1);
+        }
+    }
+
+    Ok(())
+}
@@ -1,21 +1,31 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::cmp::Ordering;
 use std::sync::Arc;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use super::value::{Value, List, ClosureData};
-use super::instructions::{Instruction, FfiType};
-use super::stack::Frame;
+use super::bigint::BigInt;
+use super::instructions::{Instruction, FfiType, LogLevel};
+use super::stack::{Frame, Handler};
 use super::errors::RuntimeError;
+use super::builtins::BUILTIN_NAMES;
 use super::ffi::{FfiState, ffi_type_size};
+use super::verifier;
+use super::debugger::{StepInfo, StopReason};
 use crate::parser::Parser;
 use crate::compiler::Compiler;
 
+/// Default VM::max_call_depth: deep enough for ordinary non-tail recursion,
+/// while runaway recursion fails with an error instead of exhausting memory
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 100_000;
+
 pub struct VM {
     pub instruction_pointer: usize,
     pub value_stack: Vec<Value>,
     pub call_stack: Vec<Frame>,
+    pub handlers: Vec<Handler>,             // Active try handlers, innermost last
     pub functions: HashMap<String, Vec<Instruction>>,
     pub current_bytecode: Vec<Instruction>,
     pub halted: bool,
@@ -24,7 +34,18 @@ pub struct VM {
     pub loaded_modules: HashSet<String>,     // Track loaded modules for require
     pub loading_modules: Vec<String>,        // Stack of modules currently being loaded (for circular dep detection)
     pub module_exports: HashMap<String, HashSet<String>>, // Module name -> exported symbols
+    pub provided_modules: HashMap<String, String>, // Module name -> file that declared it with provide
     pub ffi_state: FfiState,                 // FFI state for foreign function interface
+    pub min_log_level: LogLevel,             // log-* calls below this level are skipped
+    pub log_writer: Box<dyn std::io::Write>, // Destination for log-* and trace output (stderr by default)
+    pub strict_mode: bool,                   // Reject mixed integer/float arithmetic (see Compiler::set_strict_mode)
+    pub gensym_counter: usize,               // Next number used by gensym (G__0, G__1, ... or prefix__N)
+    pub verify_bytecode: bool,               // Run the bytecode verifier over main and all functions in run()
+    pub trace_bytecode: bool,                // Write a TRACE line to log_writer before every instruction
+    pub checked_arithmetic: bool,            // Integer overflow in + - * is an error instead of promoting to a bigint
+    pub max_call_depth: usize,               // Calls nested deeper than this fail with "maximum recursion depth exceeded"
+    pub breakpoints: HashSet<String>,        // run_with_hook pauses before a call into any of these functions
+    resuming_from_breakpoint: bool,          // The next run_with_hook starts on the call it paused at, so let it through
 }
 
 impl VM {
@@ -34,6 +55,7 @@ impl VM {
             instruction_pointer: 0,
             value_stack: Vec::new(),
             call_stack: Vec::new(),
+            handlers: Vec::new(),
             functions: HashMap::new(),
             current_bytecode: Vec::new(),
             halted: false,
@@ -42,181 +64,339 @@ impl VM {
             loaded_modules: HashSet::new(),
             loading_modules: Vec::new(),
             module_exports: HashMap::new(),
+            provided_modules: HashMap::new(),
             ffi_state: FfiState::new(),
+            min_log_level: LogLevel::Info,
+            log_writer: Box::new(std::io::stderr()),
+            strict_mode: false,
+            gensym_counter: 0,
+            verify_bytecode: false,
+            trace_bytecode: false,
+            checked_arithmetic: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            breakpoints: HashSet::new(),
+            resuming_from_breakpoint: false,
         };
         vm.register_builtins();
         vm
     }
 
+    /// Choose what integer `+`, `-`, `*`, `neg` and `abs` do when the result
+    /// doesn't fit in an i64. By default it is promoted to a bigint; with
+    /// checking enabled they raise an "integer overflow" runtime error instead.
+    pub fn set_checked_arithmetic(&mut self, checked: bool) {
+        self.checked_arithmetic = checked;
+    }
+
+    /// Limit how deeply calls may nest (see DEFAULT_MAX_CALL_DEPTH). Tail
+    /// calls reuse their frame, so they never count against it.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Fail a call that would push a frame past max_call_depth. The error's
+    /// call stack keeps only the innermost frames, which show the recursion.
+    fn check_call_depth(&self) -> Result<(), RuntimeError> {
+        const SHOWN_FRAMES: usize = 10;
+
+        let depth = self.call_stack.len();
+        if depth < self.max_call_depth {
+            return Ok(());
+        }
+        let mut trace = Vec::new();
+        if depth > SHOWN_FRAMES {
+            trace.push(format!("... {} more", depth - SHOWN_FRAMES));
+        }
+        trace.extend(self.get_stack_trace().into_iter().skip(depth.saturating_sub(SHOWN_FRAMES)));
+
+        let mut error = RuntimeError::with_suggestion(
+            format!("maximum recursion depth exceeded ({} nested calls)", self.max_call_depth),
+            "Make the recursive call a tail call, or raise the limit with VM::set_max_call_depth".to_string(),
+        );
+        error.call_stack = trace;
+        Err(error)
+    }
+
+    /// Names of every function (builtins included) and global variable defined in this VM,
+    /// sorted. Used for REPL completion and other introspection.
+    pub fn known_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.functions.keys().chain(self.global_vars.keys()).cloned().collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
     fn register_builtins(&mut self) {
+        for &name in BUILTIN_NAMES {
+            self.functions.insert(name.to_string(), Self::builtin_bytecode(name));
+        }
+    }
+
+    /// Bytecode of the builtin function `name`, one of BUILTIN_NAMES
+    fn builtin_bytecode(name: &str) -> Vec<Instruction> {
         use Instruction::*;
 
-        // Arithmetic operations (binary)
-        self.functions.insert("+".to_string(), vec![LoadArg(0), LoadArg(1), Add, Ret]);
-        self.functions.insert("-".to_string(), vec![LoadArg(0), LoadArg(1), Sub, Ret]);
-        self.functions.insert("*".to_string(), vec![LoadArg(0), LoadArg(1), Mul, Ret]);
-        self.functions.insert("/".to_string(), vec![LoadArg(0), LoadArg(1), Div, Ret]);
-        self.functions.insert("%".to_string(), vec![LoadArg(0), LoadArg(1), Mod, Ret]);
-        // Arithmetic operations (unary)
-        self.functions.insert("neg".to_string(), vec![LoadArg(0), Neg, Ret]);
-
-        // Comparison operations
-        self.functions.insert("<=".to_string(), vec![LoadArg(0), LoadArg(1), Leq, Ret]);
-        self.functions.insert("<".to_string(), vec![LoadArg(0), LoadArg(1), Lt, Ret]);
-        self.functions.insert(">".to_string(), vec![LoadArg(0), LoadArg(1), Gt, Ret]);
-        self.functions.insert(">=".to_string(), vec![LoadArg(0), LoadArg(1), Gte, Ret]);
-        self.functions.insert("==".to_string(), vec![LoadArg(0), LoadArg(1), Eq, Ret]);
-        self.functions.insert("!=".to_string(), vec![LoadArg(0), LoadArg(1), Neq, Ret]);
-
-        // List operations
-        self.functions.insert("cons".to_string(), vec![LoadArg(0), LoadArg(1), Cons, Ret]);
-        self.functions.insert("car".to_string(), vec![LoadArg(0), Car, Ret]);
-        self.functions.insert("cdr".to_string(), vec![LoadArg(0), Cdr, Ret]);
-        self.functions.insert("list?".to_string(), vec![LoadArg(0), IsList, Ret]);
-        self.functions.insert("append".to_string(), vec![LoadArg(0), LoadArg(1), Append, Ret]);
-        self.functions.insert("list-ref".to_string(), vec![LoadArg(0), LoadArg(1), ListRef, Ret]);
-        self.functions.insert("list-length".to_string(), vec![LoadArg(0), ListLength, Ret]);
-        self.functions.insert("null?".to_string(), vec![LoadArg(0), ListLength, Push(Value::Integer(0)), Eq, Ret]);
-
-        // Type predicates
-        self.functions.insert("integer?".to_string(), vec![LoadArg(0), IsInteger, Ret]);
-        self.functions.insert("float?".to_string(), vec![LoadArg(0), IsFloat, Ret]);
-        self.functions.insert("number?".to_string(), vec![LoadArg(0), IsNumber, Ret]); // int or float
-        self.functions.insert("boolean?".to_string(), vec![LoadArg(0), IsBoolean, Ret]);
-        self.functions.insert("function?".to_string(), vec![LoadArg(0), IsFunction, Ret]);
-        self.functions.insert("closure?".to_string(), vec![LoadArg(0), IsClosure, Ret]);
-        self.functions.insert("procedure?".to_string(), vec![LoadArg(0), IsProcedure, Ret]);
-
-        // String operations
-        self.functions.insert("string?".to_string(), vec![LoadArg(0), IsString, Ret]);
-        self.functions.insert("symbol?".to_string(), vec![LoadArg(0), IsSymbol, Ret]);
-        self.functions.insert("symbol->string".to_string(), vec![LoadArg(0), SymbolToString, Ret]);
-        self.functions.insert("string->symbol".to_string(), vec![LoadArg(0), StringToSymbol, Ret]);
-        self.functions.insert("string-length".to_string(), vec![LoadArg(0), StringLength, Ret]);
-        self.functions.insert("substring".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), Substring, Ret]);
-        self.functions.insert("string-append".to_string(), vec![LoadArg(0), LoadArg(1), StringAppend, Ret]);
-        self.functions.insert("string->list".to_string(), vec![LoadArg(0), StringToList, Ret]);
-        self.functions.insert("list->string".to_string(), vec![LoadArg(0), ListToString, Ret]);
-        self.functions.insert("char-code".to_string(), vec![LoadArg(0), CharCode, Ret]);
-        self.functions.insert("number->string".to_string(), vec![LoadArg(0), NumberToString, Ret]);
-        self.functions.insert("string->number".to_string(), vec![LoadArg(0), StringToNumber, Ret]);
-        self.functions.insert("string-split".to_string(), vec![LoadArg(0), LoadArg(1), StringSplit, Ret]);
-        self.functions.insert("string-join".to_string(), vec![LoadArg(0), LoadArg(1), StringJoin, Ret]);
-        self.functions.insert("string-trim".to_string(), vec![LoadArg(0), StringTrim, Ret]);
-        self.functions.insert("string-replace".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), StringReplace, Ret]);
-        // String predicates and utilities
-        self.functions.insert("string-starts-with?".to_string(), vec![LoadArg(0), LoadArg(1), StringStartsWith, Ret]);
-        self.functions.insert("string-ends-with?".to_string(), vec![LoadArg(0), LoadArg(1), StringEndsWith, Ret]);
-        self.functions.insert("string-contains?".to_string(), vec![LoadArg(0), LoadArg(1), StringContains, Ret]);
-        self.functions.insert("string-upcase".to_string(), vec![LoadArg(0), StringUpcase, Ret]);
-        self.functions.insert("string-downcase".to_string(), vec![LoadArg(0), StringDowncase, Ret]);
-        self.functions.insert("format".to_string(), vec![LoadArg(0), LoadArg(1), Format, Ret]);
-
-        // File I/O operations
-        self.functions.insert("read-file".to_string(), vec![LoadArg(0), ReadFile, Ret]);
-        self.functions.insert("write-file".to_string(), vec![LoadArg(0), LoadArg(1), WriteFile, Ret]);
-        self.functions.insert("file-exists?".to_string(), vec![LoadArg(0), FileExists, Ret]);
-        self.functions.insert("write-binary-file".to_string(), vec![LoadArg(0), LoadArg(1), WriteBinaryFile, Ret]);
-        self.functions.insert("load".to_string(), vec![LoadArg(0), LoadFile, Ret]);
-        self.functions.insert("require".to_string(), vec![LoadArg(0), RequireFile, Ret]);
-
-        // Date/Time operations
-        self.functions.insert("current-timestamp".to_string(), vec![CurrentTimestamp, Ret]);
-        self.functions.insert("format-timestamp".to_string(), vec![LoadArg(0), LoadArg(1), FormatTimestamp, Ret]);
-
-        // Other operations
-        self.functions.insert("get-args".to_string(), vec![GetArgs, Ret]);
-        self.functions.insert("print".to_string(), vec![LoadArg(0), Print, Ret]);
-        self.functions.insert("apply".to_string(), vec![LoadArg(0), LoadArg(1), Apply, Ret]);
-
-        // HashMap operations
-        self.functions.insert("hashmap?".to_string(), vec![LoadArg(0), IsHashMap, Ret]);
-        self.functions.insert("hashmap-get".to_string(), vec![LoadArg(0), LoadArg(1), HashMapGet, Ret]);
-        self.functions.insert("hashmap-set".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), HashMapSet, Ret]);
-        self.functions.insert("hashmap-keys".to_string(), vec![LoadArg(0), HashMapKeys, Ret]);
-        self.functions.insert("hashmap-values".to_string(), vec![LoadArg(0), HashMapValues, Ret]);
-        self.functions.insert("hashmap-contains-key?".to_string(), vec![LoadArg(0), LoadArg(1), HashMapContainsKey, Ret]);
-
-        // Vector operations
-        self.functions.insert("vector?".to_string(), vec![LoadArg(0), IsVector, Ret]);
-        self.functions.insert("vector-ref".to_string(), vec![LoadArg(0), LoadArg(1), VectorGet, Ret]);
-        self.functions.insert("vector-set".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), VectorSet, Ret]);
-        self.functions.insert("vector-push".to_string(), vec![LoadArg(0), LoadArg(1), VectorPush, Ret]);
-        self.functions.insert("vector-pop".to_string(), vec![LoadArg(0), VectorPop, Ret]);
-        self.functions.insert("vector-length".to_string(), vec![LoadArg(0), VectorLength, Ret]);
-
-        // Type conversions
-        self.functions.insert("list->vector".to_string(), vec![LoadArg(0), ListToVector, Ret]);
-        self.functions.insert("vector->list".to_string(), vec![LoadArg(0), VectorToList, Ret]);
-        self.functions.insert("int->float".to_string(), vec![LoadArg(0), IntToFloat, Ret]);
-        self.functions.insert("float->int".to_string(), vec![LoadArg(0), FloatToInt, Ret]);
-
-        // Math functions
-        self.functions.insert("sqrt".to_string(), vec![LoadArg(0), Sqrt, Ret]);
-        self.functions.insert("sin".to_string(), vec![LoadArg(0), Sin, Ret]);
-        self.functions.insert("cos".to_string(), vec![LoadArg(0), Cos, Ret]);
-        self.functions.insert("tan".to_string(), vec![LoadArg(0), Tan, Ret]);
-        self.functions.insert("atan".to_string(), vec![LoadArg(0), Atan, Ret]);
-        self.functions.insert("atan2".to_string(), vec![LoadArg(0), LoadArg(1), Atan2, Ret]);
-        self.functions.insert("log".to_string(), vec![LoadArg(0), Log, Ret]);
-        self.functions.insert("exp".to_string(), vec![LoadArg(0), Exp, Ret]);
-        self.functions.insert("floor".to_string(), vec![LoadArg(0), Floor, Ret]);
-        self.functions.insert("ceil".to_string(), vec![LoadArg(0), Ceil, Ret]);
-        self.functions.insert("abs".to_string(), vec![LoadArg(0), Abs, Ret]);
-        self.functions.insert("pow".to_string(), vec![LoadArg(0), LoadArg(1), Pow, Ret]);
-        self.functions.insert("random".to_string(), vec![Random, Ret]);
-        self.functions.insert("random-int".to_string(), vec![LoadArg(0), RandomInt, Ret]);
-        self.functions.insert("seed-random".to_string(), vec![LoadArg(0), SeedRandom, Ret]);
-
-        // Metaprogramming
-        self.functions.insert("eval".to_string(), vec![LoadArg(0), Eval, Ret]);
-
-        // Reflection - Function Introspection
-        self.functions.insert("function-arity".to_string(), vec![LoadArg(0), FunctionArity, Ret]);
-        self.functions.insert("function-params".to_string(), vec![LoadArg(0), FunctionParams, Ret]);
-        self.functions.insert("closure-captured".to_string(), vec![LoadArg(0), ClosureCaptured, Ret]);
-        self.functions.insert("function-name".to_string(), vec![LoadArg(0), FunctionName, Ret]);
-
-        // Type inspection
-        self.functions.insert("type-of".to_string(), vec![LoadArg(0), TypeOf, Ret]);
-
-        // Symbol generation
-        self.functions.insert("gensym".to_string(), vec![GenSym, Ret]);
-
-        // Parallel Collections (Phase 12a)
-        self.functions.insert("pmap".to_string(), vec![LoadArg(0), LoadArg(1), PMap, Ret]);
-        self.functions.insert("pfilter".to_string(), vec![LoadArg(0), LoadArg(1), PFilter, Ret]);
-        self.functions.insert("preduce".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), PReduce, Ret]);
-
-        // HTTP/Networking (Phase 14)
-        self.functions.insert("http-listen".to_string(), vec![LoadArg(0), HttpListen, Ret]);
-        self.functions.insert("http-accept".to_string(), vec![LoadArg(0), HttpAccept, Ret]);
-        self.functions.insert("http-read-request".to_string(), vec![LoadArg(0), HttpReadRequest, Ret]);
-        self.functions.insert("http-send-response".to_string(), vec![LoadArg(0), LoadArg(1), HttpSendResponse, Ret]);
-        self.functions.insert("http-close".to_string(), vec![LoadArg(0), HttpClose, Ret]);
-
-        // Multi-threaded HTTP (Phase 14b)
-        self.functions.insert("http-listen-shared".to_string(), vec![LoadArg(0), HttpListenShared, Ret]);
-        self.functions.insert("http-serve-parallel".to_string(), vec![LoadArg(0), LoadArg(1), LoadArg(2), LoadArg(3), HttpServeParallel, Ret]);
-
-        // FFI (Foreign Function Interface) - Phase 21
-        self.functions.insert("ffi-load".to_string(), vec![LoadArg(0), FfiLoadLibrary, Ret]);
-        self.functions.insert("ffi-symbol".to_string(), vec![LoadArg(0), LoadArg(1), FfiGetSymbol, Ret]);
-        self.functions.insert("ffi-pointer->string".to_string(), vec![LoadArg(0), FfiPointerToString, Ret]);
-        self.functions.insert("ffi-string->pointer".to_string(), vec![LoadArg(0), FfiStringToPointer, Ret]);
-        self.functions.insert("ffi-free-string".to_string(), vec![LoadArg(0), FfiFreeString, Ret]);
-        self.functions.insert("ffi-null".to_string(), vec![FfiNullPointer, Ret]);
-        self.functions.insert("ffi-null?".to_string(), vec![LoadArg(0), FfiPointerNull, Ret]);
-        self.functions.insert("pointer?".to_string(), vec![LoadArg(0), IsPointer, Ret]);
-        self.functions.insert("ffi-pointer+".to_string(), vec![LoadArg(0), LoadArg(1), FfiPointerAdd, Ret]);
-        self.functions.insert("ffi-read-int".to_string(), vec![LoadArg(0), FfiReadInt, Ret]);
-        self.functions.insert("ffi-write-int".to_string(), vec![LoadArg(0), LoadArg(1), FfiWriteInt, Ret]);
-        self.functions.insert("ffi-read-float".to_string(), vec![LoadArg(0), FfiReadFloat, Ret]);
-        self.functions.insert("ffi-write-float".to_string(), vec![LoadArg(0), LoadArg(1), FfiWriteFloat, Ret]);
-        self.functions.insert("ffi-read-byte".to_string(), vec![LoadArg(0), FfiReadByte, Ret]);
-        self.functions.insert("ffi-write-byte".to_string(), vec![LoadArg(0), LoadArg(1), FfiWriteByte, Ret]);
-        self.functions.insert("ffi-allocate".to_string(), vec![LoadArg(0), FfiAllocate, Ret]);
-        self.functions.insert("ffi-free".to_string(), vec![LoadArg(0), FfiFree, Ret]);
+        match name {
+            // Arithmetic and binary bitwise operations (two or more args, folded from the left
+            // like the inline forms, so (apply + 1 2 '(3 4)) sees every argument)
+            "+" | "-" | "*" | "/" | "bit-and" | "bit-or" | "bit-xor" | "<<" | ">>" => {
+                let op = match name {
+                    "+" => Add,
+                    "-" => Sub,
+                    "*" => Mul,
+                    "/" => Div,
+                    "bit-and" => BitAnd,
+                    "bit-or" => BitOr,
+                    "bit-xor" => BitXor,
+                    "<<" => Shl,
+                    _ => Shr,
+                };
+                vec![
+                    PackRestArgs(2),
+                    Push(Value::Function(Arc::new(name.to_string()))),
+                    LoadArg(0), LoadArg(1), op,
+                    LoadArg(2), Reduce, Ret,
+                ]
+            }
+            "%" => vec![LoadArg(0), LoadArg(1), Mod, Ret],
+            // Scheme-style integer division: remainder is % under its Scheme name
+            "quotient" => vec![LoadArg(0), LoadArg(1), Quotient, Ret],
+            "remainder" => vec![LoadArg(0), LoadArg(1), Mod, Ret],
+            "modulo" => vec![LoadArg(0), LoadArg(1), Modulo, Ret],
+            "gcd" => vec![LoadArg(0), LoadArg(1), Gcd, Ret],
+            "lcm" => vec![LoadArg(0), LoadArg(1), Lcm, Ret],
+            // Bitwise operations
+            "bit-not" => vec![LoadArg(0), BitNot, Ret],
+            // Arithmetic operations (unary)
+            "neg" => vec![LoadArg(0), Neg, Ret],
+            "inc" => vec![LoadArg(0), Push(Value::Integer(1)), Add, Ret],
+            "dec" => vec![LoadArg(0), Push(Value::Integer(1)), Sub, Ret],
+
+            // Comparison operations
+            "<=" => vec![LoadArg(0), LoadArg(1), Leq, Ret],
+            "<" => vec![LoadArg(0), LoadArg(1), Lt, Ret],
+            ">" => vec![LoadArg(0), LoadArg(1), Gt, Ret],
+            ">=" => vec![LoadArg(0), LoadArg(1), Gte, Ret],
+            "==" => vec![LoadArg(0), LoadArg(1), Eq, Ret],
+            "!=" => vec![LoadArg(0), LoadArg(1), Neq, Ret],
+            "equal?" => vec![LoadArg(0), LoadArg(1), Equal, Ret],
+            "eq?" => vec![LoadArg(0), LoadArg(1), EqIdentity, Ret],
+
+            // List operations
+            "cons" => vec![LoadArg(0), LoadArg(1), Cons, Ret],
+            "car" => vec![LoadArg(0), Car, Ret],
+            "cdr" => vec![LoadArg(0), Cdr, Ret],
+            // (uncons lst) -> (head . tail), erroring on the empty list
+            "uncons" => vec![LoadArg(0), Uncons, Cons, Ret],
+            "list?" => vec![LoadArg(0), IsList, Ret],
+            "append" => vec![PackRestArgs(0), LoadArg(0), AppendList, Ret],
+            "list-ref" => vec![LoadArg(0), LoadArg(1), ListRef, Ret],
+            "list-length" => vec![LoadArg(0), ListLength, Ret],
+            "list-copy" => vec![LoadArg(0), ListCopy, Ret],
+            "shares-structure?" => vec![LoadArg(0), LoadArg(1), SharesStructure, Ret],
+            "null?" => vec![LoadArg(0), IsNull, Ret],
+            "nil?" => vec![LoadArg(0), IsNull, Ret],
+            "map" => vec![PackRestArgs(2), LoadArg(0), LoadArg(1), LoadArg(2), Map, Ret],
+            "filter" => vec![LoadArg(0), LoadArg(1), Filter, Ret],
+            "reduce" => vec![LoadArg(0), LoadArg(1), LoadArg(2), Reduce, Ret],
+            "foldr" => vec![LoadArg(0), LoadArg(1), LoadArg(2), FoldRight, Ret],
+            "sort" => vec![LoadArg(0), LoadArg(1), Sort, Ret],
+            "member?" => vec![LoadArg(0), LoadArg(1), Member, Ret],
+            "assoc" => vec![LoadArg(0), LoadArg(1), Assoc, Ret],
+            "assoc-set" => vec![LoadArg(0), LoadArg(1), LoadArg(2), AssocSet, Ret],
+            "assoc-update" => vec![LoadArg(0), LoadArg(1), LoadArg(2), AssocUpdate, Ret],
+            "reverse" => vec![LoadArg(0), Reverse, Ret],
+            "take" => vec![LoadArg(0), LoadArg(1), Take, Ret],
+            "drop" => vec![LoadArg(0), LoadArg(1), Drop, Ret],
+            "zip" => vec![LoadArg(0), LoadArg(1), Zip, Ret],
+            "unzip" => vec![LoadArg(0), Unzip, Ret],
+            "enumerate" => vec![LoadArg(0), Enumerate, Ret],
+            "flatten" => vec![LoadArg(0), Flatten, Ret],
+            "remove-duplicates" => vec![LoadArg(0), RemoveDuplicates, Ret],
+            "cons*" => vec![PackRestArgs(0), LoadArg(0), ListStar, Ret],
+            "list*" => vec![PackRestArgs(0), LoadArg(0), ListStar, Ret],
+            "range" => vec![LoadArg(0), LoadArg(1), Push(Value::Integer(1)), Range, Ret],
+
+            // Type predicates
+            "integer?" => vec![LoadArg(0), IsInteger, Ret],
+            "bigint?" => vec![LoadArg(0), IsBigInt, Ret],
+            "float?" => vec![LoadArg(0), IsFloat, Ret],
+            "nan?" => vec![LoadArg(0), IsNaN, Ret],
+            "infinite?" => vec![LoadArg(0), IsInfinite, Ret],
+            "number?" => vec![LoadArg(0), IsNumber, Ret], // int, bigint or float
+            "boolean?" => vec![LoadArg(0), IsBoolean, Ret],
+            "function?" => vec![LoadArg(0), IsFunction, Ret],
+            "closure?" => vec![LoadArg(0), IsClosure, Ret],
+            "procedure?" => vec![LoadArg(0), IsProcedure, Ret],
+
+            // String operations
+            "string?" => vec![LoadArg(0), IsString, Ret],
+            "symbol?" => vec![LoadArg(0), IsSymbol, Ret],
+            "symbol->string" => vec![LoadArg(0), SymbolToString, Ret],
+            "string->symbol" => vec![LoadArg(0), StringToSymbol, Ret],
+            "symbol-namespace" => vec![LoadArg(0), SymbolNamespace, Ret],
+            "symbol-name" => vec![LoadArg(0), SymbolName, Ret],
+            "qualified-symbol?" => vec![LoadArg(0), IsQualifiedSymbol, Ret],
+            "make-qualified-symbol" => vec![LoadArg(0), LoadArg(1), MakeQualifiedSymbol, Ret],
+            "string-length" => vec![LoadArg(0), StringLength, Ret],
+            "string-ref" => vec![LoadArg(0), LoadArg(1), StringRef, Ret],
+            "substring" => vec![LoadArg(0), LoadArg(1), LoadArg(2), Substring, Ret],
+            "string-append" => vec![PackRestArgs(0), LoadArg(0), StringAppendList, Ret],
+            "string->list" => vec![LoadArg(0), StringToList, Ret],
+            "list->string" => vec![LoadArg(0), ListToString, Ret],
+            "for-each-char" => vec![LoadArg(0), LoadArg(1), ForEachChar, Ret],
+            "string-map" => vec![LoadArg(0), LoadArg(1), StringMap, Ret],
+            "string-filter" => vec![LoadArg(0), LoadArg(1), StringFilter, Ret],
+            "char-code" => vec![LoadArg(0), CharCode, Ret],
+            "integer->char" => vec![LoadArg(0), IntToChar, Ret],
+            "char-alphabetic?" => vec![LoadArg(0), CharAlphabetic, Ret],
+            "char-numeric?" => vec![LoadArg(0), CharNumeric, Ret],
+            "char-whitespace?" => vec![LoadArg(0), CharWhitespace, Ret],
+            "char-upcase" => vec![LoadArg(0), CharUpcase, Ret],
+            "char-downcase" => vec![LoadArg(0), CharDowncase, Ret],
+            "number->string" => vec![PackRestArgs(1), LoadArg(0), LoadArg(1), NumberToString, Ret],
+            "format-int" => vec![PackRestArgs(1), LoadArg(0), LoadArg(1), FormatInt, Ret],
+            "string->number" => vec![PackRestArgs(1), LoadArg(0), LoadArg(1), StringToNumber, Ret],
+            "string-split" => vec![LoadArg(0), LoadArg(1), StringSplit, Ret],
+            "string-join" => vec![LoadArg(0), LoadArg(1), StringJoin, Ret],
+            "string-trim" => vec![LoadArg(0), StringTrim, Ret],
+            "string-replace" => vec![LoadArg(0), LoadArg(1), LoadArg(2), StringReplace, Ret],
+            // String predicates and utilities
+            "string-starts-with?" => vec![LoadArg(0), LoadArg(1), StringStartsWith, Ret],
+            "string-ends-with?" => vec![LoadArg(0), LoadArg(1), StringEndsWith, Ret],
+            "string-contains?" => vec![LoadArg(0), LoadArg(1), StringContains, Ret],
+            "string-index-of" => vec![LoadArg(0), LoadArg(1), StringIndexOf, Ret],
+            // Aliases for the starts/ends-with predicates; same (string, affix) argument order
+            "string-prefix?" => vec![LoadArg(0), LoadArg(1), StringStartsWith, Ret],
+            "string-suffix?" => vec![LoadArg(0), LoadArg(1), StringEndsWith, Ret],
+            "string-upcase" => vec![LoadArg(0), StringUpcase, Ret],
+            "string-downcase" => vec![LoadArg(0), StringDowncase, Ret],
+            "format" => vec![PackRestArgs(1), LoadArg(0), LoadArg(1), Format, Ret],
+
+            // File I/O operations
+            "read-file" => vec![LoadArg(0), ReadFile, Ret],
+            "write-file" => vec![LoadArg(0), LoadArg(1), WriteFile, Ret],
+            "file-exists?" => vec![LoadArg(0), FileExists, Ret],
+            "write-binary-file" => vec![LoadArg(0), LoadArg(1), WriteBinaryFile, Ret],
+            "load" => vec![LoadArg(0), LoadFile, Ret],
+            "require" => vec![LoadArg(0), RequireFile, Ret],
+
+            // Date/Time operations
+            "current-timestamp" => vec![CurrentTimestamp, Ret],
+            "format-timestamp" => vec![LoadArg(0), LoadArg(1), FormatTimestamp, Ret],
+
+            // Logging
+            "log-debug" => vec![LoadArg(0), LogMessage(LogLevel::Debug), Ret],
+            "log-info" => vec![LoadArg(0), LogMessage(LogLevel::Info), Ret],
+            "log-warn" => vec![LoadArg(0), LogMessage(LogLevel::Warn), Ret],
+            "log-error" => vec![LoadArg(0), LogMessage(LogLevel::Error), Ret],
+            "set-log-level" => vec![LoadArg(0), SetLogLevel, Ret],
+
+            // Other operations
+            "get-args" => vec![GetArgs, Ret],
+            "print" => vec![PackRestArgs(0), LoadArg(0), PrintList, Ret],
+            "display" => vec![PackRestArgs(0), LoadArg(0), DisplayList, Ret],
+            "raise" => vec![LoadArg(0), Raise, Ret],
+            "apply" => vec![LoadArg(0), LoadArg(1), Apply, Ret],
+
+            // HashMap operations
+            "hashmap?" => vec![LoadArg(0), IsHashMap, Ret],
+            "hashmap-get" => vec![LoadArg(0), LoadArg(1), HashMapGet, Ret],
+            "hashmap-set" => vec![LoadArg(0), LoadArg(1), LoadArg(2), HashMapSet, Ret],
+            "hashmap-keys" => vec![LoadArg(0), HashMapKeys, Ret],
+            "hashmap-values" => vec![LoadArg(0), HashMapValues, Ret],
+            "hashmap-contains-key?" => vec![LoadArg(0), LoadArg(1), HashMapContainsKey, Ret],
+
+            // Vector operations
+            "vector?" => vec![LoadArg(0), IsVector, Ret],
+            "vector-ref" => vec![LoadArg(0), LoadArg(1), VectorGet, Ret],
+            "vector-set" => vec![LoadArg(0), LoadArg(1), LoadArg(2), VectorSet, Ret],
+            "vector-push" => vec![LoadArg(0), LoadArg(1), VectorPush, Ret],
+            "vector-pop" => vec![LoadArg(0), VectorPop, Ret],
+            "vector-length" => vec![LoadArg(0), VectorLength, Ret],
+            "vector-map" => vec![LoadArg(0), LoadArg(1), VectorMap, Ret],
+            "vector-for-each" => vec![LoadArg(0), LoadArg(1), VectorForEach, Ret],
+
+            // Type conversions
+            "list->vector" => vec![LoadArg(0), ListToVector, Ret],
+            "vector->list" => vec![LoadArg(0), VectorToList, Ret],
+            "deep-list->vector" => vec![LoadArg(0), DeepListToVector, Ret],
+            "deep-vector->list" => vec![LoadArg(0), DeepVectorToList, Ret],
+            "int->float" => vec![LoadArg(0), IntToFloat, Ret],
+            "float->int" => vec![LoadArg(0), FloatToInt, Ret],
+
+            // Math functions
+            "sqrt" => vec![LoadArg(0), Sqrt, Ret],
+            "sin" => vec![LoadArg(0), Sin, Ret],
+            "cos" => vec![LoadArg(0), Cos, Ret],
+            "tan" => vec![LoadArg(0), Tan, Ret],
+            "atan" => vec![LoadArg(0), Atan, Ret],
+            "atan2" => vec![LoadArg(0), LoadArg(1), Atan2, Ret],
+            "log" => vec![LoadArg(0), Log, Ret],
+            "exp" => vec![LoadArg(0), Exp, Ret],
+            "floor" => vec![LoadArg(0), Floor, Ret],
+            "ceil" => vec![LoadArg(0), Ceil, Ret],
+            "round" => vec![LoadArg(0), Round, Ret],
+            "abs" => vec![LoadArg(0), Abs, Ret],
+            "pow" => vec![LoadArg(0), LoadArg(1), Pow, Ret],
+            "expt" => vec![LoadArg(0), LoadArg(1), IExpt, Ret],
+            "random" => vec![Random, Ret],
+            "random-int" => vec![LoadArg(0), RandomInt, Ret],
+            "seed-random" => vec![LoadArg(0), SeedRandom, Ret],
+
+            // Metaprogramming
+            "eval" => vec![LoadArg(0), Eval, Ret],
+
+            // Reflection - Function Introspection
+            "function-arity" => vec![LoadArg(0), FunctionArity, Ret],
+            "function-params" => vec![LoadArg(0), FunctionParams, Ret],
+            "closure-captured" => vec![LoadArg(0), ClosureCaptured, Ret],
+            "function-name" => vec![LoadArg(0), FunctionName, Ret],
+            "module-exports" => vec![LoadArg(0), ModuleExports, Ret],
+            "module-list" => vec![ModuleList, Ret],
+
+            // Type inspection
+            "type-of" => vec![LoadArg(0), TypeOf, Ret],
+
+            // Symbol generation
+            "gensym" => vec![PackRestArgs(0), LoadArg(0), GenSym, Ret],
+
+            // Parallel Collections (Phase 12a)
+            "pmap" => vec![LoadArg(0), LoadArg(1), PMap, Ret],
+            "pfilter" => vec![LoadArg(0), LoadArg(1), PFilter, Ret],
+            "preduce" => vec![LoadArg(0), LoadArg(1), LoadArg(2), PReduce, Ret],
+
+            // HTTP/Networking (Phase 14)
+            "http-listen" => vec![LoadArg(0), HttpListen, Ret],
+            "http-accept" => vec![LoadArg(0), HttpAccept, Ret],
+            "http-read-request" => vec![LoadArg(0), HttpReadRequest, Ret],
+            "http-send-response" => vec![LoadArg(0), LoadArg(1), HttpSendResponse, Ret],
+            "http-close" => vec![LoadArg(0), HttpClose, Ret],
+
+            // Multi-threaded HTTP (Phase 14b)
+            "http-listen-shared" => vec![LoadArg(0), HttpListenShared, Ret],
+            "http-serve-parallel" => vec![LoadArg(0), LoadArg(1), LoadArg(2), LoadArg(3), HttpServeParallel, Ret],
+
+            // FFI (Foreign Function Interface) - Phase 21
+            "ffi-load" => vec![LoadArg(0), FfiLoadLibrary, Ret],
+            "ffi-symbol" => vec![LoadArg(0), LoadArg(1), FfiGetSymbol, Ret],
+            "ffi-pointer->string" => vec![LoadArg(0), FfiPointerToString, Ret],
+            "ffi-string->pointer" => vec![LoadArg(0), FfiStringToPointer, Ret],
+            "ffi-free-string" => vec![LoadArg(0), FfiFreeString, Ret],
+            "ffi-null" => vec![FfiNullPointer, Ret],
+            "ffi-null?" => vec![LoadArg(0), FfiPointerNull, Ret],
+            "pointer?" => vec![LoadArg(0), IsPointer, Ret],
+            "ffi-pointer+" => vec![LoadArg(0), LoadArg(1), FfiPointerAdd, Ret],
+            "ffi-read-int" => vec![LoadArg(0), FfiReadInt, Ret],
+            "ffi-write-int" => vec![LoadArg(0), LoadArg(1), FfiWriteInt, Ret],
+            "ffi-read-float" => vec![LoadArg(0), FfiReadFloat, Ret],
+            "ffi-write-float" => vec![LoadArg(0), LoadArg(1), FfiWriteFloat, Ret],
+            "ffi-read-byte" => vec![LoadArg(0), FfiReadByte, Ret],
+            "ffi-write-byte" => vec![LoadArg(0), LoadArg(1), FfiWriteByte, Ret],
+            "ffi-allocate" => vec![LoadArg(0), FfiAllocate, Ret],
+            "ffi-free" => vec![LoadArg(0), FfiFree, Ret],
+            _ => unreachable!("no bytecode for builtin '{}'", name),
+        }
     }
 
     pub fn execute_one_instruction(&mut self) -> Result<(), RuntimeError> {
@@ -225,6 +405,9 @@ impl VM {
             self.halted = true;
             return Ok(());
         }
+        if self.trace_bytecode {
+            self.trace_instruction(ip);
+        }
 
         // Match on reference to avoid cloning every instruction.
         // For instructions with payloads, clone only the data we need.
@@ -234,12 +417,18 @@ impl VM {
                 self.value_stack.push(v);
                 self.instruction_pointer += 1;
             }
+            // Arithmetic result types: integer op integer stays an integer,
+            // and any float operand promotes the result to a float. Integer
+            // '/' truncates toward zero and '%' takes the sign of the
+            // dividend; a zero divisor is an error for both kinds.
             Instruction::Add => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Add operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Add operation".to_string()))?;
+                self.check_strict_coercion("+", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
-                        self.value_stack.push(Value::Integer(x + y));
+                        let sum = self.integer_result("+", x.checked_add(*y), || &BigInt::from(*x) + &BigInt::from(*y))?;
+                        self.value_stack.push(sum);
                     }
                     (Value::Float(x), Value::Float(y)) => {
                         self.value_stack.push(Value::Float(x + y));
@@ -250,6 +439,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Float(x + *y as f64));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let result = Self::bigint_arith("+", &a, &b)?;
+                        self.value_stack.push(result);
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '+' expects two numbers, got {} and {}",
@@ -263,9 +456,11 @@ impl VM {
             Instruction::Sub => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Sub operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Sub operation".to_string()))?;
+                self.check_strict_coercion("-", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
-                        self.value_stack.push(Value::Integer(x - y));
+                        let difference = self.integer_result("-", x.checked_sub(*y), || &BigInt::from(*x) - &BigInt::from(*y))?;
+                        self.value_stack.push(difference);
                     }
                     (Value::Float(x), Value::Float(y)) => {
                         self.value_stack.push(Value::Float(x - y));
@@ -276,6 +471,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Float(x - *y as f64));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let result = Self::bigint_arith("-", &a, &b)?;
+                        self.value_stack.push(result);
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '-' expects two numbers, got {} and {}",
@@ -289,9 +488,11 @@ impl VM {
             Instruction::Mul => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Mul operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Mul operation".to_string()))?;
+                self.check_strict_coercion("*", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
-                        self.value_stack.push(Value::Integer(x * y));
+                        let product = self.integer_result("*", x.checked_mul(*y), || &BigInt::from(*x) * &BigInt::from(*y))?;
+                        self.value_stack.push(product);
                     }
                     (Value::Float(x), Value::Float(y)) => {
                         self.value_stack.push(Value::Float(x * y));
@@ -302,6 +503,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Float(x * *y as f64));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let result = Self::bigint_arith("*", &a, &b)?;
+                        self.value_stack.push(result);
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '*' expects two numbers, got {} and {}",
@@ -315,6 +520,7 @@ impl VM {
             Instruction::Div => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Div operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Div operation".to_string()))?;
+                self.check_strict_coercion("/", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         if *y == 0 {
@@ -323,7 +529,9 @@ impl VM {
                                 "Check your divisor before dividing. You can use an if-expression to handle zero cases: (if (== y 0) 0 (/ x y))".to_string(),
                             ));
                         }
-                        self.value_stack.push(Value::Integer(x / y));
+                        // Only i64::MIN / -1 overflows once zero is ruled out, and its quotient is -x
+                        let q = self.integer_result("/", x.checked_div(*y), || -&BigInt::from(*x))?;
+                        self.value_stack.push(q);
                     }
                     (Value::Float(x), Value::Float(y)) => {
                         if *y == 0.0 {
@@ -352,6 +560,10 @@ impl VM {
                         }
                         self.value_stack.push(Value::Float(x / *y as f64));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let result = Self::bigint_arith("/", &a, &b)?;
+                        self.value_stack.push(result);
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '/' expects two numbers, got {} and {}",
@@ -365,6 +577,7 @@ impl VM {
             Instruction::Mod => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Mod operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Mod operation".to_string()))?;
+                self.check_strict_coercion("%", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         if *y == 0 {
@@ -373,7 +586,8 @@ impl VM {
                                 "Check your divisor before using modulo. You can use an if-expression: (if (== y 0) 0 (% x y))".to_string(),
                             ));
                         }
-                        self.value_stack.push(Value::Integer(x % y));
+                        // wrapping_rem only wraps for i64::MIN by -1, where the answer is 0
+                        self.value_stack.push(Value::Integer(x.wrapping_rem(*y)));
                     }
                     (Value::Float(x), Value::Float(y)) => {
                         if *y == 0.0 {
@@ -402,6 +616,10 @@ impl VM {
                         }
                         self.value_stack.push(Value::Float(x % (*y as f64)));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let result = Self::bigint_arith("%", &a, &b)?;
+                        self.value_stack.push(result);
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '%' expects two numbers, got {} and {}",
@@ -416,7 +634,11 @@ impl VM {
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Neg operation".to_string()))?;
                 match &a {
                     Value::Integer(x) => {
-                        self.value_stack.push(Value::Integer(-x));
+                        let negated = self.integer_result("neg", x.checked_neg(), || -&BigInt::from(*x))?;
+                        self.value_stack.push(negated);
+                    }
+                    Value::BigInt(x) => {
+                        self.value_stack.push(Value::from_bigint(-x.as_ref()));
                     }
                     Value::Float(x) => {
                         self.value_stack.push(Value::Float(-x));
@@ -430,9 +652,136 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::Gcd | Instruction::Lcm => {
+                let lcm = matches!(self.current_bytecode[ip], Instruction::Lcm);
+                let name = if lcm { "lcm" } else { "gcd" };
+                let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+                let b = self.value_stack.pop().ok_or_else(underflow)?;
+                let a = self.value_stack.pop().ok_or_else(underflow)?;
+                let result = match (&a, &b) {
+                    (Value::Integer(x), Value::Integer(y)) => {
+                        // Work on magnitudes in u128 so gcd(i64::MIN, 0) and the
+                        // lcm product can't overflow before the range check
+                        let (x, y) = (x.unsigned_abs() as u128, y.unsigned_abs() as u128);
+                        let g = Self::gcd_u128(x, y);
+                        let r = if lcm { x.checked_div(g).map_or(0, |q| q * y) } else { g };
+                        self.integer_result(name, i64::try_from(r).ok(), || BigInt::from(r))?
+                    }
+                    _ => match (Self::as_exact_integer(&a), Self::as_exact_integer(&b)) {
+                        (Some(x), Some(y)) => Value::from_bigint(Self::bigint_gcd_lcm(x, y, lcm)),
+                        _ => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: '{}' expects two integers, got {} and {}",
+                                name,
+                                Self::type_name(&a),
+                                Self::type_name(&b)
+                            )));
+                        }
+                    },
+                };
+                self.value_stack.push(result);
+                self.instruction_pointer += 1;
+            }
+            // Bit operations on the two's complement form when either operand is a bigint
+            Instruction::BitAnd | Instruction::BitOr | Instruction::BitXor | Instruction::Shl | Instruction::Shr
+                if self.value_stack.iter().rev().take(2).any(Value::is_bigint) =>
+            {
+                let name = match self.current_bytecode[ip] {
+                    Instruction::BitAnd => "bit-and",
+                    Instruction::BitOr => "bit-or",
+                    Instruction::BitXor => "bit-xor",
+                    Instruction::Shl => "<<",
+                    _ => ">>",
+                };
+                self.bigint_bitwise(name)?;
+            }
+            Instruction::BitAnd => {
+                let (a, b) = self.pop_integer_operands("bit-and")?;
+                self.value_stack.push(Value::Integer(a & b));
+                self.instruction_pointer += 1;
+            }
+            Instruction::BitOr => {
+                let (a, b) = self.pop_integer_operands("bit-or")?;
+                self.value_stack.push(Value::Integer(a | b));
+                self.instruction_pointer += 1;
+            }
+            Instruction::BitXor => {
+                let (a, b) = self.pop_integer_operands("bit-xor")?;
+                self.value_stack.push(Value::Integer(a ^ b));
+                self.instruction_pointer += 1;
+            }
+            Instruction::BitNot => {
+                let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in BitNot operation".to_string()))?;
+                match a {
+                    Value::Integer(x) => self.value_stack.push(Value::Integer(!x)),
+                    Value::BigInt(x) => self.value_stack.push(Value::from_bigint(!x.as_ref())),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'bit-not' expects an integer, got {}",
+                            Self::type_name(&a)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::Shl => self.shift(true)?,
+            Instruction::Shr => self.shift(false)?,
+            // Exact quotient or modulo when either operand is a bigint
+            Instruction::Quotient | Instruction::Modulo if self.value_stack.iter().rev().take(2).any(Value::is_bigint) => {
+                let floored = matches!(self.current_bytecode[ip], Instruction::Modulo);
+                let name = if floored { "modulo" } else { "quotient" };
+                let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+                let b = self.value_stack.pop().ok_or_else(underflow)?;
+                let a = self.value_stack.pop().ok_or_else(underflow)?;
+                let (x, y) = match (Self::as_exact_integer(&a), Self::as_exact_integer(&b)) {
+                    (Some(x), Some(y)) => (x, y),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: '{}' expects two integers, got {} and {}",
+                            name,
+                            Self::type_name(&a),
+                            Self::type_name(&b)
+                        )));
+                    }
+                };
+                // A bigint is never zero, so only an integer divisor can be
+                if let Value::Integer(divisor) = b {
+                    Self::check_integer_divisor(name, divisor)?;
+                }
+                let (q, r) = x.div_rem(&y).ok_or_else(|| RuntimeError::new(format!("Division by zero in '{}'", name)))?;
+                let result = if !floored {
+                    q
+                } else if !r.is_zero() && r.is_negative() != y.is_negative() {
+                    // Move the truncated remainder into the divisor's sign
+                    &r + &y
+                } else {
+                    r
+                };
+                self.value_stack.push(Value::from_bigint(result));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Quotient => {
+                let (a, b) = self.pop_integer_operands("quotient")?;
+                Self::check_integer_divisor("quotient", b)?;
+                // Only i64::MIN / -1 overflows once zero is ruled out, and its quotient is -a
+                let q = self.integer_result("quotient", a.checked_div(b), || -&BigInt::from(a))?;
+                self.value_stack.push(q);
+                self.instruction_pointer += 1;
+            }
+            Instruction::Modulo => {
+                let (a, b) = self.pop_integer_operands("modulo")?;
+                Self::check_integer_divisor("modulo", b)?;
+                // Truncated remainder, moved into the divisor's sign when they differ.
+                // wrapping_rem only wraps for i64::MIN by -1, where the answer is 0.
+                let r = a.wrapping_rem(b);
+                let m = if r != 0 && (r < 0) != (b < 0) { r + b } else { r };
+                self.value_stack.push(Value::Integer(m));
+                self.instruction_pointer += 1;
+            }
             Instruction::Leq => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Leq operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Leq operation".to_string()))?;
+                Self::check_orderable("<=", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(x <= y));
@@ -446,6 +795,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(*x <= (*y as f64)));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let ordering = Self::compare_bigint_operands("<=", &a, &b)?;
+                        self.value_stack.push(Value::Boolean(matches!(ordering, Ordering::Less | Ordering::Equal)));
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '<=' expects two numbers, got {} and {}",
@@ -459,6 +812,7 @@ impl VM {
             Instruction::Lt => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Lt operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Lt operation".to_string()))?;
+                Self::check_orderable("<", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(x < y));
@@ -472,6 +826,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(*x < (*y as f64)));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let ordering = Self::compare_bigint_operands("<", &a, &b)?;
+                        self.value_stack.push(Value::Boolean(matches!(ordering, Ordering::Less)));
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '<' expects two numbers, got {} and {}",
@@ -485,6 +843,7 @@ impl VM {
             Instruction::Gt => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Gt operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Gt operation".to_string()))?;
+                Self::check_orderable(">", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(x > y));
@@ -498,6 +857,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(*x > (*y as f64)));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let ordering = Self::compare_bigint_operands(">", &a, &b)?;
+                        self.value_stack.push(Value::Boolean(matches!(ordering, Ordering::Greater)));
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '>' expects two numbers, got {} and {}",
@@ -511,6 +874,7 @@ impl VM {
             Instruction::Gte => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Gte operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Gte operation".to_string()))?;
+                Self::check_orderable(">=", &a, &b)?;
                 match (&a, &b) {
                     (Value::Integer(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(x >= y));
@@ -524,6 +888,10 @@ impl VM {
                     (Value::Float(x), Value::Integer(y)) => {
                         self.value_stack.push(Value::Boolean(*x >= (*y as f64)));
                     }
+                    (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                        let ordering = Self::compare_bigint_operands(">=", &a, &b)?;
+                        self.value_stack.push(Value::Boolean(matches!(ordering, Ordering::Greater | Ordering::Equal)));
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: '>=' expects two numbers, got {} and {}",
@@ -537,15 +905,21 @@ impl VM {
             Instruction::Eq => {
                 let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Eq operation".to_string()))?;
                 let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Eq operation".to_string()))?;
-                // Handle numeric type coercion for equality
-                let result = match (&a, &b) {
-                    (Value::Integer(x), Value::Integer(y)) => x == y,
-                    (Value::Float(x), Value::Float(y)) => x == y,
-                    (Value::Integer(x), Value::Float(y)) => *x as f64 == *y,
-                    (Value::Float(x), Value::Integer(y)) => *x == *y as f64,
-                    _ => a == b, // For non-numeric types, use standard PartialEq
-                };
-                self.value_stack.push(Value::Boolean(result));
+                self.value_stack.push(Value::Boolean(Self::values_equal(&a, &b)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Equal => {
+                let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Equal operation".to_string()))?;
+                let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Equal operation".to_string()))?;
+                // Value's PartialEq walks lists, vectors and hashmaps element-wise and
+                // never coerces numbers, so (equal? 1 1.0) is false where (== 1 1.0) is true
+                self.value_stack.push(Value::Boolean(a == b));
+                self.instruction_pointer += 1;
+            }
+            Instruction::EqIdentity => {
+                let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in EqIdentity operation".to_string()))?;
+                let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in EqIdentity operation".to_string()))?;
+                self.value_stack.push(Value::Boolean(a.is_identical(&b)));
                 self.instruction_pointer += 1;
             }
             Instruction::Neq => {
@@ -557,6 +931,7 @@ impl VM {
                     (Value::Float(x), Value::Float(y)) => x != y,
                     (Value::Integer(x), Value::Float(y)) => *x as f64 != *y,
                     (Value::Float(x), Value::Integer(y)) => *x != *y as f64,
+                    (Value::BigInt(_), Value::Float(_)) | (Value::Float(_), Value::BigInt(_)) => !Self::values_equal(&a, &b),
                     _ => a != b, // For non-numeric types, use standard PartialEq
                 };
                 self.value_stack.push(Value::Boolean(result));
@@ -583,6 +958,24 @@ impl VM {
                     }
                 }
             }
+            Instruction::JmpIfTrue(addr) => {
+                let addr = *addr;
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in JmpIfTrue operation".to_string()))?;
+                match value {
+                    Value::Boolean(true) => {
+                        self.instruction_pointer = addr;
+                    }
+                    Value::Boolean(false) => {
+                        self.instruction_pointer += 1;
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: conditional expects boolean, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                }
+            }
             Instruction::LoadArg(idx) => {
                 let idx = *idx;
                 let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame to load arg from".to_string()))?;
@@ -736,6 +1129,66 @@ impl VM {
                     self.instruction_pointer += 1;
                 }
             }
+            Instruction::CheckContract(message) => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in CheckContract".to_string()))?;
+                match value {
+                    Value::Boolean(true) => {}
+                    Value::Boolean(false) => {
+                        return Err(RuntimeError::new(message.clone()));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: contract condition must be a boolean, got {} ({})",
+                            Self::type_name(&value),
+                            message
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::Assert(message, location) => {
+                // Anything but false passes and stays on the stack as the result
+                match self.value_stack.last() {
+                    Some(Value::Boolean(false)) => {
+                        return Err(RuntimeError::with_location(message.clone(), location.clone()));
+                    }
+                    Some(_) => {}
+                    None => return Err(RuntimeError::new("Stack underflow in Assert".to_string())),
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::PushHandler(addr) => {
+                let handler = Handler {
+                    catch_address: *addr,
+                    bytecode: self.current_bytecode.clone(),
+                    call_depth: self.call_stack.len(),
+                    stack_depth: self.value_stack.len(),
+                };
+                self.handlers.push(handler);
+                self.instruction_pointer += 1;
+            }
+            Instruction::PopHandler => {
+                self.handlers.pop().ok_or_else(|| RuntimeError::new("No handler to pop".to_string()))?;
+                self.instruction_pointer += 1;
+            }
+            Instruction::Raise => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Raise".to_string()))?;
+                let message = Self::value_to_display_string(&value);
+                return Err(RuntimeError::raised(value, message));
+            }
+            Instruction::ArgListLength(idx) => {
+                let idx = *idx;
+                // Computed once at function entry so pattern clauses can compare against it
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for ArgListLength".to_string()))?;
+                // An improper list is not a list to the patterns, as with IsList:
+                // its cached length only counts the elements before the dot
+                let len = match frame.locals.get(idx) {
+                    Some(Value::List(list)) if list.is_proper() => list.len() as i64,
+                    _ => -1,
+                };
+                self.value_stack.push(Value::Integer(len));
+                self.instruction_pointer += 1;
+            }
             Instruction::PackRestArgs(required_count) => {
                 let required_count = *required_count;
                 // Collect args from required_count onwards into a list
@@ -757,6 +1210,120 @@ impl VM {
 
                 self.instruction_pointer += 1;
             }
+            Instruction::JmpIfArgSupplied(idx, jump_addr) => {
+                let idx = *idx;
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for JmpIfArgSupplied".to_string()))?;
+                // Optionals are filled in order, so a shorter list means a required arg is missing
+                if frame.locals.len() < idx {
+                    return Err(RuntimeError::new(format!(
+                        "Not enough arguments: expected at least {}, got {}",
+                        idx,
+                        frame.locals.len()
+                    )));
+                }
+                if frame.locals.len() > idx {
+                    self.instruction_pointer = *jump_addr;
+                } else {
+                    self.instruction_pointer += 1;
+                }
+            }
+            Instruction::BindArg(idx) => {
+                let idx = *idx;
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in BindArg".to_string()))?;
+                let frame = self.call_stack.last_mut().ok_or_else(|| RuntimeError::new("No frame for BindArg".to_string()))?;
+                match idx.cmp(&frame.locals.len()) {
+                    std::cmp::Ordering::Less => frame.locals[idx] = value,
+                    std::cmp::Ordering::Equal => frame.locals.push(value),
+                    std::cmp::Ordering::Greater => {
+                        return Err(RuntimeError::new(format!("Arg index {} out of bounds", idx)));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::CheckMaxArgs(max) => {
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for CheckMaxArgs".to_string()))?;
+                if frame.locals.len() > *max {
+                    return Err(RuntimeError::new(format!(
+                        "Too many arguments: expected at most {}, got {}",
+                        max,
+                        frame.locals.len()
+                    )));
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::BindKeyArgs(first_key, keys) => {
+                let first_key = *first_key;
+                let frame = self.call_stack.last_mut().ok_or_else(|| RuntimeError::new("No frame for BindKeyArgs".to_string()))?;
+                if frame.locals.len() < first_key {
+                    return Err(RuntimeError::new(format!(
+                        "Not enough arguments: expected at least {}, got {}",
+                        first_key,
+                        frame.locals.len()
+                    )));
+                }
+
+                // Everything after the required args must be :key value pairs
+                let fn_name = frame.function_name.clone();
+                let mut values = vec![Value::List(List::Nil); keys.len()];
+                let mut supplied = vec![Value::Boolean(true); first_key];
+                supplied.resize(first_key + keys.len(), Value::Boolean(false));
+                let mut pairs = frame.locals.drain(first_key..).collect::<Vec<_>>().into_iter();
+                while let Some(keyword) = pairs.next() {
+                    let key = match &keyword {
+                        Value::Symbol(s) if s.len() > 1 && s.starts_with(':') => s[1..].to_string(),
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Expected a keyword argument like :name in call to '{}', got {}",
+                                fn_name,
+                                Self::value_to_display_string(other)
+                            )));
+                        }
+                    };
+                    let index = match keys.iter().position(|name| *name == key) {
+                        Some(index) => index,
+                        None => {
+                            let known: Vec<String> = keys.iter().map(|name| format!(":{}", name)).collect();
+                            return Err(RuntimeError::with_suggestion(
+                                format!("Unknown keyword argument :{} in call to '{}'", key, fn_name),
+                                format!("'{}' accepts {}", fn_name, known.join(" ")),
+                            ));
+                        }
+                    };
+                    let value = pairs.next().ok_or_else(|| RuntimeError::new(format!(
+                        "Keyword argument :{} is missing a value",
+                        key
+                    )))?;
+                    if supplied[first_key + index] == Value::Boolean(true) {
+                        return Err(RuntimeError::new(format!("Keyword argument :{} given more than once", key)));
+                    }
+                    values[index] = value;
+                    supplied[first_key + index] = Value::Boolean(true);
+                }
+
+                frame.locals.extend(values);
+                frame.locals.push(Value::Vector(Arc::new(supplied)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::JmpIfKeySupplied(idx, jump_addr) => {
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for JmpIfKeySupplied".to_string()))?;
+                let supplied = match frame.locals.last() {
+                    Some(Value::Vector(flags)) => flags.get(*idx) == Some(&Value::Boolean(true)),
+                    _ => return Err(RuntimeError::new("JmpIfKeySupplied without BindKeyArgs".to_string())),
+                };
+                if supplied {
+                    self.instruction_pointer = *jump_addr;
+                } else {
+                    self.instruction_pointer += 1;
+                }
+            }
+            Instruction::NoMatchingClause(message) => {
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for NoMatchingClause".to_string()))?;
+                return Err(RuntimeError::new(format!(
+                    "{} for arguments ({})",
+                    message,
+                    Self::format_print_line(&frame.locals, false)
+                )));
+            }
             Instruction::MakeClosure(params, body, num_captured) => {
                 let params = params.clone();
                 let body = body.clone();
@@ -817,6 +1384,7 @@ impl VM {
                 self.instruction_pointer += 1;
             }
             Instruction::CallClosure(arg_count) => {
+                self.check_call_depth()?;
                 let arg_count = *arg_count;
                 // Pop arguments from stack (in reverse order)
                 let mut args = Vec::new();
@@ -852,32 +1420,7 @@ impl VM {
                         self.instruction_pointer = 0;
                     }
                     Value::Closure(ref closure_data) => {
-                        // Verify arity
-                        match &closure_data.rest_param {
-                            None => {
-                                // Regular closure - exact arity match required
-                                if closure_data.params.len() != args.len() {
-                                    return Err(RuntimeError::new(format!(
-                                        "Closure arity mismatch: expected {} argument(s), got {}",
-                                        closure_data.params.len(),
-                                        args.len()
-                                    )));
-                                }
-                            }
-                            Some(_rest_name) => {
-                                // Variadic closure - need at least the required params
-                                if args.len() < closure_data.params.len() {
-                                    return Err(RuntimeError::new(format!(
-                                        "Variadic closure arity mismatch: expected at least {} argument(s), got {}",
-                                        closure_data.params.len(),
-                                        args.len()
-                                    )));
-                                }
-                                // Pack extra args into a list and append to args
-                                let rest_args: Vec<Value> = args.drain(closure_data.params.len()..).collect();
-                                args.push(Value::List(List::from_vec(rest_args)));
-                            }
-                        }
+                        let args = Self::closure_locals(closure_data, args)?;
 
                         // Create frame with arguments and captured environment
                         let frame = Frame {
@@ -906,16 +1449,78 @@ impl VM {
                     }
                 }
             }
-            Instruction::Apply => {
+            Instruction::TailCallClosure(arg_count) => {
+                let arg_count = *arg_count;
+                let mut args = Vec::new();
+                for _ in 0..arg_count {
+                    args.push(self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in TailCallClosure".to_string()))?);
+                }
+                args.reverse();
+
+                let callable = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in TailCallClosure".to_string()))?;
+
+                let (function_name, locals, captured, body) = match callable {
+                    Value::Function(ref fn_name) => {
+                        let fn_bytecode = self.functions.get(fn_name.as_str())
+                            .ok_or_else(|| RuntimeError::new(format!("Undefined function '{}'", fn_name)))?
+                            .clone();
+                        (fn_name.to_string(), args, Vec::new(), fn_bytecode)
+                    }
+                    Value::Closure(ref closure_data) => (
+                        "<closure>".to_string(),
+                        Self::closure_locals(closure_data, args)?,
+                        closure_data.captured.iter().map(|(_, v)| v.clone()).collect(),
+                        closure_data.body.clone(),
+                    ),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: expected function or closure, got {}",
+                            Self::type_name(&callable)
+                        )));
+                    }
+                };
+
+                // Reuse the current frame like TailCall, so the stack stays flat
+                if let Some(frame) = self.call_stack.last_mut() {
+                    self.value_stack.truncate(frame.stack_base);
+                    frame.locals = locals;
+                    frame.captured = captured;
+                    frame.function_name = function_name;
+                    frame.loop_start = None;
+                    frame.loop_bindings_start = None;
+                    frame.loop_bindings_count = None;
+                } else {
+                    // No frame exists (top-level call), treat as regular call
+                    self.call_stack.push(Frame {
+                        return_address: self.instruction_pointer + 1,
+                        locals,
+                        return_bytecode: self.current_bytecode.clone(),
+                        function_name,
+                        captured,
+                        stack_base: self.value_stack.len(),
+                        loop_start: None,
+                        loop_bindings_start: None,
+                        loop_bindings_count: None,
+                    });
+                }
+
+                self.current_bytecode = body;
+                self.instruction_pointer = 0;
+            }
+            Instruction::Apply | Instruction::ApplyN(_) => {
+                self.check_call_depth()?;
                 // Apply function to a list of arguments
-                // Stack: ... <function/closure> <list> (top)
+                // Stack: ... <function/closure> <fixed args of ApplyN...> <list> (top)
 
                 // Pop the argument list
                 let arg_list = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Apply".to_string()))?;
 
                 // Extract arguments from list
-                let args = match arg_list {
-                    Value::List(list) => list.to_vec(),
+                let list_args = match arg_list {
+                    Value::List(list) => {
+                        Self::require_proper_list("apply", &list)?;
+                        list.to_vec()
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error in apply: expected list of arguments, got {}",
@@ -924,6 +1529,17 @@ impl VM {
                     }
                 };
 
+                // ApplyN's fixed args go in front of the list's elements
+                let fixed_count = match self.current_bytecode[self.instruction_pointer] {
+                    Instruction::ApplyN(count) => count.saturating_sub(1),
+                    _ => 0,
+                };
+                if self.value_stack.len() < fixed_count {
+                    return Err(RuntimeError::new("Stack underflow in Apply".to_string()));
+                }
+                let mut args = self.value_stack.split_off(self.value_stack.len() - fixed_count);
+                args.extend(list_args);
+
                 // Pop the function/closure
                 let callable = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Apply".to_string()))?;
 
@@ -1016,6 +1632,29 @@ impl VM {
                 self.value_stack.push(value);
                 self.instruction_pointer += 1;
             }
+            Instruction::LetrecFill(n) => {
+                let n = *n;
+                if self.value_stack.len() < n {
+                    return Err(RuntimeError::new("Stack underflow in LetrecFill".to_string()));
+                }
+                let templates = self.value_stack.split_off(self.value_stack.len() - n);
+                for template in &templates {
+                    let tied = Self::tie_letrec(template, &templates)?;
+                    self.value_stack.push(tied);
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::LoadLetrec(idx, group_size) => {
+                let (idx, group_size) = (*idx, *group_size);
+                let frame = self.call_stack.last().ok_or_else(|| RuntimeError::new("No frame for LoadLetrec".to_string()))?;
+                if frame.captured.len() < group_size || idx >= group_size {
+                    return Err(RuntimeError::new(format!("Letrec sibling index {} out of bounds", idx)));
+                }
+                // The group slots of a running member hold the untied templates
+                let tied = Self::tie_letrec(&frame.captured[idx], &frame.captured[..group_size])?;
+                self.value_stack.push(tied);
+                self.instruction_pointer += 1;
+            }
             Instruction::Print => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Print".to_string()))?;
                 println!("{}", Self::format_value(&value));
@@ -1023,12 +1662,15 @@ impl VM {
                 self.value_stack.push(value);
                 self.instruction_pointer += 1;
             }
+            Instruction::PrintList => self.print_list(false)?,
+            Instruction::DisplayList => self.print_list(true)?,
             Instruction::Ret => {
                 let frame = self.call_stack.pop().ok_or_else(|| RuntimeError::new("No frame to return from".to_string()))?;
                 self.current_bytecode = frame.return_bytecode;
                 self.instruction_pointer = frame.return_address;
             }
             Instruction::Call(fn_name, arg_count) => {
+                self.check_call_depth()?;
                 let fn_name = fn_name.clone();
                 let arg_count = *arg_count;
                 let fn_bytecode = self.functions.get(&fn_name)
@@ -1115,10 +1757,10 @@ impl VM {
 
                 // cons creates a list by prepending first to second
                 // (cons 1 '(2 3)) -> '(1 2 3)
-                // (cons 1 2) -> '(1 2) [improper list, to be represented as proper list]
+                // (cons 1 2) -> '(1 . 2) [improper list ending in a dotted atom]
                 let new_list = match second {
                     Value::List(tail) => List::cons(first, tail),
-                    other => List::cons(first, List::cons(other, List::Nil)),
+                    other => List::cons(first, List::Dotted(Box::new(other))),
                 };
                 self.value_stack.push(Value::List(new_list));
                 self.instruction_pointer += 1;
@@ -1129,7 +1771,10 @@ impl VM {
                     Value::List(list) => {
                         match list.car() {
                             Some(head) => self.value_stack.push(head.clone()),
-                            None => return Err(RuntimeError::new("'car' cannot take the first element of an empty list".to_string())),
+                            None => return Err(RuntimeError::with_suggestion(
+                                "'car' cannot take the first element of an empty list".to_string(),
+                                "Check for the empty list with (null? lst) before calling car.".to_string(),
+                            )),
                         }
                     }
                     _ => {
@@ -1145,9 +1790,12 @@ impl VM {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Cdr".to_string()))?;
                 match value {
                     Value::List(list) => {
-                        match list.cdr() {
-                            Some(tail) => self.value_stack.push(Value::List(tail)),
-                            None => return Err(RuntimeError::new("'cdr' cannot take the rest of an empty list".to_string())),
+                        match list.cdr_value() {
+                            Some(tail) => self.value_stack.push(tail),
+                            None => return Err(RuntimeError::with_suggestion(
+                                "'cdr' cannot take the rest of an empty list".to_string(),
+                                "Check for the empty list with (null? lst) before calling cdr.".to_string(),
+                            )),
                         }
                     }
                     _ => {
@@ -1159,27 +1807,74 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::Uncons => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Uncons".to_string()))?;
+                match &value {
+                    Value::List(List::Cons(cell)) => {
+                        self.value_stack.push(cell.head.clone());
+                        self.value_stack.push(cell.tail.to_value());
+                    }
+                    Value::List(List::Nil) => {
+                        return Err(RuntimeError::with_suggestion(
+                            "'uncons' cannot split an empty list".to_string(),
+                            "Check for the empty list with (null? lst) before calling uncons.".to_string(),
+                        ));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'uncons' expects a list, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
             Instruction::IsList => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsList".to_string()))?;
-                let is_list = matches!(value, Value::List(_));
+                // Improper lists like (1 . 2) are pairs, not lists
+                let is_list = matches!(&value, Value::List(list) if list.is_proper());
                 self.value_stack.push(Value::Boolean(is_list));
                 self.instruction_pointer += 1;
             }
+            Instruction::IsNull => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsNull".to_string()))?;
+                // Only the empty list is null; false, 0 and non-lists are not
+                let is_null = matches!(value, Value::List(List::Nil));
+                self.value_stack.push(Value::Boolean(is_null));
+                self.instruction_pointer += 1;
+            }
             Instruction::IsInteger => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsInteger".to_string()))?;
-                let is_integer = matches!(value, Value::Integer(_));
+                let is_integer = matches!(value, Value::Integer(_) | Value::BigInt(_));
                 self.value_stack.push(Value::Boolean(is_integer));
                 self.instruction_pointer += 1;
             }
+            Instruction::IsBigInt => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsBigInt".to_string()))?;
+                self.value_stack.push(Value::Boolean(value.is_bigint()));
+                self.instruction_pointer += 1;
+            }
             Instruction::IsFloat => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsFloat".to_string()))?;
                 let is_float = matches!(value, Value::Float(_));
                 self.value_stack.push(Value::Boolean(is_float));
                 self.instruction_pointer += 1;
             }
+            Instruction::IsNaN => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsNaN".to_string()))?;
+                let is_nan = matches!(value, Value::Float(f) if f.is_nan());
+                self.value_stack.push(Value::Boolean(is_nan));
+                self.instruction_pointer += 1;
+            }
+            Instruction::IsInfinite => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsInfinite".to_string()))?;
+                let is_infinite = matches!(value, Value::Float(f) if f.is_infinite());
+                self.value_stack.push(Value::Boolean(is_infinite));
+                self.instruction_pointer += 1;
+            }
             Instruction::IsNumber => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsNumber".to_string()))?;
-                let is_number = matches!(value, Value::Integer(_) | Value::Float(_));
+                let is_number = value.is_number();
                 self.value_stack.push(Value::Boolean(is_number));
                 self.instruction_pointer += 1;
             }
@@ -1249,6 +1944,112 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::RegisterModule(name, exports) => {
+                self.module_exports.insert(name.clone(), exports.iter().cloned().collect());
+                self.instruction_pointer += 1;
+            }
+            Instruction::ProvideModules(modules) => {
+                // Files run by require are identified by their canonical path
+                let file = self.loading_modules.last().cloned().unwrap_or_else(|| "<main>".to_string());
+                for module in modules {
+                    if let Some(previous) = self.provided_modules.get(module) {
+                        if previous != &file {
+                            return Err(RuntimeError::new(format!(
+                                "Module '{}' is provided by both '{}' and '{}'",
+                                module, previous, file
+                            )));
+                        }
+                    }
+                    self.provided_modules.insert(module.clone(), file.clone());
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::ModuleExports => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ModuleExports".to_string()))?;
+                let module_name = match &value {
+                    Value::Symbol(s) | Value::String(s) => s.to_string(),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'module-exports' expects a symbol or string, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                };
+                let exports = self.module_exports.get(&module_name).ok_or_else(|| {
+                    let mut known: Vec<&str> = self.module_exports.keys().map(|k| k.as_str()).collect();
+                    known.sort();
+                    let suggestion = if known.is_empty() {
+                        "No modules are loaded yet.".to_string()
+                    } else {
+                        format!("Known modules: {}", known.join(", "))
+                    };
+                    RuntimeError::with_suggestion(format!("Unknown module '{}'", module_name), suggestion)
+                })?;
+                let mut names: Vec<&String> = exports.iter().collect();
+                names.sort();
+                let symbols = names.into_iter().map(|n| Value::Symbol(Arc::new(n.clone()))).collect();
+                self.value_stack.push(Value::List(List::from_vec(symbols)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::ModuleList => {
+                let mut names: Vec<&String> = self.module_exports.keys().collect();
+                names.sort();
+                let symbols = names.into_iter().map(|n| Value::Symbol(Arc::new(n.clone()))).collect();
+                self.value_stack.push(Value::List(List::from_vec(symbols)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::SymbolNamespace => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in SymbolNamespace".to_string()))?;
+                if !value.is_symbol() {
+                    return Err(RuntimeError::new(format!(
+                        "Type error: 'symbol-namespace' expects a symbol, got {}",
+                        Self::type_name(&value)
+                    )));
+                }
+                let result = match value.symbol_namespace() {
+                    Some(ns) => Value::String(Arc::new(ns.to_string())),
+                    None => Value::List(List::Nil),
+                };
+                self.value_stack.push(result);
+                self.instruction_pointer += 1;
+            }
+            Instruction::SymbolName => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in SymbolName".to_string()))?;
+                let name = value.symbol_name().ok_or_else(|| RuntimeError::new(format!(
+                    "Type error: 'symbol-name' expects a symbol, got {}",
+                    Self::type_name(&value)
+                )))?;
+                self.value_stack.push(Value::String(Arc::new(name.to_string())));
+                self.instruction_pointer += 1;
+            }
+            Instruction::IsQualifiedSymbol => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IsQualifiedSymbol".to_string()))?;
+                self.value_stack.push(Value::Boolean(value.symbol_namespace().is_some()));
+                self.instruction_pointer += 1;
+            }
+            Instruction::MakeQualifiedSymbol => {
+                let name = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in MakeQualifiedSymbol".to_string()))?;
+                let namespace = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in MakeQualifiedSymbol".to_string()))?;
+                match (&namespace, &name) {
+                    (Value::String(ns) | Value::Symbol(ns), Value::String(n) | Value::Symbol(n)) => {
+                        if ns.is_empty() || n.is_empty() || n.contains('/') {
+                            return Err(RuntimeError::with_suggestion(
+                                format!("'make-qualified-symbol' cannot build a symbol from namespace \"{}\" and name \"{}\"", ns, n),
+                                "The namespace must be non-empty and the name must be non-empty without '/'.".to_string(),
+                            ));
+                        }
+                        self.value_stack.push(Value::Symbol(Arc::new(format!("{}/{}", ns, n))));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'make-qualified-symbol' expects strings or symbols, got {} and {}",
+                            Self::type_name(&namespace),
+                            Self::type_name(&name)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
             Instruction::Append => {
                 // Pop two lists and append them
                 let second = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Append".to_string()))?;
@@ -1257,12 +2058,15 @@ impl VM {
                 match (&first, &second) {
                     (Value::List(first_list), Value::List(second_list)) => {
                         // For append, we need to copy the first list and attach second to the end
-                        // This is O(n) in the first list length - append is inherently expensive
-                        let first_vec = first_list.to_vec();
-                        let second_vec = second_list.to_vec();
-                        let mut result = first_vec;
-                        result.extend(second_vec);
-                        self.value_stack.push(Value::List(List::from_vec(result)));
+                        // This is O(n) in the first list length - append is inherently expensive.
+                        // The second list is shared as the tail, so an improper one keeps its dot:
+                        // (append '(1) (cons 2 3)) => (1 2 . 3)
+                        Self::require_proper_list("append", first_list)?;
+                        let mut result = second_list.clone();
+                        for item in first_list.to_vec().into_iter().rev() {
+                            result = List::cons(item, result);
+                        }
+                        self.value_stack.push(Value::List(result));
                     }
                     _ => {
                         return Err(RuntimeError::new(format!(
@@ -1274,6 +2078,42 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            // (append l1 l2 ...): every list but the last is copied, the last is
+            // shared as the tail, so (append) is '() and (append l) is l itself
+            Instruction::AppendList => {
+                let args = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in AppendList".to_string()))?;
+                let args = match args {
+                    Value::List(list) => list.to_vec(),
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: AppendList expects an argument list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let mut result = List::Nil;
+                for (i, arg) in args.into_iter().rev().enumerate() {
+                    let list = match arg {
+                        Value::List(list) => list,
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'append' expects lists, got {}",
+                                Self::type_name(&other)
+                            )));
+                        }
+                    };
+                    if i == 0 {
+                        result = list;
+                        continue;
+                    }
+                    Self::require_proper_list("append", &list)?;
+                    for item in list.to_vec().into_iter().rev() {
+                        result = List::cons(item, result);
+                    }
+                }
+                self.value_stack.push(Value::List(result));
+                self.instruction_pointer += 1;
+            }
             Instruction::MakeList(n) => {
                 let n = *n;
                 // Pop n values and create a list
@@ -1306,81 +2146,654 @@ impl VM {
                                     idx, list.len()
                                 ))),
                             }
-                        }
-                        match current.car() {
-                            Some(val) => self.value_stack.push(val.clone()),
-                            None => return Err(RuntimeError::new(format!(
-                                "'list-ref' index {} out of bounds for list of length {}",
-                                idx, list.len()
-                            ))),
-                        }
+                        }
+                        match current.car() {
+                            Some(val) => self.value_stack.push(val.clone()),
+                            None => return Err(RuntimeError::new(format!(
+                                "'list-ref' index {} out of bounds for list of length {}",
+                                idx, list.len()
+                            ))),
+                        }
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'list-ref' expects a list and an integer, got {} and {}",
+                            Self::type_name(&list_val),
+                            Self::type_name(&index)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::ListLength => {
+                // Pop list and push its length
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListLength".to_string()))?;
+                match value {
+                    Value::List(items) => {
+                        Self::require_proper_list("list-length", &items)?;
+                        self.value_stack.push(Value::Integer(items.len() as i64));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'list-length' expects a list, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            // List callbacks run on this VM via call_value, so functions and
+            // closures behave exactly as they do when called directly
+            Instruction::Map => {
+                let more = match self.value_stack.pop() {
+                    Some(Value::List(more)) => more,
+                    _ => return Err(RuntimeError::new("Stack corruption: Map expects a list of extra lists".to_string())),
+                };
+                let (list, callable) = self.pop_list_callback_args("map")?;
+                let mut results = Vec::with_capacity(list.len());
+                if more.is_empty() {
+                    for item in list.iter() {
+                        results.push(self.call_value(callable.clone(), vec![item.clone()])?);
+                    }
+                } else {
+                    // With several lists the callable gets one element from
+                    // each, stopping at the shortest: (map + '(1 2) '(10 20)) => (11 22)
+                    let mut columns = vec![list.to_vec()];
+                    for extra in more.iter() {
+                        match extra {
+                            Value::List(items) => {
+                                Self::require_proper_list("map", items)?;
+                                columns.push(items.to_vec());
+                            }
+                            other => {
+                                return Err(RuntimeError::new(format!(
+                                    "Type error: 'map' expects lists after the function, got {}",
+                                    Self::type_name(other)
+                                )));
+                            }
+                        }
+                    }
+                    let len = columns.iter().map(Vec::len).min().unwrap_or(0);
+                    for i in 0..len {
+                        let args = columns.iter().map(|column| column[i].clone()).collect();
+                        results.push(self.call_value(callable.clone(), args)?);
+                    }
+                }
+                self.value_stack.push(Value::List(List::from_vec(results)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::VectorMap => {
+                let (items, callable) = self.pop_vector_callback_args("vector-map")?;
+                let mut results = Vec::with_capacity(items.len());
+                for item in items.iter() {
+                    results.push(self.call_value(callable.clone(), vec![item.clone()])?);
+                }
+                self.value_stack.push(Value::Vector(results.into()));
+                self.instruction_pointer += 1;
+            }
+            Instruction::VectorForEach => {
+                let (items, callable) = self.pop_vector_callback_args("vector-for-each")?;
+                for item in items.iter() {
+                    self.call_value(callable.clone(), vec![item.clone()])?;
+                }
+                self.value_stack.push(Value::List(List::Nil));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Filter => {
+                let (list, predicate) = self.pop_list_callback_args("filter")?;
+                let mut kept = Vec::new();
+                for item in list.iter() {
+                    match self.call_value(predicate.clone(), vec![item.clone()])? {
+                        Value::Boolean(true) => kept.push(item.clone()),
+                        Value::Boolean(false) => {}
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'filter' predicate must return a boolean, got {}",
+                                Self::type_name(&other)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(Value::List(List::from_vec(kept)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Reduce => {
+                // (reduce f init lst) folds from the left: (f (f init a) b) ...
+                let underflow = || RuntimeError::new("Stack underflow in 'reduce'".to_string());
+                let list = self.value_stack.pop().ok_or_else(underflow)?;
+                let mut acc = self.value_stack.pop().ok_or_else(underflow)?;
+                let callable = self.value_stack.pop().ok_or_else(underflow)?;
+                let list = match (&callable, list) {
+                    (Value::Function(_) | Value::Closure(_), Value::List(items)) => {
+                        Self::require_proper_list("reduce", &items)?;
+                        items
+                    }
+                    (_, list) => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'reduce' expects a function, an initial value and a list, got {} and {}",
+                            Self::type_name(&callable),
+                            Self::type_name(&list)
+                        )));
+                    }
+                };
+                for item in list.iter() {
+                    acc = self.call_value(callable.clone(), vec![acc, item.clone()])?;
+                }
+                self.value_stack.push(acc);
+                self.instruction_pointer += 1;
+            }
+            Instruction::FoldRight => {
+                // (foldr f init lst) folds from the right: (f a (f b ... (f z init)))
+                let underflow = || RuntimeError::new("Stack underflow in 'foldr'".to_string());
+                let list = self.value_stack.pop().ok_or_else(underflow)?;
+                let mut acc = self.value_stack.pop().ok_or_else(underflow)?;
+                let callable = self.value_stack.pop().ok_or_else(underflow)?;
+                let items = match (&callable, list) {
+                    (Value::Function(_) | Value::Closure(_), Value::List(items)) => {
+                        Self::require_proper_list("foldr", &items)?;
+                        items.to_vec()
+                    }
+                    (_, list) => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'foldr' expects a function, an initial value and a list, got {} and {}",
+                            Self::type_name(&callable),
+                            Self::type_name(&list)
+                        )));
+                    }
+                };
+                for item in items.into_iter().rev() {
+                    acc = self.call_value(callable.clone(), vec![item, acc])?;
+                }
+                self.value_stack.push(acc);
+                self.instruction_pointer += 1;
+            }
+            Instruction::Member => {
+                let (item, list) = self.pop_lookup_args("member?")?;
+                let mut rest = list;
+                let found = loop {
+                    match rest.car() {
+                        Some(head) if Self::values_equal(head, &item) => break Value::List(rest),
+                        Some(_) => rest = rest.cdr().unwrap_or(List::Nil),
+                        None => break Value::Boolean(false),
+                    }
+                };
+                self.value_stack.push(found);
+                self.instruction_pointer += 1;
+            }
+            Instruction::Assoc => {
+                let (key, alist) = self.pop_lookup_args("assoc")?;
+                let mut found = Value::Boolean(false);
+                for entry in alist.iter() {
+                    let pair_key = match entry {
+                        Value::List(pair) => pair.car(),
+                        _ => None,
+                    };
+                    match pair_key {
+                        Some(k) if Self::values_equal(k, &key) => {
+                            found = entry.clone();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'assoc' expects a list of pairs, found {} entry",
+                                Self::type_name(entry)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(found);
+                self.instruction_pointer += 1;
+            }
+            Instruction::AssocSet | Instruction::AssocUpdate => {
+                let is_set = matches!(self.current_bytecode[ip], Instruction::AssocSet);
+                let name = if is_set { "assoc-set" } else { "assoc-update" };
+                let (new_value_or_fn, alist) = self.pop_lookup_args(name)?;
+                let key = self.value_stack.pop().ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+                let mut entries = alist.to_vec();
+                // Only the first matching entry changes, mirroring what assoc finds
+                let mut found: Option<(usize, List)> = None;
+                for (i, entry) in entries.iter().enumerate() {
+                    match entry {
+                        Value::List(pair) if pair.car().is_some() => {
+                            if found.is_none() && pair.car().is_some_and(|k| Self::values_equal(k, &key)) {
+                                found = Some((i, pair.clone()));
+                            }
+                        }
+                        _ => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: '{}' expects a list of pairs, found {} entry",
+                                name,
+                                Self::type_name(entry)
+                            )));
+                        }
+                    }
+                }
+                match (found, is_set) {
+                    (Some((i, pair)), _) => {
+                        let value = if is_set {
+                            new_value_or_fn
+                        } else {
+                            self.call_value(new_value_or_fn, vec![Self::alist_entry_value(&pair)])?
+                        };
+                        entries[i] = Value::List(Self::with_alist_entry_value(&pair, value));
+                    }
+                    // A new entry is a dotted pair, so (cdr (assoc key alist)) gives the value back
+                    (None, true) => entries.push(Value::List(List::cons(key, List::Dotted(Box::new(new_value_or_fn))))),
+                    (None, false) => {
+                        return Err(RuntimeError::with_suggestion(
+                            format!("'assoc-update' found no entry for key {}", Self::format_value(&key)),
+                            "Use assoc-set to add a new key, or check with (assoc key alist) first.".to_string(),
+                        ));
+                    }
+                }
+                self.value_stack.push(Value::List(List::from_vec(entries)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Reverse => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Reverse".to_string()))?;
+                match value {
+                    Value::List(list) => {
+                        Self::require_proper_list("reverse", &list)?;
+                        let mut reversed = List::Nil;
+                        for item in list.iter() {
+                            reversed = List::cons(item.clone(), reversed);
+                        }
+                        self.value_stack.push(Value::List(reversed));
+                    }
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'reverse' expects a list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::Zip => {
+                let second = self.pop_list_arg("zip")?;
+                let first = self.pop_list_arg("zip")?;
+                let pairs: Vec<Value> = first.iter().zip(second.iter())
+                    .map(|(a, b)| Value::list_from_vec(vec![a.clone(), b.clone()]))
+                    .collect();
+                self.value_stack.push(Value::list_from_vec(pairs));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Unzip => {
+                let pairs = self.pop_list_arg("unzip")?;
+                let mut firsts = Vec::with_capacity(pairs.len());
+                let mut seconds = Vec::with_capacity(pairs.len());
+                for pair in pairs.iter() {
+                    match pair {
+                        Value::List(items) if items.len() == 2 => {
+                            let mut items = items.iter();
+                            firsts.extend(items.next().cloned());
+                            seconds.extend(items.next().cloned());
+                        }
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'unzip' expects a list of two-element lists, got an element of type {}",
+                                Self::type_name(other)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(Value::list_from_vec(vec![
+                    Value::list_from_vec(firsts),
+                    Value::list_from_vec(seconds),
+                ]));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Enumerate => {
+                let list = self.pop_list_arg("enumerate")?;
+                let pairs: Vec<Value> = list.iter().enumerate()
+                    .map(|(i, item)| Value::list_from_vec(vec![Value::Integer(i as i64), item.clone()]))
+                    .collect();
+                self.value_stack.push(Value::list_from_vec(pairs));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Flatten => {
+                let list = self.pop_list_arg("flatten")?;
+                // Walk with an explicit stack of pending sublists so deep nesting
+                // can't overflow the native stack
+                let mut atoms = Vec::new();
+                let mut pending = vec![list];
+                while let Some(current) = pending.pop() {
+                    let mut rest = current;
+                    while let Some(head) = rest.car().cloned() {
+                        rest = rest.cdr().unwrap_or(List::Nil);
+                        if let Value::List(nested) = head {
+                            Self::require_proper_list("flatten", &nested)?;
+                            pending.push(rest);
+                            rest = nested;
+                        } else {
+                            atoms.push(head);
+                        }
+                    }
+                }
+                self.value_stack.push(Value::list_from_vec(atoms));
+                self.instruction_pointer += 1;
+            }
+            Instruction::RemoveDuplicates => {
+                let list = self.pop_list_arg("remove-duplicates")?;
+                let mut kept: Vec<Value> = Vec::new();
+                for item in list.iter() {
+                    if !kept.iter().any(|seen| Self::values_equal(seen, item)) {
+                        kept.push(item.clone());
+                    }
+                }
+                self.value_stack.push(Value::list_from_vec(kept));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Take => {
+                let (count, list) = self.pop_count_and_list("take")?;
+                Self::require_proper_list("take", &list)?;
+                let taken: Vec<Value> = list.iter().take(count).cloned().collect();
+                self.value_stack.push(Value::List(List::from_vec(taken)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Drop => {
+                let (count, list) = self.pop_count_and_list("drop")?;
+                // The remaining tail is shared, not copied
+                let mut rest = list;
+                for _ in 0..count {
+                    match rest.cdr() {
+                        Some(tail) => rest = tail,
+                        None => break,
+                    }
+                }
+                self.value_stack.push(rest.to_value());
+                self.instruction_pointer += 1;
+            }
+            Instruction::ListStar => {
+                let args = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListStar".to_string()))?;
+                let mut args = match args {
+                    Value::List(list) => list.to_vec(),
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: ListStar expects an argument list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let last = args.pop().ok_or_else(|| {
+                    RuntimeError::new("'cons*' expects at least one argument".to_string())
+                })?;
+                // Same rule as cons: a non-list tail follows the dot, (cons* 1 2) is (1 . 2).
+                // A lone non-list argument is just returned.
+                let mut result = match last {
+                    Value::List(tail) => tail,
+                    other => List::Dotted(Box::new(other)),
+                };
+                for item in args.into_iter().rev() {
+                    result = List::cons(item, result);
+                }
+                self.value_stack.push(result.to_value());
+                self.instruction_pointer += 1;
+            }
+            Instruction::Range => {
+                let underflow = || RuntimeError::new("Stack underflow in 'range'".to_string());
+                let step = self.value_stack.pop().ok_or_else(underflow)?;
+                let end = self.value_stack.pop().ok_or_else(underflow)?;
+                let start = self.value_stack.pop().ok_or_else(underflow)?;
+                let (start, end, step) = match (&start, &end, &step) {
+                    (Value::Integer(s), Value::Integer(e), Value::Integer(st)) => (*s, *e, *st),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'range' expects integers, got {}, {} and {}",
+                            Self::type_name(&start),
+                            Self::type_name(&end),
+                            Self::type_name(&step)
+                        )));
+                    }
+                };
+                if step == 0 {
+                    return Err(RuntimeError::with_suggestion(
+                        "'range' step cannot be zero".to_string(),
+                        "Use a positive step to count up or a negative one to count down".to_string(),
+                    ));
+                }
+                let mut items = Vec::new();
+                let mut current = Some(start);
+                while let Some(n) = current {
+                    if (step > 0 && n >= end) || (step < 0 && n <= end) {
+                        break;
+                    }
+                    items.push(Value::Integer(n));
+                    current = n.checked_add(step);
+                }
+                self.value_stack.push(Value::List(List::from_vec(items)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::Sort => {
+                let underflow = || RuntimeError::new("Stack underflow in 'sort'".to_string());
+                let comparator = self.value_stack.pop().ok_or_else(underflow)?;
+                let list = self.value_stack.pop().ok_or_else(underflow)?;
+                let items = match list {
+                    Value::List(items) => {
+                        Self::require_proper_list("sort", &items)?;
+                        items.to_vec()
+                    }
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'sort' expects a list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let sorted = match comparator {
+                    // No comparator: ascending numeric order
+                    Value::List(List::Nil) => Self::merge_sort(items, &mut Self::numeric_less)?,
+                    callable @ (Value::Function(_) | Value::Closure(_)) => {
+                        Self::merge_sort(items, &mut |a, b| {
+                            match self.call_value(callable.clone(), vec![a.clone(), b.clone()])? {
+                                Value::Boolean(less) => Ok(less),
+                                other => Err(RuntimeError::new(format!(
+                                    "Type error: 'sort' comparator must return a boolean, got {}",
+                                    Self::type_name(&other)
+                                ))),
+                            }
+                        })?
+                    }
+                    other => {
+                        return Err(RuntimeError::with_suggestion(
+                            format!("Type error: 'sort' expects a comparator function, got {}", Self::type_name(&other)),
+                            "Pass a function returning true when its first argument sorts first, e.g. (sort xs >), or leave it out for ascending numbers".to_string(),
+                        ));
+                    }
+                };
+                self.value_stack.push(Value::List(List::from_vec(sorted)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::ListCopy => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListCopy".to_string()))?;
+                match value {
+                    Value::List(items) => {
+                        self.value_stack.push(Value::List(items.copy()));
                     }
                     _ => {
                         return Err(RuntimeError::new(format!(
-                            "Type error: 'list-ref' expects a list and an integer, got {} and {}",
-                            Self::type_name(&list_val),
-                            Self::type_name(&index)
+                            "Type error: 'list-copy' expects a list, got {}",
+                            Self::type_name(&value)
                         )));
                     }
                 }
                 self.instruction_pointer += 1;
             }
-            Instruction::ListLength => {
-                // Pop list and push its length
-                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListLength".to_string()))?;
-                match value {
-                    Value::List(items) => {
-                        self.value_stack.push(Value::Integer(items.len() as i64));
+            Instruction::SharesStructure => {
+                let second = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in SharesStructure".to_string()))?;
+                let first = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in SharesStructure".to_string()))?;
+                match (&first, &second) {
+                    (Value::List(a), Value::List(b)) => {
+                        self.value_stack.push(Value::Boolean(a.shares_structure(b)));
                     }
                     _ => {
                         return Err(RuntimeError::new(format!(
-                            "Type error: 'list-length' expects a list, got {}",
-                            Self::type_name(&value)
+                            "Type error: 'shares-structure?' expects two lists, got {} and {}",
+                            Self::type_name(&first),
+                            Self::type_name(&second)
                         )));
                     }
                 }
                 self.instruction_pointer += 1;
             }
             Instruction::NumberToString => {
-                // Pop integer and push string representation
+                // Pop the optional precision (as a list) and the number, push its string form
+                let options = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in NumberToString".to_string()))?;
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in NumberToString".to_string()))?;
-                match value {
-                    Value::Integer(n) => {
-                        self.value_stack.push(Value::String(Arc::new(n.to_string())));
+                let options = match options {
+                    Value::List(list) => list.to_vec(),
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: NumberToString expects an options list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let precision = match options.as_slice() {
+                    [] => None,
+                    [Value::Integer(p)] if *p >= 0 => Some(*p as usize),
+                    [Value::Integer(p)] => {
+                        return Err(RuntimeError::new(format!(
+                            "'number->string' precision cannot be negative: {}",
+                            p
+                        )));
                     }
+                    [other] => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'number->string' precision must be an integer, got {}",
+                            Self::type_name(other)
+                        )));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "'number->string' expects a number and an optional precision, got {} extra arguments",
+                            options.len()
+                        )));
+                    }
+                };
+                let text = match (&value, precision) {
+                    // Shortest form that reads back to the same number
+                    (Value::Integer(n), None) => n.to_string(),
+                    (Value::BigInt(n), None) => n.to_string(),
+                    (Value::Float(_), None) => Self::format_value(&value),
+                    // Exact for integers of any size
+                    (Value::Integer(n), Some(0)) => n.to_string(),
+                    (Value::Integer(n), Some(p)) => format!("{}.{}", n, "0".repeat(p)),
+                    (Value::BigInt(n), Some(0)) => n.to_string(),
+                    (Value::BigInt(n), Some(p)) => format!("{}.{}", n, "0".repeat(p)),
+                    (Value::Float(f), Some(p)) => format!("{:.*}", p, f),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'number->string' expects a number, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                };
+                self.value_stack.push(Value::String(Arc::new(text)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::FormatInt => {
+                let options = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in FormatInt".to_string()))?;
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in FormatInt".to_string()))?;
+                let n = match &value {
+                    Value::Integer(n) => n.to_string(),
+                    Value::BigInt(n) => n.to_string(),
                     _ => {
                         return Err(RuntimeError::new(format!(
-                            "Type error: 'number->string' expects an integer, got {}",
+                            "Type error: 'format-int' expects an integer, got {}",
                             Self::type_name(&value)
                         )));
                     }
+                };
+                let options: Vec<Value> = match &options {
+                    Value::List(list) => list.iter().cloned().collect(),
+                    _ => return Err(RuntimeError::new("Stack corruption: FormatInt expects an option list".to_string())),
+                };
+                if !options.len().is_multiple_of(2) {
+                    return Err(RuntimeError::new("format-int options must come in :option value pairs".to_string()));
+                }
+
+                // Defaults: no grouping, no padding
+                let mut grouping = String::new();
+                let mut width = 0usize;
+                let mut pad = ' ';
+                for pair in options.chunks(2) {
+                    match (&pair[0], &pair[1]) {
+                        (Value::Symbol(k), Value::String(s)) if k.as_str() == ":grouping" => grouping = s.to_string(),
+                        (Value::Symbol(k), Value::Integer(w)) if k.as_str() == ":width" && *w >= 0 => width = *w as usize,
+                        (Value::Symbol(k), Value::String(s)) if k.as_str() == ":pad" && s.chars().count() == 1 => {
+                            pad = s.chars().next().unwrap_or(' ');
+                        }
+                        (Value::Symbol(k), v) if matches!(k.as_str(), ":grouping" | ":width" | ":pad") => {
+                            let expected = match k.as_str() {
+                                ":grouping" => "a string",
+                                ":width" => "a non-negative integer",
+                                _ => "a one-character string",
+                            };
+                            return Err(RuntimeError::new(format!(
+                                "Type error: format-int option {} expects {}, got {}",
+                                k, expected, Self::format_value(v)
+                            )));
+                        }
+                        (k, _) => {
+                            return Err(RuntimeError::with_suggestion(
+                                format!("Unknown format-int option {}", Self::format_value(k)),
+                                "Supported options are :grouping, :width and :pad".to_string(),
+                            ));
+                        }
+                    }
                 }
+
+                let formatted = Self::format_int(&n, &grouping, width, pad);
+                self.value_stack.push(Value::String(Arc::new(formatted)));
                 self.instruction_pointer += 1;
             }
             Instruction::StringToNumber => {
-                // Pop string and push integer (or error if not a valid number)
+                // Pop the optional radix (as a list) and a string, push the number or false
+                let options = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringToNumber".to_string()))?;
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringToNumber".to_string()))?;
-                match value {
-                    Value::String(s) => {
-                        match s.trim().parse::<i64>() {
-                            Ok(n) => {
-                                self.value_stack.push(Value::Integer(n));
-                            }
-                            Err(_) => {
-                                return Err(RuntimeError::new(format!(
-                                    "Type error: 'string->number' cannot parse '{}' as a number",
-                                    s
-                                )));
-                            }
-                        }
+                let options = match options {
+                    Value::List(list) => list.to_vec(),
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: StringToNumber expects an options list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let radix = match options.as_slice() {
+                    [] => 10,
+                    [Value::Integer(r @ (2 | 8 | 10 | 16))] => *r as u32,
+                    [Value::Integer(r)] => {
+                        return Err(RuntimeError::new(format!(
+                            "'string->number' radix must be 2, 8, 10 or 16, got {}",
+                            r
+                        )));
+                    }
+                    [other] => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'string->number' radix must be an integer, got {}",
+                            Self::type_name(other)
+                        )));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "'string->number' expects a string and an optional radix, got {} extra arguments",
+                            options.len()
+                        )));
                     }
+                };
+                let s = match &value {
+                    Value::String(s) => s.trim(),
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: 'string->number' expects a string, got {}",
                             Self::type_name(&value)
                         )));
                     }
-                }
+                };
+                self.value_stack.push(Self::parse_number(s, radix));
                 self.instruction_pointer += 1;
             }
             Instruction::LoadGlobal(name) => {
@@ -1401,7 +2814,8 @@ impl VM {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringLength".to_string()))?;
                 match value {
                     Value::String(s) => {
-                        self.value_stack.push(Value::Integer(s.len() as i64));
+                        // Length in characters, matching substring and string-ref indices
+                        self.value_stack.push(Value::Integer(s.chars().count() as i64));
                     }
                     _ => {
                         return Err(RuntimeError::new(format!(
@@ -1412,6 +2826,34 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::StringRef => {
+                let index = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringRef".to_string()))?;
+                let string = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringRef".to_string()))?;
+
+                match (&string, &index) {
+                    (Value::String(s), Value::Integer(idx)) => {
+                        let found = usize::try_from(*idx).ok().and_then(|i| s.chars().nth(i));
+                        match found {
+                            Some(c) => self.value_stack.push(Value::String(Arc::new(c.to_string()))),
+                            None => {
+                                return Err(RuntimeError::new(format!(
+                                    "'string-ref' index {} out of bounds for string of length {}",
+                                    idx,
+                                    s.chars().count()
+                                )));
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'string-ref' expects a string and an integer, got {} and {}",
+                            Self::type_name(&string),
+                            Self::type_name(&index)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
             Instruction::Substring => {
                 let end = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Substring".to_string()))?;
                 let start = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Substring".to_string()))?;
@@ -1419,15 +2861,16 @@ impl VM {
 
                 match (&string, &start, &end) {
                     (Value::String(s), Value::Integer(start_idx), Value::Integer(end_idx)) => {
+                        let char_count = s.chars().count();
                         let start = (*start_idx).max(0) as usize;
-                        let end = (*end_idx).min(s.len() as i64) as usize;
-                        if start <= end && end <= s.len() {
+                        let end = (*end_idx).min(char_count as i64) as usize;
+                        if start <= end && end <= char_count {
                             let result = s.chars().skip(start).take(end - start).collect::<String>();
                             self.value_stack.push(Value::String(Arc::new(result)));
                         } else {
                             return Err(RuntimeError::new(format!(
                                 "'substring' invalid indices: start={}, end={}, string length={}",
-                                start_idx, end_idx, s.len()
+                                start_idx, end_idx, char_count
                             )));
                         }
                     }
@@ -1460,6 +2903,35 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::StringAppendList => {
+                let args = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringAppendList".to_string()))?;
+                let args = match args {
+                    Value::List(list) => list.to_vec(),
+                    other => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: StringAppendList expects an argument list, got {}",
+                            Self::type_name(&other)
+                        )));
+                    }
+                };
+                let mut result = String::new();
+                for arg in &args {
+                    match arg {
+                        Value::String(s) => result.push_str(s),
+                        _ => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'string-append' expects strings, got {}",
+                                Self::type_name(arg)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(Value::String(Arc::new(result)));
+                self.instruction_pointer += 1;
+            }
+            // One one-char string per Unicode scalar value, so list->string
+            // (plain concatenation) rebuilds the original exactly, combining
+            // marks and multi-codepoint emoji included
             Instruction::StringToList => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringToList".to_string()))?;
                 match value {
@@ -1478,10 +2950,56 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            // Char callbacks receive one-char strings (the representation
+            // string->list uses) and run on this VM via call_value
+            Instruction::ForEachChar => {
+                let (text, callable) = self.pop_char_callback_args("for-each-char")?;
+                for ch in text.chars() {
+                    self.call_value(callable.clone(), vec![Value::String(Arc::new(ch.to_string()))])?;
+                }
+                self.value_stack.push(Value::List(List::Nil));
+                self.instruction_pointer += 1;
+            }
+            Instruction::StringMap => {
+                let (text, callable) = self.pop_char_callback_args("string-map")?;
+                let mut result = String::with_capacity(text.len());
+                for ch in text.chars() {
+                    match self.call_value(callable.clone(), vec![Value::String(Arc::new(ch.to_string()))])? {
+                        Value::String(s) => result.push_str(&s),
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'string-map' function must return a string, got {}",
+                                Self::type_name(&other)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(Value::String(Arc::new(result)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::StringFilter => {
+                let (text, callable) = self.pop_char_callback_args("string-filter")?;
+                let mut result = String::new();
+                for ch in text.chars() {
+                    match self.call_value(callable.clone(), vec![Value::String(Arc::new(ch.to_string()))])? {
+                        Value::Boolean(true) => result.push(ch),
+                        Value::Boolean(false) => {}
+                        other => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'string-filter' predicate must return a boolean, got {}",
+                                Self::type_name(&other)
+                            )));
+                        }
+                    }
+                }
+                self.value_stack.push(Value::String(Arc::new(result)));
+                self.instruction_pointer += 1;
+            }
             Instruction::ListToString => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListToString".to_string()))?;
                 match value {
                     Value::List(list) => {
+                        Self::require_proper_list("list->string", &list)?;
                         let mut result = String::new();
                         for item in list.iter() {
                             match item {
@@ -1527,6 +3045,45 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::IntToChar => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IntToChar".to_string()))?;
+                let code = match value {
+                    Value::Integer(n) => n,
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'integer->char' expects an integer, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                };
+                // Surrogates (0xD800-0xDFFF) and values past 0x10FFFF are not characters
+                let c = u32::try_from(code).ok().and_then(char::from_u32).ok_or_else(|| RuntimeError::new(format!(
+                    "'integer->char' expects a Unicode scalar value, got {}",
+                    code
+                )))?;
+                self.value_stack.push(Value::String(Arc::new(c.to_string())));
+                self.instruction_pointer += 1;
+            }
+            Instruction::CharAlphabetic | Instruction::CharNumeric | Instruction::CharWhitespace => {
+                let (name, test): (&str, fn(char) -> bool) = match self.current_bytecode[ip] {
+                    Instruction::CharAlphabetic => ("char-alphabetic?", char::is_alphabetic),
+                    Instruction::CharNumeric => ("char-numeric?", char::is_numeric),
+                    _ => ("char-whitespace?", char::is_whitespace),
+                };
+                let c = self.pop_char(name)?;
+                self.value_stack.push(Value::Boolean(test(c)));
+                self.instruction_pointer += 1;
+            }
+            Instruction::CharUpcase | Instruction::CharDowncase => {
+                // Some characters change length, e.g. (char-upcase "ß") is "SS"
+                let converted: String = if matches!(self.current_bytecode[ip], Instruction::CharUpcase) {
+                    self.pop_char("char-upcase")?.to_uppercase().collect()
+                } else {
+                    self.pop_char("char-downcase")?.to_lowercase().collect()
+                };
+                self.value_stack.push(Value::String(Arc::new(converted)));
+                self.instruction_pointer += 1;
+            }
             Instruction::StringSplit => {
                 let delimiter = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringSplit".to_string()))?;
                 let string = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringSplit".to_string()))?;
@@ -1555,6 +3112,7 @@ impl VM {
                 let list_val = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringJoin".to_string()))?;
                 match (&list_val, &delimiter) {
                     (Value::List(list), Value::String(delim)) => {
+                        Self::require_proper_list("string-join", list)?;
                         let mut parts = Vec::new();
                         for item in list.iter() {
                             match item {
@@ -1666,6 +3224,25 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::StringIndexOf => {
+                let needle = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringIndexOf".to_string()))?;
+                let string = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringIndexOf".to_string()))?;
+                match (&string, &needle) {
+                    (Value::String(s), Value::String(n)) => {
+                        // Char index, like substring and string-length
+                        let index = s.find(n.as_str()).map_or(-1, |byte| s[..byte].chars().count() as i64);
+                        self.value_stack.push(Value::Integer(index));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'string-index-of' expects two strings, got {} and {}",
+                            Self::type_name(&string),
+                            Self::type_name(&needle)
+                        )));
+                    }
+                }
+                self.instruction_pointer += 1;
+            }
             Instruction::StringUpcase => {
                 let string = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in StringUpcase".to_string()))?;
                 match string {
@@ -1696,54 +3273,57 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::Interpolate(n) => {
+                let start = self.value_stack.len().checked_sub(*n)
+                    .ok_or_else(|| RuntimeError::new("Stack underflow in Interpolate".to_string()))?;
+                let text: String = self.value_stack.drain(start..).map(|v| Self::value_to_display_string(&v)).collect();
+                self.value_stack.push(Value::String(Arc::new(text)));
+                self.instruction_pointer += 1;
+            }
             Instruction::Format => {
+                // Pops the template and the list of values passed after it
                 let args = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Format".to_string()))?;
                 let format_string = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Format".to_string()))?;
-
-                match (&format_string, &args) {
-                    (Value::String(fmt), Value::List(arg_list)) => {
-                        let mut result = String::new();
-                        let mut chars = fmt.chars().peekable();
-                        let mut arg_iter = arg_list.iter();
-
-                        while let Some(ch) = chars.next() {
-                            if ch == '{' {
-                                if chars.peek() == Some(&'}') {
-                                    chars.next(); // consume '}'
-                                    // Replace {} with next argument
-                                    if let Some(arg_value) = arg_iter.next() {
-                                        result.push_str(&Self::value_to_display_string(arg_value));
-                                    } else {
-                                        return Err(RuntimeError::with_suggestion(
-                                            "Not enough arguments for format placeholders".to_string(),
-                                            "The format string has more {} placeholders than provided arguments. Make sure the list has enough values.".to_string(),
-                                        ));
-                                    }
-                                } else {
-                                    result.push(ch);
-                                }
-                            } else {
-                                result.push(ch);
-                            }
-                        }
-
-                        self.value_stack.push(Value::String(Arc::new(result)));
-                    }
-                    (Value::String(_), _) => {
+                let values: Vec<Value> = match &args {
+                    Value::List(list) => list.iter().cloned().collect(),
+                    _ => return Err(RuntimeError::new("Stack corruption: Format expects an argument list".to_string())),
+                };
+                let fmt = match &format_string {
+                    Value::String(s) => s.clone(),
+                    _ => {
                         return Err(RuntimeError::with_suggestion(
-                            format!("Type error: 'format' expects a list as second argument, got {}", Self::type_name(&args)),
-                            "The format function takes a format string and a list of values: (format \"x={}\" (list 42))".to_string(),
+                            format!("Type error: 'format' expects a string template, got {}", Self::type_name(&format_string)),
+                            "Usage: (format \"~a + ~a\" 1 2) or (format \"Hello, {}!\" (list \"world\"))".to_string(),
                         ));
                     }
-                    (_, _) => {
-                        return Err(RuntimeError::with_suggestion(
-                            format!("Type error: 'format' expects a string and a list, got {} and {}",
-                                Self::type_name(&format_string),
-                                Self::type_name(&args)),
-                            "Usage: (format \"Hello, {}!\" (list \"world\"))".to_string(),
-                        ));
+                };
+
+                // A template with ~ directives takes the values directly; a
+                // {} template takes them as one list: (format "x={}" (list 42))
+                let result = if fmt.contains('~') {
+                    Self::format_directives(&fmt, &values)?
+                } else {
+                    match values.as_slice() {
+                        [] => Self::format_placeholders(&fmt, &[])?,
+                        [Value::List(list)] => {
+                            Self::require_proper_list("format", list)?;
+                            Self::format_placeholders(&fmt, &list.to_vec())?
+                        }
+                        [other] => {
+                            return Err(RuntimeError::with_suggestion(
+                                format!("Type error: 'format' expects a list as second argument, got {}", Self::type_name(other)),
+                                "The format function takes a format string and a list of values: (format \"x={}\" (list 42))".to_string(),
+                            ));
+                        }
+                        _ => {
+                            return Err(RuntimeError::with_suggestion(
+                                format!("'format' with {{}} placeholders expects one list of values, got {} arguments", values.len()),
+                                "Pass the values as a list, (format \"{} {}\" (list a b)), or use directives: (format \"~a ~a\" a b)".to_string(),
+                            ));
+                        }
                     }
-                }
+                };
+                self.value_stack.push(Value::String(Arc::new(result)));
                 self.instruction_pointer += 1;
             }
             Instruction::ReadFile => {
@@ -1819,6 +3399,7 @@ impl VM {
 
                 match (&path, &bytes_list) {
                     (Value::String(path_str), Value::List(bytes)) => {
+                        Self::require_proper_list("write-binary-file", bytes)?;
                         // Convert list of integers to Vec<u8>
                         let mut byte_vec = Vec::new();
                         for byte_val in bytes.iter() {
@@ -1885,8 +3466,9 @@ impl VM {
 
                         // Execute the loaded file's main code
                         self.instruction_pointer = 0;
+                        let handler_base = self.handlers.len();
                         while !self.halted && self.instruction_pointer < self.current_bytecode.len() {
-                            self.execute_one_instruction()?;
+                            self.execute_with_handlers(handler_base)?;
                         }
 
                         // Restore previous state
@@ -1947,11 +3529,14 @@ impl VM {
                                 RuntimeError::new(format!("'require' failed to parse '{}': {}", path_str, e))
                             })?;
 
-                            // Compile the file, passing existing module exports for import validation
+                            // Compile the file against everything loaded so far, so it can import
+                            // modules and read globals from files required before it
                             let mut compiler = Compiler::new();
                             for (module, exports) in &self.module_exports {
                                 compiler.with_known_module_exports(module, exports);
                             }
+                            compiler.with_known_functions(self.functions.keys());
+                            compiler.with_known_globals(self.global_vars.keys());
                             let (functions, main) = compiler.compile_program(&exprs).map_err(|e| {
                                 self.loading_modules.pop();
                                 RuntimeError::new(format!("'require' failed to compile '{}': {}", path_str, e.message))
@@ -1972,8 +3557,13 @@ impl VM {
 
                             // Execute the loaded file's main code
                             self.instruction_pointer = 0;
+                            let mut run_result = Ok(());
+                            let handler_base = self.handlers.len();
                             while !self.halted && self.instruction_pointer < self.current_bytecode.len() {
-                                self.execute_one_instruction()?;
+                                if let Err(e) = self.execute_with_handlers(handler_base) {
+                                    run_result = Err(e);
+                                    break;
+                                }
                             }
 
                             // Restore previous state
@@ -1981,9 +3571,10 @@ impl VM {
                             self.instruction_pointer = saved_ip;
                             self.halted = false;
 
-                            // Mark as loaded and remove from loading stack
-                            self.loaded_modules.insert(canonical_path);
+                            // A file that failed part-way is not marked as loaded
                             self.loading_modules.pop();
+                            run_result?;
+                            self.loaded_modules.insert(canonical_path);
 
                             // Push a success value (true) onto the stack
                             self.value_stack.push(Value::Boolean(true));
@@ -2288,6 +3879,7 @@ impl VM {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in ListToVector".to_string()))?;
                 match value {
                     Value::List(list) => {
+                        Self::require_proper_list("list->vector", &list)?;
                         self.value_stack.push(Value::Vector(Arc::new(list.to_vec())));
                     }
                     _ => {
@@ -2299,6 +3891,16 @@ impl VM {
                 }
                 self.instruction_pointer += 1;
             }
+            Instruction::DeepListToVector => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in DeepListToVector".to_string()))?;
+                self.value_stack.push(value.deep_list_to_vector());
+                self.instruction_pointer += 1;
+            }
+            Instruction::DeepVectorToList => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in DeepVectorToList".to_string()))?;
+                self.value_stack.push(value.deep_vector_to_list());
+                self.instruction_pointer += 1;
+            }
             Instruction::VectorToList => {
                 // Pop vector and push list with same elements
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in VectorToList".to_string()))?;
@@ -2321,6 +3923,9 @@ impl VM {
                     Value::Integer(n) => {
                         self.value_stack.push(Value::Float(n as f64));
                     }
+                    Value::BigInt(n) => {
+                        self.value_stack.push(Value::Float(n.to_f64()));
+                    }
                     _ => {
                         return Err(RuntimeError::new(format!(
                             "Type error: 'int->float' expects an integer, got {}",
@@ -2390,41 +3995,22 @@ impl VM {
                 self.value_stack.push(Value::Float(f.cos()));
                 self.instruction_pointer += 1;
             }
-            Instruction::Floor => {
-                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Floor".to_string()))?;
-                let result = match value {
-                    Value::Float(f) => f.floor() as i64,
-                    Value::Integer(n) => n,
-                    _ => {
-                        return Err(RuntimeError::new(format!(
-                            "Type error: 'floor' expects a number, got {}",
-                            Self::type_name(&value)
-                        )));
-                    }
-                };
-                self.value_stack.push(Value::Integer(result));
-                self.instruction_pointer += 1;
-            }
-            Instruction::Ceil => {
-                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Ceil".to_string()))?;
-                let result = match value {
-                    Value::Float(f) => f.ceil() as i64,
-                    Value::Integer(n) => n,
-                    _ => {
-                        return Err(RuntimeError::new(format!(
-                            "Type error: 'ceil' expects a number, got {}",
-                            Self::type_name(&value)
-                        )));
-                    }
-                };
-                self.value_stack.push(Value::Integer(result));
-                self.instruction_pointer += 1;
-            }
+            Instruction::Floor => self.round_to_integer("floor", f64::floor)?,
+            Instruction::Ceil => self.round_to_integer("ceil", f64::ceil)?,
+            Instruction::Round => self.round_to_integer("round", f64::round_ties_even)?,
             Instruction::Abs => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Abs".to_string()))?;
                 match value {
                     Value::Integer(n) => {
-                        self.value_stack.push(Value::Integer(n.abs()));
+                        // Only i64::MIN has no i64 absolute value
+                        let abs = self.integer_result("abs", n.checked_abs(), || -&BigInt::from(n))?;
+                        self.value_stack.push(abs);
+                    }
+                    Value::BigInt(n) if n.is_negative() => {
+                        self.value_stack.push(Value::from_bigint(-n.as_ref()));
+                    }
+                    Value::BigInt(_) => {
+                        self.value_stack.push(value);
                     }
                     Value::Float(f) => {
                         self.value_stack.push(Value::Float(f.abs()));
@@ -2466,6 +4052,42 @@ impl VM {
                 self.value_stack.push(Value::Float(base_f.powf(exp_f)));
                 self.instruction_pointer += 1;
             }
+            Instruction::IExpt => {
+                let exponent = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IExpt".to_string()))?;
+                let base = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in IExpt".to_string()))?;
+                let result = match (&base, &exponent) {
+                    // Exact power of two integers
+                    (Value::Integer(_) | Value::BigInt(_), Value::Integer(e)) => {
+                        if *e < 0 {
+                            return Err(RuntimeError::with_suggestion(
+                                format!("'expt' of an integer needs a non-negative integer exponent, got {}", e),
+                                "Use a float base for a fractional result: (expt 2.0 -1)".to_string(),
+                            ));
+                        }
+                        let e = u32::try_from(*e).map_err(|_| RuntimeError::new(format!(
+                            "'expt' exponent {} is too large for an exact result", e
+                        )))?;
+                        match &base {
+                            Value::Integer(x) => self.integer_result("expt", x.checked_pow(e), || BigInt::from(*x).pow(e))?,
+                            Value::BigInt(x) => Value::from_bigint(x.pow(e)),
+                            _ => unreachable!(),
+                        }
+                    }
+                    // Anything else is a float power, like pow
+                    _ => match (Self::numeric_as_f64(&base), Self::numeric_as_f64(&exponent)) {
+                        (Some(x), Some(y)) => Value::Float(x.powf(y)),
+                        _ => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: 'expt' expects numbers, got {} and {}",
+                                Self::type_name(&base),
+                                Self::type_name(&exponent)
+                            )));
+                        }
+                    },
+                };
+                self.value_stack.push(result);
+                self.instruction_pointer += 1;
+            }
             Instruction::Log => {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in Log".to_string()))?;
                 let f = match value {
@@ -2634,7 +4256,41 @@ impl VM {
                             Self::type_name(&format)
                         )));
                     }
-                }
+                }
+                self.instruction_pointer += 1;
+            }
+            Instruction::LogMessage(level) => {
+                let level = *level;
+                let message = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in LogMessage".to_string()))?;
+                // Check the threshold first so suppressed messages cost no formatting
+                if level >= self.min_log_level {
+                    let line = Self::format_log_line(level, &message);
+                    // Logging is best-effort: a broken log destination must not abort the program
+                    let _ = writeln!(self.log_writer, "{}", line);
+                    let _ = self.log_writer.flush();
+                }
+                self.value_stack.push(message);
+                self.instruction_pointer += 1;
+            }
+            Instruction::SetLogLevel => {
+                let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in SetLogLevel".to_string()))?;
+                let name = match &value {
+                    Value::Symbol(s) | Value::String(s) => s.to_string(),
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'set-log-level' expects a symbol or string, got {}",
+                            Self::type_name(&value)
+                        )));
+                    }
+                };
+                let level = LogLevel::from_name(&name).ok_or_else(|| {
+                    RuntimeError::with_suggestion(
+                        format!("Unknown log level '{}'", name),
+                        "Use one of: debug, info, warn, error, off.".to_string(),
+                    )
+                })?;
+                let previous = std::mem::replace(&mut self.min_log_level, level);
+                self.value_stack.push(Value::Symbol(Arc::new(previous.name().to_string())));
                 self.instruction_pointer += 1;
             }
             Instruction::Eval => {
@@ -2666,8 +4322,9 @@ impl VM {
 
                         // Execute the eval'd code
                         self.instruction_pointer = 0;
+                        let handler_base = self.handlers.len();
                         while !self.halted && self.instruction_pointer < self.current_bytecode.len() {
-                            self.execute_one_instruction()?;
+                            self.execute_with_handlers(handler_base)?;
                         }
 
                         // Restore previous state
@@ -2711,8 +4368,8 @@ impl VM {
                                         has_check_arity = true;
                                         break;
                                     }
-                                    Instruction::PackRestArgs(n) => {
-                                        // Variadic function - n is the number of required params
+                                    Instruction::PackRestArgs(n) | Instruction::BindKeyArgs(n, _) => {
+                                        // Variadic function (rest or &key params) - n is the number of required params
                                         is_variadic = true;
                                         max_arg_index = Some(*n);
                                         break;
@@ -2845,6 +4502,7 @@ impl VM {
                 let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new("Stack underflow in TypeOf".to_string()))?;
                 let type_symbol = match value {
                     Value::Integer(_) => "integer",
+                    Value::BigInt(_) => "bigint",
                     Value::Float(_) => "float",
                     Value::Boolean(_) => "boolean",
                     Value::List(_) => "list",
@@ -2863,11 +4521,30 @@ impl VM {
                 self.instruction_pointer += 1;
             }
 
+            // (gensym) => G__N, (gensym "loop") => loop__N; one counter for both
             Instruction::GenSym => {
-                use std::sync::atomic::{AtomicUsize, Ordering};
-                static GENSYM_COUNTER: AtomicUsize = AtomicUsize::new(0);
-                let counter = GENSYM_COUNTER.fetch_add(1, Ordering::SeqCst);
-                let sym = format!("G__{}", counter);
+                let options = match self.value_stack.pop() {
+                    Some(Value::List(options)) => options.to_vec(),
+                    _ => return Err(RuntimeError::new("Stack corruption: GenSym expects a list of options".to_string())),
+                };
+                let prefix = match options.as_slice() {
+                    [] => "G".to_string(),
+                    [Value::String(prefix)] => prefix.to_string(),
+                    [other] => {
+                        return Err(RuntimeError::new(format!(
+                            "Type error: 'gensym' prefix must be a string, got {}",
+                            Self::type_name(other)
+                        )));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(format!(
+                            "'gensym' expects at most 1 argument (a prefix string), got {}",
+                            options.len()
+                        )));
+                    }
+                };
+                let sym = format!("{}__{}", prefix, self.gensym_counter);
+                self.gensym_counter += 1;
                 self.value_stack.push(Value::Symbol(Arc::new(sym)));
                 self.instruction_pointer += 1;
             }
@@ -2886,6 +4563,7 @@ impl VM {
 
                 match list {
                     Value::List(items) => {
+                        Self::require_proper_list("pmap", &items)?;
                         // Collect items into a vector for parallel processing
                         let vec: Vec<Value> = items.iter().cloned().collect();
 
@@ -2955,6 +4633,7 @@ impl VM {
 
                 match list {
                     Value::List(items) => {
+                        Self::require_proper_list("pfilter", &items)?;
                         let vec: Vec<Value> = items.iter().cloned().collect();
 
                         let (func_bytecode, func_params, func_rest, func_captured) = match &predicate {
@@ -3025,6 +4704,7 @@ impl VM {
 
                 match list {
                     Value::List(items) => {
+                        Self::require_proper_list("preduce", &items)?;
                         let vec: Vec<Value> = items.iter().cloned().collect();
 
                         // Handle empty list: just return the initial value
@@ -4103,28 +5783,422 @@ impl VM {
         Ok(())
     }
 
-    fn type_name(value: &Value) -> &str {
+    /// NaN policy: `==` and `!=` follow IEEE 754 (NaN is unequal to everything, itself
+    /// included), but ordering comparisons reject NaN instead of silently answering
+    /// false, so sorting or min/max over data containing NaN fails loudly.
+    fn check_orderable(op: &str, a: &Value, b: &Value) -> Result<(), RuntimeError> {
+        let is_nan = |v: &Value| matches!(v, Value::Float(f) if f.is_nan());
+        if is_nan(a) || is_nan(b) {
+            return Err(RuntimeError::with_suggestion(
+                format!("'{}' cannot order NaN", op),
+                "Check for NaN with (nan? x) before comparing.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Pop the (function, string) arguments shared by the char iteration builtins
+    fn pop_char_callback_args(&mut self, name: &str) -> Result<(Arc<String>, Value), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let text = self.value_stack.pop().ok_or_else(underflow)?;
+        let callable = self.value_stack.pop().ok_or_else(underflow)?;
+        match (callable, text) {
+            (callable @ (Value::Function(_) | Value::Closure(_)), Value::String(s)) => Ok((s, callable)),
+            (callable, text) => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects a function and a string, got {} and {}",
+                name,
+                Self::type_name(&callable),
+                Self::type_name(&text)
+            ))),
+        }
+    }
+
+    /// string->number: an integer in the given radix, or in base 10 a float
+    /// when there is a decimal point or exponent. Anything else is false so
+    /// callers can branch on it
+    fn parse_number(s: &str, radix: u32) -> Value {
+        if let Some(n) = BigInt::parse_radix(s, radix) {
+            return Value::from_bigint(n);
+        }
+        // Only plain decimal notation; f64's parser would also take "inf" and "NaN"
+        let decimal = s.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'));
+        let has_fraction = s.contains(['.', 'e', 'E']);
+        if radix == 10 && decimal && has_fraction && s.chars().any(|c| c.is_ascii_digit()) {
+            if let Ok(f) = s.parse::<f64>() {
+                return Value::Float(f);
+            }
+        }
+        Value::Boolean(false)
+    }
+
+    /// Stable merge sort with a fallible "sorts before" test. An element
+    /// from the right half only goes first when it is strictly less, so
+    /// equal elements keep their order. Unlike slice::sort_by this never
+    /// panics when a user comparator is not a total order.
+    fn merge_sort(
+        mut items: Vec<Value>,
+        less: &mut impl FnMut(&Value, &Value) -> Result<bool, RuntimeError>,
+    ) -> Result<Vec<Value>, RuntimeError> {
+        if items.len() < 2 {
+            return Ok(items);
+        }
+        let right = items.split_off(items.len() / 2);
+        let left = Self::merge_sort(items, less)?;
+        let right = Self::merge_sort(right, less)?;
+
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let mut left = left.into_iter().peekable();
+        let mut right = right.into_iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            if less(r, l)? {
+                merged.extend(right.next());
+            } else {
+                merged.extend(left.next());
+            }
+        }
+        merged.extend(left);
+        merged.extend(right);
+        Ok(merged)
+    }
+
+    /// Numeric `<` for sort's default order
+    fn numeric_less(a: &Value, b: &Value) -> Result<bool, RuntimeError> {
+        Self::check_orderable("sort", a, b)?;
+        match (a, b) {
+            (Value::Integer(x), Value::Integer(y)) => Ok(x < y),
+            (Value::Float(x), Value::Float(y)) => Ok(x < y),
+            (Value::Integer(x), Value::Float(y)) => Ok((*x as f64) < *y),
+            (Value::Float(x), Value::Integer(y)) => Ok(*x < (*y as f64)),
+            (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                Ok(Self::compare_bigint_operands("sort", a, b)? == Ordering::Less)
+            }
+            _ => Err(RuntimeError::with_suggestion(
+                format!("Type error: 'sort' without a comparator expects numbers, got {} and {}", Self::type_name(a), Self::type_name(b)),
+                "Pass a comparator to sort other values: (sort xs (lambda (a b) ...))".to_string(),
+            )),
+        }
+    }
+
+    /// Equality used by '=' and the list lookups: numbers compare across
+    /// Integer and Float, and floats follow IEEE 754 (NaN is never equal,
+    /// see check_orderable). Everything else uses structural PartialEq.
+    fn values_equal(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Integer(x), Value::Integer(y)) => x == y,
+            (Value::Float(x), Value::Float(y)) => x == y,
+            (Value::Integer(x), Value::Float(y)) => *x as f64 == *y,
+            (Value::Float(x), Value::Integer(y)) => *x == *y as f64,
+            (Value::BigInt(x), Value::Float(y)) | (Value::Float(y), Value::BigInt(x)) => x.to_f64() == *y,
+            _ => a == b,
+        }
+    }
+
+    /// Reject an improper list like (1 . 2) where a builtin walks the whole
+    /// list: iterating it would quietly drop the atom after the dot
+    fn require_proper_list(name: &str, list: &List) -> Result<(), RuntimeError> {
+        if list.is_proper() {
+            return Ok(());
+        }
+        let atom = list.dotted_tail().map(Self::format_value).unwrap_or_default();
+        Err(RuntimeError::new(format!(
+            "Type error: '{}' expects a proper list, got an improper list ending in . {}",
+            name, atom
+        )))
+    }
+
+    /// Pop a single list argument for a list builtin
+    fn pop_list_arg(&mut self, name: &str) -> Result<List, RuntimeError> {
+        let value = self.value_stack.pop()
+            .ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+        match value {
+            Value::List(items) => {
+                Self::require_proper_list(name, &items)?;
+                Ok(items)
+            }
+            other => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects a list, got {}",
+                name,
+                Self::type_name(&other)
+            ))),
+        }
+    }
+
+    /// Pop the (item, list) arguments shared by member? and assoc
+    fn pop_lookup_args(&mut self, name: &str) -> Result<(Value, List), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let list = self.value_stack.pop().ok_or_else(underflow)?;
+        let item = self.value_stack.pop().ok_or_else(underflow)?;
+        match list {
+            Value::List(items) => {
+                Self::require_proper_list(name, &items)?;
+                Ok((item, items))
+            }
+            other => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects a list as its second argument, got {}",
+                name,
+                Self::type_name(&other)
+            ))),
+        }
+    }
+
+    /// The value stored in an alist entry: the atom of a dotted pair (k . v),
+    /// the second item of a list entry (k v), or '() for a bare (k)
+    fn alist_entry_value(pair: &List) -> Value {
+        match &pair.cdr() {
+            Some(List::Dotted(atom)) => (**atom).clone(),
+            Some(rest) => rest.car().cloned().unwrap_or(Value::List(List::Nil)),
+            None => Value::List(List::Nil),
+        }
+    }
+
+    /// Rebuild an alist entry with a new value, keeping its shape: a dotted
+    /// pair stays dotted and a list entry keeps any items after the value
+    fn with_alist_entry_value(pair: &List, value: Value) -> List {
+        let key = pair.car().cloned().unwrap_or(Value::List(List::Nil));
+        let tail = match &pair.cdr() {
+            Some(List::Dotted(_)) => List::Dotted(Box::new(value)),
+            Some(List::Cons(cell)) => List::cons(value, cell.tail.clone()),
+            _ => List::cons(value, List::Nil),
+        };
+        List::cons(key, tail)
+    }
+
+    /// Copy a letrec member with its first group.len() captures set to the
+    /// group's templates. The templates keep their placeholder slots, so no
+    /// closure ever contains itself; siblings are re-tied each time one is
+    /// loaded (LoadLetrec)
+    fn tie_letrec(template: &Value, group: &[Value]) -> Result<Value, RuntimeError> {
+        match template {
+            Value::Closure(data) => {
+                let mut data = Arc::clone(data);
+                for (slot, member) in Arc::make_mut(&mut data).captured.iter_mut().zip(group) {
+                    slot.1 = member.clone();
+                }
+                Ok(Value::Closure(data))
+            }
+            other => Err(RuntimeError::new(format!(
+                "letrec binding must be a closure, got {}",
+                Self::type_name(other)
+            ))),
+        }
+    }
+
+    /// Pop the (count, list) arguments shared by take and drop
+    fn pop_count_and_list(&mut self, name: &str) -> Result<(usize, List), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let list = self.value_stack.pop().ok_or_else(underflow)?;
+        let count = self.value_stack.pop().ok_or_else(underflow)?;
+        match (count, list) {
+            (Value::Integer(n), Value::List(_)) if n < 0 => {
+                Err(RuntimeError::new(format!("'{}' count cannot be negative: {}", name, n)))
+            }
+            (Value::Integer(n), Value::List(items)) => Ok((n as usize, items)),
+            (count, list) => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects an integer and a list, got {} and {}",
+                name,
+                Self::type_name(&count),
+                Self::type_name(&list)
+            ))),
+        }
+    }
+
+    /// Pop the (function, list) arguments shared by map and filter
+    fn pop_list_callback_args(&mut self, name: &str) -> Result<(List, Value), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let list = self.value_stack.pop().ok_or_else(underflow)?;
+        let callable = self.value_stack.pop().ok_or_else(underflow)?;
+        match (callable, list) {
+            (callable @ (Value::Function(_) | Value::Closure(_)), Value::List(items)) => {
+                Self::require_proper_list(name, &items)?;
+                Ok((items, callable))
+            }
+            (callable, list) => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects a function and a list, got {} and {}",
+                name,
+                Self::type_name(&callable),
+                Self::type_name(&list)
+            ))),
+        }
+    }
+
+    /// Pop the (function, vector) arguments shared by vector-map and vector-for-each
+    fn pop_vector_callback_args(&mut self, name: &str) -> Result<(Arc<Vec<Value>>, Value), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let vector = self.value_stack.pop().ok_or_else(underflow)?;
+        let callable = self.value_stack.pop().ok_or_else(underflow)?;
+        match (callable, vector) {
+            (callable @ (Value::Function(_) | Value::Closure(_)), Value::Vector(items)) => Ok((items, callable)),
+            (callable, vector) => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects a function and a vector, got {} and {}",
+                name,
+                Self::type_name(&callable),
+                Self::type_name(&vector)
+            ))),
+        }
+    }
+
+    /// In strict mode, arithmetic on one integer and one float is an error
+    /// instead of an implicit promotion to float
+    fn check_strict_coercion(&self, op: &str, a: &Value, b: &Value) -> Result<(), RuntimeError> {
+        let is_integer = |v: &Value| matches!(v, Value::Integer(_) | Value::BigInt(_));
+        if self.strict_mode && ((is_integer(a) && b.is_float()) || (a.is_float() && is_integer(b))) {
+            return Err(RuntimeError::with_suggestion(
+                format!("Type error: '{}' cannot mix {} and {} in strict mode", op, a.type_name(), b.type_name()),
+                "Convert explicitly with 'int->float' or 'float->int'".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Pick the result of an integer operation: `checked` is None when it
+    /// overflowed, which is an error with checked arithmetic enabled and
+    /// otherwise computes the exact result as a bigint with `promote`
+    fn integer_result(&self, op: &str, checked: Option<i64>, promote: impl FnOnce() -> BigInt) -> Result<Value, RuntimeError> {
+        match checked {
+            Some(n) => Ok(Value::Integer(n)),
+            None if self.checked_arithmetic => Err(RuntimeError::with_suggestion(
+                format!("Arithmetic error: integer overflow in '{}'", op),
+                "The result does not fit in a 64-bit integer; turn off checked arithmetic to get a bigint.".to_string(),
+            )),
+            None => Ok(Value::from_bigint(promote())),
+        }
+    }
+
+    /// Arithmetic where at least one operand is a bigint. Two integers give
+    /// an exact result (division truncates, like i64); a float operand makes
+    /// the result a float
+    fn bigint_arith(op: &str, a: &Value, b: &Value) -> Result<Value, RuntimeError> {
+        let zero_divisor = || {
+            let what = if op == "/" { "Division" } else { "Modulo" };
+            RuntimeError::new(format!("{} by zero", what))
+        };
+        if let (Some(x), Some(y)) = (Self::as_exact_integer(a), Self::as_exact_integer(b)) {
+            let result = match op {
+                "+" => &x + &y,
+                "-" => &x - &y,
+                "*" => &x * &y,
+                "/" => x.div_rem(&y).ok_or_else(zero_divisor)?.0,
+                _ => x.div_rem(&y).ok_or_else(zero_divisor)?.1,
+            };
+            return Ok(Value::from_bigint(result));
+        }
+        let (x, y) = match (Self::numeric_as_f64(a), Self::numeric_as_f64(b)) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                return Err(RuntimeError::new(format!(
+                    "Type error: '{}' expects two numbers, got {} and {}",
+                    op,
+                    Self::type_name(a),
+                    Self::type_name(b)
+                )));
+            }
+        };
+        if matches!(op, "/" | "%") && y == 0.0 {
+            return Err(zero_divisor());
+        }
+        Ok(Value::Float(match op {
+            "+" => x + y,
+            "-" => x - y,
+            "*" => x * y,
+            "/" => x / y,
+            _ => x % y,
+        }))
+    }
+
+    /// Order two numbers when at least one is a bigint
+    fn compare_bigint_operands(op: &str, a: &Value, b: &Value) -> Result<Ordering, RuntimeError> {
+        let ordering = match (a, b) {
+            (Value::BigInt(x), Value::BigInt(y)) => Some(x.as_ref().cmp(y)),
+            (Value::BigInt(x), Value::Integer(y)) => Some(x.as_ref().cmp(&BigInt::from(*y))),
+            (Value::Integer(x), Value::BigInt(y)) => Some(BigInt::from(*x).cmp(y)),
+            _ => match (Self::numeric_as_f64(a), Self::numeric_as_f64(b)) {
+                (Some(x), Some(y)) => x.partial_cmp(&y),
+                _ => None,
+            },
+        };
+        ordering.ok_or_else(|| RuntimeError::new(format!(
+            "Type error: '{}' expects two numbers, got {} and {}",
+            op,
+            Self::type_name(a),
+            Self::type_name(b)
+        )))
+    }
+
+    /// Pop a number and push it rounded to an integer by `round` (floor, ceil,
+    /// round). Integers pass through; floats beyond i64 range become bigints
+    fn round_to_integer(&mut self, name: &str, round: fn(f64) -> f64) -> Result<(), RuntimeError> {
+        let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+        let result = match value {
+            Value::Integer(_) | Value::BigInt(_) => value,
+            Value::Float(f) => {
+                let exact = BigInt::from_f64(round(f)).ok_or_else(|| RuntimeError::with_suggestion(
+                    format!("'{}' cannot turn {} into an integer", name, f),
+                    "Check for NaN and infinity with (nan? x) and (infinite? x) first.".to_string(),
+                ))?;
+                Value::from_bigint(exact)
+            }
+            _ => {
+                return Err(RuntimeError::new(format!(
+                    "Type error: '{}' expects a number, got {}",
+                    name,
+                    Self::type_name(&value)
+                )));
+            }
+        };
+        self.value_stack.push(result);
+        self.instruction_pointer += 1;
+        Ok(())
+    }
+
+    /// Euclid's algorithm; gcd(0, 0) is 0
+    fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// gcd or lcm (non-negative, 0 when either operand is 0) of two bigints
+    fn bigint_gcd_lcm(x: BigInt, y: BigInt, lcm: bool) -> BigInt {
+        let abs = |n: BigInt| if n.is_negative() { -&n } else { n };
+        let (x, y) = (abs(x), abs(y));
+        let (mut a, mut b) = (x.clone(), y.clone());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).map(|(_, r)| r).unwrap_or_else(BigInt::zero);
+            (a, b) = (b, r);
+        }
+        if !lcm || a.is_zero() {
+            a
+        } else {
+            &x.div_rem(&a).map(|(q, _)| q).unwrap_or_else(BigInt::zero) * &y
+        }
+    }
+
+    fn as_exact_integer(value: &Value) -> Option<BigInt> {
         match value {
-            Value::Integer(_) => "integer",
-            Value::Float(_) => "float",
-            Value::Boolean(_) => "boolean",
-            Value::List(_) => "list",
-            Value::Symbol(_) => "symbol",
-            Value::String(_) => "string",
-            Value::Function(_) => "function",
-            Value::Closure { .. } => "closure",
-            Value::HashMap(_) => "hashmap",
-            Value::Vector(_) => "vector",
-            Value::TcpListener(_) => "tcp-listener",
-            Value::TcpStream(_) => "tcp-stream",
-            Value::SharedTcpListener(_) => "shared-tcp-listener",
-            Value::Pointer(_) => "pointer",
+            Value::Integer(n) => Some(BigInt::from(*n)),
+            Value::BigInt(n) => Some(n.as_ref().clone()),
+            _ => None,
         }
     }
 
+    fn numeric_as_f64(value: &Value) -> Option<f64> {
+        match value {
+            Value::Integer(n) => Some(*n as f64),
+            Value::BigInt(n) => Some(n.to_f64()),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    fn type_name(value: &Value) -> &str {
+        value.type_name()
+    }
+
     fn format_value(value: &Value) -> String {
         match value {
             Value::Integer(n) => n.to_string(),
+            Value::BigInt(n) => n.to_string(),
             Value::Float(f) => {
                 // Format float nicely - show decimal point even for whole numbers
                 if f.fract() == 0.0 && f.is_finite() {
@@ -4139,14 +6213,15 @@ impl VM {
                     .iter()
                     .map(|v| Self::format_value(v))
                     .collect();
-                format!("({})", formatted_items.join(" "))
+                match list.dotted_tail() {
+                    Some(atom) => format!("({} . {})", formatted_items.join(" "), Self::format_value(atom)),
+                    None => format!("({})", formatted_items.join(" ")),
+                }
             }
             Value::Symbol(s) => s.to_string(),
             Value::String(s) => format!("\"{}\"", s),
             Value::Function(name) => format!("<function {}>", name),
-            Value::Closure(closure_data) => {
-                format!("<closure/{}>", closure_data.params.len())
-            }
+            Value::Closure(closure_data) => closure_data.describe(),
             Value::HashMap(map) => {
                 let mut items: Vec<String> = map.iter()
                     .map(|(k, v)| format!("{} {}", Self::format_value(&Value::String(Arc::new(k.clone()))), Self::format_value(v)))
@@ -4168,10 +6243,313 @@ impl VM {
         }
     }
 
+    /// Write the trace line for the instruction at `ip`, about to execute:
+    /// "TRACE depth=<frames> fn=<function> ip=<ip> <instruction> stack=[...]".
+    /// The stack shows its top three values, top last, with ".." when
+    /// there are more below.
+    fn trace_instruction(&mut self, ip: usize) {
+        use std::io::Write;
+
+        let function = self.call_stack.last().map_or("<main>", |frame| frame.function_name.as_str());
+        let shown = self.value_stack.len().min(3);
+        let mut stack: Vec<String> = self.value_stack[self.value_stack.len() - shown..]
+            .iter()
+            .map(Self::format_value)
+            .collect();
+        if self.value_stack.len() > shown {
+            stack.insert(0, "..".to_string());
+        }
+        let line = format!(
+            "TRACE depth={} fn={} ip={} {} stack=[{}]",
+            self.call_stack.len(),
+            function,
+            ip,
+            self.current_bytecode[ip],
+            stack.join(" ")
+        );
+        // Best-effort like logging: a broken destination must not abort the program
+        let _ = writeln!(self.log_writer, "{}", line);
+    }
+
+    /// Shared by PrintList and DisplayList: pop a list of values, write them
+    /// as one line and push the last value (or '() when there are none)
+    fn print_list(&mut self, display: bool) -> Result<(), RuntimeError> {
+        let name = if display { "display" } else { "print" };
+        let value = self.value_stack.pop().ok_or_else(|| RuntimeError::new(format!("Stack underflow in {}", name)))?;
+        let values = match value {
+            Value::List(list) => list.to_vec(),
+            other => {
+                return Err(RuntimeError::new(format!(
+                    "Type error: '{}' expects a list of values, got {}",
+                    name,
+                    Self::type_name(&other)
+                )));
+            }
+        };
+        println!("{}", Self::format_print_line(&values, display));
+        self.value_stack.push(values.last().cloned().unwrap_or(Value::List(List::Nil)));
+        self.instruction_pointer += 1;
+        Ok(())
+    }
+
+    /// Check a closure call's argument count and build the frame locals,
+    /// packing extra arguments into a list for a variadic closure
+    fn closure_locals(closure_data: &ClosureData, mut args: Vec<Value>) -> Result<Vec<Value>, RuntimeError> {
+        match &closure_data.rest_param {
+            None => {
+                // Regular closure - exact arity match required
+                if closure_data.params.len() != args.len() {
+                    return Err(RuntimeError::new(format!(
+                        "Closure arity mismatch: expected {} argument(s), got {}",
+                        closure_data.params.len(),
+                        args.len()
+                    )));
+                }
+            }
+            Some(_rest_name) => {
+                // Variadic closure - need at least the required params
+                if args.len() < closure_data.params.len() {
+                    return Err(RuntimeError::new(format!(
+                        "Variadic closure arity mismatch: expected at least {} argument(s), got {}",
+                        closure_data.params.len(),
+                        args.len()
+                    )));
+                }
+                // Pack extra args into a list and append to args
+                let rest_args: Vec<Value> = args.drain(closure_data.params.len()..).collect();
+                args.push(Value::List(List::from_vec(rest_args)));
+            }
+        }
+        Ok(args)
+    }
+
+    /// Shared by Shl and Shr. Bits shifted out are lost; >> keeps the sign.
+    fn shift(&mut self, left: bool) -> Result<(), RuntimeError> {
+        let name = if left { "<<" } else { ">>" };
+        let (a, n) = self.pop_integer_operands(name)?;
+        if !(0..64).contains(&n) {
+            return Err(RuntimeError::with_suggestion(
+                format!("Shift amount out of range in '{}': {}", name, n),
+                "Integers are 64 bits wide, so shift by 0 to 63 places".to_string(),
+            ));
+        }
+        self.value_stack.push(Value::Integer(if left { a << n } else { a >> n }));
+        self.instruction_pointer += 1;
+        Ok(())
+    }
+
+    /// A bit operation where at least one operand is a bigint. A bigint has
+    /// no fixed width, so shifts take any amount that fits in a u32
+    fn bigint_bitwise(&mut self, name: &str) -> Result<(), RuntimeError> {
+        let underflow = || RuntimeError::new(format!("Stack underflow in '{}'", name));
+        let b = self.value_stack.pop().ok_or_else(underflow)?;
+        let a = self.value_stack.pop().ok_or_else(underflow)?;
+        let (x, y) = match (Self::as_exact_integer(&a), Self::as_exact_integer(&b)) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                return Err(RuntimeError::new(format!(
+                    "Type error: '{}' expects two integers, got {} and {}",
+                    name,
+                    Self::type_name(&a),
+                    Self::type_name(&b)
+                )));
+            }
+        };
+        let result = match name {
+            "bit-and" => &x & &y,
+            "bit-or" => &x | &y,
+            "bit-xor" => &x ^ &y,
+            _ => {
+                let bits = y.to_i64().and_then(|n| u32::try_from(n).ok()).ok_or_else(|| RuntimeError::new(format!(
+                    "Shift amount out of range in '{}': {}",
+                    name, y
+                )))?;
+                if name == "<<" { x.shl(bits) } else { x.shr(bits) }
+            }
+        };
+        self.value_stack.push(Value::from_bigint(result));
+        self.instruction_pointer += 1;
+        Ok(())
+    }
+
+    /// Pop the argument of a char-* instruction: a string of exactly one character
+    fn pop_char(&mut self, name: &str) -> Result<char, RuntimeError> {
+        let value = self.value_stack.pop()
+            .ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+        let s = match &value {
+            Value::String(s) => s,
+            _ => {
+                return Err(RuntimeError::new(format!(
+                    "Type error: '{}' expects a string, got {}",
+                    name,
+                    Self::type_name(&value)
+                )));
+            }
+        };
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(RuntimeError::new(format!(
+                "'{}' expects a single-character string, got {} characters",
+                name,
+                s.chars().count()
+            ))),
+        }
+    }
+
+    /// Pop the two integer operands of a bitwise instruction, as (a, b)
+    fn pop_integer_operands(&mut self, name: &str) -> Result<(i64, i64), RuntimeError> {
+        let b = self.value_stack.pop().ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+        let a = self.value_stack.pop().ok_or_else(|| RuntimeError::new(format!("Stack underflow in '{}'", name)))?;
+        match (&a, &b) {
+            (Value::Integer(x), Value::Integer(y)) => Ok((*x, *y)),
+            _ => Err(RuntimeError::new(format!(
+                "Type error: '{}' expects two integers, got {} and {}",
+                name,
+                Self::type_name(&a),
+                Self::type_name(&b)
+            ))),
+        }
+    }
+
+    /// Format an integer, given in decimal, for `format-int`. The separator
+    /// goes between groups of three digits; a minus sign stays in front of
+    /// the first digit. Padding fills up to `width` characters: a '0' pad
+    /// goes between the sign and the digits (and is not grouped), any other
+    /// pad goes before the sign.
+    fn format_int(n: &str, grouping: &str, width: usize, pad: char) -> String {
+        let (sign, digits) = match n.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", n),
+        };
+        let mut body = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                body.push_str(grouping);
+            }
+            body.push(digit);
+        }
+
+        let len = sign.len() + body.chars().count();
+        let fill: String = std::iter::repeat_n(pad, width.saturating_sub(len)).collect();
+        if pad == '0' {
+            format!("{}{}{}", sign, fill, body)
+        } else {
+            format!("{}{}{}", fill, sign, body)
+        }
+    }
+
+    /// Fill each {} in a format template with the next value in display form
+    fn format_placeholders(fmt: &str, values: &[Value]) -> Result<String, RuntimeError> {
+        let mut result = String::new();
+        let mut chars = fmt.chars().peekable();
+        let mut arg_iter = values.iter();
+
+        while let Some(ch) = chars.next() {
+            if ch == '{' && chars.peek() == Some(&'}') {
+                chars.next(); // consume '}'
+                // Replace {} with next argument
+                match arg_iter.next() {
+                    Some(arg_value) => result.push_str(&Self::value_to_display_string(arg_value)),
+                    None => {
+                        return Err(RuntimeError::with_suggestion(
+                            "Not enough arguments for format placeholders".to_string(),
+                            "The format string has more {} placeholders than provided arguments. Make sure the list has enough values.".to_string(),
+                        ));
+                    }
+                }
+            } else {
+                result.push(ch);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Expand the directives in a format template: ~a (display form), ~s
+    /// (print form, strings quoted), ~d (integer), ~% (newline) and ~~ (a
+    /// literal tilde). Every value has to be used exactly once.
+    fn format_directives(fmt: &str, values: &[Value]) -> Result<String, RuntimeError> {
+        let mut result = String::new();
+        let mut chars = fmt.chars();
+        let mut arg_iter = values.iter();
+        let mut used = 0;
+
+        while let Some(ch) = chars.next() {
+            if ch != '~' {
+                result.push(ch);
+                continue;
+            }
+            let directive = chars.next().ok_or_else(|| RuntimeError::with_suggestion(
+                "Format template ends with an unfinished '~' directive".to_string(),
+                "Write ~~ for a literal tilde".to_string(),
+            ))?;
+            match directive {
+                '%' => result.push('\n'),
+                '~' => result.push('~'),
+                'a' | 's' | 'd' => {
+                    let value = arg_iter.next().ok_or_else(|| RuntimeError::new(format!(
+                        "Not enough arguments for format directives: '{}' needs more than {}",
+                        fmt, values.len()
+                    )))?;
+                    used += 1;
+                    match (directive, value) {
+                        ('a', _) => result.push_str(&Self::value_to_display_string(value)),
+                        ('s', _) => result.push_str(&Self::format_value(value)),
+                        (_, Value::Integer(n)) => result.push_str(&n.to_string()),
+                        (_, Value::BigInt(n)) => result.push_str(&n.to_string()),
+                        _ => {
+                            return Err(RuntimeError::new(format!(
+                                "Type error: format directive ~d expects an integer, got {}",
+                                Self::type_name(value)
+                            )));
+                        }
+                    }
+                }
+                other => {
+                    return Err(RuntimeError::with_suggestion(
+                        format!("Unknown format directive '~{}'", other),
+                        "Supported directives are ~a, ~s, ~d, ~% and ~~".to_string(),
+                    ));
+                }
+            }
+        }
+
+        if used < values.len() {
+            return Err(RuntimeError::new(format!(
+                "Too many arguments for format directives: '{}' uses {}, got {}",
+                fmt, used, values.len()
+            )));
+        }
+        Ok(result)
+    }
+
+    /// Shared by Quotient and Modulo
+    fn check_integer_divisor(name: &str, divisor: i64) -> Result<(), RuntimeError> {
+        if divisor == 0 {
+            return Err(RuntimeError::with_suggestion(
+                format!("Division by zero in '{}'", name),
+                format!("Check your divisor first. You can use an if-expression: (if (== y 0) 0 ({} x y))", name),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The line `print` (or `display`, when `display` is true) writes for
+    /// `values`, without the trailing newline: each value formatted on its
+    /// own and separated by one space. `print` writes strings quoted;
+    /// `display` writes them as plain text.
+    pub fn format_print_line(values: &[Value], display: bool) -> String {
+        let formatted: Vec<String> = values.iter()
+            .map(|v| if display { Self::value_to_display_string(v) } else { Self::format_value(v) })
+            .collect();
+        formatted.join(" ")
+    }
+
     /// Format value for display in format strings (strings without quotes)
     fn value_to_display_string(value: &Value) -> String {
         match value {
             Value::Integer(n) => n.to_string(),
+            Value::BigInt(n) => n.to_string(),
             Value::Float(f) => {
                 if f.fract() == 0.0 && f.is_finite() {
                     format!("{}.0", f)
@@ -4187,12 +6565,13 @@ impl VM {
                     .iter()
                     .map(|v| Self::value_to_display_string(v))
                     .collect();
-                format!("({})", formatted_items.join(" "))
+                match list.dotted_tail() {
+                    Some(atom) => format!("({} . {})", formatted_items.join(" "), Self::value_to_display_string(atom)),
+                    None => format!("({})", formatted_items.join(" ")),
+                }
             }
             Value::Function(name) => format!("<function {}>", name),
-            Value::Closure(closure_data) => {
-                format!("<closure/{}>", closure_data.params.len())
-            }
+            Value::Closure(closure_data) => closure_data.describe(),
             Value::HashMap(map) => {
                 let mut items: Vec<String> = map.iter()
                     .map(|(k, v)| format!("{} {}", k, Self::value_to_display_string(v)))
@@ -4214,10 +6593,23 @@ impl VM {
         }
     }
 
+    /// Format a log line as "[<UTC timestamp>] [<LEVEL>] <message>".
+    /// String messages are written without quotes.
+    fn format_log_line(level: LogLevel, message: &Value) -> String {
+        let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+        format!(
+            "[{}] [{}] {}",
+            timestamp,
+            level.name().to_ascii_uppercase(),
+            Self::value_to_display_string(message)
+        )
+    }
+
     pub fn run(&mut self) -> Result<(), RuntimeError> {
+        self.verify_before_run()?;
         while !self.halted {
             // Execute instruction and capture stack trace on error
-            if let Err(mut error) = self.execute_one_instruction() {
+            if let Err(mut error) = self.execute_with_handlers(0) {
                 // If the error doesn't already have a call stack, add it
                 if error.call_stack.is_empty() {
                     error.call_stack = self.get_stack_trace();
@@ -4228,6 +6620,193 @@ impl VM {
         Ok(())
     }
 
+    fn verify_before_run(&self) -> Result<(), RuntimeError> {
+        if self.verify_bytecode {
+            let main_args = self.call_stack.last().map_or(0, |frame| frame.locals.len());
+            verifier::verify_program(&self.functions, &self.current_bytecode, main_args, &HashMap::new())?;
+        }
+        Ok(())
+    }
+
+    /// Execute the next instruction and report what it did, for single-stepping
+    /// a program. Errors are handled as in `run`: an active try handler
+    /// catches them, otherwise they are returned with the call stack attached.
+    pub fn step(&mut self) -> Result<StepInfo, RuntimeError> {
+        if self.halted {
+            return Err(RuntimeError::new("Cannot step: the program has halted".to_string()));
+        }
+        let ip = self.instruction_pointer;
+        // Running off the end of the bytecode halts, like an explicit Halt
+        let instruction = self.current_bytecode.get(ip).cloned().unwrap_or(Instruction::Halt);
+        let function = self.call_stack.last().map_or("<main>", |frame| frame.function_name.as_str()).to_string();
+        let depth = self.call_stack.len();
+
+        self.resuming_from_breakpoint = false;
+        if let Err(mut error) = self.execute_with_handlers(0) {
+            if error.call_stack.is_empty() {
+                error.call_stack = self.get_stack_trace();
+            }
+            return Err(error);
+        }
+
+        let shown = self.value_stack.len().min(3);
+        Ok(StepInfo {
+            instruction,
+            ip,
+            instruction_pointer: self.instruction_pointer,
+            function,
+            depth,
+            stack_top: self.value_stack[self.value_stack.len() - shown..].to_vec(),
+        })
+    }
+
+    /// Run like `run`, calling `hook` after every instruction. Before a call
+    /// into a function named in `breakpoints` it pauses and returns
+    /// `StopReason::Breakpoint`; calling `run_with_hook` (or `step`) again
+    /// continues from that call.
+    pub fn run_with_hook(&mut self, mut hook: impl FnMut(&StepInfo)) -> Result<StopReason, RuntimeError> {
+        if !self.resuming_from_breakpoint {
+            self.verify_before_run()?;
+        }
+        while !self.halted {
+            if !self.resuming_from_breakpoint {
+                if let Some(name) = self.next_callee().filter(|name| self.breakpoints.contains(name)) {
+                    self.resuming_from_breakpoint = true;
+                    return Ok(StopReason::Breakpoint(name));
+                }
+            }
+            let info = self.step()?;
+            hook(&info);
+        }
+        Ok(StopReason::Halted)
+    }
+
+    /// Pause `run_with_hook` before calls into the named function
+    pub fn set_breakpoint(&mut self, name: &str) {
+        self.breakpoints.insert(name.to_string());
+    }
+
+    /// Remove a breakpoint; returns whether it was set
+    pub fn clear_breakpoint(&mut self, name: &str) -> bool {
+        self.breakpoints.remove(name)
+    }
+
+    /// The named function the next instruction calls, if it is a call. A
+    /// closure call only has a name when the callee is a function value.
+    fn next_callee(&self) -> Option<String> {
+        match self.current_bytecode.get(self.instruction_pointer)? {
+            Instruction::Call(name, _) | Instruction::TailCall(name, _) => Some(name.clone()),
+            Instruction::CallClosure(argc) | Instruction::TailCallClosure(argc) => {
+                let callee_index = self.value_stack.len().checked_sub(argc + 1)?;
+                match &self.value_stack[callee_index] {
+                    Value::Function(name) => Some(name.to_string()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Execute one instruction; if it fails and a try handler above
+    /// `handler_base` is active, recover into the innermost one instead of
+    /// returning the error. Handlers at or below the base belong to an outer
+    /// run loop and are left for it.
+    fn execute_with_handlers(&mut self, handler_base: usize) -> Result<(), RuntimeError> {
+        let error = match self.execute_one_instruction() {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        if self.handlers.len() <= handler_base {
+            return Err(error);
+        }
+        let handler = self.handlers.pop().expect("handler above base");
+
+        // Drop the frames and values of everything that ran inside the try
+        self.call_stack.truncate(handler.call_depth);
+        self.value_stack.truncate(handler.stack_depth);
+        let caught = error.raised.map_or_else(|| Value::String(Arc::new(error.message)), |value| *value);
+        self.value_stack.push(caught);
+        self.current_bytecode = handler.bytecode;
+        self.instruction_pointer = handler.catch_address;
+        Ok(())
+    }
+
+    /// Run to completion and return the value of the last top-level
+    /// expression, for using the VM as an evaluator from Rust.
+    /// The value is popped off the stack; a program that leaves nothing
+    /// behind (e.g. only definitions) yields the empty list.
+    pub fn run_to_value(&mut self) -> Result<Value, RuntimeError> {
+        self.run()?;
+        Ok(self.value_stack.pop().unwrap_or(Value::List(List::Nil)))
+    }
+
+    /// Call a named function (user-defined or builtin) with the given
+    /// arguments and return its result. Usually called after `run` has
+    /// loaded the program's definitions. The VM is left as it was found,
+    /// including on error, so calls can be repeated.
+    pub fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        if !self.functions.contains_key(name) {
+            return Err(RuntimeError::new(format!("Undefined function '{}'", name)));
+        }
+        self.call_value(Value::function(name), args)
+    }
+
+    /// Call a function or closure value and run it to completion. Works both
+    /// from Rust and from inside an instruction that takes a callback (the
+    /// caller's bytecode and position are restored afterwards).
+    pub fn call_value(&mut self, callable: Value, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let name = match &callable {
+            Value::Function(name) => name.to_string(),
+            _ => "<closure>".to_string(),
+        };
+
+        // Dispatch through CallClosure so arity checks and rest parameters
+        // behave exactly as they do for calls in compiled code
+        let saved_bytecode = std::mem::replace(&mut self.current_bytecode, vec![Instruction::CallClosure(args.len())]);
+        let saved_ip = self.instruction_pointer;
+        let saved_halted = self.halted;
+        let call_depth = self.call_stack.len();
+        let stack_base = self.value_stack.len();
+        let handler_base = self.handlers.len();
+
+        self.value_stack.push(callable);
+        self.value_stack.extend(args);
+        self.instruction_pointer = 0;
+        self.halted = false;
+
+        // The call is done once its frame has been popped; tail calls reuse
+        // the frame, so the depth only drops on the final Ret
+        let mut outcome = self.execute_with_handlers(handler_base);
+        while outcome.is_ok() && self.call_stack.len() > call_depth {
+            if self.halted {
+                outcome = Err(RuntimeError::new(format!("Function '{}' halted before returning", name)));
+                break;
+            }
+            outcome = self.execute_with_handlers(handler_base);
+        }
+
+        let result = match outcome {
+            Ok(()) => self.value_stack.pop()
+                .ok_or_else(|| RuntimeError::new(format!("No return value from '{}'", name))),
+            Err(mut error) => {
+                if error.call_stack.is_empty() {
+                    error.call_stack = self.get_stack_trace();
+                }
+                Err(error)
+            }
+        };
+
+        if result.is_err() {
+            self.call_stack.truncate(call_depth);
+            self.value_stack.truncate(stack_base);
+            self.handlers.truncate(handler_base);
+        }
+        self.current_bytecode = saved_bytecode;
+        self.instruction_pointer = saved_ip;
+        self.halted = saved_halted;
+        result
+    }
+
     /// Execute a closure call in isolation (used for parallel operations)
     /// Returns the result value
     fn execute_closure_call(
@@ -4276,8 +6855,9 @@ impl VM {
         self.call_stack.push(frame);
 
         // Execute the bytecode
+        let handler_base = self.handlers.len();
         while !self.halted && self.instruction_pointer < self.current_bytecode.len() {
-            self.execute_one_instruction()?;
+            self.execute_with_handlers(handler_base)?;
         }
 
         // Pop the call frame
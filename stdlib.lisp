@@ -6,26 +6,27 @@
 ;; List Utilities
 ;; ------------------------------------------------------------
 
-;; map: Apply function to each element of a list
-(defun map (f lst)
-  (if (null? lst)
-      '()
-      (cons (f (car lst))
-            (map f (cdr lst)))))
-
-;; filter: Keep only elements that satisfy predicate
-(defun filter (pred lst)
-  (if (null? lst)
+;; map, filter and reduce are native builtins:
+;;   (map f lst ...)        With several lists, f gets one element from each:
+;;                          (map + '(1 2) '(10 20)) => (11 22)
+;;   (filter pred lst)      Keep only elements that satisfy pred
+;;   (reduce f init lst)    Fold from the left: (f (f init a) b) ...
+;;   (foldr f init lst)     Fold from the right: (f a (f b ... (f z init)))
+
+;; for-each: Call function on each element for its side effects, return '()
+;; Accepts several lists like map, stopping at the shortest
+(defun for-each (f lst . more)
+  (if (null? more)
+      (for-each-multi f (list lst))
+      (for-each-multi f (cons lst more))))
+
+;; for-each-multi: Helper for for-each
+(defun for-each-multi (f lists)
+  (if (any? null? lists)
       '()
-      (if (pred (car lst))
-          (cons (car lst) (filter pred (cdr lst)))
-          (filter pred (cdr lst)))))
-
-;; reduce/fold: Reduce list with binary function and initial value
-(defun reduce (f init lst)
-  (if (null? lst)
-      init
-      (reduce f (f init (car lst)) (cdr lst))))
+      (do
+        (apply f (map car lists))
+        (for-each-multi f (map cdr lists)))))
 
 ;; length: Get length of a list
 (defun length (lst)
@@ -33,51 +34,24 @@
       0
       (+ 1 (length (cdr lst)))))
 
-;; reverse-helper: Helper for reverse
-(defun reverse-helper (lst acc)
-  (if (null? lst)
-      acc
-      (reverse-helper (cdr lst) (cons (car lst) acc))))
-
-;; reverse: Reverse a list
-(defun reverse (lst)
-  (reverse-helper lst '()))
-
-;; take: Take first n elements of a list
-(defun take (n lst)
-  (if (<= n 0)
-      '()
-      (if (null? lst)
-          '()
-          (cons (car lst) (take (- n 1) (cdr lst))))))
-
-;; drop: Drop first n elements of a list
-(defun drop (n lst)
-  (if (<= n 0)
-      lst
-      (if (null? lst)
-          '()
-          (drop (- n 1) (cdr lst)))))
+;; reverse, take and drop are native builtins:
+;; (reverse lst), (take n lst) and (drop n lst)
 
 ;; nth: Get nth element (0-indexed, alias for list-ref)
 (defun nth (lst n)
   (list-ref lst n))
 
-;; range: Create a list from start to end (exclusive)
-(defun range (start end)
-  (if (>= start end)
-      '()
-      (cons start (range (+ start 1) end))))
+;; range is a native builtin: (range end), (range start end) or
+;; (range start end step) lists integers up to end (exclusive)
 
-;; zip: Combine two lists into pairs
-;; Note: Uses builtin 'list' function
-(defun zip (lst1 lst2)
-  (if (null? lst1)
-      '()
-      (if (null? lst2)
-          '()
-          (cons (list (car lst1) (car lst2))
-                (zip (cdr lst1) (cdr lst2))))))
+;; zip, unzip and enumerate are native builtins:
+;;   (zip '(1 2 3) '(a b c))  => ((1 a) (2 b) (3 c)), stopping at the shorter list
+;;   (unzip '((1 a) (2 b)))   => ((1 2) (a b))
+;;   (enumerate '(a b))       => ((0 a) (1 b))
+
+;; flatten and remove-duplicates are native builtins:
+;;   (flatten '(1 (2 (3 4)) 5))       => (1 2 3 4 5)
+;;   (remove-duplicates '(3 1 3 2 1)) => (3 1 2), keeping first occurrences
 
 ;; all?: Check if all elements satisfy predicate
 (defun all? (pred lst)
@@ -157,9 +131,7 @@
 ;; Predicates
 ;; ------------------------------------------------------------
 
-;; null?: Check if list is empty
-(defun null? (lst)
-  (== (list-length lst) 0))
+;; null? is a builtin: true only for the empty list, false for any other value
 
 ;; empty?: Alias for null?
 (defun empty? (lst)
@@ -229,23 +201,13 @@
 (defun group-by (f lst)
   (group-by-helper f lst '()))
 
-;; sort: Sort a list using comparison function
-;; Uses a simple insertion sort
-(defun insert-sorted (cmp item lst)
-  (if (null? lst)
-      (list item)
-      (if (cmp item (car lst))
-          (cons item lst)
-          (cons (car lst) (insert-sorted cmp item (cdr lst))))))
-
-(defun sort (cmp lst)
-  (if (null? lst)
-      '()
-      (insert-sorted cmp (car lst) (sort cmp (cdr lst)))))
+;; sort is a native builtin: (sort lst) sorts numbers ascending,
+;; (sort lst cmp) takes cmp returning true when its first argument sorts first.
+;; The sort is stable.
 
 ;; sort-by: Sort a list by applying function to elements
 (defun sort-by (f cmp lst)
-  (sort (lambda (a b) (cmp (f a) (f b))) lst))
+  (sort lst (lambda (a b) (cmp (f a) (f b)))))
 
 ;; ------------------------------------------------------------
 ;; String Utilities
@@ -360,8 +322,8 @@
   (if (ok? r)
       (car (cdr r))
       (print (if (err? r)
-                 (string-append "PANIC: " (string-append message (string-append ": " (car (cdr r)))))
-                 (string-append "PANIC: " (string-append message ": not a result type"))))))
+                 (string-append "PANIC: " message ": " (car (cdr r)))
+                 (string-append "PANIC: " message ": not a result type")))))
 
 ;; expect-err: Extract error from err, panic with custom message on ok
 (defun expect-err (r message)
@@ -418,18 +380,11 @@
       (list 'let (list (list '__t_end__ (list 'current-timestamp)))
         (list 'let (list (list '__t_elapsed__ (list '- '__t_end__ '__t_start__)))
           (list 'do
-            (list 'print (list 'string-append
-                              (list 'string-append "Elapsed time: "
-                                    (list 'number->string '__t_elapsed__))
+            (list 'print (list 'string-append "Elapsed time: "
+                              (list 'number->string '__t_elapsed__)
                               " seconds"))
             '__t_result__))))))
 
-;; assert: Runtime assertion for testing and debugging
-;; Usage: (assert condition)
-;; Note: Simplified version - macros don't support variadic parameters
-(defmacro assert (condition)
-  `(if (not ,condition)
-       (do
-         (print "Assertion failed")
-         (err "Assertion failed"))
-       true))
+;; assert is a special form: (assert condition) or (assert condition "message")
+;; returns the condition's value, or fails with the message and source location
+;; when it is false
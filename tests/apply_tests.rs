@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -279,3 +280,195 @@ fn test_apply_with_variadic_recursive_sum() {
     let result = compile_and_run(source).unwrap();
     assert_eq!(result.trim(), "100");
 }
+
+// ==================== Multi-Arity Dispatch ====================
+
+#[test]
+fn test_apply_multi_arity_selects_one_arg_clause() {
+    let source = r#"
+        (defun area
+          ((r) (* r r))
+          ((w h) (* w h)))
+        (apply area (list 3))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "9");
+}
+
+#[test]
+fn test_apply_multi_arity_selects_two_arg_clause() {
+    let source = r#"
+        (defun area
+          ((r) (* r r))
+          ((w h) (* w h)))
+        (apply area (list 3 4))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "12");
+}
+
+#[test]
+fn test_apply_multi_arity_varying_arg_counts() {
+    // The same function applied with different argument counts in one run
+    let source = r#"
+        (defun describe
+          ((x) (list 'one x))
+          ((x y) (list 'two x y)))
+        (defun apply-each (calls)
+          (if (null? calls)
+              '()
+              (cons (apply describe (car calls)) (apply-each (cdr calls)))))
+        (apply-each (list (list 1) (list 1 2) (list 3)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "((one 1) (two 1 2) (one 3))");
+}
+
+#[test]
+fn test_apply_multi_arity_with_patterns() {
+    let source = r#"
+        (defun sum-pairs
+          ((()) 0)
+          (((x . rest)) (+ x (sum-pairs rest)))
+          ((a b) (+ a b)))
+        (list (apply sum-pairs (list (list 1 2 3))) (apply sum-pairs (list 4 5)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(6 9)");
+}
+
+// ==================== Call-Site Splat Tests ====================
+
+#[test]
+fn test_splat_last_argument() {
+    let source = r#"
+        (defun add3 (a b c) (+ a (+ b c)))
+        (def rest (list 2 3))
+        (add3 1 ...rest)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "6");
+}
+
+#[test]
+fn test_splat_in_middle_and_multiple_splats() {
+    // Each splat is spliced where it stands
+    let source = r#"
+        (defun show (xs ys) (list 0 ...xs 9 ...ys 10))
+        (show (list 1 2) (list 3))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(0 1 2 9 3 10)");
+}
+
+#[test]
+fn test_splat_of_empty_list_passes_no_arguments() {
+    let source = r#"
+        (defun count-args (. xs) (list-length xs))
+        (defun go (xs) (count-args 1 ...xs 2))
+        (go '())
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "2");
+}
+
+#[test]
+fn test_splat_forwards_variadic_arguments() {
+    let source = r#"
+        (defun tagged (tag . items) (cons tag items))
+        (defun forward (. args) (tagged 'call ...args))
+        (forward 1 2 3)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(call 1 2 3)");
+}
+
+#[test]
+fn test_bare_splat_takes_following_expression() {
+    let source = r#"
+        (defun pair (a b) (list b a))
+        (defun swap-head (xs) (pair ... (cdr xs)))
+        (swap-head (list 1 2 3))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(3 2)");
+}
+
+#[test]
+fn test_splat_of_list_form_is_spliced_at_compile_time() {
+    // (+ ...(list 1 2 3)) is compiled exactly as (+ 1 2 3)
+    let exprs = Parser::new("(+ ... (list 1 2 3))").parse_all().unwrap();
+    let (_, main) = Compiler::new().compile_program(&exprs).unwrap();
+    let expected = Parser::new("(+ 1 2 3)").parse_all().unwrap();
+    let (_, expected_main) = Compiler::new().compile_program(&expected).unwrap();
+    assert_eq!(main, expected_main);
+    assert_eq!(compile_and_run("(+ ... (list 1 2 3))").unwrap(), "6");
+}
+
+#[test]
+fn test_splat_into_closure_and_list_and_vector() {
+    let source = r#"
+        (defun go (xs)
+          (let ((sub (lambda (a b) (- a b))))
+            (list (sub ...xs) (list ...xs) (vector 0 ...xs))))
+        (go (list 5 2))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(3 (5 2) [0 5 2])");
+}
+
+#[test]
+fn test_splat_into_inline_arithmetic_is_rejected() {
+    let err = compile_and_run("(defun total (xs) (% ...xs))").unwrap_err();
+    assert!(err.contains("Cannot splat a runtime list into '%'"), "{}", err);
+}
+
+#[test]
+fn test_splat_errors() {
+    let err = compile_and_run("(defun f (a) a) (f ...)").unwrap_err();
+    assert!(err.contains("Splat '...' must be followed by the expression to splice"), "{}", err);
+
+    let err = compile_and_run("(defun f (a) a) (defun g (x) (f ...x)) (g 5)").unwrap_err();
+    assert!(err.contains("expects two lists"), "{}", err);
+}
+
+#[test]
+fn test_splat_left_alone_in_macros() {
+    // Macros receive their arguments unevaluated, splat symbols included
+    let source = r#"
+        (defmacro first-arg (x y) (list 'quote x))
+        (first-arg ...xs 1)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "...xs");
+}
+
+#[test]
+fn test_apply_with_leading_args() {
+    assert_eq!(compile_and_run("(apply + 1 2 '(3 4))").unwrap(), "10");
+    assert_eq!(compile_and_run("(apply + 1 2 '())").unwrap(), "3");
+}
+
+#[test]
+fn test_apply_leading_args_come_first() {
+    let source = r#"
+        (let ((f (lambda (a b . rest) (list a b rest))))
+          (apply f 1 2 (list 3 4)))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(1 2 (3 4))");
+    assert_eq!(compile_and_run("(apply - 10 '(1 2))").unwrap(), "7");
+}
+
+#[test]
+fn test_apply_leading_args_to_named_function() {
+    let source = r#"
+        (defun add3 (a b c) (+ a (+ b c)))
+        (apply add3 1 (list 2 3))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "6");
+}
+
+#[test]
+fn test_apply_leading_args_still_needs_a_list() {
+    let err = compile_and_run("(apply + 1 2 3)").unwrap_err();
+    assert!(err.contains("expected list"), "{}", err);
+}
+
+#[test]
+fn test_apply_arithmetic_uses_every_argument() {
+    assert_eq!(compile_and_run("(apply + (list 1 2 3 4))").unwrap(), "10");
+    assert_eq!(compile_and_run("(apply * (list 2 3 4))").unwrap(), "24");
+}
@@ -0,0 +1,77 @@
+// Tests for the assert special form (no stdlib loaded)
+
+use lisp_bytecode_vm::*;
+
+fn run_code_err(source: &str) -> Result<Value, RuntimeError> {
+    let mut parser = parser::Parser::new(source);
+    let exprs = parser.parse_all().expect("parse failed");
+
+    let mut compiler = Compiler::new();
+    let (functions, main_bytecode) = compiler.compile_program(&exprs)
+        .expect("compile failed");
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main_bytecode;
+
+    vm.run_to_value()
+}
+
+fn run_code(source: &str) -> Result<Value, String> {
+    run_code_err(source).map_err(|e| e.message)
+}
+
+fn compile_error(source: &str) -> String {
+    let mut parser = parser::Parser::new(source);
+    let exprs = parser.parse_all().expect("parse failed");
+    match Compiler::new().compile_program(&exprs) {
+        Ok(_) => panic!("expected compile error for {}", source),
+        Err(e) => e.message,
+    }
+}
+
+#[test]
+fn test_assert_passes_value_through() {
+    assert_eq!(run_code("(+ 1 (assert (* 2 3)))").unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_assert_only_false_fails() {
+    assert_eq!(run_code("(list-length (assert '()))").unwrap(), Value::Integer(0));
+    assert_eq!(run_code("(assert true)").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn test_assert_failure_shows_expression() {
+    let err = run_code("(assert (== 1 2))").unwrap_err();
+    assert_eq!(err, "Assertion failed: (== 1 2)");
+}
+
+#[test]
+fn test_assert_failure_uses_message() {
+    let err = run_code("(assert false \"boom\")").unwrap_err();
+    assert_eq!(err, "Assertion failed: boom");
+}
+
+#[test]
+fn test_assert_failure_reports_location() {
+    let err = run_code_err("(def x 1)\n\n  (assert (> x 5))").unwrap_err();
+    let location = err.location.expect("assert failure should carry a location");
+    assert_eq!(location.line, 3);
+    assert_eq!(location.column, 3);
+}
+
+#[test]
+fn test_assert_inside_function() {
+    let source = "(defun safe-div (a b) (/ a (assert (!= b 0) \"divisor is zero\")))
+                  (safe-div 10 0)";
+    assert_eq!(run_code(source).unwrap_err(), "Assertion failed: divisor is zero");
+    assert_eq!(run_code("(defun f (a) (/ 10 (assert a))) (f 5)").unwrap(), Value::Integer(2));
+}
+
+#[test]
+fn test_assert_rejects_bad_forms() {
+    assert!(compile_error("(assert)").contains("assert expects"));
+    assert!(compile_error("(assert true 1 2)").contains("assert expects"));
+    assert!(compile_error("(assert true (+ 1 2))").contains("string literal"));
+}
@@ -0,0 +1,75 @@
+// Tests for the assoc-set and assoc-update alist builders (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn shown(source: &str) -> String {
+    VM::format_print_line(&[run_code(source).unwrap()], false)
+}
+
+#[test]
+fn test_assoc_set_replaces_existing_key_in_place() {
+    assert_eq!(shown("(assoc-set 'b 20 '((a 1) (b 2) (c 3)))"), "((a 1) (b 20) (c 3))");
+    // Only the first entry for a key changes, the one assoc would find
+    assert_eq!(shown("(assoc-set 'a 9 '((a 1) (a 2)))"), "((a 9) (a 2))");
+}
+
+#[test]
+fn test_assoc_set_appends_new_key() {
+    assert_eq!(shown("(assoc-set 'd 4 '((a 1) (b 2)))"), "((a 1) (b 2) (d . 4))");
+    assert_eq!(shown("(assoc-set \"k\" 1 '())"), "((\"k\" . 1))");
+    // Read back the same way as an existing dotted entry
+    assert_eq!(run_code("(cdr (assoc 'c (assoc-set 'c 3 '())))").unwrap(), Value::Integer(3));
+    assert_eq!(run_code("(cdr (assoc 'a (assoc-set 'a 3 '((a . 1)))))").unwrap(), Value::Integer(3));
+}
+
+#[test]
+fn test_assoc_set_keeps_entry_shape() {
+    assert_eq!(shown("(assoc-set 'b 5 '((a . 1) (b . 2)))"), "((a . 1) (b . 5))");
+    assert_eq!(shown("(assoc-set 'a 0 '((a 1 extra)))"), "((a 0 extra))");
+}
+
+#[test]
+fn test_assoc_set_leaves_original_untouched() {
+    let source = "(let ((alist '((a 1) (b 2))))
+  (list alist (assoc-set 'a 100 alist)))";
+    assert_eq!(shown(source), "(((a 1) (b 2)) ((a 100) (b 2)))");
+}
+
+#[test]
+fn test_assoc_update_applies_closure_to_current_value() {
+    assert_eq!(shown("(assoc-update 'b (lambda (v) (* v 10)) '((a 1) (b 2)))"), "((a 1) (b 20))");
+    assert_eq!(shown("(assoc-update 'n (lambda (v) (+ v 1)) '((n . 41)))"), "((n . 42))");
+
+    let source = "(let ((step 5))
+  (assoc-update 'x (lambda (v) (+ v step)) '((x 1) (y 2))))";
+    assert_eq!(shown(source), "((x 6) (y 2))");
+}
+
+#[test]
+fn test_assoc_update_missing_key_is_error() {
+    let err = run_code("(assoc-update 'z (lambda (v) v) '((a 1)))").unwrap_err();
+    assert!(err.contains("'assoc-update' found no entry for key z"), "got: {}", err);
+}
+
+#[test]
+fn test_assoc_builders_as_first_class_values() {
+    let source = "(defun set-a (f) (f 'a 7 '((a 1))))
+(set-a assoc-set)";
+    assert_eq!(shown(source), "((a 7))");
+    assert_eq!(shown("(apply assoc-update (list 'a (lambda (v) (- 0 v)) '((a 1))))"), "((a -1))");
+}
+
+#[test]
+fn test_assoc_builders_reject_non_pair_entries() {
+    let err = run_code("(assoc-set 'a 1 '((a 1) 5))").unwrap_err();
+    assert!(err.contains("'assoc-set' expects a list of pairs, found integer entry"), "got: {}", err);
+
+    let err = run_code("(assoc-update 'a (lambda (v) v) '(() (a 1)))").unwrap_err();
+    assert!(err.contains("'assoc-update' expects a list of pairs"), "got: {}", err);
+
+    let err = run_code("(assoc-set 'a 1 5)").unwrap_err();
+    assert!(err.contains("'assoc-set' expects a list as its second argument"), "got: {}", err);
+}
@@ -0,0 +1,203 @@
+// Tests for arbitrary-precision integers: overflow promotes to a bigint (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn printed(source: &str) -> String {
+    VM::format_print_line(&[run_code(source).unwrap()], false)
+}
+
+const FACT: &str = "(defun fact (n) (if (== n 0) 1 (* n (fact (- n 1)))))";
+const MAX: &str = "9223372036854775807";
+
+#[test]
+fn test_factorial_25() {
+    assert_eq!(printed(&format!("{} (fact 25)", FACT)), "15511210043330985984000000");
+}
+
+#[test]
+fn test_factorial_50() {
+    assert_eq!(
+        printed(&format!("{} (fact 50)", FACT)),
+        "30414093201713378043612608166064768844377641568960512000000000000"
+    );
+}
+
+#[test]
+fn test_small_results_stay_integers() {
+    assert_eq!(run_code(&format!("{} (fact 20)", FACT)).unwrap(), Value::Integer(2432902008176640000));
+    // Coming back into range gives a plain integer again
+    assert_eq!(run_code(&format!("(- (+ {} 10) 20)", MAX)).unwrap(), Value::Integer(i64::MAX - 10));
+    assert_eq!(run_code(&format!("(quotient (* {} 4) 8)", MAX)).unwrap(), Value::Integer(i64::MAX / 2));
+}
+
+#[test]
+fn test_min_divided_by_minus_one_promotes() {
+    let min = format!("(- (- 0 {}) 1)", MAX);
+    assert_eq!(printed(&format!("(/ {} -1)", min)), "9223372036854775808");
+    assert_eq!(printed(&format!("(quotient {} -1)", min)), "9223372036854775808");
+    assert_eq!(run_code(&format!("(remainder {} -1)", min)).unwrap(), Value::Integer(0));
+    assert_eq!(run_code(&format!("(% {} -1)", min)).unwrap(), Value::Integer(0));
+    assert_eq!(run_code(&format!("(modulo {} -1)", min)).unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_bigint_predicates() {
+    let source = format!("(let ((b (+ {} 1))) (list (bigint? b) (integer? b) (number? b) (bigint? 5) (type-of b)))", MAX);
+    assert_eq!(printed(&source), "(true true true false bigint)");
+}
+
+#[test]
+fn test_negative_bigints() {
+    assert_eq!(printed(&format!("(- 0 (* {} 3))", MAX)), "-27670116110564327421");
+    assert_eq!(printed(&format!("(neg (+ {} 1))", MAX)), "-9223372036854775808");
+    assert_eq!(run_code(&format!("(neg (+ {} 1))", MAX)).unwrap(), Value::Integer(i64::MIN));
+    assert_eq!(printed(&format!("(neg (- (- 0 {}) 1))", MAX)), "9223372036854775808");
+    assert_eq!(printed(&format!("(abs (* {} -2))", MAX)), "18446744073709551614");
+}
+
+#[test]
+fn test_bigint_equality_and_ordering() {
+    let big = format!("(* {} 2)", MAX);
+    assert_eq!(run_code(&format!("(== {} {})", big, big)).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(&format!("(!= {} {})", big, big)).unwrap(), Value::Boolean(false));
+    assert_eq!(run_code(&format!("(== {} {})", big, MAX)).unwrap(), Value::Boolean(false));
+    assert_eq!(run_code(&format!("(> {} {})", big, MAX)).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(&format!("(< (- 0 {}) 0)", big)).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(&format!("(<= {} (+ {} 1))", big, big)).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(&format!("(>= {} 1.0e30)", big)).unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_bigint_division() {
+    let big = format!("(* {} {})", MAX, MAX);
+    assert_eq!(printed(&format!("(/ {} {})", big, MAX)), MAX);
+    assert_eq!(run_code(&format!("(% (+ {} 5) {})", big, MAX)).unwrap(), Value::Integer(5));
+    assert_eq!(run_code(&format!("(modulo (- 0 (+ {} 5)) {})", big, MAX)).unwrap(), Value::Integer(i64::MAX - 5));
+    assert_eq!(run_code(&format!("(remainder (- 0 (+ {} 5)) {})", big, MAX)).unwrap(), Value::Integer(-5));
+    let err = run_code(&format!("(/ {} 0)", big)).unwrap_err();
+    assert!(err.contains("Division by zero"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_bigint_with_float_gives_float() {
+    assert_eq!(run_code(&format!("(+ (* {} 2) 0.5)", MAX)).unwrap(), Value::Float(18446744073709551614.5));
+    assert_eq!(run_code(&format!("(int->float (* {} 2))", MAX)).unwrap(), Value::Float(18446744073709551614.0));
+}
+
+#[test]
+fn test_bigint_to_string() {
+    assert_eq!(
+        run_code(&format!("(number->string (* {} 10))", MAX)).unwrap(),
+        Value::String(std::sync::Arc::new("92233720368547758070".to_string()))
+    );
+}
+
+#[test]
+fn test_bigint_parse_and_display_round_trip() {
+    for digits in ["0", "-1", "4294967296", "-18446744073709551616", "123456789012345678901234567890"] {
+        assert_eq!(BigInt::parse(digits).unwrap().to_string(), digits);
+    }
+    assert!(BigInt::parse("12a").is_none());
+}
+
+#[test]
+fn test_bigint_constant_survives_bytecode_round_trip() {
+    let value = Value::from_bigint(BigInt::parse("-123456789012345678901234567890").unwrap());
+    let main = vec![Instruction::Push(value.clone()), Instruction::Halt];
+    let bytes = bytecode::serialize_bytecode(&std::collections::HashMap::new(), &main);
+    let (_, loaded) = bytecode::deserialize_bytecode(&bytes).unwrap();
+    assert!(matches!(&loaded[0], Instruction::Push(v) if *v == value));
+}
+
+#[test]
+fn test_integer_literals_beyond_i64_are_bigints() {
+    assert_eq!(printed("99999999999999999999"), "99999999999999999999");
+    assert_eq!(printed("-99999999999999999999"), "-99999999999999999999");
+    assert_eq!(printed("(list (bigint? 99999999999999999999) (+ 99999999999999999999 1))"), "(true 100000000000000000000)");
+    assert_eq!(printed("'(1 99999999999999999999)"), "(1 99999999999999999999)");
+    // The i64 bounds themselves stay plain integers
+    assert_eq!(run_code(MAX).unwrap(), Value::Integer(i64::MAX));
+    assert_eq!(run_code("-9223372036854775808").unwrap(), Value::Integer(i64::MIN));
+    assert_eq!(printed("(- 9223372036854775808 1)"), MAX);
+}
+
+#[test]
+fn test_string_to_number_beyond_i64() {
+    assert_eq!(printed(r#"(string->number "99999999999999999999")"#), "99999999999999999999");
+    assert_eq!(printed(r#"(string->number "-99999999999999999999")"#), "-99999999999999999999");
+    assert_eq!(printed(r#"(string->number "ffffffffffffffffffff" 16)"#), "1208925819614629174706175");
+    assert_eq!(run_code(r#"(string->number "42")"#).unwrap(), Value::Integer(42));
+    assert_eq!(run_code(r#"(string->number "9999x")"#).unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_sort_orders_bigints_without_a_comparator() {
+    assert_eq!(
+        printed("(sort (list 99999999999999999999 1 -99999999999999999999 2.5))"),
+        "(-99999999999999999999 1 2.5 99999999999999999999)"
+    );
+}
+
+#[test]
+fn test_format_bigints_as_integers() {
+    let string = |s: &str| Value::String(std::sync::Arc::new(s.to_string()));
+    assert_eq!(run_code(r#"(format "~d!" 99999999999999999999)"#).unwrap(), string("99999999999999999999!"));
+    assert_eq!(run_code(r#"(format-int 99999999999999999999 :grouping ",")"#).unwrap(), string("99,999,999,999,999,999,999"));
+    assert_eq!(run_code(r#"(format-int -12345678901234567890 :width 24 :pad "0")"#).unwrap(), string("-00012345678901234567890"));
+}
+
+#[test]
+fn test_bit_operations_on_bigints() {
+    // Against i128, which holds all of these exactly
+    let a: i128 = 99999999999999999999;
+    let b: i128 = -12345678901234567890123;
+    let cases = [
+        (format!("(bit-and {} {})", a, b), a & b),
+        (format!("(bit-or {} {})", a, b), a | b),
+        (format!("(bit-xor {} {})", a, b), a ^ b),
+        (format!("(bit-and {} 65535)", b), b & 65535),
+        (format!("(bit-not {})", b), !b),
+        (format!("(<< {} 20)", b), b << 20),
+        (format!("(>> {} 40)", b), b >> 40),
+        (format!("(>> {} 40)", a), a >> 40),
+        (format!("(>> {} 100)", b), b >> 100),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(printed(&source), expected.to_string(), "{}", source);
+    }
+    // Results back in range are plain integers
+    assert_eq!(run_code(&format!("(bit-xor {} {})", a, a + 1)).unwrap(), Value::Integer((a ^ (a + 1)) as i64));
+    let err = run_code(&format!("(<< {} -1)", a)).unwrap_err();
+    assert_eq!(err, "Shift amount out of range in '<<': -1");
+}
+
+#[test]
+fn test_bigint_div_rem_against_multiplication() {
+    // q * d + r == n, |r| < |d|, for divisors of one and several digits
+    let divisors = ["7", "4294967295", "18446744073709551557", "-340282366920938463463374607431768211297", "79228162514264337593543950336"];
+    let quotients = ["1", "123456789012345678901234567890123456789", "-98765432109876543210987654321"];
+    for d in divisors {
+        for q in quotients {
+            let d = BigInt::parse(d).unwrap();
+            let q = BigInt::parse(q).unwrap();
+            // The remainder takes the dividend's sign
+            let product = &q * &d;
+            let r = BigInt::parse(if product.is_negative() { "-5" } else { "5" }).unwrap();
+            let n = &product + &r;
+            assert_eq!(n.div_rem(&d).unwrap(), (q.clone(), r.clone()), "{} / {}", n, d);
+        }
+    }
+    // The divisor's top digit has its high bit set after normalizing, and the
+    // first quotient digit estimate is one too large
+    let n = BigInt::parse("340282366920938463463374607431768211455").unwrap();
+    let d = BigInt::parse("18446744073709551617").unwrap();
+    let (q, r) = n.div_rem(&d).unwrap();
+    assert_eq!((q.to_string(), r.to_string()), ("18446744073709551615".to_string(), "0".to_string()));
+    let n = BigInt::parse(&0x00008000_0000fffe_00000000u128.to_string()).unwrap();
+    let d = BigInt::parse(&0x00008000_0000ffffu128.to_string()).unwrap();
+    let (q, r) = n.div_rem(&d).unwrap();
+    assert_eq!((q.to_string(), r.to_string()), ("4294967295".to_string(), "140733193453567".to_string()));
+}
@@ -0,0 +1,98 @@
+// Tests for the bitwise integer operators
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn int(n: i64) -> Value {
+    Value::Integer(n)
+}
+
+#[test]
+fn test_bit_and() {
+    assert_eq!(run_code("(bit-and 12 10)").unwrap(), int(8));
+}
+
+#[test]
+fn test_shift_left() {
+    assert_eq!(run_code("(<< 1 4)").unwrap(), int(16));
+}
+
+#[test]
+fn test_bit_or_and_xor() {
+    assert_eq!(run_code("(bit-or 12 10)").unwrap(), int(14));
+    assert_eq!(run_code("(bit-xor 12 10)").unwrap(), int(6));
+}
+
+#[test]
+fn test_bit_not() {
+    assert_eq!(run_code("(bit-not 0)").unwrap(), int(-1));
+    assert_eq!(run_code("(bit-not 5)").unwrap(), int(-6));
+}
+
+#[test]
+fn test_shift_right_keeps_sign() {
+    assert_eq!(run_code("(>> 256 4)").unwrap(), int(16));
+    assert_eq!(run_code("(>> -16 2)").unwrap(), int(-4));
+}
+
+#[test]
+fn test_bitwise_operators_fold_left() {
+    assert_eq!(run_code("(bit-or 1 2 4 8)").unwrap(), int(15));
+    assert_eq!(run_code("(bit-and 255 60 15)").unwrap(), int(12));
+    assert_eq!(run_code("(bit-xor 1 3 7)").unwrap(), int(5));
+    // (<< 1 2 3) is (<< (<< 1 2) 3)
+    assert_eq!(run_code("(<< 1 2 3)").unwrap(), int(32));
+}
+
+#[test]
+fn test_bitwise_operators_as_values() {
+    assert_eq!(run_code("(apply bit-xor (list 12 10))").unwrap(), int(6));
+    assert_eq!(run_code("(apply bit-not (list 7))").unwrap(), int(-8));
+}
+
+#[test]
+fn test_bitwise_operators_as_values_fold_left() {
+    // Same results as the direct calls in test_bitwise_operators_fold_left
+    assert_eq!(run_code("(apply bit-or (list 1 2 4 8))").unwrap(), int(15));
+    assert_eq!(run_code("(apply bit-and (list 255 60 15))").unwrap(), int(12));
+    assert_eq!(run_code("(apply bit-xor (list 1 3 7))").unwrap(), int(5));
+    assert_eq!(run_code("(apply << (list 1 2 3))").unwrap(), int(32));
+    assert_eq!(run_code("(apply >> (list 256 2 2))").unwrap(), int(16));
+    assert_eq!(
+        run_code("(map bit-or (list 1 16) (list 2 32) (list 4 64))").unwrap(),
+        Value::list_from_vec(vec![int(7), int(112)])
+    );
+    assert_eq!(run_code("(let ((flags (list 1 2 4))) (bit-or ...flags))").unwrap(), int(7));
+}
+
+#[test]
+fn test_bitwise_hash_step() {
+    // One round of an FNV-style mix, as used when porting hashing code
+    let source = "(bit-and (bit-xor (<< 2166136261 5) 97) 4294967295)";
+    assert_eq!(run_code(source).unwrap(), int(((2166136261i64 << 5) ^ 97) & 0xFFFF_FFFF));
+}
+
+#[test]
+fn test_bitwise_operators_reject_non_integers() {
+    let err = run_code("(bit-and 1.5 2)").unwrap_err();
+    assert_eq!(err, "Type error: 'bit-and' expects two integers, got float and integer");
+    let err = run_code(r#"(bit-not "x")"#).unwrap_err();
+    assert_eq!(err, "Type error: 'bit-not' expects an integer, got string");
+    let err = run_code("(<< 1 2.0)").unwrap_err();
+    assert_eq!(err, "Type error: '<<' expects two integers, got integer and float");
+}
+
+#[test]
+fn test_shift_amount_out_of_range() {
+    assert_eq!(run_code("(<< 1 64)").unwrap_err(), "Shift amount out of range in '<<': 64");
+    assert_eq!(run_code("(>> 1 -1)").unwrap_err(), "Shift amount out of range in '>>': -1");
+}
+
+#[test]
+fn test_bitwise_arity_errors() {
+    assert!(run_code("(bit-or 1)").unwrap_err().contains("bit-or expects at least 2 arguments"));
+    assert!(run_code("(bit-not 1 2)").unwrap_err().contains("bit-not expects exactly 1 argument"));
+    assert_eq!(run_code("(apply bit-or (list 1))").unwrap_err(), "Not enough arguments: expected at least 2, got 1");
+}
@@ -1,4 +1,4 @@
-use lisp_bytecode_vm::{bytecode, Instruction, Value};
+use lisp_bytecode_vm::{bytecode, parser::Parser, Compiler, Instruction, Location, Value, VM};
 use std::collections::HashMap;
 
 #[test]
@@ -225,3 +225,190 @@ fn test_serialize_large_numbers() {
     assert!(matches!(loaded_main[0], Instruction::Push(Value::Integer(i64::MAX))));
     assert!(matches!(loaded_main[1], Instruction::Push(Value::Integer(i64::MIN))));
 }
+
+#[test]
+fn test_serialize_assert_keeps_location() {
+    let functions = HashMap::new();
+    let main = vec![
+        Instruction::Push(Value::Boolean(true)),
+        Instruction::Assert(
+            "Assertion failed: x".to_string(),
+            Location::new(4, 7, "main.lisp".to_string()),
+        ),
+        Instruction::Halt,
+    ];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (_, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    match &loaded_main[1] {
+        Instruction::Assert(message, location) => {
+            assert_eq!(message, "Assertion failed: x");
+            assert_eq!(location.line, 4);
+            assert_eq!(location.column, 7);
+            assert_eq!(location.file, "main.lisp");
+        }
+        other => panic!("Expected Assert, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_serialize_optional_arg_instructions() {
+    let mut functions = HashMap::new();
+    functions.insert("greet".to_string(), vec![
+        Instruction::CheckMaxArgs(2),
+        Instruction::JmpIfArgSupplied(1, 4),
+        Instruction::Push(Value::Integer(10)),
+        Instruction::BindArg(1),
+        Instruction::LoadArg(1),
+        Instruction::Ret,
+    ]);
+    let main = vec![Instruction::Halt];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (loaded_functions, _) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    let greet = &loaded_functions["greet"];
+    assert!(matches!(greet[0], Instruction::CheckMaxArgs(2)));
+    assert!(matches!(greet[1], Instruction::JmpIfArgSupplied(1, 4)));
+    assert!(matches!(greet[3], Instruction::BindArg(1)));
+}
+
+#[test]
+fn test_serialize_key_arg_instructions() {
+    let mut functions = HashMap::new();
+    functions.insert("window".to_string(), vec![
+        Instruction::BindKeyArgs(1, vec!["width".to_string(), "height".to_string()]),
+        Instruction::JmpIfKeySupplied(1, 4),
+        Instruction::Push(Value::Integer(100)),
+        Instruction::BindArg(1),
+        Instruction::LoadArg(1),
+        Instruction::Ret,
+    ]);
+    let main = vec![Instruction::Halt];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (loaded_functions, _) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    let window = &loaded_functions["window"];
+    match &window[0] {
+        Instruction::BindKeyArgs(1, keys) => assert_eq!(keys, &["width".to_string(), "height".to_string()]),
+        other => panic!("Expected BindKeyArgs, got {:?}", other),
+    }
+    assert!(matches!(window[1], Instruction::JmpIfKeySupplied(1, 4)));
+}
+
+#[test]
+fn test_serialize_no_matching_clause() {
+    let functions = HashMap::new();
+    let main = vec![Instruction::NoMatchingClause("No matching clause in function 'f'".to_string())];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (_, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    match &loaded_main[0] {
+        Instruction::NoMatchingClause(message) => assert_eq!(message, "No matching clause in function 'f'"),
+        other => panic!("Expected NoMatchingClause, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_serialize_apply_n() {
+    let functions = HashMap::new();
+    let main = vec![Instruction::ApplyN(3), Instruction::Halt];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (_, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    assert!(matches!(loaded_main[0], Instruction::ApplyN(3)));
+}
+
+#[test]
+fn test_serialize_interpolate() {
+    let functions = HashMap::new();
+    let main = vec![Instruction::Interpolate(3), Instruction::Halt];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (_, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    assert!(matches!(loaded_main[0], Instruction::Interpolate(3)));
+}
+
+#[test]
+fn test_serialize_improper_list_constant() {
+    let functions = HashMap::new();
+    let pair = Value::List(lisp_bytecode_vm::List::cons(
+        Value::Integer(1),
+        lisp_bytecode_vm::List::Dotted(Box::new(Value::Integer(2))),
+    ));
+    let main = vec![Instruction::Push(pair.clone()), Instruction::Halt];
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (_, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    match &loaded_main[0] {
+        Instruction::Push(value) => assert_eq!(value, &pair),
+        other => panic!("Expected Push, got {:?}", other),
+    }
+}
+
+// ==================== COMPILED PROGRAMS ====================
+
+fn compile(source: &str) -> (HashMap<String, Vec<Instruction>>, Vec<Instruction>) {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    Compiler::new().compile_program(&exprs).unwrap()
+}
+
+fn run(functions: HashMap<String, Vec<Instruction>>, main: Vec<Instruction>) -> String {
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    match vm.run_to_value() {
+        Ok(value) => VM::format_print_line(&[value], false),
+        Err(e) => format!("error: {}", e.message),
+    }
+}
+
+#[test]
+fn test_stdlib_and_builtins_round_trip_exactly() {
+    let (mut functions, main) = compile(&std::fs::read_to_string("stdlib.lisp").unwrap());
+    functions.extend(VM::new().functions);
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (loaded_functions, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    assert_eq!(loaded_main, main);
+    assert_eq!(loaded_functions.len(), functions.len());
+    for (name, bytecode) in &functions {
+        assert_eq!(&loaded_functions[name], bytecode, "function '{}' changed", name);
+    }
+}
+
+#[test]
+fn test_round_tripped_program_runs_like_the_original() {
+    let source = r#"
+        (defun make-counter (start) (lambda (step) (+ start step)))
+        (defun fib (n a b) (if (<= n 0) a (fib (- n 1) b (+ a b))))
+        (defun describe ((0) "zero") ((n) (format "n={}" (list n))))
+        (def table (hash-map "a" 1 "b" 2))
+        (def cells (vector 1.5 "two" 'three))
+        (list ((make-counter 10) 5)
+              (fib 100 0 1)
+              (describe 0)
+              (describe 7)
+              (hashmap-get table "b")
+              (vector-ref cells 1)
+              '(1 . 2)
+              (try (raise "boom") (catch e e))
+              (map (lambda (x) (* x x)) (range 1 5))
+              (let ((x 2) (y 3.5)) (* x y)))
+    "#;
+    let (functions, main) = compile(source);
+    let direct = run(functions.clone(), main.clone());
+
+    let bytes = bytecode::serialize_bytecode(&functions, &main);
+    let (loaded_functions, loaded_main) = bytecode::deserialize_bytecode(&bytes).unwrap();
+
+    assert!(!direct.starts_with("error"), "{}", direct);
+    assert_eq!(run(loaded_functions, loaded_main), direct);
+}
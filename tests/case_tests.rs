@@ -0,0 +1,93 @@
+// Tests for the case special form (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_case_matches_equivalent_cond() {
+    let source = r#"
+        (defun with-case (x)
+          (case x
+            (1 "one")
+            ((2 3 4) "few")
+            (else "many")))
+        (defun with-cond (x)
+          (cond
+            ((== x 1) "one")
+            ((or (== x 2) (== x 3) (== x 4)) "few")
+            (else "many")))
+        (list (map with-case '(1 2 4 5 0)) (map with-cond '(1 2 4 5 0)))
+    "#;
+    let result = run_code(source).unwrap();
+    let items: Vec<Value> = result.as_list().unwrap().iter().cloned().collect();
+    assert_eq!(items[0], items[1]);
+    assert_eq!(items[0], run_code(r#"(list "one" "few" "few" "many" "many")"#).unwrap());
+}
+
+#[test]
+fn test_case_symbols_strings_and_booleans() {
+    let source = r#"
+        (defun kind (x)
+          (case x
+            ((a b) 'letter)
+            ("s" 'string)
+            (true 'yes)
+            (else 'other)))
+        (map kind (list 'b "s" true 2.5))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("'(letter string yes other)").unwrap());
+}
+
+#[test]
+fn test_case_without_match_is_false() {
+    assert_eq!(run_code("(case 9 (1 'one) (2 'two))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_case_evaluates_key_once() {
+    let source = r#"
+        (defun classify (xs) (case (car xs) (1 'first) (2 'second) (else 'rest)))
+        (list (classify '(2 9)) (+ 1 (case (+ 1 1) (1 10) (2 20))))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("(list 'second 21)").unwrap());
+}
+
+#[test]
+fn test_case_inside_let_and_closure() {
+    let source = r#"
+        (defun run (n)
+          (let ((bonus 100)
+                (f (lambda (k) (case k (0 bonus) (else (* k n))))))
+            (list (f 0) (f 3))))
+        (run 5)
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("(list 100 15)").unwrap());
+}
+
+#[test]
+fn test_case_bodies_keep_tail_calls() {
+    let source = r#"
+        (defun count-down (n acc)
+          (case n
+            (0 acc)
+            (else (count-down (- n 1) (+ acc 1)))))
+        (count-down 100000 0)
+    "#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(100000));
+}
+
+#[test]
+fn test_case_with_recur_in_loop() {
+    let source = "(loop ((i 0) (acc 0)) (case i (5 acc) (else (recur (+ i 1) (+ acc i)))))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(10));
+}
+
+#[test]
+fn test_case_errors() {
+    assert!(run_code("(case 1)").unwrap_err().contains("case expects a key expression"));
+    assert!(run_code("(case 1 (else 0) (1 1))").unwrap_err().contains("else clause must be the last"));
+    assert!(run_code("(case 1 (() 0))").unwrap_err().contains("at least one datum"));
+    assert!(run_code("(case 1 (1 2 3))").unwrap_err().contains("case clause must be a list"));
+}
@@ -0,0 +1,87 @@
+// Tests for the single-character string helpers and integer->char (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string().into())
+}
+
+#[test]
+fn test_char_predicates() {
+    assert_eq!(run_code("(char-numeric? \"5\")").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(char-numeric? \"x\")").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(char-alphabetic? \"q\")").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(char-alphabetic? \"é\")").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(char-alphabetic? \"7\")").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(char-whitespace? \" \")").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(char-whitespace? \"\n\")").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(char-whitespace? \"-\")").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_char_case_conversion() {
+    assert_eq!(run_code("(char-upcase \"a\")").unwrap(), string("A"));
+    assert_eq!(run_code("(char-upcase \"A\")").unwrap(), string("A"));
+    assert_eq!(run_code("(char-upcase \"1\")").unwrap(), string("1"));
+    assert_eq!(run_code("(char-downcase \"Q\")").unwrap(), string("q"));
+    assert_eq!(run_code("(char-upcase \"ß\")").unwrap(), string("SS"));
+}
+
+#[test]
+fn test_char_helpers_over_string_to_list() {
+    let result = run_code("(list->string (map char-upcase (string->list \"abc1\")))").unwrap();
+    assert_eq!(result, string("ABC1"));
+    let digits = run_code("(list->string (filter char-numeric? (string->list \"a1b22\")))").unwrap();
+    assert_eq!(digits, string("122"));
+}
+
+#[test]
+fn test_multi_char_input_is_an_error() {
+    let err = run_code("(char-upcase \"ab\")").unwrap_err();
+    assert_eq!(err, "'char-upcase' expects a single-character string, got 2 characters");
+    let err = run_code("(char-numeric? \"\")").unwrap_err();
+    assert!(err.contains("got 0 characters"), "{}", err);
+}
+
+#[test]
+fn test_non_string_input_is_a_type_error() {
+    let err = run_code("(char-alphabetic? 65)").unwrap_err();
+    assert_eq!(err, "Type error: 'char-alphabetic?' expects a string, got integer");
+}
+
+#[test]
+fn test_integer_to_char() {
+    assert_eq!(run_code("(integer->char 65)").unwrap(), string("A"));
+    assert_eq!(run_code("(integer->char 955)").unwrap(), string("λ"));
+    assert_eq!(run_code("(integer->char 0)").unwrap(), string("\0"));
+    assert_eq!(run_code("(char-code (integer->char 122))").unwrap(), Value::Integer(122));
+}
+
+#[test]
+fn test_integer_to_char_as_function_value() {
+    let result = run_code("(list->string (map integer->char (list 72 105)))").unwrap();
+    assert_eq!(result, string("Hi"));
+}
+
+#[test]
+fn test_caesar_shift_with_char_code() {
+    let source = r#"
+        (defun shift (c) (integer->char (+ 97 (% (+ (- (char-code c) 97) 3) 26))))
+        (list->string (map shift (string->list "xyzabc")))
+    "#;
+    assert_eq!(run_code(source).unwrap(), string("abcdef"));
+}
+
+#[test]
+fn test_integer_to_char_rejects_invalid_codepoints() {
+    for code in ["-1", "55296", "1114112", "99999999999"] {
+        let err = run_code(&format!("(integer->char {})", code)).unwrap_err();
+        assert_eq!(err, format!("'integer->char' expects a Unicode scalar value, got {}", code));
+    }
+    let err = run_code("(integer->char \"A\")").unwrap_err();
+    assert_eq!(err, "Type error: 'integer->char' expects an integer, got string");
+    assert!(run_code("(integer->char)").is_err());
+}
@@ -0,0 +1,79 @@
+// Tests for VM::set_checked_arithmetic: integer overflow promotes to a bigint
+// by default and is a runtime error when checking is enabled (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+
+fn run_code(source: &str, checked: bool) -> Result<Value, String> {
+    common::run_code_with(source, |vm| vm.set_checked_arithmetic(checked))
+}
+
+fn big(digits: &str) -> Value {
+    Value::from_bigint(BigInt::parse(digits).unwrap())
+}
+
+#[test]
+fn test_multiplication_promotes_by_default() {
+    assert_eq!(run_code("(* 9223372036854775807 2)", false).unwrap(), big("18446744073709551614"));
+}
+
+#[test]
+fn test_multiplication_overflow_is_error_when_checked() {
+    let err = run_code("(* 9223372036854775807 2)", true).unwrap_err();
+    assert!(err.contains("integer overflow"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_addition_and_subtraction_overflow() {
+    assert_eq!(run_code("(+ 9223372036854775807 1)", false).unwrap(), big("9223372036854775808"));
+    assert_eq!(run_code("(- (- 0 9223372036854775807) 2)", false).unwrap(), big("-9223372036854775809"));
+
+    let err = run_code("(+ 9223372036854775807 1)", true).unwrap_err();
+    assert!(err.contains("integer overflow in '+'"), "unexpected error: {}", err);
+    let err = run_code("(- (- 0 9223372036854775807) 2)", true).unwrap_err();
+    assert!(err.contains("integer overflow in '-'"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_min_divided_by_minus_one() {
+    let min = "(- (- 0 9223372036854775807) 1)";
+    assert_eq!(run_code(&format!("(/ {} -1)", min), false).unwrap(), big("9223372036854775808"));
+    let err = run_code(&format!("(/ {} -1)", min), true).unwrap_err();
+    assert!(err.contains("integer overflow in '/'"), "unexpected error: {}", err);
+    let err = run_code(&format!("(quotient {} -1)", min), true).unwrap_err();
+    assert!(err.contains("integer overflow in 'quotient'"), "unexpected error: {}", err);
+    // The remainder is 0 and fits, so checked mode has nothing to report
+    assert_eq!(run_code(&format!("(remainder {} -1)", min), true).unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_checked_mode_leaves_in_range_results_alone() {
+    let source = "(defun fact (n) (if (== n 0) 1 (* n (fact (- n 1))))) (fact 20)";
+    assert_eq!(run_code(source, true).unwrap(), Value::Integer(2432902008176640000));
+}
+
+#[test]
+fn test_overflow_in_recursion_is_caught() {
+    let source = "(defun fact (n) (if (== n 0) 1 (* n (fact (- n 1))))) (fact 21)";
+    let err = run_code(source, true).unwrap_err();
+    assert!(err.contains("integer overflow"), "unexpected error: {}", err);
+    assert!(run_code(source, false).is_ok());
+}
+
+#[test]
+fn test_overflow_through_variadic_builtin() {
+    let err = run_code("(apply * '(9223372036854775807 1 2))", true).unwrap_err();
+    assert!(err.contains("integer overflow"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_overflow_can_be_caught_with_try() {
+    let source = r#"(try (+ 9223372036854775807 1) (catch e "overflowed"))"#;
+    assert_eq!(run_code(source, true).unwrap(), Value::String(std::sync::Arc::new("overflowed".to_string())));
+}
+
+#[test]
+fn test_float_arithmetic_is_unaffected() {
+    assert_eq!(run_code("(* 9223372036854775807.0 2)", true).unwrap(), Value::Float(9223372036854775807.0 * 2.0));
+}
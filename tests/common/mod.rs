@@ -0,0 +1,33 @@
+// Helpers shared by the integration tests. Each test file is its own crate
+// and uses only some of them.
+#![allow(dead_code)]
+
+use lisp_bytecode_vm::*;
+
+/// Compile `source` without the stdlib and run it on a fresh VM, returning
+/// the value it leaves or the parse, compile or runtime error message
+pub fn run_code(source: &str) -> Result<Value, String> {
+    run_code_with(source, |_| {})
+}
+
+/// Like `run_code`, with `configure` applied to the VM before it runs
+pub fn run_code_with(source: &str, configure: impl FnOnce(&mut VM)) -> Result<Value, String> {
+    let mut parser = parser::Parser::new(source);
+    let exprs = parser.parse_all().map_err(|e| e.to_string())?;
+
+    let mut compiler = Compiler::new();
+    let (functions, main_bytecode) = compiler.compile_program(&exprs)
+        .map_err(|e| e.message)?;
+
+    let mut vm = VM::new();
+    configure(&mut vm);
+    vm.functions.extend(functions);
+    vm.current_bytecode = main_bytecode;
+
+    vm.run_to_value().map_err(|e| e.message)
+}
+
+/// A proper list of integers
+pub fn ints(items: &[i64]) -> Value {
+    Value::list_from_vec(items.iter().map(|n| Value::Integer(*n)).collect())
+}
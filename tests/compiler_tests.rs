@@ -324,3 +324,43 @@ fn test_compile_error_single_arg_arithmetic() {
                 "Error message for {} should mention 'at least 2 arguments'", op);
     }
 }
+
+#[test]
+fn test_compile_or_jumps_to_one_shared_exit() {
+    let mut parser = Parser::new("(or (> 1 2) (> 3 4) (> 5 6))");
+    let exprs = parser.parse_all().unwrap();
+
+    let mut compiler = Compiler::new();
+    let (_, main) = compiler.compile_program(&exprs).unwrap();
+
+    // Each test but the last jumps straight to a single `Push(true)`
+    let targets: Vec<usize> = main.iter().filter_map(|i| match i {
+        Instruction::JmpIfTrue(target) => Some(*target),
+        _ => None,
+    }).collect();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0], targets[1]);
+    assert!(matches!(main[targets[0]], Instruction::Push(Value::Boolean(true))));
+    assert!(!main.iter().any(|i| matches!(i, Instruction::JmpIfFalse(_))));
+    let pushes = main.iter().filter(|i| matches!(i, Instruction::Push(Value::Boolean(_)))).count();
+    assert_eq!(pushes, 1);
+}
+
+#[test]
+fn test_compile_and_jumps_to_one_shared_exit() {
+    let mut parser = Parser::new("(and (> 1 2) (> 3 4) (> 5 6))");
+    let exprs = parser.parse_all().unwrap();
+
+    let mut compiler = Compiler::new();
+    let (_, main) = compiler.compile_program(&exprs).unwrap();
+
+    let targets: Vec<usize> = main.iter().filter_map(|i| match i {
+        Instruction::JmpIfFalse(target) => Some(*target),
+        _ => None,
+    }).collect();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0], targets[1]);
+    assert!(matches!(main[targets[0]], Instruction::Push(Value::Boolean(false))));
+    let pushes = main.iter().filter(|i| matches!(i, Instruction::Push(Value::Boolean(_)))).count();
+    assert_eq!(pushes, 1);
+}
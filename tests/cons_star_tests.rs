@@ -0,0 +1,46 @@
+// Tests for the cons* / list* builtin (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_cons_star_prepends_onto_last_list() {
+    assert_eq!(run_code("(cons* 1 2 3 '(4 5))").unwrap(), ints(&[1, 2, 3, 4, 5]));
+    assert_eq!(run_code("(list* 1 '(2))").unwrap(), ints(&[1, 2]));
+}
+
+#[test]
+fn test_cons_star_single_argument_is_the_tail() {
+    assert_eq!(run_code("(cons* '(1 2))").unwrap(), ints(&[1, 2]));
+    assert_eq!(run_code("(cons* '())").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_cons_star_non_list_tail_follows_cons() {
+    // Like (cons 1 2), a non-list tail follows the dot
+    assert_eq!(run_code("(cons* 1 2 3)").unwrap(), run_code("(cons 1 (cons 2 3))").unwrap());
+    assert_eq!(run_code("(cons* 7)").unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_cons_star_as_value_matches_inline_form() {
+    let source = r#"
+        (defun forward (f first rest) (apply f (cons* first rest)))
+        (list (apply cons* (list 1 2 '(3)))
+              (forward list* 0 (list 1 '(2 3)))
+              (apply list* (list 4)))
+    "#;
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::list_from_vec(vec![ints(&[1, 2, 3]), ints(&[0, 1, 2, 3]), Value::Integer(4)])
+    );
+}
+
+#[test]
+fn test_cons_star_needs_an_argument() {
+    assert!(run_code("(cons*)").is_err());
+    let err = run_code("(apply cons* '())").unwrap_err();
+    assert!(err.contains("at least one argument"), "{}", err);
+}
@@ -0,0 +1,129 @@
+use lisp_bytecode_vm::{Compiler, VM, Value, parser::Parser};
+
+fn compile_and_run(source: &str) -> Result<Value, String> {
+    let mut parser = Parser::new(source);
+    let exprs = parser.parse_all().map_err(|e| format!("Parse error: {:?}", e))?;
+
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).map_err(|e| format!("Compile error: {:?}", e))?;
+
+    let mut vm = VM::new();
+    for (name, bytecode) in functions {
+        vm.functions.insert(name, bytecode);
+    }
+    vm.current_bytecode = main;
+    vm.run().map_err(|e| e.message)?;
+
+    vm.value_stack.last().cloned().ok_or_else(|| "No value on stack".to_string())
+}
+
+const SQRT_INT: &str = r#"
+    (defun sqrt-iter (n guess)
+      (if (> (* guess guess) n)
+          (- guess 1)
+          (sqrt-iter n (+ guess 1))))
+    (defun sqrt-int (n)
+      (pre (>= n 0))
+      (post (lambda (r) (<= (* r r) n)))
+      (sqrt-iter n 0))
+"#;
+
+// ==================== Preconditions ====================
+
+#[test]
+fn test_precondition_passes() {
+    let source = format!("{}(sqrt-int 17)", SQRT_INT);
+    assert_eq!(compile_and_run(&source).unwrap(), Value::Integer(4));
+}
+
+#[test]
+fn test_precondition_violation() {
+    let source = format!("{}(sqrt-int -4)", SQRT_INT);
+    let err = compile_and_run(&source).unwrap_err();
+    assert_eq!(err, "Precondition failed in 'sqrt-int': (>= n 0)");
+}
+
+#[test]
+fn test_multiple_preconditions_checked_in_order() {
+    let source = r#"
+        (defun safe-div (a b)
+          (pre (integer? a))
+          (pre (!= b 0))
+          (/ a b))
+        (safe-div 10 0)
+    "#;
+    let err = compile_and_run(source).unwrap_err();
+    assert_eq!(err, "Precondition failed in 'safe-div': (!= b 0)");
+}
+
+#[test]
+fn test_precondition_on_variadic_function() {
+    let source = r#"
+        (defun first-of (x . rest)
+          (pre (list? rest))
+          x)
+        (first-of 1 2 3)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), Value::Integer(1));
+}
+
+// ==================== Postconditions ====================
+
+#[test]
+fn test_postcondition_violation() {
+    let source = r#"
+        (defun broken-abs (x)
+          (post (lambda (r) (>= r 0)))
+          x)
+        (broken-abs -3)
+    "#;
+    let err = compile_and_run(source).unwrap_err();
+    assert_eq!(err, "Postcondition failed in 'broken-abs': (lambda (r) (>= r 0))");
+}
+
+#[test]
+fn test_postcondition_with_named_predicate() {
+    let source = r#"
+        (defun positive? (n) (> n 0))
+        (defun double (x)
+          (post positive?)
+          (* x 2))
+        (list (double 4) (double 1))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, Value::List(lisp_bytecode_vm::List::from_vec(vec![Value::Integer(8), Value::Integer(2)])));
+
+    let source = r#"
+        (defun positive? (n) (> n 0))
+        (defun double (x)
+          (post positive?)
+          (* x 2))
+        (double 0)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap_err(), "Postcondition failed in 'double': positive?");
+}
+
+#[test]
+fn test_contracts_on_recursive_function() {
+    // The body is no longer a tail call, but recursion still checks every level
+    let source = r#"
+        (defun fact (n)
+          (pre (>= n 0))
+          (post (lambda (r) (>= r 1)))
+          (if (== n 0) 1 (* n (fact (- n 1)))))
+        (fact 10)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), Value::Integer(3628800));
+}
+
+#[test]
+fn test_contract_condition_must_be_boolean() {
+    let source = r#"
+        (defun f (x)
+          (pre x)
+          x)
+        (f 5)
+    "#;
+    let err = compile_and_run(source).unwrap_err();
+    assert!(err.contains("contract condition must be a boolean"));
+}
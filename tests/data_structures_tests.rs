@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -610,9 +611,39 @@ fn test_string_to_number_invalid() {
     let source = r#"
         (string->number "not-a-number")
     "#;
-    let result = compile_and_run(source);
-    assert!(result.is_err());
-    assert!(result.unwrap_err().contains("cannot parse"));
+    // Unparseable input is false rather than an error so callers can branch
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "false");
+}
+
+#[test]
+fn test_string_to_number_floats() {
+    assert_eq!(compile_and_run(r#"(string->number "3.14")"#).unwrap().trim(), "3.14");
+    assert_eq!(compile_and_run(r#"(string->number "1e3")"#).unwrap().trim(), "1000.0");
+    assert_eq!(compile_and_run(r#"(string->number "-2.5E-1")"#).unwrap().trim(), "-0.25");
+    assert_eq!(compile_and_run(r#"(float? (string->number "2.0"))"#).unwrap().trim(), "true");
+    // Words that f64 would accept are not numbers here
+    assert_eq!(compile_and_run(r#"(string->number "inf")"#).unwrap().trim(), "false");
+    assert_eq!(compile_and_run(r#"(string->number ".")"#).unwrap().trim(), "false");
+    assert_eq!(compile_and_run(r#"(string->number "xyz")"#).unwrap().trim(), "false");
+}
+
+#[test]
+fn test_string_to_number_radix() {
+    assert_eq!(compile_and_run(r#"(string->number "ff" 16)"#).unwrap().trim(), "255");
+    assert_eq!(compile_and_run(r#"(string->number "-101" 2)"#).unwrap().trim(), "-5");
+    assert_eq!(compile_and_run(r#"(string->number "17" 8)"#).unwrap().trim(), "15");
+    assert_eq!(compile_and_run(r#"(string->number "42" 10)"#).unwrap().trim(), "42");
+    assert_eq!(compile_and_run(r#"(string->number "1.5" 16)"#).unwrap().trim(), "false");
+    assert_eq!(compile_and_run(r#"(string->number "xyz" 16)"#).unwrap().trim(), "false");
+}
+
+#[test]
+fn test_string_to_number_radix_errors_and_value_use() {
+    assert!(compile_and_run(r#"(string->number "10" 3)"#).unwrap_err().contains("radix must be 2, 8, 10 or 16"));
+    assert!(compile_and_run(r#"(string->number 10)"#).unwrap_err().contains("expects a string"));
+    assert_eq!(compile_and_run(r#"(apply string->number (list "ff" 16))"#).unwrap().trim(), "255");
+    assert_eq!(compile_and_run(r#"(car (map string->number (list "7")))"#).unwrap().trim(), "7");
 }
 
 #[test]
@@ -725,3 +756,116 @@ fn test_conversion_with_mixed_types() {
     let result = compile_and_run(source).unwrap();
     assert_eq!(result.trim(), "3");
 }
+
+// ==================== List Copy & Sharing Tests ====================
+
+#[test]
+fn test_list_copy_is_equal_but_independent() {
+    let source = r#"
+        (def xs (list 1 (list 2 3) "four"))
+        (def ys (list-copy xs))
+        (list (== xs ys) (shares-structure? xs ys))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(true false)");
+}
+
+#[test]
+fn test_list_copy_empty() {
+    assert_eq!(compile_and_run("(list-copy (list))").unwrap(), "()");
+}
+
+#[test]
+fn test_list_copy_rejects_non_list() {
+    let err = compile_and_run("(list-copy 5)").unwrap_err();
+    assert!(err.contains("'list-copy' expects a list, got integer"), "got: {}", err);
+}
+
+#[test]
+fn test_shares_structure_with_tail() {
+    let source = r#"
+        (def xs (list 1 2 3))
+        (list (shares-structure? xs (cdr xs))
+              (shares-structure? (cdr (cdr xs)) xs)
+              (shares-structure? (cons 0 (cdr xs)) (cons 9 (cdr xs))))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(true true true)");
+}
+
+#[test]
+fn test_shares_structure_separate_lists() {
+    let source = r#"
+        (list (shares-structure? (list 1 2) (list 1 2))
+              (shares-structure? (list) (list))
+              (shares-structure? (list 1 2 3) (append (list 1) (list 2 3))))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(false false false)");
+}
+
+#[test]
+fn test_list_vector_conversion_keeps_element_identity() {
+    // One-level conversions move the elements themselves, not copies
+    let source = r#"
+        (def inner (list 1 2))
+        (def v (list->vector (list inner 3)))
+        (list (shares-structure? inner (vector-ref v 0))
+              (shares-structure? inner (car (vector->list v))))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(true true)");
+}
+
+#[test]
+fn test_deep_list_to_vector() {
+    let source = r#"(deep-list->vector (list 1 (list 2 (list 3 "s")) (vector 4 (list 5)) (list)))"#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"[1 [2 [3 "s"]] [4 [5]] []]"#);
+}
+
+#[test]
+fn test_deep_vector_to_list() {
+    let source = r#"(deep-vector->list (vector 1 (vector 2 (vector 3 "s")) (list 4 (vector 5)) (vector)))"#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"(1 (2 (3 "s")) (4 (5)) ())"#);
+}
+
+#[test]
+fn test_deep_conversions_round_trip() {
+    let source = r#"
+        (def data (list 1 (list 2 (list 3)) (list)))
+        (deep-vector->list (deep-list->vector data))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(1 (2 (3)) ())");
+}
+
+#[test]
+fn test_deep_conversions_leave_other_values_unchanged() {
+    assert_eq!(compile_and_run("(deep-list->vector 5)").unwrap(), "5");
+    assert_eq!(compile_and_run(r#"(deep-vector->list "text")"#).unwrap(), "\"text\"");
+    // Hash maps are not traversed: the list inside stays a list
+    let source = r#"
+        (def converted (deep-list->vector (list (hash-map "k" (list 1 2)))))
+        (hashmap-get (vector-ref converted 0) "k")
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(1 2)");
+}
+
+#[test]
+fn test_deep_conversion_of_very_deep_nesting() {
+    // Far deeper than the native stack could handle with recursion
+    let depth = 200_000;
+    let mut nested = Value::Integer(0);
+    for _ in 0..depth {
+        nested = Value::list_from_vec(vec![nested]);
+    }
+
+    let converted = nested.deep_list_to_vector();
+    let mut levels = 0;
+    let mut current = &converted;
+    while let Value::Vector(items) = current {
+        levels += 1;
+        current = &items[0];
+    }
+    assert_eq!(levels, depth);
+    assert_eq!(current, &Value::Integer(0));
+
+    // Dropping nested values still recurses (see List's Drop), so leak them
+    std::mem::forget(converted);
+    std::mem::forget(nested);
+}
@@ -1,4 +1,4 @@
-use lisp_bytecode_vm::{disassembler, Instruction, Value};
+use lisp_bytecode_vm::{disassembler, parser::Parser, Compiler, Instruction, Program, Value};
 use std::collections::HashMap;
 
 #[test]
@@ -222,3 +222,132 @@ fn test_disassemble_conditional_jumps() {
     assert!(output.contains("JmpIfFalse(4)"));
     assert!(output.contains("Jmp(5)"));
 }
+
+// ==================== GROUPED PROGRAM DUMP ====================
+
+fn disassemble_source(source: &str) -> String {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    Compiler::new().compile_to_program(&exprs).unwrap().disassemble_program()
+}
+
+#[test]
+fn test_program_disassemble_snapshot() {
+    let output = disassemble_source(
+        "(defun make-adder (n) (lambda (x) (+ x n)))
+         (defun count-down (n) (if (<= n 0) 0 (count-down (- n 1))))
+         ((make-adder 1) (count-down 3))",
+    );
+
+    let expected = "\
+=== Function: count-down (n) ===
+     0: LoadArg(0)
+     1: Push(Integer(0))
+     2: Leq
+     3: JmpIfFalse(6)  ; -> 6 (else)
+     4: Push(Integer(0))
+     5: Jmp(10)  ; -> 10 (end)
+     6: LoadArg(0)
+     7: Push(Integer(1))
+     8: Sub
+     9: TailCall(\"count-down\", 1)  ; tail call
+    10: Ret
+
+=== Function: make-adder (n) ===
+     0: LoadArg(0)
+     1: MakeClosure([\"x\"], 4 instructions, 1 captured)
+        closure (x)
+        captures:
+          [0] <- LoadArg(0)
+        body:
+             0: LoadArg(0)
+             1: LoadCaptured(0)
+             2: Add
+             3: Ret
+     2: Ret
+
+=== Main ===
+     0: Push(Integer(1))
+     1: Call(\"make-adder\", 1)  ; call
+     2: Push(Integer(3))
+     3: Call(\"count-down\", 1)  ; call
+     4: CallClosure(1)  ; call
+     5: Halt
+";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_program_disassemble_variadic_params_and_closures() {
+    let output = disassemble_source(
+        "(defun sum (first . rest) (+ first (length rest)))
+         (lambda (a . more) more)",
+    );
+
+    assert!(output.contains("=== Function: sum (first . rest) ==="));
+    assert!(output.contains("closure (a . more)"));
+    assert!(output.contains("captures: none"));
+}
+
+#[test]
+fn test_program_without_params_marks_them_unknown() {
+    let mut functions = HashMap::new();
+    functions.insert("f".to_string(), vec![Instruction::LoadArg(0), Instruction::Ret]);
+    let program = Program::new(functions, vec![Instruction::Halt]);
+
+    assert!(program.disassemble_program().starts_with("=== Function: f (?) ===\n"));
+}
+
+#[test]
+fn test_disassemble_if_annotates_both_jump_targets() {
+    let exprs = Parser::new("(if (< 1 2) 10 20)").parse_all().unwrap();
+    let output = Compiler::new().compile_to_program(&exprs).unwrap().disassemble_program();
+
+    assert!(output.contains("3: JmpIfFalse(6)  ; -> 6 (else)"), "{}", output);
+    assert!(output.contains("5: Jmp(7)  ; -> 7 (end)"), "{}", output);
+    assert!(output.contains("6: Push(Integer(20))"), "{}", output);
+}
+
+#[test]
+fn test_disassemble_unlabelled_jump_shows_plain_target() {
+    let program = Program::new(HashMap::new(), vec![
+        Instruction::Push(Value::Boolean(true)),
+        Instruction::JmpIfTrue(3),
+        Instruction::Push(Value::Integer(1)),
+        Instruction::Halt,
+    ]);
+
+    assert!(program.disassemble_program().contains("1: JmpIfTrue(3)  ; -> 3\n"));
+}
+
+#[test]
+fn test_disassemble_function_by_name() {
+    let exprs = Parser::new(
+        "(defun count-down (n) (if (<= n 0) 0 (count-down (- n 1))))
+         (defun twice (x) (* 2 x))",
+    ).parse_all().unwrap();
+    let program = Compiler::new().compile_to_program(&exprs).unwrap();
+
+    let output = program.disassemble_function("count-down").unwrap();
+    assert!(output.starts_with("=== Function: count-down (n) ===\n"));
+    assert!(output.contains("TailCall(\"count-down\", 1)  ; tail call"));
+    assert!(!output.contains("twice"));
+    assert!(!output.contains("=== Main ==="));
+
+    assert!(program.disassemble_function("missing").is_none());
+}
+
+#[test]
+fn test_disassemble_try_labels_the_handler() {
+    let exprs = Parser::new("(try (raise 1) (catch e e))").parse_all().unwrap();
+    let output = Compiler::new().compile_to_program(&exprs).unwrap().disassemble_program();
+
+    assert!(output.contains("PushHandler("), "{}", output);
+    assert!(output.contains(" (handler)\n"), "{}", output);
+}
+
+#[test]
+fn test_instruction_display_matches_listing() {
+    assert_eq!(Instruction::Push(Value::Integer(42)).to_string(), "Push(Integer(42))");
+    assert_eq!(Instruction::JmpIfFalse(7).to_string(), "JmpIfFalse(7)");
+    assert_eq!(Instruction::Ret.to_string(), "Ret");
+}
@@ -0,0 +1,92 @@
+// Tests for the dotimes and dolist iteration forms (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::ints;
+
+fn run_code(source: &str) -> Result<Value, String> {
+    common::run_code_with(source, |vm| vm.verify_bytecode = true)
+}
+
+#[test]
+fn test_dotimes_runs_body_n_times() {
+    let source = "(def-mutable squares (vector))
+                  (dotimes (i 4) (set! squares (vector-push squares (* i i))))
+                  (vector->list squares)";
+    assert_eq!(run_code(source).unwrap(), ints(&[0, 1, 4, 9]));
+}
+
+#[test]
+fn test_dotimes_zero_or_negative_count_skips_body() {
+    let source = "(let ((count 0))
+                    (do (dotimes (i 0) (incf count))
+                        (dotimes (i -3) (incf count))
+                        count))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_dolist_visits_every_element() {
+    let source = "(let ((seen '()))
+                    (do (dolist (x '(a b c)) (set! seen (cons x seen)))
+                        seen))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(c b a)").unwrap());
+}
+
+#[test]
+fn test_dolist_empty_list() {
+    assert_eq!(run_code("(dolist (x '()) (car x))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_iteration_returns_false_in_tail_position() {
+    let source = "(defun run (n) (dotimes (i n) (+ i 1)))
+                  (defun walk (lst) (dolist (x lst) (list x)))
+                  (list (run 3) (walk '(1 2)))";
+    let expected = Value::List(List::from_vec(vec![Value::Boolean(false), Value::Boolean(false)]));
+    assert_eq!(run_code(source).unwrap(), expected);
+}
+
+#[test]
+fn test_nested_iteration_and_slot_cleanup() {
+    // Locals bound after the loops still see the right slots
+    let source = "(defun pairs (lst n)
+                    (let ((total 0))
+                      (do (dolist (x lst)
+                            (dotimes (j n) (set! total (+ total (* x j)))))
+                          (let ((after 100)) (+ total after)))))
+                  (pairs '(1 2 3) 3)";
+    // (1+2+3) * (0+1+2) = 18
+    assert_eq!(run_code(source).unwrap(), Value::Integer(118));
+}
+
+#[test]
+fn test_iteration_body_sequence_and_closures() {
+    let source = "(let ((fns '()) (sum 0))
+                    (do (dotimes (i 3)
+                          (incf sum i)
+                          (set! fns (cons (lambda () (* i 10)) fns)))
+                        (list sum (map (lambda (f) (f)) fns))))";
+    let expected = Value::List(List::from_vec(vec![Value::Integer(3), ints(&[20, 10, 0])]));
+    assert_eq!(run_code(source).unwrap(), expected);
+}
+
+#[test]
+fn test_iteration_bad_forms() {
+    assert!(run_code("(dotimes i 3)").unwrap_err().contains("dotimes expects (dotimes (var count) body ...)"));
+    assert!(run_code("(dolist (x '(1)))").unwrap_err().contains("dolist expects"));
+    assert!(run_code("(dotimes (1 3) 0)").unwrap_err().contains("variable must be a symbol"));
+    assert!(run_code("(dolist (x 5) x)").is_err());
+}
+
+#[test]
+fn test_dotimes_requires_integer_count() {
+    let err = run_code("(dotimes (i 2.5) 1)").unwrap_err();
+    assert!(err.contains("Type error: 'dotimes' expects an integer count, got float"), "got: {}", err);
+    let err = run_code("(dotimes (i \"3\") 1)").unwrap_err();
+    assert!(err.contains("expects an integer count, got string"), "got: {}", err);
+
+    let source = "(let ((count 0) (n 3)) (do (dotimes (i n) (incf count)) count))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(3));
+}
@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use lisp_bytecode_vm::{Compiler, List, VM, Value, parser::Parser};
+
+/// Helper to compile source into a VM that is ready to run
+fn load(source: &str) -> VM {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    vm
+}
+
+// ==================== run_to_value ====================
+
+#[test]
+fn test_run_to_value_returns_last_expression() {
+    let mut vm = load("(+ 1 2) (* 3 4)");
+    assert_eq!(vm.run_to_value().unwrap(), Value::Integer(12));
+}
+
+#[test]
+fn test_run_to_value_after_definitions() {
+    let source = r#"
+        (def base 8080)
+        (defun port-for (n) (+ base n))
+        (port-for 3)
+    "#;
+    let mut vm = load(source);
+    assert_eq!(vm.run_to_value().unwrap(), Value::Integer(8083));
+}
+
+#[test]
+fn test_run_to_value_returns_compound_values() {
+    let mut vm = load("(list 1 2.5 \"three\")");
+    let expected = Value::List(List::from_vec(vec![
+        Value::Integer(1),
+        Value::Float(2.5),
+        Value::String(Arc::new("three".to_string())),
+    ]));
+    assert_eq!(vm.run_to_value().unwrap(), expected);
+}
+
+#[test]
+fn test_run_to_value_pops_the_result() {
+    let mut vm = load("42");
+    assert_eq!(vm.run_to_value().unwrap(), Value::Integer(42));
+    assert!(vm.value_stack.is_empty());
+}
+
+#[test]
+fn test_run_to_value_with_only_definitions() {
+    let mut vm = load("(defun f (x) x)");
+    assert_eq!(vm.run_to_value().unwrap(), Value::List(List::Nil));
+}
+
+#[test]
+fn test_run_to_value_propagates_errors() {
+    let mut vm = load("(/ 1 0)");
+    assert_eq!(vm.run_to_value().unwrap_err().message, "Division by zero");
+}
+
+// ==================== call_function ====================
+
+const PROGRAM: &str = r#"
+    (def greeting "hello")
+    (defun square (x) (* x x))
+    (defun greet (name) (string-append greeting (string-append ", " name)))
+    (defun count-down (n acc) (if (== n 0) acc (count-down (- n 1) (+ acc 1))))
+    (defun sum-squares (lst) (if (null? lst) 0 (+ (square (car lst)) (sum-squares (cdr lst)))))
+"#;
+
+/// Helper to load PROGRAM's definitions into a VM
+fn loaded() -> VM {
+    let mut vm = load(PROGRAM);
+    vm.run().unwrap();
+    vm
+}
+
+#[test]
+fn test_call_user_function() {
+    let mut vm = loaded();
+    assert_eq!(vm.call_function("square", vec![Value::Integer(7)]).unwrap(), Value::Integer(49));
+    assert_eq!(vm.call_function("square", vec![Value::Float(1.5)]).unwrap(), Value::Float(2.25));
+}
+
+#[test]
+fn test_call_builtin_function() {
+    let mut vm = loaded();
+    let args = vec![Value::Integer(2), Value::Integer(3)];
+    assert_eq!(vm.call_function("+", args).unwrap(), Value::Integer(5));
+}
+
+#[test]
+fn test_call_variadic_builtin_function() {
+    // The builtin '*' packs arguments past the second into a rest list
+    let mut vm = loaded();
+    let args = vec![Value::Integer(2), Value::Integer(3), Value::Integer(4)];
+    assert_eq!(vm.call_function("*", args).unwrap(), Value::Integer(24));
+
+    let err = vm.call_function("*", vec![Value::Integer(2)]).unwrap_err();
+    assert!(err.message.contains("expected at least 2"), "{}", err.message);
+}
+
+#[test]
+fn test_call_multi_clause_function() {
+    let mut vm = load("(defun fact ((0) 1) ((n) (* n (fact (- n 1)))))");
+    vm.run().unwrap();
+    assert_eq!(vm.call_function("fact", vec![Value::Integer(0)]).unwrap(), Value::Integer(1));
+    assert_eq!(vm.call_function("fact", vec![Value::Integer(5)]).unwrap(), Value::Integer(120));
+}
+
+#[test]
+fn test_call_function_using_globals() {
+    let mut vm = loaded();
+    let args = vec![Value::String(Arc::new("world".to_string()))];
+    assert_eq!(
+        vm.call_function("greet", args).unwrap(),
+        Value::String(Arc::new("hello, world".to_string()))
+    );
+}
+
+#[test]
+fn test_call_function_with_nested_calls() {
+    let mut vm = loaded();
+    let lst = Value::List(List::from_vec(vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]));
+    assert_eq!(vm.call_function("sum-squares", vec![lst]).unwrap(), Value::Integer(14));
+}
+
+#[test]
+fn test_call_function_with_tail_calls() {
+    let mut vm = loaded();
+    let args = vec![Value::Integer(100000), Value::Integer(0)];
+    assert_eq!(vm.call_function("count-down", args).unwrap(), Value::Integer(100000));
+    assert!(vm.call_stack.is_empty());
+}
+
+#[test]
+fn test_call_function_repeatedly() {
+    let mut vm = loaded();
+    for n in 0..10 {
+        assert_eq!(vm.call_function("square", vec![Value::Integer(n)]).unwrap(), Value::Integer(n * n));
+    }
+    assert!(vm.value_stack.is_empty());
+    assert!(vm.call_stack.is_empty());
+}
+
+#[test]
+fn test_call_undefined_function() {
+    let mut vm = loaded();
+    let err = vm.call_function("no-such-fn", vec![]).unwrap_err();
+    assert_eq!(err.message, "Undefined function 'no-such-fn'");
+}
+
+#[test]
+fn test_call_function_error_leaves_vm_usable() {
+    let mut vm = loaded();
+    let err = vm.call_function("square", vec![Value::Boolean(true)]).unwrap_err();
+    assert!(err.message.contains("Type error"), "{}", err.message);
+    assert_eq!(err.call_stack, vec!["square".to_string()]);
+    assert!(vm.call_stack.is_empty());
+    assert!(vm.value_stack.is_empty());
+
+    assert_eq!(vm.call_function("square", vec![Value::Integer(4)]).unwrap(), Value::Integer(16));
+}
+
+#[test]
+fn test_call_value_with_closure() {
+    let mut vm = load("(def base 10) (lambda (x) (+ x base))");
+    let closure = vm.run_to_value().unwrap();
+    assert_eq!(vm.call_value(closure.clone(), vec![Value::Integer(5)]).unwrap(), Value::Integer(15));
+
+    let err = vm.call_value(closure, vec![]).unwrap_err();
+    assert!(err.message.contains("arity mismatch"), "{}", err.message);
+    assert!(vm.value_stack.is_empty());
+}
+
+// ==================== Value conversions ====================
+
+#[test]
+fn test_values_from_rust_types() {
+    assert_eq!(Value::from(42), Value::Integer(42));
+    assert_eq!(Value::from(2.5), Value::Float(2.5));
+    assert_eq!(Value::from(true), Value::Boolean(true));
+    assert_eq!(Value::from("hi"), Value::String(Arc::new("hi".to_string())));
+    assert_eq!(Value::from("hi".to_string()), Value::from("hi"));
+    assert_eq!(
+        Value::from(vec![Value::from(1), Value::from(2)]),
+        Value::List(List::from_vec(vec![Value::Integer(1), Value::Integer(2)]))
+    );
+}
+
+#[test]
+fn test_rust_types_from_values() {
+    assert_eq!(i64::try_from(Value::Integer(7)).unwrap(), 7);
+    assert_eq!(f64::try_from(Value::Float(0.5)).unwrap(), 0.5);
+    assert_eq!(f64::try_from(Value::Integer(3)).unwrap(), 3.0);
+    assert!(bool::try_from(Value::Boolean(false)).is_ok_and(|b| !b));
+    assert_eq!(String::try_from(Value::from("text")).unwrap(), "text");
+    assert_eq!(
+        Vec::<Value>::try_from(Value::from(vec![Value::from(1), Value::from("a")])).unwrap(),
+        vec![Value::Integer(1), Value::from("a")]
+    );
+}
+
+#[test]
+fn test_failed_conversions_report_types() {
+    assert_eq!(
+        i64::try_from(Value::Float(1.5)).unwrap_err().message,
+        "Type error: expected integer, got float"
+    );
+    assert_eq!(
+        f64::try_from(Value::from("1.5")).unwrap_err().message,
+        "Type error: expected number, got string"
+    );
+    assert_eq!(
+        String::try_from(Value::symbol("sym")).unwrap_err().message,
+        "Type error: expected string, got symbol"
+    );
+    assert_eq!(
+        Vec::<Value>::try_from(Value::Integer(1)).unwrap_err().message,
+        "Type error: expected list, got integer"
+    );
+    assert!(bool::try_from(Value::Integer(0)).is_err());
+}
+
+#[test]
+fn test_result_accessors() {
+    assert_eq!(Value::Integer(5).as_integer().unwrap(), 5);
+    assert!(Value::Boolean(true).as_integer().is_err());
+    assert_eq!(Value::from("abc").as_string().unwrap(), "abc");
+    assert!(Value::symbol("abc").as_string().is_err());
+}
+
+#[test]
+fn test_conversions_round_trip_through_call_function() {
+    let mut vm = loaded();
+    let result = vm.call_function("square", vec![12.into()]).unwrap();
+    assert_eq!(i64::try_from(result).unwrap(), 144);
+
+    let greeting = vm.call_function("greet", vec!["rust".into()]).unwrap();
+    assert_eq!(greeting.as_string().unwrap(), "hello, rust");
+}
@@ -0,0 +1,61 @@
+// Tests for the eq? identity predicate and how it differs from equal? (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn is_true(source: &str) -> bool {
+    match run_code(source).unwrap() {
+        Value::Boolean(b) => b,
+        other => panic!("expected a boolean from {}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn test_eq_symbols_compare_by_name() {
+    assert!(is_true("(eq? 'a 'a)"));
+    assert!(!is_true("(eq? 'a 'b)"));
+    assert!(is_true("(eq? (car '(x y)) 'x)"));
+}
+
+#[test]
+fn test_eq_immediates_compare_by_value() {
+    assert!(is_true("(eq? 42 42)"));
+    assert!(is_true("(eq? true true)"));
+    assert!(is_true("(eq? 1.5 1.5)"));
+    assert!(!is_true("(eq? 1 1.0)"));
+    assert!(is_true("(eq? '() '())"));
+    assert!(is_true("(eq? car car)"));
+}
+
+#[test]
+fn test_fresh_lists_are_equal_but_not_eq() {
+    assert!(!is_true("(eq? (list 1 2) (list 1 2))"));
+    assert!(is_true("(equal? (list 1 2) (list 1 2))"));
+    assert!(!is_true("(eq? (cons 'a 1) (cons 'a 1))"));
+    assert!(is_true("(equal? (cons 'a 1) (cons 'a 1))"));
+}
+
+#[test]
+fn test_eq_on_shared_structure() {
+    assert!(is_true("(let ((xs (list 1 2 3))) (eq? xs xs))"));
+    assert!(is_true("(let ((xs (list 1 2 3))) (eq? (cdr xs) (cdr (cons 0 (cdr xs)))))"));
+    assert!(!is_true("(let ((xs (list 1 2 3))) (eq? xs (list-copy xs)))"));
+}
+
+#[test]
+fn test_eq_on_vectors_and_closures() {
+    assert!(is_true("(let ((v (vector 1 2))) (eq? v v))"));
+    assert!(!is_true("(eq? (vector 1 2) (vector 1 2))"));
+    assert!(is_true("(let ((f (lambda (x) x))) (eq? f f))"));
+    assert!(!is_true("(eq? (lambda (x) x) (lambda (x) x))"));
+}
+
+#[test]
+fn test_eq_as_first_class_value() {
+    assert!(is_true("(apply eq? (list 'k 'k))"));
+    assert!(!is_true("(apply eq? (list (list 1) (list 1)))"));
+    let err = run_code("(eq? 1 2 3)").unwrap_err();
+    assert!(err.contains("eq? expects exactly 2 arguments"), "got: {}", err);
+}
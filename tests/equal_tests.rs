@@ -0,0 +1,65 @@
+// Tests for structural equality with equal? versus numeric == (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn is_true(source: &str) -> bool {
+    match run_code(source).unwrap() {
+        Value::Boolean(b) => b,
+        other => panic!("expected a boolean from {}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn test_equal_on_atoms() {
+    assert!(is_true("(equal? 3 3)"));
+    assert!(is_true("(equal? \"abc\" \"abc\")"));
+    assert!(is_true("(equal? 'sym 'sym)"));
+    assert!(!is_true("(equal? 'a 'b)"));
+    assert!(!is_true("(equal? \"1\" 1)"));
+}
+
+#[test]
+fn test_equal_does_not_coerce_numbers() {
+    assert!(!is_true("(equal? 1 1.0)"));
+    assert!(is_true("(== 1 1.0)"));
+    assert!(!is_true("(equal? '(1 2) '(1 2.0))"));
+    assert!(is_true("(equal? (expt 2 65) (expt 2 65))"));
+    assert!(!is_true("(equal? (expt 2 65) (* 1.0 (expt 2 65)))"));
+}
+
+#[test]
+fn test_equal_on_nested_lists() {
+    assert!(is_true("(equal? '(1 (2 (3 4)) 5) (list 1 (list 2 (list 3 4)) 5))"));
+    assert!(!is_true("(equal? '(1 (2 (3 4)) 5) '(1 (2 (3 5)) 5))"));
+    assert!(!is_true("(equal? '(1 2) '(1 2 3))"));
+    assert!(is_true("(equal? '(a . 1) (cons 'a 1))"));
+    assert!(!is_true("(equal? '(a . 1) '(a 1))"));
+    assert!(is_true("(equal? '() '())"));
+}
+
+#[test]
+fn test_equal_on_vectors_and_hashmaps() {
+    assert!(is_true("(equal? (vector 1 (list 2 3) \"x\") (vector 1 '(2 3) \"x\"))"));
+    assert!(!is_true("(equal? (vector 1 2) (vector 2 1))"));
+    assert!(!is_true("(equal? (vector 1 2) '(1 2))"));
+    assert!(is_true("(equal? (list (vector 1 2)) (list (vector 1 2)))"));
+    assert!(is_true("(equal? (hash-map \"a\" '(1 2) \"b\" 2) (hashmap-set (hash-map \"a\" '(1 2)) \"b\" 2))"));
+    assert!(!is_true("(equal? (hash-map \"a\" 1) (hash-map \"a\" 1.0))"));
+}
+
+#[test]
+fn test_equal_as_first_class_value() {
+    assert!(is_true("(apply equal? (list '(1 (2)) '(1 (2))))"));
+    let source = "(defun same-as (x f) (f x '(1 2)))
+(same-as (list 1 2) equal?)";
+    assert!(is_true(source));
+}
+
+#[test]
+fn test_equal_arity_is_checked() {
+    let err = run_code("(equal? 1)").unwrap_err();
+    assert!(err.contains("equal? expects exactly 2 arguments"), "got: {}", err);
+}
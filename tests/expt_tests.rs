@@ -0,0 +1,61 @@
+// Tests for expt: exact integer powers, with a float fallback (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_expt_of_integers_is_exact_integer() {
+    assert_eq!(run_code("(expt 2 10)").unwrap(), Value::Integer(1024));
+    assert_eq!(run_code("(expt -3 3)").unwrap(), Value::Integer(-27));
+    assert_eq!(run_code("(expt 7 0)").unwrap(), Value::Integer(1));
+    assert_eq!(run_code("(expt 0 0)").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn test_expt_negative_exponent_is_an_error() {
+    let err = run_code("(expt 2 -1)").unwrap_err();
+    assert!(err.contains("non-negative integer exponent"), "unexpected error: {}", err);
+    assert_eq!(run_code("(expt 2.0 -1)").unwrap(), Value::Float(0.5));
+}
+
+#[test]
+fn test_expt_with_float_operand_is_float() {
+    assert_eq!(run_code("(expt 2.0 3)").unwrap(), Value::Float(8.0));
+    assert_eq!(run_code("(expt 4 0.5)").unwrap(), Value::Float(2.0));
+}
+
+#[test]
+fn test_expt_overflow_promotes_to_bigint() {
+    assert_eq!(
+        run_code("(expt 2 100)").unwrap(),
+        Value::from_bigint(BigInt::parse("1267650600228229401496703205376").unwrap())
+    );
+    assert_eq!(run_code("(== (expt (expt 2 64) 2) (expt 2 128))").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn test_expt_overflow_is_error_in_checked_mode() {
+    let exprs = parser::Parser::new("(expt 10 19)").parse_all().unwrap();
+    let (functions, main) = Compiler::new().compile_program(&exprs).unwrap();
+    let mut vm = VM::new();
+    vm.set_checked_arithmetic(true);
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    let err = vm.run_to_value().unwrap_err().message;
+    assert!(err.contains("integer overflow in 'expt'"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_expt_as_value() {
+    assert_eq!(run_code("(map (lambda (n) (expt n 2)) '(1 2 3))").unwrap(), run_code("'(1 4 9)").unwrap());
+    assert_eq!(run_code("(apply expt '(3 4))").unwrap(), Value::Integer(81));
+}
+
+#[test]
+fn test_expt_arity_and_types() {
+    assert!(run_code("(expt 2)").unwrap_err().contains("expects exactly 2 arguments"));
+    let err = run_code("(expt \"2\" 3)").unwrap_err();
+    assert!(err.contains("Type error: 'expt' expects numbers"), "unexpected error: {}", err);
+}
@@ -0,0 +1,58 @@
+// Tests for the native flatten and remove-duplicates builtins (no stdlib loaded)
+
+mod common;
+
+use common::{run_code, ints};
+
+#[test]
+fn test_flatten_nested() {
+    assert_eq!(run_code("(flatten '(1 (2 (3 4)) 5))").unwrap(), ints(&[1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn test_flatten_empty_and_flat() {
+    assert_eq!(run_code("(flatten '())").unwrap(), ints(&[]));
+    assert_eq!(run_code("(flatten '(1 2 3))").unwrap(), ints(&[1, 2, 3]));
+}
+
+#[test]
+fn test_flatten_drops_empty_sublists() {
+    assert_eq!(run_code("(flatten '(() (1 ()) ((2)) 3))").unwrap(), ints(&[1, 2, 3]));
+}
+
+#[test]
+fn test_flatten_mixed_atoms() {
+    assert_eq!(
+        run_code(r#"(flatten '(a ("b" (true)) 1.5))"#).unwrap(),
+        run_code(r#"'(a "b" true 1.5)"#).unwrap()
+    );
+}
+
+#[test]
+fn test_flatten_deeply_nested() {
+    let source = "(loop ((i 0) (acc (list 1))) (if (== i 2000) (flatten (list 0 acc 2)) (recur (+ i 1) (list acc))))";
+    assert_eq!(run_code(source).unwrap(), ints(&[0, 1, 2]));
+}
+
+#[test]
+fn test_remove_duplicates_keeps_first_occurrence() {
+    assert_eq!(run_code("(remove-duplicates '(3 1 3 2 1))").unwrap(), ints(&[3, 1, 2]));
+    assert_eq!(run_code("(remove-duplicates '())").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_remove_duplicates_compares_structurally() {
+    assert_eq!(
+        run_code(r#"(remove-duplicates '((1 2) "a" (1 2) "a" b))"#).unwrap(),
+        run_code(r#"'((1 2) "a" b)"#).unwrap()
+    );
+}
+
+#[test]
+fn test_flatten_and_remove_duplicates_require_lists() {
+    let err = run_code("(flatten 5)").unwrap_err();
+    assert!(err.contains("'flatten' expects a list, got integer"), "{}", err);
+
+    let err = run_code("(remove-duplicates 5)").unwrap_err();
+    assert!(err.contains("'remove-duplicates' expects a list, got integer"), "{}", err);
+}
@@ -394,6 +394,47 @@ fn test_ceil_integer() {
     assert_eq!(get_int(&vm), 5);
 }
 
+#[test]
+fn test_floor_negative_half() {
+    let vm = compile_and_run("(floor -1.5)");
+    assert_eq!(get_int(&vm), -2);
+}
+
+#[test]
+fn test_round_half_to_even() {
+    for (source, expected) in [("(round 2.5)", 2), ("(round 3.5)", 4), ("(round -2.5)", -2), ("(round 0.5)", 0)] {
+        let vm = compile_and_run(source);
+        assert_eq!(get_int(&vm), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_round_to_nearest() {
+    let vm = compile_and_run("(list (round 2.4) (round 2.6) (round -2.6))");
+    assert_eq!(vm.value_stack.last(), Some(&Value::list_from_vec(vec![Value::Integer(2), Value::Integer(3), Value::Integer(-3)])));
+}
+
+#[test]
+fn test_round_integer() {
+    let vm = compile_and_run("(round 7)");
+    assert_eq!(get_int(&vm), 7);
+}
+
+#[test]
+fn test_rounding_large_float_gives_bigint() {
+    let vm = compile_and_run("(== (round 1.0e20) (* 10000000000 10000000000))");
+    assert!(get_bool(&vm));
+    let vm = compile_and_run("(bigint? (floor -1.0e19))");
+    assert!(get_bool(&vm));
+}
+
+#[test]
+fn test_rounding_nan_is_an_error() {
+    let inf = "(* 1.0e308 10.0)";
+    assert!(run_err(&format!("(round (- {} {}))", inf, inf)).contains("cannot turn NaN into an integer"));
+    assert!(run_err(&format!("(floor {})", inf)).contains("cannot turn inf into an integer"));
+}
+
 #[test]
 fn test_pow_integer_base() {
     let vm = compile_and_run("(pow 2 3)");
@@ -458,3 +499,165 @@ fn test_pythagorean_theorem() {
     let vm = compile_and_run("(sqrt (+ (* 3 3) (* 4 4)))");
     assert_eq!(get_float(&vm), 5.0);
 }
+
+// ============================================================
+// NaN and Infinity Policy
+// ============================================================
+
+/// Helper for programs expected to fail at runtime
+fn run_err(source: &str) -> String {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    vm.run().unwrap_err().message
+}
+
+#[test]
+fn test_nan_predicate() {
+    assert!(get_bool(&compile_and_run("(nan? (sqrt -1.0))")));
+    assert!(!get_bool(&compile_and_run("(nan? 1.5)")));
+    assert!(!get_bool(&compile_and_run("(nan? 3)")));
+    assert!(!get_bool(&compile_and_run("(nan? \"nan\")")));
+}
+
+#[test]
+fn test_infinite_predicate() {
+    assert!(get_bool(&compile_and_run("(infinite? (* 1e308 10.0))")));
+    assert!(get_bool(&compile_and_run("(infinite? (* -1e308 10.0))")));
+    assert!(!get_bool(&compile_and_run("(infinite? 1e308)")));
+    assert!(!get_bool(&compile_and_run("(infinite? (sqrt -1.0))")));
+    assert!(!get_bool(&compile_and_run("(infinite? 42)")));
+}
+
+#[test]
+fn test_nan_equality_follows_ieee() {
+    // NaN is unequal to everything, itself included
+    assert!(!get_bool(&compile_and_run("(let ((n (sqrt -1.0))) (== n n))")));
+    assert!(get_bool(&compile_and_run("(let ((n (sqrt -1.0))) (!= n n))")));
+    assert!(!get_bool(&compile_and_run("(== (sqrt -1.0) 1)")));
+}
+
+#[test]
+fn test_nan_ordering_is_an_error() {
+    for op in ["<", "<=", ">", ">="] {
+        let err = run_err(&format!("({} (sqrt -1.0) 1.0)", op));
+        assert_eq!(err, format!("'{}' cannot order NaN", op));
+        let err = run_err(&format!("({} 1 (sqrt -1.0))", op));
+        assert_eq!(err, format!("'{}' cannot order NaN", op));
+    }
+}
+
+#[test]
+fn test_infinity_ordering() {
+    assert!(get_bool(&compile_and_run("(> (* 1e308 10.0) 1e308)")));
+    assert!(get_bool(&compile_and_run("(< (* -1e308 10.0) -1e308)")));
+}
+
+// ============================================================
+// Mixed-Mode Arithmetic Rules
+// ============================================================
+
+/// Helper to get the result value with its exact type
+fn eval(source: &str) -> Value {
+    compile_and_run(source).value_stack.last().cloned().unwrap()
+}
+
+#[test]
+fn test_int_int_arithmetic_stays_integer() {
+    assert_eq!(eval("(+ 7 2)"), Value::Integer(9));
+    assert_eq!(eval("(- 7 2)"), Value::Integer(5));
+    assert_eq!(eval("(* 7 2)"), Value::Integer(14));
+    assert_eq!(eval("(/ 7 2)"), Value::Integer(3));
+    assert_eq!(eval("(% 7 2)"), Value::Integer(1));
+}
+
+#[test]
+fn test_int_float_arithmetic_promotes() {
+    assert_eq!(eval("(+ 7 2.0)"), Value::Float(9.0));
+    assert_eq!(eval("(- 7 2.0)"), Value::Float(5.0));
+    assert_eq!(eval("(* 7 2.0)"), Value::Float(14.0));
+    assert_eq!(eval("(/ 7 2.0)"), Value::Float(3.5));
+    assert_eq!(eval("(% 7 2.0)"), Value::Float(1.0));
+}
+
+#[test]
+fn test_float_int_arithmetic_promotes() {
+    assert_eq!(eval("(+ 7.0 2)"), Value::Float(9.0));
+    assert_eq!(eval("(- 7.0 2)"), Value::Float(5.0));
+    assert_eq!(eval("(* 7.0 2)"), Value::Float(14.0));
+    assert_eq!(eval("(/ 7.0 2)"), Value::Float(3.5));
+    assert_eq!(eval("(% 7.0 2)"), Value::Float(1.0));
+}
+
+#[test]
+fn test_float_float_arithmetic_stays_float() {
+    assert_eq!(eval("(+ 7.0 2.0)"), Value::Float(9.0));
+    assert_eq!(eval("(- 7.0 2.0)"), Value::Float(5.0));
+    assert_eq!(eval("(* 7.0 2.0)"), Value::Float(14.0));
+    assert_eq!(eval("(/ 7.0 2.0)"), Value::Float(3.5));
+    assert_eq!(eval("(% 7.5 2.0)"), Value::Float(1.5));
+}
+
+#[test]
+fn test_whole_float_results_keep_float_type() {
+    // A float operand promotes even when the result is a whole number
+    assert_eq!(eval("(* 2 0.5)"), Value::Float(1.0));
+    assert_eq!(eval("(/ 4 2.0)"), Value::Float(2.0));
+    assert_eq!(eval("(+ 1 2 3.0)"), Value::Float(6.0));
+}
+
+#[test]
+fn test_promotion_is_per_operation() {
+    // (/ 7 2) truncates before the float is ever seen
+    assert_eq!(eval("(+ (/ 7 2) 0.5)"), Value::Float(3.5));
+    assert_eq!(eval("(/ 7 2 1.0)"), Value::Float(3.0));
+    assert_eq!(eval("(/ 7 1.0 2)"), Value::Float(3.5));
+}
+
+#[test]
+fn test_integer_division_truncates_toward_zero() {
+    assert_eq!(eval("(/ 7 2)"), Value::Integer(3));
+    assert_eq!(eval("(/ -7 2)"), Value::Integer(-3));
+    assert_eq!(eval("(/ 7 -2)"), Value::Integer(-3));
+    assert_eq!(eval("(/ -7 -2)"), Value::Integer(3));
+}
+
+#[test]
+fn test_modulo_takes_sign_of_dividend() {
+    assert_eq!(eval("(% 7 2)"), Value::Integer(1));
+    assert_eq!(eval("(% -7 2)"), Value::Integer(-1));
+    assert_eq!(eval("(% 7 -2)"), Value::Integer(1));
+    assert_eq!(eval("(% -7 -2)"), Value::Integer(-1));
+    assert_eq!(eval("(% -7.5 2)"), Value::Float(-1.5));
+    assert_eq!(eval("(% 7.5 -2)"), Value::Float(1.5));
+}
+
+#[test]
+fn test_division_identity_holds_for_integers() {
+    // (+ (* (/ a b) b) (% a b)) == a
+    for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (0, 5)] {
+        let src = format!("(+ (* (/ {a} {b}) {b}) (% {a} {b}))");
+        assert_eq!(eval(&src), Value::Integer(a));
+    }
+}
+
+#[test]
+fn test_zero_divisor_is_an_error_for_every_mix() {
+    for (a, b) in [("1", "0"), ("1", "0.0"), ("1.0", "0"), ("1.0", "0.0")] {
+        assert_eq!(run_err(&format!("(/ {} {})", a, b)), "Division by zero");
+        assert_eq!(run_err(&format!("(% {} {})", a, b)), "Modulo by zero");
+    }
+}
+
+#[test]
+fn test_integer_division_overflow_promotes() {
+    let min = i64::MIN;
+    assert_eq!(eval(&format!("(% {} -1)", min)), Value::Integer(0));
+    assert!(eval(&format!("(/ {} -1)", min)).is_bigint());
+    // A float operand gives the approximate float result
+    assert_eq!(eval(&format!("(/ {} -1.0)", min)), Value::Float(-(min as f64)));
+}
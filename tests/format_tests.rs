@@ -1,4 +1,4 @@
-use lisp_bytecode_vm::{Compiler, VM, parser::Parser, Value};
+use lisp_bytecode_vm::{Compiler, Instruction, VM, parser::Parser, Value};
 
 fn compile_and_run(source: &str) -> Result<String, String> {
     let mut parser = Parser::new(source);
@@ -167,3 +167,330 @@ fn test_format_nested() {
     let result = compile_and_run(source).unwrap();
     assert_eq!(result, "Outer: Inner: 42");
 }
+
+// ==================== String Interpolation ====================
+
+#[test]
+fn test_interpolation_of_variables_and_calls() {
+    let source = r##"
+        (def name "Ann")
+        (def items (list 1 2 3))
+        "Hello #{name}, you have #{(list-length items)} items"
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "Hello Ann, you have 3 items");
+}
+
+#[test]
+fn test_interpolation_of_mixed_types() {
+    let source = r##"
+        "#{1} #{2.5} #{true} #{(list 1 2)} #{'sym}"
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "1 2.5 true (1 2) sym");
+}
+
+#[test]
+fn test_interpolation_in_function_body() {
+    let source = r##"
+        (defun describe (name age)
+          "#{name} is #{age} years old, #{(- 100 age)} to go")
+        (describe "Bob" 40)
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "Bob is 40 years old, 60 to go");
+}
+
+#[test]
+fn test_interpolation_captured_by_closure() {
+    let source = r##"
+        (defun greeter (greeting)
+          (lambda (who) "#{greeting}, #{who}!"))
+        ((greeter "Hi") "there")
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "Hi, there!");
+}
+
+#[test]
+fn test_interpolation_keeps_literal_braces() {
+    // Text that looks like a format placeholder is not treated as one
+    let source = r##"
+        (def x 1)
+        "{} #{x} {#{x}}"
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "{} 1 {1}");
+}
+
+#[test]
+fn test_interpolation_escape() {
+    let source = r##"
+        (def x 1)
+        "\#{x} is #{x}"
+    "##;
+
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result, "#{x} is 1");
+}
+
+#[test]
+fn test_escaped_interpolation_only_is_plain_string() {
+    let result = compile_and_run(r##""no \#{interpolation} here""##).unwrap();
+    assert_eq!(result, "no #{interpolation} here");
+}
+
+#[test]
+fn test_interpolation_with_nested_string_literals() {
+    let source = r##""#{(string-append "x" "y")} and #{(string-append "}" "{")}""##;
+    assert_eq!(compile_and_run(source).unwrap(), "xy and }{");
+}
+
+#[test]
+fn test_interpolation_ignores_shadowed_format_and_list() {
+    let source = r##"
+        (let ((format (lambda (a b) "local format"))
+              (list (lambda (x) "local list"))
+              (x 1))
+          "x is #{x}")
+    "##;
+    assert_eq!(compile_and_run(source).unwrap(), "x is 1");
+
+    let source = r##"
+        (defun list (x) "user list")
+        (defun format (a b) "user format")
+        (def y 2)
+        "y is #{y}"
+    "##;
+    assert_eq!(compile_and_run(source).unwrap(), "y is 2");
+}
+
+#[test]
+fn test_interpolation_compiles_to_one_instruction() {
+    let exprs = Parser::new(r##"(def n 3) "n=#{n}!""##).parse_all().unwrap();
+    let (_, main) = Compiler::new().compile_program(&exprs).unwrap();
+    assert!(main.contains(&Instruction::Interpolate(3)), "{:?}", main);
+    assert!(!main.contains(&Instruction::Format), "{:?}", main);
+}
+
+#[test]
+fn test_interpolation_errors() {
+    let unterminated = compile_and_run(r##""value: #{(+ 1 2)""##).unwrap_err();
+    assert!(unterminated.contains("Unterminated #{ in string literal"), "{}", unterminated);
+
+    let two_exprs = compile_and_run(r##""#{1 2}""##).unwrap_err();
+    assert!(two_exprs.contains("must contain exactly one expression, found 2"), "{}", two_exprs);
+
+    let empty = compile_and_run(r##""#{}""##).unwrap_err();
+    assert!(empty.contains("found 0"), "{}", empty);
+
+    let bad = compile_and_run(r##""#{(+ 1}""##).unwrap_err();
+    assert!(bad.contains("In string interpolation #{(+ 1}"), "{}", bad);
+}
+
+// ============================================================
+// format-int
+// ============================================================
+
+#[test]
+fn test_format_int_defaults_match_number_to_string() {
+    for n in ["0", "7", "-7", "1234567", "-9223372036854775808"] {
+        let formatted = compile_and_run(&format!("(format-int {})", n)).unwrap();
+        let plain = compile_and_run(&format!("(number->string {})", n)).unwrap();
+        assert_eq!(formatted, plain);
+    }
+}
+
+#[test]
+fn test_format_int_grouping() {
+    assert_eq!(compile_and_run(r#"(format-int 1234567 :grouping ",")"#).unwrap(), "1,234,567");
+    assert_eq!(compile_and_run(r#"(format-int 123456 :grouping ",")"#).unwrap(), "123,456");
+    assert_eq!(compile_and_run(r#"(format-int 999 :grouping ",")"#).unwrap(), "999");
+    assert_eq!(compile_and_run(r#"(format-int 1000 :grouping " ")"#).unwrap(), "1 000");
+    assert_eq!(compile_and_run(r#"(format-int 1234567 :grouping "'")"#).unwrap(), "1'234'567");
+}
+
+#[test]
+fn test_format_int_grouping_negative() {
+    // The sign is never separated from the first digit group
+    assert_eq!(compile_and_run(r#"(format-int -123456 :grouping ",")"#).unwrap(), "-123,456");
+    assert_eq!(compile_and_run(r#"(format-int -1234567 :grouping ",")"#).unwrap(), "-1,234,567");
+    assert_eq!(
+        compile_and_run(r#"(format-int -9223372036854775808 :grouping ",")"#).unwrap(),
+        "-9,223,372,036,854,775,808"
+    );
+}
+
+#[test]
+fn test_format_int_padding() {
+    assert_eq!(compile_and_run("(format-int 42 :width 6)").unwrap(), "    42");
+    assert_eq!(compile_and_run(r#"(format-int 42 :width 6 :pad "0")"#).unwrap(), "000042");
+    assert_eq!(compile_and_run(r#"(format-int -42 :width 6 :pad "0")"#).unwrap(), "-00042");
+    assert_eq!(compile_and_run("(format-int -42 :width 6)").unwrap(), "   -42");
+    assert_eq!(compile_and_run(r#"(format-int 42 :width 5 :pad "*")"#).unwrap(), "***42");
+    // Width is a minimum; wider numbers are never truncated
+    assert_eq!(compile_and_run("(format-int 123456 :width 3)").unwrap(), "123456");
+}
+
+#[test]
+fn test_format_int_grouping_with_padding() {
+    assert_eq!(
+        compile_and_run(r#"(format-int 1234567 :grouping "," :width 12)"#).unwrap(),
+        "   1,234,567"
+    );
+    assert_eq!(
+        compile_and_run(r#"(format-int -1234 :grouping "," :width 9 :pad "0")"#).unwrap(),
+        "-0001,234"
+    );
+}
+
+#[test]
+fn test_format_int_in_report_line() {
+    let source = r#"
+        (defun row (label amount)
+          (string-append label (format-int amount :grouping "," :width 12)))
+        (row "Total:" 1048576)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "Total:   1,048,576");
+}
+
+#[test]
+fn test_format_int_through_apply() {
+    let source = r#"(apply format-int (list 1234567 (quote :grouping) "."))"#;
+    assert_eq!(compile_and_run(source).unwrap(), "1.234.567");
+}
+
+#[test]
+fn test_format_int_errors() {
+    let err = compile_and_run("(format-int 1.5)").unwrap_err();
+    assert!(err.contains("'format-int' expects an integer, got float"), "{}", err);
+
+    let err = compile_and_run("(format-int 1 :commas true)").unwrap_err();
+    assert!(err.contains("Unknown format-int option :commas"), "{}", err);
+
+    let err = compile_and_run("(format-int 1 :width -2)").unwrap_err();
+    assert!(err.contains("option :width expects a non-negative integer, got -2"), "{}", err);
+
+    let err = compile_and_run(r#"(format-int 1 :pad "ab")"#).unwrap_err();
+    assert!(err.contains("option :pad expects a one-character string"), "{}", err);
+
+    let err = compile_and_run(r#"(format-int 1 :grouping)"#).unwrap_err();
+    assert!(err.contains("format-int expects an integer followed by :option value pairs"), "{}", err);
+
+    let err = compile_and_run(r#"(format-int 1 "grouping" ",")"#).unwrap_err();
+    assert!(err.contains("format-int options must be keywords"), "{}", err);
+}
+
+#[test]
+fn test_format_directives() {
+    assert_eq!(
+        compile_and_run(r#"(format "~a + ~a = ~d~%" 1 2 3)"#).unwrap(),
+        "1 + 2 = 3\n"
+    );
+}
+
+#[test]
+fn test_format_directive_a_versus_s() {
+    let source = r#"(format "~a and ~s" "plain" "quoted")"#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"plain and "quoted""#);
+
+    let source = r#"(format "~s" (list 1 "two"))"#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"(1 "two")"#);
+}
+
+#[test]
+fn test_format_literal_tilde_and_braces() {
+    assert_eq!(compile_and_run(r#"(format "~~~a {}" 5)"#).unwrap(), "~5 {}");
+}
+
+#[test]
+fn test_format_directives_in_function() {
+    let source = r#"
+        (defun describe (name n) (format "~a has ~d item~a" name n (if (== n 1) "" "s")))
+        (string-append (describe "box" 1) ", " (describe "bag" 3))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "box has 1 item, bag has 3 items");
+}
+
+#[test]
+fn test_format_directives_through_apply() {
+    let source = r#"(apply format (list "~a-~a" 1 2))"#;
+    assert_eq!(compile_and_run(source).unwrap(), "1-2");
+}
+
+#[test]
+fn test_format_interpolation_keeps_tilde_literal() {
+    let source = r#"(let ((x 1)) "~a is #{x}")"#;
+    assert_eq!(compile_and_run(source).unwrap(), "~a is 1");
+}
+
+#[test]
+fn test_format_directive_errors() {
+    let err = compile_and_run(r#"(format "~d" 1.5)"#).unwrap_err();
+    assert!(err.contains("~d expects an integer, got float"), "{}", err);
+
+    let err = compile_and_run(r#"(format "~x" 1)"#).unwrap_err();
+    assert!(err.contains("Unknown format directive '~x'"), "{}", err);
+
+    let err = compile_and_run(r#"(format "~a ~a" 1)"#).unwrap_err();
+    assert!(err.contains("Not enough arguments for format directives"), "{}", err);
+
+    let err = compile_and_run(r#"(format "~a" 1 2)"#).unwrap_err();
+    assert!(err.contains("Too many arguments for format directives"), "{}", err);
+
+    let err = compile_and_run(r#"(format "50~" )"#).unwrap_err();
+    assert!(err.contains("unfinished '~' directive"), "{}", err);
+
+    let err = compile_and_run(r#"(format "{} {}" 1 2)"#).unwrap_err();
+    assert!(err.contains("expects one list of values, got 2 arguments"), "{}", err);
+}
+
+// ============================================================
+// number->string
+// ============================================================
+
+#[test]
+fn test_number_to_string_integers() {
+    assert_eq!(compile_and_run("(number->string 42)").unwrap(), "42");
+    assert_eq!(compile_and_run("(number->string -7)").unwrap(), "-7");
+    assert_eq!(compile_and_run("(number->string 9223372036854775807)").unwrap(), "9223372036854775807");
+}
+
+#[test]
+fn test_number_to_string_floats_default() {
+    assert_eq!(compile_and_run("(number->string 3.0)").unwrap(), "3.0");
+    assert_eq!(compile_and_run("(number->string 3.14159)").unwrap(), "3.14159");
+    // Shortest representation that reads back to the same float
+    assert_eq!(compile_and_run("(number->string (+ 0.1 0.2))").unwrap(), "0.30000000000000004");
+}
+
+#[test]
+fn test_number_to_string_precision() {
+    assert_eq!(compile_and_run("(number->string 3.14159 2)").unwrap(), "3.14");
+    assert_eq!(compile_and_run("(number->string 2.5 0)").unwrap(), "2");
+    assert_eq!(compile_and_run("(number->string 3.0 3)").unwrap(), "3.000");
+    assert_eq!(compile_and_run("(number->string 12 2)").unwrap(), "12.00");
+    assert_eq!(compile_and_run("(number->string 12 0)").unwrap(), "12");
+}
+
+#[test]
+fn test_number_to_string_as_value() {
+    assert_eq!(compile_and_run("(apply number->string (list 1.25 1))").unwrap(), "1.2");
+    assert_eq!(compile_and_run("(car (map number->string '(1.5)))").unwrap(), "1.5");
+}
+
+#[test]
+fn test_number_to_string_errors() {
+    let err = compile_and_run(r#"(number->string "5")"#).unwrap_err();
+    assert!(err.contains("'number->string' expects a number"), "{}", err);
+    let err = compile_and_run("(number->string 1.5 -1)").unwrap_err();
+    assert!(err.contains("precision cannot be negative"), "{}", err);
+    let err = compile_and_run("(number->string 1.5 1.0)").unwrap_err();
+    assert!(err.contains("precision must be an integer"), "{}", err);
+    assert!(compile_and_run("(number->string 1 2 3)").is_err());
+}
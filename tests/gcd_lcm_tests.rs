@@ -0,0 +1,76 @@
+// Tests for gcd and lcm (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn run_printed(source: &str) -> String {
+    VM::format_print_line(&[run_code(source).unwrap()], false)
+}
+
+#[test]
+fn test_gcd_of_two_integers() {
+    assert_eq!(run_code("(gcd 48 36)").unwrap(), Value::Integer(12));
+    assert_eq!(run_code("(gcd 17 5)").unwrap(), Value::Integer(1));
+    assert_eq!(run_code("(gcd -48 36)").unwrap(), Value::Integer(12));
+    assert_eq!(run_code("(gcd 0 7)").unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_gcd_of_zeros_is_zero() {
+    assert_eq!(run_code("(gcd 0 0)").unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_lcm_of_two_integers() {
+    assert_eq!(run_code("(lcm 4 6)").unwrap(), Value::Integer(12));
+    assert_eq!(run_code("(lcm -4 6)").unwrap(), Value::Integer(12));
+    assert_eq!(run_code("(lcm 5 0)").unwrap(), Value::Integer(0));
+    assert_eq!(run_code("(lcm 0 0)").unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_gcd_and_lcm_fold_over_several_arguments() {
+    assert_eq!(run_code("(gcd 12 18 24)").unwrap(), Value::Integer(6));
+    assert_eq!(run_code("(lcm 2 3 4)").unwrap(), Value::Integer(12));
+}
+
+#[test]
+fn test_gcd_and_lcm_as_first_class_functions() {
+    assert_eq!(run_code("(let ((f gcd)) (f 48 36))").unwrap(), Value::Integer(12));
+    assert_eq!(run_code("(let ((f lcm)) (f 4 6))").unwrap(), Value::Integer(12));
+}
+
+#[test]
+fn test_results_beyond_i64_promote_to_bigint() {
+    assert_eq!(run_printed("(gcd -9223372036854775808 0)"), "9223372036854775808");
+    assert_eq!(run_printed("(lcm 9223372036854775807 2)"), "18446744073709551614");
+}
+
+#[test]
+fn test_gcd_and_lcm_of_bigints() {
+    // (expt 2 65) = 36893488147419103232
+    assert_eq!(run_code("(gcd (expt 2 65) 12)").unwrap(), Value::Integer(4));
+    assert_eq!(run_printed("(lcm (expt 2 65) 3)"), "110680464442257309696");
+    assert_eq!(run_printed("(gcd (expt 2 65) (expt 6 30))"), "1073741824");
+}
+
+#[test]
+fn test_gcd_rejects_non_integers() {
+    let err = run_code("(gcd 1.5 3)").unwrap_err();
+    assert!(err.contains("'gcd' expects two integers"), "{}", err);
+    let err = run_code("(lcm 4 \"6\")").unwrap_err();
+    assert!(err.contains("'lcm' expects two integers"), "{}", err);
+}
+
+#[test]
+fn test_gcd_needs_at_least_two_arguments() {
+    assert!(run_code("(gcd 4)").is_err());
+}
+
+#[test]
+fn test_user_defined_gcd_takes_precedence() {
+    let source = "(defun gcd (a b) (list a b)) (gcd 48 36)";
+    assert_eq!(VM::format_print_line(&[run_code(source).unwrap()], false), "(48 36)");
+}
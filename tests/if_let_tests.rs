@@ -0,0 +1,70 @@
+// Tests for the if-let and when-let binding conditionals (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_if_let_binds_found_value() {
+    let source = "(if-let (pair (assoc 'b '((a 1) (b 2)))) (car (cdr pair)) 'missing)";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(2));
+}
+
+#[test]
+fn test_if_let_false_takes_else() {
+    let source = "(if-let (pair (assoc 'z '((a 1)))) pair 'missing)";
+    assert_eq!(run_code(source).unwrap(), run_code("'missing").unwrap());
+}
+
+#[test]
+fn test_if_let_empty_list_takes_else() {
+    assert_eq!(run_code("(if-let (xs '()) xs 'empty)").unwrap(), run_code("'empty").unwrap());
+}
+
+#[test]
+fn test_if_let_zero_and_true_are_truthy() {
+    assert_eq!(run_code("(if-let (n 0) (+ n 1) -1)").unwrap(), Value::Integer(1));
+    assert_eq!(run_code("(if-let (b true) b false)").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn test_if_let_else_sees_outer_binding() {
+    let source = "(let ((x 'outer)) (if-let (x false) 'inner x))";
+    assert_eq!(run_code(source).unwrap(), run_code("'outer").unwrap());
+}
+
+#[test]
+fn test_when_let() {
+    assert_eq!(run_code("(when-let (xs (member? 3 '(1 2 3 4))) (car (cdr xs)))").unwrap(), Value::Integer(4));
+    assert_eq!(run_code("(when-let (xs (member? 9 '(1 2))) (car xs))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_if_let_cleans_up_its_slot() {
+    let source = r#"
+        (defun lookup (k) (let ((table '((a 10) (b 20)))) (+ 1 (if-let (hit (assoc k table)) (car (cdr hit)) 0))))
+        (list (lookup 'a) (lookup 'q) (let ((y 5)) (+ y (when-let (v 3) v) y)))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("(list 11 1 13)").unwrap());
+}
+
+#[test]
+fn test_if_let_in_closures_and_tail_position() {
+    let source = r#"
+        (defun count-hits (keys n)
+          (if-let (ks (member? 'x keys))
+            (count-hits (cdr ks) (+ n 1))
+            n))
+        (list (count-hits '(x a x x) 0)
+              (map (lambda (k) (if-let (p (assoc k '((a 1)))) p 'none)) '(a b)))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("(list 3 '((a 1) none))").unwrap());
+}
+
+#[test]
+fn test_if_let_errors() {
+    assert!(run_code("(if-let (x 1) x)").unwrap_err().contains("if-let expects exactly 3 arguments"));
+    assert!(run_code("(when-let x 1)").unwrap_err().contains("if-let binding must be a list"));
+    assert!(run_code("(if-let ((a) 1) a 2)").unwrap_err().contains("binding name must be a symbol"));
+}
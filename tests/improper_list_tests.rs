@@ -0,0 +1,106 @@
+// Tests for improper lists (dotted pairs) built by cons (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn printed(source: &str) -> String {
+    VM::format_print_line(&[run_code(source).unwrap()], false)
+}
+
+#[test]
+fn test_cons_onto_atom_makes_a_pair() {
+    assert_eq!(run_code("(car (cons 1 2))").unwrap(), Value::Integer(1));
+    assert_eq!(run_code("(cdr (cons 1 2))").unwrap(), Value::Integer(2));
+}
+
+#[test]
+fn test_pair_prints_with_a_dot() {
+    assert_eq!(printed("(cons 1 2)"), "(1 . 2)");
+    assert_eq!(printed("(cons 1 (cons 2 3))"), "(1 2 . 3)");
+    assert_eq!(printed("(list (cons 'a 1) (cons 'b 2))"), "((a . 1) (b . 2))");
+}
+
+#[test]
+fn test_cons_onto_list_stays_proper() {
+    assert_eq!(printed("(cons 1 '(2 3))"), "(1 2 3)");
+    assert_eq!(printed("(cons 1 '())"), "(1)");
+}
+
+#[test]
+fn test_pair_is_not_a_list() {
+    assert_eq!(run_code("(list? (cons 1 2))").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(list? (cons 1 (cons 2 3)))").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(list? (cons 1 '(2)))").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(null? (cons 1 2))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_cdr_down_an_improper_list() {
+    assert_eq!(run_code("(cdr (cdr (cons 1 (cons 2 3))))").unwrap(), Value::Integer(3));
+    assert_eq!(printed("(cdr (cons 1 (cons 2 3)))"), "(2 . 3)");
+}
+
+#[test]
+fn test_quoted_dotted_list_matches_cons() {
+    assert_eq!(run_code("'(1 . 2)").unwrap(), run_code("(cons 1 2)").unwrap());
+    assert_eq!(printed("'(a b . c)"), "(a b . c)");
+    assert_eq!(run_code("'(1 . (2 3))").unwrap(), run_code("'(1 2 3)").unwrap());
+}
+
+#[test]
+fn test_pairs_compare_by_both_halves() {
+    assert_eq!(run_code("(== (cons 1 2) (cons 1 2))").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(== (cons 1 2) (cons 1 3))").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(== (cons 1 2) (list 1 2))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_uncons_splits_a_pair() {
+    // The cdr comes back as the atom, not a one-element list
+    assert_eq!(printed("(uncons (cons 1 2))"), "(1 . 2)");
+    assert_eq!(printed("(cdr (uncons (cons 1 2)))"), "2");
+}
+
+#[test]
+fn test_list_builtins_reject_improper_lists() {
+    let cases = [
+        ("(list-length (cons 1 2))", "list-length"),
+        ("(reverse (cons 1 (cons 2 3)))", "reverse"),
+        ("(map (lambda (x) x) (cons 1 2))", "map"),
+        ("(sort (cons 2 1))", "sort"),
+        ("(take 1 (cons 1 2))", "take"),
+        ("(list->vector (cons 1 2))", "list->vector"),
+        ("(string-join (cons \"a\" \"b\") \",\")", "string-join"),
+        ("(foldr + 0 (cons 1 2))", "foldr"),
+        ("(remove-duplicates (cons 1 2))", "remove-duplicates"),
+        ("(zip '(1 2) (cons 1 2))", "zip"),
+        ("(apply + (cons 1 2))", "apply"),
+        ("(append (cons 1 2) '(3))", "append"),
+    ];
+    for (source, op) in cases {
+        let err = run_code(source).unwrap_err();
+        let expected = format!("Type error: '{}' expects a proper list", op);
+        assert!(err.contains(&expected), "{}: got {}", source, err);
+    }
+}
+
+#[test]
+fn test_improper_list_error_names_the_atom() {
+    let err = run_code("(list-length (cons 1 (cons 2 3)))").unwrap_err();
+    assert!(err.ends_with("got an improper list ending in . 3"), "got: {}", err);
+}
+
+#[test]
+fn test_append_keeps_an_improper_last_list() {
+    assert_eq!(printed("(append '(1 2) (cons 3 4))"), "(1 2 3 . 4)");
+    assert_eq!(printed("(append '() (cons 1 2))"), "(1 . 2)");
+    assert_eq!(printed("(append '(1) '(2 3))"), "(1 2 3)");
+}
+
+#[test]
+fn test_list_copy_keeps_the_dotted_atom() {
+    assert_eq!(printed("(list-copy (cons 1 (cons 2 3)))"), "(1 2 . 3)");
+    assert_eq!(run_code("(let ((p (cons 1 2))) (shares-structure? p (list-copy p)))").unwrap(), Value::Boolean(false));
+}
@@ -0,0 +1,98 @@
+// Tests for quotient, remainder and modulo, checked against R7RS results
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn int(n: i64) -> Value {
+    Value::Integer(n)
+}
+
+/// (a b quotient remainder modulo) for every sign combination
+const SCHEME_RESULTS: [(i64, i64, i64, i64, i64); 8] = [
+    (7, 3, 2, 1, 1),
+    (-7, 3, -2, -1, 2),
+    (7, -3, -2, 1, -2),
+    (-7, -3, 2, -1, -1),
+    (6, 3, 2, 0, 0),
+    (-6, 3, -2, 0, 0),
+    (0, -5, 0, 0, 0),
+    (13, 4, 3, 1, 1),
+];
+
+#[test]
+fn test_quotient_matches_scheme() {
+    for (a, b, q, _, _) in SCHEME_RESULTS {
+        assert_eq!(run_code(&format!("(quotient {} {})", a, b)).unwrap(), int(q), "(quotient {} {})", a, b);
+    }
+}
+
+#[test]
+fn test_remainder_matches_scheme() {
+    for (a, b, _, r, _) in SCHEME_RESULTS {
+        assert_eq!(run_code(&format!("(remainder {} {})", a, b)).unwrap(), int(r), "(remainder {} {})", a, b);
+    }
+}
+
+#[test]
+fn test_modulo_matches_scheme() {
+    for (a, b, _, _, m) in SCHEME_RESULTS {
+        assert_eq!(run_code(&format!("(modulo {} {})", a, b)).unwrap(), int(m), "(modulo {} {})", a, b);
+    }
+}
+
+#[test]
+fn test_percent_is_truncated_remainder() {
+    for (a, b, _, r, _) in SCHEME_RESULTS {
+        assert_eq!(run_code(&format!("(% {} {})", a, b)).unwrap(), int(r), "(% {} {})", a, b);
+    }
+}
+
+#[test]
+fn test_quotient_and_remainder_reconstruct_dividend() {
+    for (a, b, _, _, _) in SCHEME_RESULTS {
+        let source = format!("(+ (* (quotient {a} {b}) {b}) (remainder {a} {b}))");
+        assert_eq!(run_code(&source).unwrap(), int(a));
+    }
+}
+
+#[test]
+fn test_division_by_zero_is_an_error() {
+    assert_eq!(run_code("(quotient 7 0)").unwrap_err(), "Division by zero in 'quotient'");
+    assert_eq!(run_code("(modulo -7 0)").unwrap_err(), "Division by zero in 'modulo'");
+    assert_eq!(run_code("(remainder 7 0)").unwrap_err(), "Modulo by zero");
+}
+
+#[test]
+fn test_extreme_operands() {
+    assert_eq!(run_code(&format!("(modulo {} -1)", i64::MIN)).unwrap(), int(0));
+    assert_eq!(run_code(&format!("(modulo {} 10)", i64::MIN)).unwrap(), int(2));
+    assert_eq!(run_code(&format!("(remainder {} -1)", i64::MIN)).unwrap(), int(0));
+    assert!(run_code(&format!("(quotient {} -1)", i64::MIN)).unwrap().is_bigint());
+}
+
+#[test]
+fn test_integer_division_rejects_floats() {
+    assert_eq!(
+        run_code("(modulo 7.5 2)").unwrap_err(),
+        "Type error: 'modulo' expects two integers, got float and integer"
+    );
+    assert_eq!(
+        run_code("(quotient 7 2.0)").unwrap_err(),
+        "Type error: 'quotient' expects two integers, got integer and float"
+    );
+}
+
+#[test]
+fn test_integer_division_as_values() {
+    assert_eq!(run_code("(apply modulo (list -7 3))").unwrap(), int(2));
+    assert_eq!(run_code("(apply remainder (list -7 3))").unwrap(), int(-1));
+    assert_eq!(run_code("(apply quotient (list -7 3))").unwrap(), int(-2));
+}
+
+#[test]
+fn test_integer_division_arity() {
+    assert!(run_code("(modulo 7)").unwrap_err().contains("modulo expects exactly 2 arguments"));
+    assert!(run_code("(quotient 7 2 1)").unwrap_err().contains("quotient expects exactly 2 arguments"));
+}
@@ -65,6 +65,22 @@ fn test_simple_arithmetic() {
     }
 }
 
+#[test]
+fn test_inc_dec() {
+    let tests = vec![
+        ("(inc 41)", 42),
+        ("(dec 43)", 42),
+        ("(inc (dec 0))", 0),
+        ("(loop ((i 0) (acc 0)) (if (>= i 5) acc (recur (inc i) (+ acc i))))", 10),
+        ("(apply inc '(9))", 10),
+    ];
+
+    for (source, expected) in tests {
+        let result = compile_and_get_result(source);
+        assert_eq!(result, expected, "Failed for: {}", source);
+    }
+}
+
 #[test]
 fn test_nested_arithmetic() {
     let source = "(+ (* 2 3) (- 10 5))"; // (2*3) + (10-5) = 6 + 5 = 11
@@ -940,3 +956,20 @@ fn test_reduce_max() {
     let result = compile_and_get_result(source);
     assert_eq!(result, 9);
 }
+
+#[test]
+fn test_and_or_short_circuit() {
+    // Each form contributes one bit; the operands after the deciding one
+    // would fail (car of an empty list) if they were evaluated
+    let source = r#"
+        (defun bit (b n) (if b n 0))
+        (+ (bit (or false false true) 1)
+           (bit (or false (> 2 1) (car '())) 2)
+           (bit (or false false false) 4)
+           (bit (and true true false) 8)
+           (bit (and true (< 2 1) (car '())) 16)
+           (bit (and true true true) 32))
+    "#;
+
+    assert_eq!(compile_and_get_result(source), 1 + 2 + 32);
+}
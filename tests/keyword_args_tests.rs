@@ -0,0 +1,164 @@
+// Tests for &key parameters in defun and keyword calls (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+const MAKE_WINDOW: &str = "(defun make-window (&key (width 100) (height 50)) (list width height))";
+
+fn window(call: &str) -> Result<Value, String> {
+    run_code(&format!("{} {}", MAKE_WINDOW, call))
+}
+
+// The compile error for `source`, if it fails to compile
+fn compile_error(source: &str) -> Option<String> {
+    let exprs = parser::Parser::new(source).parse_all().unwrap();
+    Compiler::new().compile_program(&exprs).err().map(|e| e.message)
+}
+
+#[test]
+fn test_all_keys_default() {
+    assert_eq!(window("(make-window)").unwrap(), run_code("(list 100 50)").unwrap());
+}
+
+#[test]
+fn test_omitted_key_takes_default() {
+    assert_eq!(window("(make-window :height 200)").unwrap(), run_code("(list 100 200)").unwrap());
+}
+
+#[test]
+fn test_keys_out_of_order() {
+    assert_eq!(window("(make-window :height 1 :width 2)").unwrap(), run_code("(list 2 1)").unwrap());
+}
+
+#[test]
+fn test_required_params_before_keys() {
+    let source = "(defun label (text &key (size 12) bold) (list text size (nil? bold)))
+                  (list (label 1) (label 2 :bold true))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        run_code("(list (list 1 12 true) (list 2 12 false))").unwrap()
+    );
+}
+
+#[test]
+fn test_unknown_keyword_is_compile_error() {
+    // Rejected even though u is never called
+    let err = compile_error(&format!("{} (defun u () (make-window :depth 3))", MAKE_WINDOW)).unwrap();
+    assert!(err.contains("Unknown keyword argument :depth in call to 'make-window'"), "unexpected error: {}", err);
+    // Also for a call that comes before the definition
+    let err = compile_error(&format!("(defun u () (make-window :depth 3)) {}", MAKE_WINDOW)).unwrap();
+    assert!(err.contains("Unknown keyword argument :depth"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_keyword_without_value_is_compile_error() {
+    let err = compile_error(&format!("{} (make-window :width)", MAKE_WINDOW)).unwrap();
+    assert!(err.contains("missing a value"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_duplicate_keyword_is_compile_error() {
+    let err = compile_error(&format!("{} (make-window :width 1 :width 2)", MAKE_WINDOW)).unwrap();
+    assert!(err.contains("more than once"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_unknown_keyword_through_apply_is_a_runtime_error() {
+    assert_eq!(compile_error(&format!("{} (apply make-window '(:depth 3))", MAKE_WINDOW)), None);
+    let err = window("(apply make-window '(:depth 3))").unwrap_err();
+    assert!(err.contains("Unknown keyword argument :depth in call to 'make-window'"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_computed_keyword_is_checked_at_runtime() {
+    let source = format!("{} (def k ':depth) (make-window k 3)", MAKE_WINDOW);
+    assert_eq!(compile_error(&source), None);
+    let err = run_code(&source).unwrap_err();
+    assert!(err.contains("Unknown keyword argument :depth"), "unexpected error: {}", err);
+    assert_eq!(window("(def k ':height) (make-window k 3)").unwrap(), run_code("(list 100 3)").unwrap());
+}
+
+#[test]
+fn test_redefinition_without_keys_drops_the_check() {
+    let source = format!("{} (defun make-window (a b) (list a b)) (make-window :depth 3)", MAKE_WINDOW);
+    assert_eq!(run_code(&source).unwrap(), run_code("(list ':depth 3)").unwrap());
+}
+
+#[test]
+fn test_keyword_call_before_definition() {
+    let source = "(defun small () (make-window :width 10))
+                  (defun make-window (&key (width 100) (height 50)) (list width height))
+                  (small)";
+    assert_eq!(run_code(source).unwrap(), run_code("(list 10 50)").unwrap());
+}
+
+#[test]
+fn test_recursive_keyword_call() {
+    let source = "(defun fact (&key (n 0) (acc 1))
+                    (if (== n 0) acc (fact :acc (* acc n) :n (- n 1))))
+                  (fact :n 5)";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(120));
+}
+
+#[test]
+fn test_non_keyword_in_key_position_is_an_error() {
+    let err = window("(make-window 3)").unwrap_err();
+    assert!(err.contains("Expected a keyword argument like :name in call to 'make-window', got 3"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_missing_required_arg_before_keys_is_an_error() {
+    let err = run_code("(defun label (text &key (size 12)) size) (label)").unwrap_err();
+    assert!(err.contains("Not enough arguments"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_key_default_can_use_earlier_params() {
+    let source = "(defun f (a &key (b a) (c (* b 10))) (list a b c)) (list (f 1) (f 1 :b 2) (f 1 :c 0))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        run_code("(list (list 1 1 10) (list 1 2 20) (list 1 1 0))").unwrap()
+    );
+}
+
+#[test]
+fn test_key_default_is_evaluated_in_callee_scope() {
+    // A caller's local named like a global the default uses doesn't leak in
+    let source = "(def size 5)
+                  (defun box (&key (side size)) side)
+                  (let ((size 99)) (box))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(5));
+}
+
+#[test]
+fn test_key_default_is_evaluated_only_when_omitted() {
+    let source = r#"(defun f (&key (x (raise "default evaluated"))) x) (f :x 7)"#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_keyword_call_through_apply_and_map() {
+    assert_eq!(
+        window("(apply make-window '(:height 3))").unwrap(),
+        run_code("(list 100 3)").unwrap()
+    );
+    assert_eq!(
+        window("(apply make-window :width 1 '(:height 2))").unwrap(),
+        run_code("(list 1 2)").unwrap()
+    );
+    let source = "(defun scale (x &key (by 2)) (* x by)) (map scale '(1 2 3))";
+    assert_eq!(run_code(source).unwrap(), run_code("(list 2 4 6)").unwrap());
+}
+
+#[test]
+fn test_keywords_evaluate_to_themselves() {
+    assert_eq!(run_code("(list :a (symbol? :a))").unwrap(), run_code("(list ':a true)").unwrap());
+}
+
+#[test]
+fn test_key_and_optional_cannot_mix() {
+    let err = run_code("(defun f (&optional a &key b) a) (f)").unwrap_err();
+    assert!(err.contains("cannot be combined"), "unexpected error: {}", err);
+}
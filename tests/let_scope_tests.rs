@@ -0,0 +1,127 @@
+// Tests for let scoping: shadowing across nested scopes, and let slots
+// staying correct when a let is compiled among pending operands
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_inner_let_shadows_then_restores_outer() {
+    assert_eq!(run_code("(let ((x 1)) (let ((x 2)) x) x)").unwrap(), Value::Integer(1));
+    assert_eq!(run_code("(let ((x 1)) (list (let ((x 2)) x) x))").unwrap(), ints(&[2, 1]));
+}
+
+#[test]
+fn test_let_body_runs_forms_in_order() {
+    let source = "(let ((x 1)) (let ((y 2)) (+ x y)) (+ x 10))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(11));
+}
+
+#[test]
+fn test_shadowed_in_binding_value() {
+    // A later binding's value sees the earlier binding, and an inner
+    // let inside a binding value does not leak into the outer scope
+    let source = "(let ((x 1) (y (let ((x 5)) (+ x 1))) (z (+ x 100))) (list x y z))";
+    assert_eq!(run_code(source).unwrap(), ints(&[1, 6, 101]));
+}
+
+#[test]
+fn test_let_shadows_parameter() {
+    let source = r#"
+        (defun f (x) (list (let ((x (* x 10))) x) x))
+        (f 3)
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[30, 3]));
+}
+
+#[test]
+fn test_destructuring_let_shadows() {
+    let source = r#"
+        (let ((x 1) (y 2))
+          (list (let (((x . y) (list 7 8))) (list x y))
+                (let (((a x) (list 5 6))) x)
+                x y))
+    "#;
+    let result = run_code(source).unwrap();
+    assert_eq!(
+        result,
+        Value::list_from_vec(vec![
+            Value::list_from_vec(vec![Value::Integer(7), ints(&[8])]),
+            Value::Integer(6),
+            Value::Integer(1),
+            Value::Integer(2),
+        ])
+    );
+}
+
+#[test]
+fn test_closure_captures_the_binding_in_scope() {
+    let source = r#"
+        (let ((x 1))
+          (let ((f (let ((x 2)) (lambda () x))))
+            (list (f) x)))
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[2, 1]));
+}
+
+#[test]
+fn test_let_among_pending_operands() {
+    // The 1 (or a) is already on the stack when the inner let binds
+    assert_eq!(run_code("(+ 1 (let ((x 2)) x))").unwrap(), Value::Integer(3));
+    assert_eq!(run_code("(let ((a 10)) (+ a (let ((x 2)) (+ x a))))").unwrap(), Value::Integer(22));
+    assert_eq!(run_code("(- 10 1 (let ((x 2)) x))").unwrap(), Value::Integer(7));
+    assert_eq!(run_code(r#"(string-append "a" "b" (let ((s "c")) s))"#).unwrap(), Value::string("abc"));
+}
+
+#[test]
+fn test_let_inside_branches_and_calls() {
+    let source = r#"
+        (defun pick (flag a)
+          (list a
+                (if flag (let ((x 1)) (+ x a)) (let ((y 2)) (+ y a)))
+                (cond ((> a 100) 0) (true (let ((z 3)) (+ z a))))
+                (and flag (let ((w true)) w))))
+        (list (pick true 10) (pick false 20))
+    "#;
+    let result = run_code(source).unwrap();
+    assert_eq!(
+        result,
+        Value::list_from_vec(vec![
+            Value::list_from_vec(vec![Value::Integer(10), Value::Integer(11), Value::Integer(13), Value::Boolean(true)]),
+            Value::list_from_vec(vec![Value::Integer(20), Value::Integer(22), Value::Integer(23), Value::Boolean(false)]),
+        ])
+    );
+}
+
+#[test]
+fn test_let_in_closure_call_arguments() {
+    let source = r#"
+        (defun g () (let ((f (lambda (a b) (- a b)))) (f (let ((y 5)) y) (let ((z 1)) z))))
+        (g)
+    "#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(4));
+}
+
+#[test]
+fn test_let_in_pattern_clause_body() {
+    let source = r#"
+        (defun len
+          (('()) 0)
+          (((h . t)) (+ 1 (let ((r (len t))) r))))
+        (len '(1 2 3))
+    "#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(3));
+}
+
+#[test]
+fn test_let_inside_quasiquote() {
+    let source = "(let ((v 7)) `(1 ,(let ((q 1)) (+ q v)) ,@(list v (let ((w 9)) w))))";
+    assert_eq!(run_code(source).unwrap(), ints(&[1, 8, 7, 9]));
+}
+
+#[test]
+fn test_let_requires_a_body() {
+    let err = run_code("(let ((x 1)))").unwrap_err();
+    assert!(err.contains("let expects bindings and at least one body expression"), "{}", err);
+}
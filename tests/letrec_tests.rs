@@ -0,0 +1,78 @@
+// Tests for letrec: local closures that refer to each other (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+const EVEN_ODD: &str = r#"
+    (letrec ((even? (lambda (n) (if (== n 0) true (odd? (- n 1)))))
+             (odd? (lambda (n) (if (== n 0) false (even? (- n 1))))))
+      (list (even? 0) (even? 7) (odd? 7) (even? 10)))
+"#;
+
+#[test]
+fn test_letrec_mutual_recursion() {
+    assert_eq!(run_code(EVEN_ODD).unwrap(), run_code("(list true false true true)").unwrap());
+}
+
+#[test]
+fn test_letrec_inside_function_captures_outer_values() {
+    let source = r#"
+        (defun scaled-factorials (scale xs)
+          (letrec ((fact (lambda (n) (if (== n 0) scale (* n (fact (- n 1)))))))
+            (map fact xs)))
+        (scaled-factorials 2 '(0 3 5))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("'(2 12 240)").unwrap());
+}
+
+#[test]
+fn test_letrec_shadows_global_function() {
+    let source = r#"
+        (defun even? (n) 'global)
+        (letrec ((even? (lambda (n) (if (== n 0) true (odd? (- n 1)))))
+                 (odd? (lambda (n) (if (== n 0) false (even? (- n 1))))))
+          (even? 4))
+    "#;
+    assert_eq!(run_code(source).unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn test_letrec_siblings_through_nested_lambdas_and_escape() {
+    let source = r#"
+        (defun make-walker ()
+          (letrec ((ping (lambda (n) (if (== n 0) 'ping (pong (- n 1)))))
+                   (pong (lambda (n) (if (== n 0) 'pong ((lambda (k) (ping k)) (- n 1))))))
+            ping))
+        (defun walk (n) (let ((w (make-walker))) (w n)))
+        (list (walk 4) (walk 5))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("'(ping pong)").unwrap());
+}
+
+#[test]
+fn test_letrec_tail_calls_do_not_grow_the_stack() {
+    let source = r#"
+        (defun parity (n)
+          (letrec ((ev? (lambda (k) (if (== k 0) 'even (od? (- k 1)))))
+                   (od? (lambda (k) (if (== k 0) 'odd (ev? (- k 1))))))
+            (ev? n)))
+        (parity 100001)
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("'odd").unwrap());
+}
+
+#[test]
+fn test_letrec_multiple_body_forms_and_nested_expression() {
+    let source = "(+ 1 (letrec ((f (lambda (n) (if (< n 1) 0 (+ n (f (- n 1))))))) (f 2) (f 4)))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(11));
+}
+
+#[test]
+fn test_letrec_bindings_must_be_lambdas() {
+    let err = run_code("(letrec ((x 1)) x)").unwrap_err();
+    assert!(err.contains("letrec binding 'x' must be a lambda"), "{}", err);
+    let err = run_code("(letrec ((f (lambda (n) n))))").unwrap_err();
+    assert!(err.contains("letrec expects bindings"), "{}", err);
+}
@@ -0,0 +1,111 @@
+// Tests for the native map, filter, reduce and foldr builtins (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_map_lambda() {
+    assert_eq!(run_code("(map (lambda (x) (* x 10)) '(1 2 3))").unwrap(), ints(&[10, 20, 30]));
+}
+
+#[test]
+fn test_map_named_function_and_builtin() {
+    let source = r#"
+        (defun square (x) (* x x))
+        (append (map square '(1 2 3)) (map car '((4 5) (6))))
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[1, 4, 9, 4, 6]));
+}
+
+#[test]
+fn test_map_closure_captures() {
+    let source = r#"
+        (defun add-all (n lst) (map (lambda (x) (+ x n)) lst))
+        (add-all 100 '(1 2 3))
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[101, 102, 103]));
+}
+
+#[test]
+fn test_map_several_lists_stops_at_shortest() {
+    let source = "(map (lambda (a b c) (+ a (+ b c))) '(1 2 3) '(10 20) '(100 200 300))";
+    assert_eq!(run_code(source).unwrap(), ints(&[111, 222]));
+    assert_eq!(run_code("(map + '() '(1 2))").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_filter() {
+    let source = "(filter (lambda (x) (> x 2)) '(1 5 2 3 0 4))";
+    assert_eq!(run_code(source).unwrap(), ints(&[5, 3, 4]));
+    assert_eq!(run_code("(filter null? '())").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_reduce_with_builtin() {
+    assert_eq!(run_code("(reduce + 0 '(1 2 3))").unwrap(), Value::Integer(6));
+    assert_eq!(run_code("(reduce + 42 '())").unwrap(), Value::Integer(42));
+}
+
+#[test]
+fn test_reduce_folds_from_the_left() {
+    let source = "(reduce (lambda (acc x) (cons x acc)) '() '(1 2 3))";
+    assert_eq!(run_code(source).unwrap(), ints(&[3, 2, 1]));
+}
+
+#[test]
+fn test_foldr_with_cons_rebuilds_list() {
+    assert_eq!(run_code("(foldr cons '() '(1 2 3))").unwrap(), ints(&[1, 2, 3]));
+    assert_eq!(run_code("(foldr cons '() '())").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_foldr_and_reduce_differ_for_subtraction() {
+    // (1 - (2 - (3 - 0))) versus (((0 - 1) - 2) - 3)
+    assert_eq!(run_code("(foldr - 0 '(1 2 3))").unwrap(), Value::Integer(2));
+    assert_eq!(run_code("(reduce - 0 '(1 2 3))").unwrap(), Value::Integer(-6));
+}
+
+#[test]
+fn test_foldr_passes_item_then_accumulator() {
+    let source = "(foldr (lambda (x acc) (cons (* x 10) acc)) '(0) '(1 2))";
+    assert_eq!(run_code(source).unwrap(), ints(&[10, 20, 0]));
+}
+
+#[test]
+fn test_callbacks_nest() {
+    let source = r#"
+        (defun row-sums (rows) (map (lambda (row) (reduce + 0 row)) rows))
+        (filter (lambda (n) (> n 3)) (row-sums '((1 2) (3 4) (5))))
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[7, 5]));
+}
+
+#[test]
+fn test_callbacks_through_apply() {
+    assert_eq!(run_code("(apply map (list + '(1 2) '(3 4)))").unwrap(), ints(&[4, 6]));
+    assert_eq!(run_code("(apply reduce (list * 1 '(2 3 4)))").unwrap(), Value::Integer(24));
+}
+
+#[test]
+fn test_callback_errors() {
+    let err = run_code("(map 1 '(1 2))").unwrap_err();
+    assert!(err.contains("'map' expects a function and a list, got integer and list"), "{}", err);
+
+    let err = run_code("(filter (lambda (x) x) '(1))").unwrap_err();
+    assert!(err.contains("'filter' predicate must return a boolean, got integer"), "{}", err);
+
+    let err = run_code("(reduce + 0 5)").unwrap_err();
+    assert!(err.contains("'reduce' expects a function, an initial value and a list"), "{}", err);
+
+    let err = run_code("(foldr 1 0 '(1))").unwrap_err();
+    assert!(err.contains("'foldr' expects a function, an initial value and a list"), "{}", err);
+
+    let err = run_code("(map + '(1) 2)").unwrap_err();
+    assert!(err.contains("'map' expects lists after the function, got integer"), "{}", err);
+
+    // Errors inside the callback propagate unchanged
+    let err = run_code("(map (lambda (x) (car x)) '(1))").unwrap_err();
+    assert!(err.contains("car"), "{}", err);
+}
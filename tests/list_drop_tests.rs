@@ -139,7 +139,7 @@ fn test_arc_refcount_behavior() {
     // Get Arc from tail
     let arc1 = match &tail {
         List::Cons(arc) => Arc::strong_count(arc),
-        List::Nil => panic!("Expected Cons"),
+        _ => panic!("Expected Cons"),
     };
     assert_eq!(arc1, 1);
 
@@ -147,7 +147,7 @@ fn test_arc_refcount_behavior() {
     let tail2 = tail.clone();
     let arc2 = match &tail {
         List::Cons(arc) => Arc::strong_count(arc),
-        List::Nil => panic!("Expected Cons"),
+        _ => panic!("Expected Cons"),
     };
     assert_eq!(arc2, 2);
 
@@ -155,7 +155,31 @@ fn test_arc_refcount_behavior() {
     drop(tail2);
     let arc3 = match &tail {
         List::Cons(arc) => Arc::strong_count(arc),
-        List::Nil => panic!("Expected Cons"),
+        _ => panic!("Expected Cons"),
     };
     assert_eq!(arc3, 1);
 }
+
+/// Test that every cons cell carries the length of the list it starts
+#[test]
+fn test_cached_length_with_shared_tails() {
+    let tail = List::from_vec(vec![Value::Integer(2), Value::Integer(3)]);
+    let a = List::cons(Value::Integer(1), tail.clone());
+    let b = List::cons(Value::Integer(0), a.clone());
+
+    assert_eq!(List::nil().len(), 0);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(a.len(), 3);
+    assert_eq!(b.len(), 4);
+    assert_eq!(b.cdr().unwrap().len(), 3);
+    assert_eq!(b.iter().count(), b.len());
+}
+
+/// Test that length of a very large list is available without walking it
+#[test]
+fn test_cached_length_large_list() {
+    let items: Vec<Value> = (0..500_000).map(|n| Value::Integer(n as i64)).collect();
+    let list = List::from_vec(items);
+    assert_eq!(list.len(), 500_000);
+    assert_eq!(list.cdr().unwrap().len(), 499_999);
+}
@@ -340,3 +340,78 @@ fn test_require_idempotent() {
     // Also verify that the module was only loaded once
     assert_eq!(vm.loaded_modules.len(), 1);
 }
+
+#[test]
+fn test_require_same_file_from_two_places_runs_once() {
+    // counter.lisp appends to a log each time its top-level code runs
+    let log = "/tmp/test-require-once-log.txt";
+    fs::write(log, "").unwrap();
+    fs::write("/tmp/test-require-once-counter.lisp", r#"
+        (provide counter)
+        (module counter
+            (export base)
+            (def base 40))
+        (write-file "/tmp/test-require-once-log.txt"
+                    (string-append (read-file "/tmp/test-require-once-log.txt") "ran;"))
+    "#).unwrap();
+    // user.lisp requires counter.lisp too, and reads its def
+    fs::write("/tmp/test-require-once-user.lisp", r#"
+        (require "/tmp/test-require-once-counter.lisp")
+        (defun user-value () (+ counter/base 2))
+    "#).unwrap();
+
+    let source = r#"
+        (require "/tmp/test-require-once-counter.lisp")
+        (require "/tmp/test-require-once-user.lisp")
+        (user-value)
+    "#;
+
+    let vm = compile_and_run(source);
+    assert_eq!(get_int_result(&vm), 42);
+    assert_eq!(fs::read_to_string(log).unwrap(), "ran;");
+    assert_eq!(vm.loaded_modules.len(), 2);
+    assert!(vm.provided_modules["counter"].contains("test-require-once-counter.lisp"));
+}
+
+#[test]
+fn test_provide_requires_module_in_same_file() {
+    let mut parser = Parser::new("(provide geometry) (defun area (w h) (* w h))");
+    let exprs = parser.parse_all().unwrap();
+    let err = Compiler::new().compile_program(&exprs).unwrap_err();
+    assert!(err.message.contains("File provides module 'geometry' but does not define it"));
+}
+
+#[test]
+fn test_module_provided_by_two_files_is_rejected() {
+    fs::write("/tmp/test-provide-twice-a.lisp", "(provide shapes) (module shapes (export sides) (def sides 3))").unwrap();
+    fs::write("/tmp/test-provide-twice-b.lisp", "(provide shapes) (module shapes (export sides) (def sides 4))").unwrap();
+
+    let mut parser = Parser::new(r#"
+        (require "/tmp/test-provide-twice-a.lisp")
+        (require "/tmp/test-provide-twice-b.lisp")
+    "#);
+    let exprs = parser.parse_all().unwrap();
+    let (functions, main) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    let err = vm.run().unwrap_err();
+    assert!(err.message.contains("Module 'shapes' is provided by both"), "{}", err.message);
+}
+
+#[test]
+fn test_failed_require_is_not_marked_loaded() {
+    fs::write("/tmp/test-require-fails.lisp", "(defun never-used () 1) (car 5)").unwrap();
+
+    let mut parser = Parser::new(r#"(require "/tmp/test-require-fails.lisp")"#);
+    let exprs = parser.parse_all().unwrap();
+    let (functions, main) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    assert!(vm.run().is_err());
+    assert!(vm.loaded_modules.is_empty());
+    assert!(vm.loading_modules.is_empty());
+}
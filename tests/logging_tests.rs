@@ -0,0 +1,197 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use lisp_bytecode_vm::{Compiler, LogLevel, VM, Value, parser::Parser};
+
+// Log writer that appends into a buffer shared with the test
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedBuffer {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.borrow().clone())
+            .unwrap()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+}
+
+fn run_with_log(source: &str, min_level: LogLevel) -> Result<(VM, SharedBuffer), String> {
+    let mut parser = Parser::new(source);
+    let exprs = parser.parse_all().map_err(|e| format!("Parse error: {:?}", e))?;
+
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).map_err(|e| format!("Compile error: {:?}", e))?;
+
+    let buffer = SharedBuffer::default();
+    let mut vm = VM::new();
+    vm.min_log_level = min_level;
+    vm.log_writer = Box::new(buffer.clone());
+    for (name, bytecode) in functions {
+        vm.functions.insert(name, bytecode);
+    }
+    vm.current_bytecode = main;
+    vm.run().map_err(|e| format!("Runtime error: {:?}", e))?;
+    Ok((vm, buffer))
+}
+
+// Strip the "[timestamp] " prefix, checking its shape along the way
+fn strip_timestamp(line: &str) -> &str {
+    assert!(line.starts_with('['), "missing timestamp in {:?}", line);
+    let end = line.find("] ").expect("unterminated timestamp");
+    let timestamp = &line[1..end];
+    assert_eq!(timestamp.len(), "2000-01-01T00:00:00Z".len(), "bad timestamp {:?}", timestamp);
+    assert!(timestamp.ends_with('Z'));
+    &line[end + 2..]
+}
+
+#[test]
+fn test_log_levels_format() {
+    let source = r#"
+        (log-info "server started")
+        (log-warn "disk almost full")
+        (log-error "request failed")
+    "#;
+    let (_, buffer) = run_with_log(source, LogLevel::Info).unwrap();
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(strip_timestamp(&lines[0]), "[INFO] server started");
+    assert_eq!(strip_timestamp(&lines[1]), "[WARN] disk almost full");
+    assert_eq!(strip_timestamp(&lines[2]), "[ERROR] request failed");
+}
+
+#[test]
+fn test_log_below_threshold_is_suppressed() {
+    let source = r#"
+        (log-debug "noisy detail")
+        (log-info "routine")
+        (log-warn "careful")
+    "#;
+    let (_, buffer) = run_with_log(source, LogLevel::Warn).unwrap();
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(strip_timestamp(&lines[0]), "[WARN] careful");
+}
+
+#[test]
+fn test_log_debug_hidden_by_default() {
+    let vm = VM::new();
+    assert_eq!(vm.min_log_level, LogLevel::Info);
+
+    let (_, buffer) = run_with_log("(log-debug \"hidden\")", LogLevel::Info).unwrap();
+    assert!(buffer.lines().is_empty());
+}
+
+#[test]
+fn test_log_returns_message_and_formats_values() {
+    let source = r#"
+        (log-info (list 1 "two" 'three))
+    "#;
+    let (vm, buffer) = run_with_log(source, LogLevel::Info).unwrap();
+    let lines = buffer.lines();
+    assert_eq!(strip_timestamp(&lines[0]), "[INFO] (1 two three)");
+    assert!(matches!(vm.value_stack.last(), Some(Value::List(_))));
+}
+
+#[test]
+fn test_set_log_level_at_runtime() {
+    let source = r#"
+        (log-debug "before")
+        (set-log-level 'debug)
+        (log-debug "after")
+        (set-log-level "off")
+        (log-error "silenced")
+    "#;
+    let (vm, buffer) = run_with_log(source, LogLevel::Info).unwrap();
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(strip_timestamp(&lines[0]), "[DEBUG] after");
+    assert_eq!(vm.min_log_level, LogLevel::Off);
+}
+
+#[test]
+fn test_set_log_level_returns_previous() {
+    let (vm, _) = run_with_log("(set-log-level 'error)", LogLevel::Warn).unwrap();
+    assert_eq!(vm.value_stack.last(), Some(&Value::Symbol(Arc::new("warn".to_string()))));
+}
+
+#[test]
+fn test_set_log_level_rejects_unknown_level() {
+    let result = run_with_log("(set-log-level 'verbose)", LogLevel::Info);
+    let err = result.err().unwrap();
+    assert!(err.contains("Unknown log level 'verbose'"));
+
+    let result = run_with_log("(set-log-level 3)", LogLevel::Info);
+    assert!(result.err().unwrap().contains("expects a symbol or string"));
+}
+
+// ============================================================
+// Bytecode Tracing
+// ============================================================
+
+fn run_traced(source: &str) -> Vec<String> {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let (functions, main) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let buffer = SharedBuffer::default();
+    let mut vm = VM::new();
+    vm.trace_bytecode = true;
+    vm.log_writer = Box::new(buffer.clone());
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    vm.run().unwrap();
+    buffer.lines()
+}
+
+#[test]
+fn test_trace_logs_every_instruction() {
+    let lines = run_traced("(defun square (x) (* x x)) (square 7)");
+    assert_eq!(lines, vec![
+        "TRACE depth=0 fn=<main> ip=0 Push(Integer(7)) stack=[]",
+        r#"TRACE depth=0 fn=<main> ip=1 Call("square", 1) stack=[7]"#,
+        "TRACE depth=1 fn=square ip=0 LoadArg(0) stack=[]",
+        "TRACE depth=1 fn=square ip=1 LoadArg(0) stack=[7]",
+        "TRACE depth=1 fn=square ip=2 Mul stack=[7 7]",
+        "TRACE depth=1 fn=square ip=3 Ret stack=[49]",
+        "TRACE depth=0 fn=<main> ip=2 Halt stack=[49]",
+    ]);
+}
+
+#[test]
+fn test_trace_shows_top_of_stack_and_tail_calls() {
+    let source = r#"
+        (defun count (n acc) (if (== n 0) acc (count (- n 1) (+ acc 1))))
+        (+ 100 (+ 200 (+ 300 (count 2 0))))
+    "#;
+    let lines = run_traced(source);
+
+    // Only the top three values are shown, top last
+    let call = r#"TRACE depth=0 fn=<main> ip=5 Call("count", 2) stack=[.. 300 2 0]"#;
+    assert!(lines.iter().any(|l| l == call), "{:#?}", lines);
+
+    // Tail calls reuse the frame, so the depth never passes 1
+    let tail_calls = lines.iter().filter(|l| l.contains(r#" TailCall("count", 2) "#)).count();
+    assert_eq!(tail_calls, 2);
+    assert!(lines.iter().all(|l| l.starts_with("TRACE depth=0 fn=<main> ") || l.starts_with("TRACE depth=1 fn=count ")));
+}
+
+#[test]
+fn test_trace_off_by_default() {
+    let (vm, buffer) = run_with_log("(defun square (x) (* x x)) (square 7)", LogLevel::Debug).unwrap();
+    assert!(!vm.trace_bytecode);
+    assert!(buffer.lines().is_empty());
+}
@@ -0,0 +1,67 @@
+// Tests for macroexpand-all (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn mentions_symbol(value: &Value, names: &[&str]) -> bool {
+    match value {
+        Value::Symbol(s) => names.contains(&s.as_str()),
+        Value::List(items) => items.iter().any(|item| mentions_symbol(item, names)),
+        _ => false,
+    }
+}
+
+const MACROS: &str = r#"
+    (defmacro my-unless (c body) `(if ,c false ,body))
+    (defmacro guard (x default) `(my-unless ,x ,default))
+    (defmacro twice (e) `(list ,e ,e))
+"#;
+
+#[test]
+fn test_macroexpand_all_follows_macro_into_macro() {
+    let source = format!("{} (macroexpand-all '(guard ok 0))", MACROS);
+    let result = run_code(&source).unwrap();
+    assert_eq!(result, run_code("'(if ok false 0)").unwrap());
+    assert!(!mentions_symbol(&result, &["guard", "my-unless"]));
+}
+
+#[test]
+fn test_macroexpand_only_expands_once() {
+    let source = format!("{} (macroexpand '(guard ok 0))", MACROS);
+    assert_eq!(run_code(&source).unwrap(), run_code("'(my-unless ok 0)").unwrap());
+}
+
+#[test]
+fn test_macroexpand_all_expands_nested_subforms() {
+    let source = format!("{} (macroexpand-all '(+ 1 (twice (guard a 2))))", MACROS);
+    let result = run_code(&source).unwrap();
+    let expected = run_code("'(+ 1 (list (if a false 2) (if a false 2)))").unwrap();
+    assert_eq!(result, expected);
+    assert!(!mentions_symbol(&result, &["twice", "guard", "my-unless"]));
+}
+
+#[test]
+fn test_macroexpand_all_leaves_quoted_data() {
+    let source = format!("{} (macroexpand-all '(list (quote (guard a 1)) (guard b 2)))", MACROS);
+    let expected = run_code("'(list (quote (guard a 1)) (if b false 2))").unwrap();
+    assert_eq!(run_code(&source).unwrap(), expected);
+}
+
+#[test]
+fn test_macroexpand_all_without_macros() {
+    assert_eq!(run_code("(macroexpand-all '(+ 1 (* 2 3)))").unwrap(), run_code("'(+ 1 (* 2 3))").unwrap());
+    assert_eq!(run_code("(macroexpand-all 'x)").unwrap(), Value::symbol("x"));
+}
+
+#[test]
+fn test_macroexpand_all_infinite_expansion_is_error() {
+    let err = run_code("(defmacro forever (x) `(forever ,x)) (macroexpand-all '(forever 1))").unwrap_err();
+    assert!(err.contains("macroexpand-all gave up"), "{}", err);
+}
+
+#[test]
+fn test_macroexpand_all_arity() {
+    assert!(run_code("(macroexpand-all)").unwrap_err().contains("macroexpand-all expects exactly 1 argument"));
+}
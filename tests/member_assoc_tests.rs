@@ -0,0 +1,59 @@
+// Tests for the member? and assoc list lookups (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_member_returns_tail_from_match() {
+    assert_eq!(run_code("(member? 3 '(1 2 3 4))").unwrap(), ints(&[3, 4]));
+    assert_eq!(run_code("(member? 1 '(1 2))").unwrap(), ints(&[1, 2]));
+}
+
+#[test]
+fn test_member_not_found_is_false() {
+    assert_eq!(run_code("(member? 9 '(1 2 3))").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(member? 1 '())").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_member_uses_numeric_equality() {
+    assert_eq!(run_code("(member? 2.0 '(1 2 3))").unwrap(), ints(&[2, 3]));
+    assert_eq!(run_code("(member? '(1) '(0 (1) 2))").unwrap(), run_code("'((1) 2)").unwrap());
+}
+
+#[test]
+fn test_assoc_finds_pair() {
+    assert_eq!(
+        run_code("(assoc 'b '((a . 1) (b . 2)))").unwrap(),
+        run_code("(cons 'b 2)").unwrap()
+    );
+    assert_eq!(
+        run_code("(assoc \"y\" (list (list \"x\" 1) (list \"y\" 2 3)))").unwrap(),
+        run_code("(list \"y\" 2 3)").unwrap()
+    );
+}
+
+#[test]
+fn test_assoc_returns_first_match_or_false() {
+    assert_eq!(run_code("(assoc 1 '((1 first) (1 second)))").unwrap(), run_code("'(1 first)").unwrap());
+    assert_eq!(run_code("(assoc 'z '((a . 1)))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_lookups_as_first_class_values() {
+    let source = "(list (apply member? (list 2 '(1 2))) (map (lambda (k) (assoc k '((a 1) (b 2)))) '(b c)))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::list_from_vec(vec![ints(&[2]), run_code("(list '(b 2) false)").unwrap()])
+    );
+}
+
+#[test]
+fn test_lookup_type_errors() {
+    let err = run_code("(member? 1 5)").unwrap_err();
+    assert!(err.contains("'member?' expects a list"), "{}", err);
+    let err = run_code("(assoc 'a '(1 2))").unwrap_err();
+    assert!(err.contains("'assoc' expects a list of pairs"), "{}", err);
+}
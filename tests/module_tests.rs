@@ -1,4 +1,4 @@
-use lisp_bytecode_vm::{Compiler, VM, parser::Parser, Value};
+use lisp_bytecode_vm::{Compiler, List, VM, parser::Parser, Value};
 use std::sync::Arc;
 
 fn compile_and_run(source: &str) -> Result<VM, String> {
@@ -546,3 +546,293 @@ fn test_complex_module_interaction() {
         _ => panic!("Expected a list"),
     }
 }
+
+// ==================== QUALIFIED SYMBOLS ====================
+
+fn sym(s: &str) -> Value {
+    Value::Symbol(Arc::new(s.to_string()))
+}
+
+fn string(s: &str) -> Value {
+    Value::String(Arc::new(s.to_string()))
+}
+
+#[test]
+fn test_qualified_symbol_parts() {
+    let vm = compile_and_run("(list (symbol-namespace 'math/add) (symbol-name 'math/add))").unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(vec![string("math"), string("add")]))));
+}
+
+#[test]
+fn test_unqualified_symbol_parts() {
+    let vm = compile_and_run("(list (symbol-namespace 'add) (symbol-name 'add) (symbol-namespace '/))").unwrap();
+    let expected = vec![Value::List(List::Nil), string("add"), Value::List(List::Nil)];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_nested_namespace_splits_at_last_slash() {
+    let vm = compile_and_run("(list (symbol-namespace 'net/http/get) (symbol-name 'net/http/get))").unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(vec![string("net/http"), string("get")]))));
+}
+
+#[test]
+fn test_qualified_symbol_predicate() {
+    let vm = compile_and_run("(list (qualified-symbol? 'math/add) (qualified-symbol? 'add) (qualified-symbol? \"math/add\"))").unwrap();
+    let expected = vec![Value::Boolean(true), Value::Boolean(false), Value::Boolean(false)];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_qualified_symbols_compare_by_full_name() {
+    let vm = compile_and_run("(list (== 'math/add 'math/add) (== 'math/add 'add) (== 'math/add 'str/add))").unwrap();
+    let expected = vec![Value::Boolean(true), Value::Boolean(false), Value::Boolean(false)];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_make_qualified_symbol() {
+    let vm = compile_and_run("(make-qualified-symbol 'math \"add\")").unwrap();
+    assert_eq!(get_stack_top(&vm), Some(sym("math/add")));
+
+    let vm = compile_and_run("(== (make-qualified-symbol \"math\" 'add) 'math/add)").unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Boolean(true)));
+
+    let err = compile_and_run("(make-qualified-symbol \"\" 'add)").err().unwrap();
+    assert!(err.contains("make-qualified-symbol"));
+}
+
+#[test]
+fn test_qualified_symbol_names_module_function() {
+    // Build a reference to a module member and resolve it through eval
+    let source = r#"
+        (module math
+            (export square)
+            (defun square (x) (* x x)))
+        (let ((f (make-qualified-symbol 'math 'square)))
+          (eval (string-append "(" (string-append (symbol->string f) " 7)"))))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(49)));
+}
+
+// ==================== RUNTIME MODULE REFLECTION ====================
+
+#[test]
+fn test_module_exports_at_runtime() {
+    let source = r#"
+        (module math
+            (export square add)
+            (defun helper (x) x)
+            (defun add (x y) (+ x y))
+            (defun square (x) (* x x)))
+        (module-exports 'math)
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(vec![sym("add"), sym("square")]))));
+}
+
+#[test]
+fn test_module_list_at_runtime() {
+    let source = r#"
+        (module strings (export shout) (defun shout (s) s))
+        (module math (export add) (defun add (x y) (+ x y)))
+        (list (module-list) (module-exports "strings"))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    let expected = vec![
+        Value::List(List::from_vec(vec![sym("math"), sym("strings")])),
+        Value::List(List::from_vec(vec![sym("shout")])),
+    ];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_module_exports_registered_by_bytecode() {
+    // The export metadata travels in the compiled program, not just the Compiler
+    let source = r#"
+        (module math (export add) (defun add (x y) (+ x y)))
+        (module-exports 'math)
+    "#;
+    let mut parser = Parser::new(source);
+    let exprs = parser.parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    vm.run().unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(vec![sym("add")]))));
+}
+
+#[test]
+fn test_module_list_empty_and_unknown_module() {
+    let vm = compile_and_run("(module-list)").unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::Nil)));
+
+    let err = compile_and_run("(module-exports 'nope)").err().unwrap();
+    assert!(err.contains("Unknown module 'nope'"));
+}
+
+// ==================== QUALIFIED FUNCTIONS AS VALUES ====================
+
+#[test]
+fn test_qualified_function_passed_to_map() {
+    let source = r#"
+        (module math
+            (export square)
+            (defun square (x) (* x x)))
+        (defun my-map (f lst)
+          (if (null? lst)
+              '()
+              (cons (f (car lst)) (my-map f (cdr lst)))))
+        (my-map math/square '(1 2 3))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    let expected = vec![Value::Integer(1), Value::Integer(4), Value::Integer(9)];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_qualified_function_passed_to_stdlib_map() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (module math
+            (export add square)
+            (defun add (x y) (+ x y))
+            (defun square (x) (* x x)))
+        (list (map math/square '(1 2 3)) (map math/add '(1 2) '(10 20)))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    let expected = vec![
+        Value::List(List::from_vec(vec![Value::Integer(1), Value::Integer(4), Value::Integer(9)])),
+        Value::List(List::from_vec(vec![Value::Integer(11), Value::Integer(22)])),
+    ];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_qualified_function_value_with_apply() {
+    let source = r#"
+        (module math
+            (export add)
+            (defun add (x y) (+ x y)))
+        (apply math/add (list 3 4))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(7)));
+}
+
+#[test]
+fn test_imported_function_passed_as_value() {
+    let source = r#"
+        (module math
+            (export square)
+            (defun square (x) (* x x)))
+        (import math square)
+        (defun twice (f x) (f (f x)))
+        (twice square 3)
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(81)));
+}
+
+#[test]
+fn test_module_passes_own_function_and_builtin_as_values() {
+    let source = r#"
+        (module lists
+            (export heads squares)
+            (defun my-map (f lst)
+              (if (null? lst)
+                  '()
+                  (cons (f (car lst)) (my-map f (cdr lst)))))
+            (defun square (x) (* x x))
+            (defun heads (lsts) (my-map car lsts))
+            (defun squares (lst) (my-map square lst)))
+        (list (lists/heads '((1 2) (3 4))) (lists/squares '(2 3)))
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    let expected = vec![
+        Value::List(List::from_vec(vec![Value::Integer(1), Value::Integer(3)])),
+        Value::List(List::from_vec(vec![Value::Integer(4), Value::Integer(9)])),
+    ];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+// ==================== INITIALIZATION ORDER ====================
+
+#[test]
+fn test_imported_module_initialized_before_importer() {
+    // b appears first, but its def reads a's def, so a must run first
+    let source = r#"
+        (module b
+            (export y)
+            (import a)
+            (def y (+ a/x 1)))
+        (module a
+            (export x)
+            (def x 41))
+        b/y
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(42)));
+}
+
+#[test]
+fn test_import_chain_initialized_depth_first() {
+    let source = r#"
+        (module c
+            (export z)
+            (import b)
+            (def z (* b/y 2)))
+        (module b
+            (export y)
+            (import a)
+            (def y (+ a/x 1)))
+        (module a
+            (export x)
+            (def x 20))
+        (list a/x b/y c/z)
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    let expected = vec![Value::Integer(20), Value::Integer(21), Value::Integer(42)];
+    assert_eq!(get_stack_top(&vm), Some(Value::List(List::from_vec(expected))));
+}
+
+#[test]
+fn test_top_level_import_initializes_later_module() {
+    let source = r#"
+        (import config port)
+        (def url-port (+ port 1))
+        (module config
+            (export port)
+            (def port 8080))
+        url-port
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(8081)));
+}
+
+#[test]
+fn test_modules_without_imports_keep_source_order() {
+    let source = r#"
+        (def base 10)
+        (module m
+            (export k)
+            (def k (+ base 1)))
+        m/k
+    "#;
+    let vm = compile_and_run(source).unwrap();
+    assert_eq!(get_stack_top(&vm), Some(Value::Integer(11)));
+}
+
+#[test]
+fn test_circular_module_imports_rejected() {
+    let source = r#"
+        (module a (export x) (import b) (def x 1))
+        (module b (export y) (import a) (def y 2))
+    "#;
+    let err = compile_and_run(source).err().unwrap();
+    assert!(err.contains("Circular import between modules: a -> b -> a"), "{}", err);
+}
@@ -0,0 +1,65 @@
+// Tests for named let loops (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_named_let_counts() {
+    let source = "(let loop ((i 0)) (if (< i 10) (loop (+ i 1)) i))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(10));
+}
+
+#[test]
+fn test_named_let_counts_to_large_number_without_overflow() {
+    let source = "(let loop ((i 0) (acc 0)) (if (< i 1000000) (loop (+ i 1) (+ acc 2)) acc))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(2000000));
+}
+
+#[test]
+fn test_named_let_inside_function_uses_params() {
+    let source = r#"
+        (defun sum-to (n)
+          (let iter ((i 1) (total 0))
+            (if (> i n) total (iter (+ i 1) (+ total i)))))
+        (list (sum-to 100) (sum-to 0))
+    "#;
+    assert_eq!(run_code(source).unwrap(), run_code("(list 5050 0)").unwrap());
+}
+
+#[test]
+fn test_named_let_non_tail_recursion() {
+    let source = "(let build ((n 3)) (if (== n 0) '() (cons n (build (- n 1)))))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(3 2 1)").unwrap());
+}
+
+#[test]
+fn test_named_let_inits_see_outer_scope() {
+    // The loop name is not bound while the initial values are evaluated
+    let source = r#"
+        (defun outer (f) (let f ((x (f 1))) (if (> x 5) x (f (* x 2)))))
+        (outer (lambda (n) (+ n 2)))
+    "#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(6));
+}
+
+#[test]
+fn test_named_let_multiple_body_forms() {
+    let source = "(+ 1 (let go ((i 3)) (* i 100) (if (== i 0) 0 (go (- i 1)))))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn test_local_name_shadows_builtin_operator() {
+    let source = "(defun apply-twice (list x) (list (list x))) (apply-twice (lambda (n) (* n 3)) 2)";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(18));
+}
+
+#[test]
+fn test_named_let_errors() {
+    let err = run_code("(let loop ((i 0)))").unwrap_err();
+    assert!(err.contains("named let 'loop' expects bindings"), "{}", err);
+    let err = run_code("(let loop ((i)) i)").unwrap_err();
+    assert!(err.contains("named let binding"), "{}", err);
+}
@@ -0,0 +1,104 @@
+// Pathologically nested input must produce errors, not native stack overflows.
+// Parsing and compiling recurse once per level, and must reach
+// MAX_NESTING_DEPTH on an ordinary thread: every test runs on a
+// default-size spawned thread, not the large stack the command-line tools use.
+
+use lisp_bytecode_vm::{Compiler, VM, parser::Parser, MAX_NESTING_DEPTH};
+
+/// Run `f` on a thread with the default stack size
+fn on_default_thread<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    std::thread::spawn(f).join().unwrap()
+}
+
+/// Returns the result in Debug form, since Values cannot leave the thread
+fn compile_and_run(source: String) -> Result<String, String> {
+    on_default_thread(move || {
+        let exprs = Parser::new(&source).parse_all()?;
+        let mut compiler = Compiler::new();
+        let (functions, main) = compiler.compile_program(&exprs).map_err(|e| e.message)?;
+
+        let mut vm = VM::new();
+        vm.functions.extend(functions);
+        vm.current_bytecode = main;
+        vm.run_to_value().map(|v| format!("{:?}", v)).map_err(|e| e.message)
+    })
+}
+
+fn nested_parens(depth: usize) -> String {
+    format!("{}{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+#[test]
+fn test_deeply_nested_parens_are_a_parse_error() {
+    let err = compile_and_run(nested_parens(100_000)).unwrap_err();
+    assert!(err.contains(&format!("nesting exceeds {} levels", MAX_NESTING_DEPTH)), "got: {}", err);
+}
+
+#[test]
+fn test_deeply_nested_quotes_are_a_parse_error() {
+    let source = format!("{}x", "'".repeat(100_000));
+    let err = compile_and_run(source).unwrap_err();
+    assert!(err.contains("nesting exceeds"), "got: {}", err);
+}
+
+#[test]
+fn test_recovering_parser_reports_deep_nesting() {
+    let source = format!("{} (+ 1 2)", nested_parens(50_000));
+    let (exprs, errors) = on_default_thread(move || {
+        let (exprs, errors) = Parser::new(&source).parse_all_recovering();
+        (exprs.len(), errors.into_iter().map(|e| e.message).collect::<Vec<_>>())
+    });
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("nesting exceeds"), "got: {:?}", errors);
+    assert_eq!(exprs, 1);
+}
+
+#[test]
+fn test_nesting_below_the_limit_compiles_and_runs() {
+    let depth = MAX_NESTING_DEPTH - 10;
+    let source = format!("{}1{}", "(- ".repeat(depth), " 0)".repeat(depth));
+    assert_eq!(compile_and_run(source).unwrap(), "Integer(1)");
+}
+
+#[test]
+fn test_deep_forms_below_the_limit_compile_and_run() {
+    let depth = MAX_NESTING_DEPTH - 10;
+    let nest = |open: &str, mid: &str, close: &str| format!("{}{}{}", open.repeat(depth), mid, close.repeat(depth));
+    let cases = [
+        // let scopes and if branches
+        (nest("(let ((x 1)) ", "x", ")"), "Integer(1)".to_string()),
+        (nest("(if true ", "1", " 0)"), "Integer(1)".to_string()),
+        // A closure body walked for its free variables
+        (format!("(let ((y 1)) ((lambda () {})))", nest("(- ", "y", " 0)")), "Integer(1)".to_string()),
+        // Quoted and quasiquoted data
+        (format!("(list? '{})", nest("(", "", ")")), "Boolean(true)".to_string()),
+        (format!("(let ((y 1)) (list? `{}))", nest("(a ", ",y", ")")), "Boolean(true)".to_string()),
+        // A nested pattern
+        (format!("((match-lambda ({} 2) (_ 3)) 1)", nest("(", "a", ")")), "Integer(3)".to_string()),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(compile_and_run(source).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_endlessly_expanding_macro_is_a_compile_error() {
+    let source = r#"
+        (defmacro forever (x) (list 'forever x))
+        (forever 1)
+    "#;
+    let err = compile_and_run(source.to_string()).unwrap_err();
+    assert!(err.contains("nesting exceeds"), "got: {}", err);
+}
+
+#[test]
+fn test_macro_producing_deep_data_is_a_compile_error() {
+    let source = r#"
+        (defmacro deep (n)
+          (loop ((i 0) (acc 1))
+            (if (== i n) (list 'quote acc) (recur (+ i 1) (list acc)))))
+        (deep 5000)
+    "#;
+    let err = compile_and_run(source.to_string()).unwrap_err();
+    assert!(err.contains("Macro expansion nests deeper than"), "got: {}", err);
+}
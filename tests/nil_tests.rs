@@ -0,0 +1,76 @@
+// Tests for the nil literal and the nil? predicate (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_nil_is_the_empty_list() {
+    assert_eq!(run_code("nil").unwrap(), Value::List(List::Nil));
+    assert_eq!(run_code("(== nil '())").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(list-length (cons 1 (cons 2 nil)))").unwrap(), Value::Integer(2));
+}
+
+#[test]
+fn test_nil_predicate_only_for_empty_list() {
+    assert_eq!(run_code("(nil? '())").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(nil? nil)").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(nil? false)").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(nil? 0)").unwrap(), Value::Boolean(false));
+    assert_eq!(run_code("(nil? '(1))").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_null_predicate_unchanged() {
+    assert_eq!(run_code("(null? '())").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(null? nil)").unwrap(), Value::Boolean(true));
+    assert_eq!(run_code("(null? false)").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_nil_predicate_as_function_value() {
+    let source = "(filter nil? (list nil false '(1) '()))";
+    assert_eq!(run_code(source).unwrap(), run_code("(list nil nil)").unwrap());
+}
+
+#[test]
+fn test_nil_in_function_and_closure() {
+    let source = "(defun describe (x) (if (nil? x) 'empty 'full))
+                  (let ((check (lambda () (describe nil))))
+                    (list (check) (describe '(1))))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(empty full)").unwrap());
+}
+
+#[test]
+fn test_nil_predicate_arity() {
+    assert!(run_code("(nil? 1 2)").unwrap_err().contains("nil? expects exactly 1 argument"));
+}
+
+#[test]
+fn test_nil_cannot_be_bound() {
+    for source in [
+        "(def nil 5)",
+        "(def-mutable nil 5)",
+        "(let ((nil 5)) nil)",
+        "(let (((a nil) '(1 2))) a)",
+        "(let (((nil . t) '(1 2))) t)",
+        "(defun f (nil) 1)",
+        "(defun f (a &optional nil) a)",
+        "(lambda (x . nil) x)",
+        "(letrec ((nil (lambda () 1))) 1)",
+        "(if-let (nil 1) 1 2)",
+        "(dolist (nil '(1 2)) 1)",
+        "(try (car '()) (catch nil 1))",
+    ] {
+        let err = run_code(source).unwrap_err();
+        assert!(err.contains("Cannot bind 'nil'"), "{}: got {}", source, err);
+    }
+}
+
+#[test]
+fn test_nil_in_a_clause_pattern_matches_the_empty_list() {
+    let source = "(defun g ((nil) 'empty) ((x) x))
+                  (list (g '()) (g 3))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(empty 3)").unwrap());
+}
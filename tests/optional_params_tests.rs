@@ -0,0 +1,85 @@
+// Tests for &optional parameters with default values in defun (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+use std::sync::Arc;
+
+const GREET: &str = r#"(defun greet (name &optional (greeting "Hello"))
+                         (string-append greeting ", " name))"#;
+
+#[test]
+fn test_optional_default_used_when_omitted() {
+    let source = format!(r#"{} (greet "Ann")"#, GREET);
+    assert_eq!(run_code(&source).unwrap(), Value::String(Arc::new("Hello, Ann".to_string())));
+}
+
+#[test]
+fn test_optional_supplied_overrides_default() {
+    let source = format!(r#"{} (greet "Ann" "Hi")"#, GREET);
+    assert_eq!(run_code(&source).unwrap(), Value::String(Arc::new("Hi, Ann".to_string())));
+}
+
+#[test]
+fn test_missing_required_arg_is_an_error() {
+    let source = format!("{} (greet)", GREET);
+    let err = run_code(&source).unwrap_err();
+    assert!(err.contains("Not enough arguments"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_too_many_args_is_an_error() {
+    let source = format!(r#"{} (greet "a" "b" "c")"#, GREET);
+    let err = run_code(&source).unwrap_err();
+    assert!(err.contains("Too many arguments: expected at most 2, got 3"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_optional_without_default_is_nil() {
+    let source = "(defun f (a &optional b) (nil? b)) (list (f 1) (f 1 2))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        run_code("(list true false)").unwrap()
+    );
+}
+
+#[test]
+fn test_default_can_use_earlier_params() {
+    let source = "(defun scale (x &optional (factor (* x 2)) (offset factor)) (+ (* x factor) offset))
+                  (list (scale 3) (scale 3 1) (scale 3 1 0))";
+    assert_eq!(run_code(source).unwrap(), run_code("(list 24 4 3)").unwrap());
+}
+
+#[test]
+fn test_default_is_evaluated_only_when_omitted() {
+    let source = r#"(defun f (&optional (x (raise "default evaluated"))) x) (f 7)"#;
+    assert_eq!(run_code(source).unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_optional_with_rest() {
+    let source = "(defun f (a &optional (b 5) . more) (list a b more)) (list (f 1) (f 1 2 3 4))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        run_code("(list (list 1 5 '()) (list 1 2 (list 3 4)))").unwrap()
+    );
+}
+
+#[test]
+fn test_optional_in_recursive_tail_call() {
+    let source = "(defun sum-to (n &optional (acc 0)) (if (== n 0) acc (sum-to (- n 1) (+ acc n)))) (sum-to 100)";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(5050));
+}
+
+#[test]
+fn test_malformed_optional_entry_is_compile_error() {
+    let err = run_code("(defun f (a &optional (b 1 2)) a) (f 1)").unwrap_err();
+    assert!(err.contains("Optional parameter"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_optional_rejected_in_lambda() {
+    let err = run_code("((lambda (a &optional b) a) 1)").unwrap_err();
+    assert!(err.contains("only supported in defun"), "unexpected error: {}", err);
+}
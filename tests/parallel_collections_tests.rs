@@ -95,24 +95,23 @@ fn test_pmap_single_element() {
     }
 }
 
-// TODO: Enable this test once range function is implemented
-// #[test]
-// fn test_pmap_large_list() {
-//     let result = run_code(r#"
-//         (defun inc (x) (+ x 1))
-//         (pmap inc (range 1 1001))
-//     "#).unwrap();
-//
-//     match result {
-//         Value::List(items) => {
-//             let vec: Vec<_> = items.iter().collect();
-//             assert_eq!(vec.len(), 1000);
-//             assert_eq!(vec[0], &Value::Integer(2));
-//             assert_eq!(vec[999], &Value::Integer(1001));
-//         }
-//         _ => panic!("Expected list"),
-//     }
-// }
+#[test]
+fn test_pmap_large_list() {
+    let result = run_code(r#"
+        (defun inc (x) (+ x 1))
+        (pmap inc (range 1 1001))
+    "#).unwrap();
+
+    match result {
+        Value::List(items) => {
+            let vec: Vec<_> = items.iter().collect();
+            assert_eq!(vec.len(), 1000);
+            assert_eq!(vec[0], &Value::Integer(2));
+            assert_eq!(vec[999], &Value::Integer(1001));
+        }
+        _ => panic!("Expected list"),
+    }
+}
 
 // ============================================================
 // pfilter Tests
@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -587,3 +588,420 @@ fn test_deeply_nested_recursion() {
     let result = compile_and_run(source).unwrap();
     assert_eq!(result.trim(), "6");
 }
+
+// ==================== Uncons Tests ====================
+
+#[test]
+fn test_uncons_builtin() {
+    // The result is a (head . tail) pair, so a proper list comes back unchanged
+    let result = compile_and_run("(uncons '(1 2 3))").unwrap();
+    assert_eq!(result.trim(), "(1 2 3)");
+
+    let result = compile_and_run("(let ((p (uncons '(1 2 3)))) (list (car p) (cdr p)))").unwrap();
+    assert_eq!(result.trim(), "(1 (2 3))");
+
+    let result = compile_and_run("(cdr (uncons '(1)))").unwrap();
+    assert_eq!(result.trim(), "()");
+
+    let result = compile_and_run("(uncons '())");
+    assert!(result.unwrap_err().contains("empty list"));
+}
+
+#[test]
+fn test_let_head_tail_pattern() {
+    let source = r#"
+        (defun sum-list (lst)
+          (if (null? lst)
+              0
+              (let (((h . t) lst))
+                (+ h (sum-list t)))))
+        (sum-list '(1 2 3 4 5))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "15");
+}
+
+#[test]
+fn test_let_head_tail_pattern_with_other_bindings() {
+    let source = r#"
+        (let ((a 10)
+              ((h . _) '(1 2))
+              ((x . rest) '(3 4 5))
+              (b 20))
+          (list a h x rest b))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(10 1 3 (4 5) 20)");
+}
+
+// ==================== Hoisted Length Dispatch Tests ====================
+
+#[test]
+fn test_hoisted_length_dispatch_many_clauses() {
+    let source = r#"
+        (defun shape
+          ((()) 'empty)
+          (((a)) 'one)
+          (((a b)) 'two)
+          (((a b c)) 'three)
+          (((a b . rest)) 'many)
+          ((x) 'not-a-list))
+        (list (shape '()) (shape '(1)) (shape '(1 2)) (shape '(1 2 3))
+              (shape '(1 2 3 4)) (shape 42) (shape "str"))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(empty one two three many not-a-list not-a-list)");
+}
+
+#[test]
+fn test_hoisted_length_bindings_and_recursion() {
+    let source = r#"
+        (defun pairs
+          ((()) '())
+          (((x)) (list x))
+          (((a b . rest)) (cons (+ a b) (pairs rest))))
+        (pairs '(1 2 3 4 5))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(3 7 5)");
+}
+
+#[test]
+fn test_hoisted_length_multiple_args_and_arities() {
+    let source = r#"
+        (defun combine
+          (((a) (b)) (+ a b))
+          (((a b) n) (* (+ a b) n))
+          ((xs) 'single))
+        (list (combine '(1) '(2)) (combine '(3 4) 10) (combine '(1 2 3)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(3 70 single)");
+}
+
+#[test]
+fn test_hoisted_length_computed_once_per_argument() {
+    use lisp_bytecode_vm::Instruction;
+
+    let source = r#"
+        (defun shape
+          ((()) 'empty)
+          (((a)) 'one)
+          (((a b)) 'two)
+          (((a b . rest)) 'many))
+    "#;
+    let mut parser = Parser::new(source);
+    let exprs = parser.parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, _) = compiler.compile_program(&exprs).unwrap();
+    let bytecode = &functions["shape"];
+
+    let hoisted = bytecode.iter().filter(|i| matches!(i, Instruction::ArgListLength(0))).count();
+    assert_eq!(hoisted, 1);
+    assert!(!bytecode.iter().any(|i| matches!(i, Instruction::IsList | Instruction::ListLength)));
+}
+
+// ==================== match-lambda Tests ====================
+
+#[test]
+fn test_match_lambda_literals_and_fallback() {
+    let source = r#"
+        (defun classify (x)
+          ((match-lambda (0 'zero) ('() 'empty) ((a b) 'pair) (_ 'other)) x))
+        (list (classify 0) (classify '()) (classify '(1 2)) (classify 9))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(zero empty pair other)");
+}
+
+#[test]
+fn test_match_lambda_binds_pattern_variables() {
+    let source = r#"((match-lambda ((h . t) (list h t)) (x x)) '(1 2 3))"#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(1 (2 3))");
+}
+
+#[test]
+fn test_match_lambda_as_argument() {
+    let source = r#"
+        (defun my-map (f lst)
+          (if (null? lst) '() (cons (f (car lst)) (my-map f (cdr lst)))))
+        (my-map (match-lambda ((key . _) key) (_ 'none)) '((a 1) 5 (b)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(a none b)");
+}
+
+#[test]
+fn test_match_lambda_with_apply() {
+    let result = compile_and_run("(apply (match-lambda ((a b) (* a b))) '((6 7)))").unwrap();
+    assert_eq!(result.trim(), "42");
+}
+
+#[test]
+fn test_match_lambda_captures_enclosing_variables() {
+    let source = r#"
+        (defun adder (n)
+          (match-lambda ((a b) (+ n (+ a b))) (x (+ n x))))
+        (list ((adder 10) '(1 2)) ((adder 10) 5))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(13 15)");
+}
+
+#[test]
+fn test_match_lambda_inside_lambda() {
+    let source = r#"
+        (defun outer (k)
+          ((lambda (y) ((match-lambda (z (+ z (+ k y)))) 1)) 2))
+        (outer 3)
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "6");
+}
+
+#[test]
+fn test_match_lambda_is_a_one_argument_closure() {
+    let err = compile_and_run("((match-lambda (x x)) 1 2)").unwrap_err();
+    assert!(err.contains("arity mismatch"), "got: {}", err);
+}
+
+#[test]
+fn test_match_lambda_malformed_clauses() {
+    let err = compile_and_run("(match-lambda)").unwrap_err();
+    assert!(err.contains("match-lambda requires at least one clause"), "got: {}", err);
+
+    let err = compile_and_run("(match-lambda (1 'one) (2))").unwrap_err();
+    assert!(err.contains("match-lambda clause must have exactly 2 elements"), "got: {}", err);
+}
+
+// ============================================================
+// Structured Literal Patterns
+// ============================================================
+
+#[test]
+fn test_quoted_list_literal_pattern() {
+    let source = r#"
+        (defun describe
+          (('(0 0)) 'origin)
+          (('(1 2 3)) 'one-two-three)
+          ((_) 'other))
+        (list (describe (list 0 0)) (describe (list 1 2 3)) (describe (list 1 2)) (describe 5))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(origin one-two-three other other)");
+}
+
+#[test]
+fn test_nested_quoted_list_literal_is_checked() {
+    // The inner list is compared too, not just the outer length
+    let source = r#"
+        (defun classify
+          (('(move (1 2))) 'exact)
+          ((_) 'other))
+        (list (classify (list 'move (list 1 2))) (classify (list 'move (list 9 9))))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(exact other)");
+}
+
+#[test]
+fn test_string_literal_pattern() {
+    let source = r#"
+        (defun greet
+          (("en") "hello")
+          (("fr") "bonjour")
+          ((_) "?"))
+        (list (greet "fr") (greet "en") (greet "de"))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"("bonjour" "hello" "?")"#);
+}
+
+#[test]
+fn test_quoted_list_with_strings_pattern() {
+    let source = r#"
+        (defun route
+          (('("GET" "/")) 'index)
+          ((_) 'not-found))
+        (list (route (list "GET" "/")) (route (list "POST" "/")))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(index not-found)");
+}
+
+#[test]
+fn test_vector_and_hashmap_literal_patterns() {
+    let source = r#"
+        (defun config-name
+          (((hash-map "mode" "fast" "level" 3)) 'turbo)
+          (((vector 1 2 3)) 'counting)
+          ((_) 'custom))
+        (list (config-name (hash-map "level" 3 "mode" "fast"))
+              (config-name (vector 1 2 3))
+              (config-name (hash-map "mode" "slow"))
+              (config-name (vector 1 2)))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(turbo counting custom custom)");
+}
+
+#[test]
+fn test_structured_literal_inside_list_pattern() {
+    let source = r#"
+        (defun handle
+          ((('set (vector 0 0) v)) (list 'reset v))
+          (((op _ v)) (list op v)))
+        (list (handle (list 'set (vector 0 0) 5)) (handle (list 'set (vector 1 0) 6)))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "((reset 5) (set 6))");
+}
+
+#[test]
+fn test_structured_literal_in_match_lambda() {
+    let source = r#"
+        (defun keys (a b)
+          (let ((on-key (match-lambda ('(ctrl "c") 'copy) ('(ctrl "v") 'paste) (_ 'ignore))))
+            (list (on-key a) (on-key b))))
+        (keys (list 'ctrl "v") (list 'ctrl "x"))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(paste ignore)");
+}
+
+#[test]
+fn test_vector_pattern_must_be_constant() {
+    let err = compile_and_run("(defun f (((vector x 1)) x) ((_) 0))").unwrap_err();
+    assert!(err.contains("Expected a constant in vector/hash-map pattern, got x"), "got: {}", err);
+}
+
+#[test]
+fn test_guard_selects_between_identical_patterns() {
+    let source = r#"
+        (defun classify
+          ((n) (when (< n 0)) "negative")
+          ((n) (when (== n 0)) "zero")
+          ((n) "positive"))
+        (list (classify -5) (classify 0) (classify 3))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"("negative" "zero" "positive")"#);
+}
+
+#[test]
+fn test_guard_sees_nested_bindings() {
+    let source = r#"
+        (defun order
+          (((a b)) (when (> a b)) (list b a))
+          (((a b)) (list a b))
+          ((x) x))
+        (list (order (list 3 1)) (order (list 1 5)) (order 7))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "((1 3) (1 5) 7)");
+}
+
+#[test]
+fn test_failed_guard_leaves_no_bindings_behind() {
+    // The second clause binds x to the stack slot the first clause's bindings used
+    let source = r#"
+        (defun pick
+          ((a b) (when (> a b)) a)
+          ((x y) y))
+        (pick 1 2)
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "2");
+}
+
+#[test]
+fn test_guard_must_be_when_form() {
+    let err = compile_and_run("(defun f ((n) (if (< n 0)) 1) ((n) 2))").unwrap_err();
+    assert!(err.contains("Clause guard must be (when condition)"), "got: {}", err);
+}
+
+#[test]
+fn test_nested_list_pattern_rejects_atom() {
+    let source = r#"
+        (defun pair-sum
+          (((a b)) (+ a b))
+          ((x) "not a pair"))
+        (list (pair-sum (list 1 2)) (pair-sum 5))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"(3 "not a pair")"#);
+}
+
+#[test]
+fn test_nested_list_pattern_checks_inner_shape() {
+    let source = r#"
+        (defun f
+          ((((a b) c)) (list a b c))
+          ((_) 'other))
+        (list (f (list (list 1 2) 3)) (f (list 4 3)) (f (list (list 1) 3)) (f (list (list 1 2 3) 4)))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "((1 2 3) other other other)");
+}
+
+#[test]
+fn test_nested_dotted_pattern_checks_shape() {
+    let source = r#"
+        (defun f
+          (((x (y . r))) (list x y r))
+          ((_) 'other))
+        (list (f (list 1 (list 2 3))) (f (list 1 2)) (f (list 1 '())))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "((1 2 (3)) other other)");
+}
+
+#[test]
+fn test_nested_literal_checked_at_depth() {
+    let source = r#"
+        (defun f
+          ((((tag 1))) tag)
+          ((_) 'other))
+        (list (f (list (list 'a 1))) (f (list (list 'a 2))))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(a other)");
+}
+
+#[test]
+fn test_no_matching_clause_is_runtime_error() {
+    let err = compile_and_run(r#"(defun f ((1) "one") ((2) "two")) (f 3)"#).unwrap_err();
+    assert!(err.contains("No matching clause in function 'f' for arguments (3)"), "got: {}", err);
+}
+
+#[test]
+fn test_no_matching_clause_can_be_caught() {
+    let source = r#"
+        (defun f ((1) "one"))
+        (list (try (f 9) (catch e "caught")) (f 1))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"("caught" "one")"#);
+}
+
+#[test]
+fn test_match_lambda_no_match_is_runtime_error() {
+    let err = compile_and_run("(let ((g (match-lambda (1 'one)))) (g 5))").unwrap_err();
+    assert!(err.contains("No matching clause in match-lambda for arguments (5)"), "got: {}", err);
+}
+
+// ==================== Improper List Arguments ====================
+
+#[test]
+fn test_list_patterns_do_not_match_dotted_pairs() {
+    let source = r#"
+        (defun g (((a)) (list 'one a)) ((_) 'other))
+        (list (g (cons 1 2)) (g '(1)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(other (one 1))");
+}
+
+#[test]
+fn test_list_patterns_do_not_match_improper_lists() {
+    let source = r#"
+        (defun two (((a b)) 'two) (((h . t)) 'dotted) ((_) 'other))
+        (list (two (cons 1 (cons 2 3))) (two '(1 2)) (two '(1 2 3)))
+    "#;
+    let result = compile_and_run(source).unwrap();
+    assert_eq!(result.trim(), "(other two dotted)");
+}
+
+#[test]
+fn test_match_lambda_does_not_match_dotted_pairs() {
+    let result = compile_and_run("((match-lambda ((a) a) (_ 'other)) (cons 1 2))").unwrap();
+    assert_eq!(result.trim(), "other");
+    let result = compile_and_run("((match-lambda ((a . b) 'pair) (_ 'other)) (cons 1 2))").unwrap();
+    assert_eq!(result.trim(), "other");
+}
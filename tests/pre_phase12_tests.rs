@@ -222,6 +222,94 @@ fn test_gensym_multiple_calls() {
     }
 }
 
+#[test]
+fn test_gensym_numbering_is_per_vm() {
+    // Each run starts from G__0, regardless of what ran before in this process
+    for _ in 0..2 {
+        let result = run_code("(list (gensym) (gensym))").unwrap();
+        assert_eq!(result, Value::List(List::from_vec(vec![Value::symbol("G__0"), Value::symbol("G__1")])));
+    }
+}
+
+#[test]
+fn test_gensym_prefix_is_unique() {
+    let result = run_code(r#"(list (gensym "loop") (gensym "loop"))"#).unwrap();
+    let names: Vec<String> = match result {
+        Value::List(items) => items.iter().map(|item| match item {
+            Value::Symbol(name) => name.to_string(),
+            other => panic!("Expected symbol, got {:?}", other),
+        }).collect(),
+        other => panic!("Expected list, got {:?}", other),
+    };
+    assert_eq!(names.len(), 2);
+    assert_ne!(names[0], names[1]);
+    for name in &names {
+        assert!(name.starts_with("loop__"), "{}", name);
+    }
+}
+
+#[test]
+fn test_gensym_prefix_shares_counter() {
+    let result = run_code(r#"(list (gensym) (gensym "tmp") (gensym))"#).unwrap();
+    let expected = vec![Value::symbol("G__0"), Value::symbol("tmp__1"), Value::symbol("G__2")];
+    assert_eq!(result, Value::List(List::from_vec(expected)));
+}
+
+#[test]
+fn test_gensym_prefix_errors() {
+    assert!(run_code("(gensym 5)").unwrap_err().contains("prefix must be a string"));
+    assert!(run_code(r#"(gensym "a" "b")"#).unwrap_err().contains("at most 1 argument"));
+}
+
+const WITH_TEMP: &str = r#"
+    (defmacro with-temp (x)
+      (let ((g (gensym)))
+        (list 'let (list (list g x)) g)))
+"#;
+
+/// The expansion of (with-temp n) when gensym returns `sym`
+fn with_temp_expansion(sym: &str, n: i64) -> Value {
+    let binding = Value::from(vec![Value::symbol(sym), Value::Integer(n)]);
+    Value::from(vec![Value::symbol("let"), Value::from(vec![binding]), Value::symbol(sym)])
+}
+
+#[test]
+fn test_gensym_in_macros_is_reproducible() {
+    let source = format!("{} (macroexpand '(with-temp 5))", WITH_TEMP);
+    let first = run_code(&source).unwrap();
+    let second = run_code(&source).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, with_temp_expansion("G__0", 5));
+}
+
+#[test]
+fn test_gensym_in_macros_is_unique_within_a_program() {
+    let source = format!("{} (list (macroexpand '(with-temp 1)) (macroexpand '(with-temp 2)))", WITH_TEMP);
+    let result = run_code(&source).unwrap();
+    assert_eq!(result, Value::from(vec![with_temp_expansion("G__0", 1), with_temp_expansion("G__1", 2)]));
+}
+
+#[test]
+fn test_compiler_gensym_counter_reset() {
+    let source = format!("{} (macroexpand '(with-temp 5))", WITH_TEMP);
+    let exprs = parser::Parser::new(&source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+
+    let expand = |compiler: &mut Compiler| {
+        compiler.clear_main_bytecode();
+        let (functions, main) = compiler.compile_program(&exprs).unwrap();
+        let mut vm = VM::new();
+        vm.functions.extend(functions);
+        vm.current_bytecode = main;
+        vm.run_to_value().unwrap()
+    };
+
+    assert_eq!(expand(&mut compiler), with_temp_expansion("G__0", 5));
+    assert_eq!(expand(&mut compiler), with_temp_expansion("G__1", 5));
+    compiler.reset_gensym_counter();
+    assert_eq!(expand(&mut compiler), with_temp_expansion("G__0", 5));
+}
+
 // ============================================================
 // macroexpand Tests
 // ============================================================
@@ -0,0 +1,71 @@
+// Tests for the native range builtin (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_range_end_only() {
+    assert_eq!(run_code("(range 5)").unwrap(), ints(&[0, 1, 2, 3, 4]));
+}
+
+#[test]
+fn test_range_start_end() {
+    assert_eq!(run_code("(range 2 6)").unwrap(), ints(&[2, 3, 4, 5]));
+    assert_eq!(run_code("(range -2 1)").unwrap(), ints(&[-2, -1, 0]));
+}
+
+#[test]
+fn test_range_with_step() {
+    assert_eq!(run_code("(range 1 10 2)").unwrap(), ints(&[1, 3, 5, 7, 9]));
+}
+
+#[test]
+fn test_range_negative_step_counts_down() {
+    assert_eq!(run_code("(range 5 0 -1)").unwrap(), ints(&[5, 4, 3, 2, 1]));
+    assert_eq!(run_code("(range 10 0 -3)").unwrap(), ints(&[10, 7, 4, 1]));
+}
+
+#[test]
+fn test_range_empty_when_bounds_point_the_other_way() {
+    assert_eq!(run_code("(range 0)").unwrap(), ints(&[]));
+    assert_eq!(run_code("(range 5 2)").unwrap(), ints(&[]));
+    assert_eq!(run_code("(range 0 5 -1)").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_range_stops_at_integer_limit() {
+    let source = "(list-length (range 9223372036854775806 9223372036854775807 5))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn test_range_zero_step_is_error() {
+    let err = run_code("(range 0 5 0)").unwrap_err();
+    assert!(err.contains("step cannot be zero"), "{}", err);
+}
+
+#[test]
+fn test_range_non_integer_is_type_error() {
+    let err = run_code("(range 1.5)").unwrap_err();
+    assert!(err.contains("'range' expects integers"), "{}", err);
+}
+
+#[test]
+fn test_range_arity_is_compile_error() {
+    assert!(run_code("(range)").is_err());
+    assert!(run_code("(range 1 2 3 4)").is_err());
+}
+
+#[test]
+fn test_range_as_value_and_inside_expressions() {
+    let source = r#"
+        (defun total (n) (reduce (lambda (acc x) (+ acc x)) 0 (range n)))
+        (list (total 5) (apply range (list 3 6)) (+ 1 (list-length (range 4))))
+    "#;
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::list_from_vec(vec![Value::Integer(10), ints(&[3, 4, 5]), Value::Integer(5)])
+    );
+}
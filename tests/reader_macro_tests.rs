@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -257,6 +258,46 @@ fn test_expression_comment_with_defun() {
     assert_eq!(result, Ok("42".to_string()));
 }
 
+#[test]
+fn test_expression_comment_before_closing_paren() {
+    assert_eq!(compile_and_run("(+ 1 2 #;3)"), Ok("3".to_string()));
+    assert_eq!(compile_and_run("(list 1 #;(2 (3 4)))"), Ok("(1)".to_string()));
+}
+
+#[test]
+fn test_expression_comment_at_end_of_input() {
+    let result = compile_and_run(r#"
+        42
+        #;(defun unfinished (x)
+            (let ((y (* x 2)))
+              (+ y 1)))
+    "#);
+    assert_eq!(result, Ok("42".to_string()));
+}
+
+#[test]
+fn test_expression_comment_skips_quoted_and_vector_forms() {
+    assert_eq!(compile_and_run("(list 1 #;'(a b) #;#(1 2) 2)"), Ok("(1 2)".to_string()));
+}
+
+// Shebang line: #! on the very first line is ignored
+
+#[test]
+fn test_shebang_line_is_ignored() {
+    let result = compile_and_run("#!/usr/bin/env vm\n(defun twice (x) (* x 2))\n(twice 21)");
+    assert_eq!(result, Ok("42".to_string()));
+}
+
+#[test]
+fn test_shebang_only_on_first_line() {
+    let result = compile_and_run("1\n#!/usr/bin/env vm\n2");
+    assert!(result.unwrap_err().contains("Unknown reader macro: #!"));
+
+    // Not at the very start of the input either
+    let result = compile_and_run(" #!/usr/bin/env vm\n2");
+    assert!(result.unwrap_err().contains("Unknown reader macro: #!"));
+}
+
 // Built-in reader macro tests: #'
 // Note: #'func is syntactic sugar for func
 // It provides visual clarity that you're referring to a function
@@ -0,0 +1,70 @@
+// Tests for the call depth limit (no stdlib loaded)
+
+use lisp_bytecode_vm::*;
+
+fn load(source: &str) -> VM {
+    let exprs = parser::Parser::new(source).parse_all().unwrap();
+    let (functions, main_bytecode) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main_bytecode;
+    vm
+}
+
+#[test]
+fn test_default_limit() {
+    assert_eq!(VM::new().max_call_depth, DEFAULT_MAX_CALL_DEPTH);
+}
+
+#[test]
+fn test_unbounded_recursion_errors_cleanly() {
+    let mut vm = load("(defun forever (n) (+ 1 (forever n))) (forever 0)");
+    let err = vm.run().unwrap_err();
+
+    assert!(err.message.contains("maximum recursion depth exceeded"), "{}", err.message);
+    // Only the innermost frames are kept, after a note of how many were left out
+    assert_eq!(err.call_stack.len(), 11);
+    assert_eq!(err.call_stack[0], format!("... {} more", DEFAULT_MAX_CALL_DEPTH - 10));
+    assert!(err.call_stack[1..].iter().all(|name| name == "forever"));
+}
+
+#[test]
+fn test_limit_is_configurable() {
+    let source = "(defun depth (n) (if (== n 0) 0 (+ 1 (depth (- n 1)))))";
+
+    let mut vm = load(&format!("{} (depth 50)", source));
+    vm.set_max_call_depth(100);
+    assert_eq!(vm.run_to_value().unwrap(), Value::Integer(50));
+
+    let mut vm = load(&format!("{} (depth 500)", source));
+    vm.set_max_call_depth(100);
+    let err = vm.run().unwrap_err();
+    assert!(err.message.contains("(100 nested calls)"), "{}", err.message);
+}
+
+#[test]
+fn test_tail_recursion_is_not_limited() {
+    let mut vm = load("(defun count (n acc) (if (== n 0) acc (count (- n 1) (+ acc 1)))) (count 5000 0)");
+    vm.set_max_call_depth(10);
+    assert_eq!(vm.run_to_value().unwrap(), Value::Integer(5000));
+}
+
+#[test]
+fn test_closure_and_apply_calls_count_too() {
+    // The closure recurses through CallClosure by calling its own argument
+    let mut vm = load("((lambda (h) (+ 1 (h h))) (lambda (h) (+ 1 (h h))))");
+    vm.set_max_call_depth(50);
+    assert!(vm.run().unwrap_err().message.contains("maximum recursion depth"));
+
+    let mut vm = load("(defun h (n) (g n)) (defun g (n) (+ 1 (apply h (list n)))) (g 0)");
+    vm.set_max_call_depth(50);
+    assert!(vm.run().unwrap_err().message.contains("maximum recursion depth"));
+}
+
+#[test]
+fn test_depth_error_can_be_caught() {
+    let mut vm = load("(defun forever (n) (+ 1 (forever n))) (try (forever 0) (catch e \"stopped\"))");
+    vm.set_max_call_depth(100);
+    assert_eq!(vm.run_to_value().unwrap(), Value::String("stopped".to_string().into()));
+}
@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -68,10 +69,17 @@ fn test_function_arity_builtin_unary() {
 #[test]
 fn test_function_arity_builtin_binary() {
     // Test arity of a builtin binary function
-    let result = compile_and_run(r#"(function-arity +)"#);
+    let result = compile_and_run(r#"(function-arity %)"#);
     assert_eq!(result, Ok("2".to_string()));
 }
 
+#[test]
+fn test_function_arity_builtin_arithmetic_is_variadic() {
+    // + - * / accept two or more arguments at runtime, like their inline forms
+    let result = compile_and_run(r#"(function-arity +)"#);
+    assert_eq!(result, Ok("-1".to_string()));
+}
+
 #[test]
 fn test_function_arity_user_defined() {
     // Test arity of user-defined function
@@ -312,6 +320,36 @@ fn test_function_name_type_error() {
     assert!(result.unwrap_err().contains("expects a function"));
 }
 
+// ========== Closure Display and Equality Tests ==========
+
+#[test]
+fn test_closure_display_shows_params() {
+    let result = compile_and_run(r#"(format "{}" (list (lambda (x y) (+ x y))))"#);
+    assert_eq!(result, Ok("\"#<closure (x y)>\"".to_string()));
+}
+
+#[test]
+fn test_closure_display_shows_variadic() {
+    let result = compile_and_run(r#"(format "{}" (list (lambda (x . rest) rest)))"#);
+    assert_eq!(result, Ok("\"#<closure (x . rest) variadic>\"".to_string()));
+}
+
+#[test]
+fn test_closure_equality_is_identity() {
+    // The same closure value is equal to itself
+    let result = compile_and_run(r#"
+        (let ((f (lambda (x) x)))
+          (== f f))
+    "#);
+    assert_eq!(result, Ok("true".to_string()));
+
+    // Structurally identical lambdas are still distinct closures
+    let result = compile_and_run(r#"
+        (== (lambda (x) x) (lambda (x) x))
+    "#);
+    assert_eq!(result, Ok("false".to_string()));
+}
+
 // ========== Integration Tests ==========
 
 #[test]
@@ -60,9 +60,22 @@ fn test_is_complete_input_deeply_nested() {
 fn test_is_complete_input_unbalanced_closing() {
     let mut repl = Repl::new();
     repl.input_buffer = "(+ 1 2))".to_string();
-    // This has an extra closing paren, depth goes negative
-    // The current implementation only checks depth == 0 at the end,
-    // so depth -1 means this is not complete
+    // An extra closing paren can never be balanced by more input,
+    // so the input is handed to the parser, which reports it
+    assert!(repl.is_complete_input());
+}
+
+#[test]
+fn test_is_complete_input_ignores_parens_in_strings_and_comments() {
+    let mut repl = Repl::new();
+    repl.input_buffer = "(print \"(\") ; )".to_string();
+    assert!(repl.is_complete_input());
+}
+
+#[test]
+fn test_is_complete_input_unterminated_string() {
+    let mut repl = Repl::new();
+    repl.input_buffer = "(print \"hello)".to_string();
     assert!(!repl.is_complete_input());
 }
 
@@ -103,3 +116,233 @@ fn test_clear_state_clears_input_buffer() {
 
     assert_eq!(repl.input_buffer.len(), 0);
 }
+
+#[test]
+fn test_completions_include_builtins_and_special_forms() {
+    let repl = Repl::new();
+    let matches = repl.completions("string-");
+    assert!(matches.contains(&"string-append".to_string()));
+    assert!(matches.contains(&"string-length".to_string()));
+    assert!(matches.iter().all(|m| m.starts_with("string-")));
+
+    let matches = repl.completions("def");
+    assert!(matches.contains(&"defun".to_string()));
+    assert!(matches.contains(&"defmacro".to_string()));
+}
+
+#[test]
+fn test_completions_sorted_without_duplicates() {
+    let repl = Repl::new();
+    let matches = repl.completions("");
+    let mut sorted = matches.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(matches, sorted);
+    assert!(!matches.iter().any(|m| m.starts_with("__")));
+}
+
+#[test]
+fn test_completions_no_match() {
+    let repl = Repl::new();
+    assert!(repl.completions("zzz-no-such-name").is_empty());
+}
+
+#[test]
+fn test_vm_known_names_include_user_definitions() {
+    use lisp_bytecode_vm::{Compiler, VM, parser::Parser};
+
+    let source = "(def answer 42) (defun square (x) (* x x))";
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).unwrap();
+
+    let compiler_names = compiler.known_names();
+    assert!(compiler_names.contains(&"square".to_string()));
+    assert!(compiler_names.contains(&"answer".to_string()));
+    assert!(compiler_names.contains(&"lambda".to_string()));
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    vm.run().unwrap();
+    let vm_names = vm.known_names();
+    assert!(vm_names.contains(&"square".to_string()));
+    assert!(vm_names.contains(&"answer".to_string()));
+    assert!(vm_names.contains(&"car".to_string()));
+}
+
+fn write_temp_lisp(name: &str, source: &str) -> String {
+    let path = std::env::temp_dir().join(format!("repl-load-{}-{}.lisp", std::process::id(), name));
+    std::fs::write(&path, source).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_load_file_keeps_definitions() {
+    let path = write_temp_lisp("defs", "(defun triple (x) (* 3 x))\n(def base 4)\n");
+    let mut repl = Repl::new();
+    repl.load_file(&path).unwrap();
+    assert_eq!(repl.eval_source("(triple base)", None).unwrap(), Some(Value::Integer(12)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_file_reports_errors_and_session_continues() {
+    let mut repl = Repl::new();
+    let err = repl.load_file("/no/such/dir/prog.lisp").unwrap_err();
+    assert!(err.contains("Cannot read"), "{}", err);
+
+    let path = write_temp_lisp("broken", "(defun ok () 1)\n(car 5)\n");
+    assert!(repl.load_file(&path).is_err());
+    assert_eq!(repl.eval_source("(+ 1 2)", None).unwrap(), Some(Value::Integer(3)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_reload_runs_last_loaded_file_again() {
+    let mut repl = Repl::new();
+    assert!(repl.reload().unwrap_err().contains("No file loaded"));
+
+    let path = write_temp_lisp("reload", "(defun version () 1)\n");
+    repl.load_file(&path).unwrap();
+    std::fs::write(&path, "(defun version () 2)\n").unwrap();
+    repl.reload().unwrap();
+    assert_eq!(repl.eval_source("(version)", None).unwrap(), Some(Value::Integer(2)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_set_mutable_global_across_lines() {
+    let mut repl = Repl::new();
+    repl.eval_source("(def-mutable hits 0)", None).unwrap();
+    repl.eval_source("(incf hits)", None).unwrap();
+    assert_eq!(repl.eval_source("(set! hits (* hits 10))", None).unwrap(), Some(Value::Integer(10)));
+    assert_eq!(repl.eval_source("hits", None).unwrap(), Some(Value::Integer(10)));
+}
+
+#[test]
+fn test_set_immutable_global_from_earlier_line_is_rejected() {
+    let mut repl = Repl::new();
+    repl.eval_source("(def limit 3)", None).unwrap();
+    let err = repl.eval_source("(set! limit 4)", None).unwrap_err();
+    assert!(err.contains("Cannot set! 'limit' - it was defined with def and is immutable"), "{}", err);
+    assert_eq!(repl.eval_source("limit", None).unwrap(), Some(Value::Integer(3)));
+
+    // Redefining it with def-mutable on a later line makes it assignable
+    repl.eval_source("(def-mutable limit 5)", None).unwrap();
+    assert_eq!(repl.eval_source("(set! limit 6)", None).unwrap(), Some(Value::Integer(6)));
+}
+
+#[test]
+fn test_keyword_call_on_a_later_line() {
+    let mut repl = Repl::new();
+    repl.eval_source("(defun make-window (&key (width 100) (height 50)) (list width height))", None).unwrap();
+    let expected = repl.eval_source("(list 100 3)", None).unwrap();
+    assert_eq!(repl.eval_source("(make-window :height 3)", None).unwrap(), expected);
+    let expected = repl.eval_source("(list 100 50)", None).unwrap();
+    assert_eq!(repl.eval_source("(make-window)", None).unwrap(), expected);
+    let expected = repl.eval_source("(list 7 50)", None).unwrap();
+    assert_eq!(repl.eval_source("(apply make-window '(:width 7))", None).unwrap(), expected);
+}
+
+const FIB: &str = "(defun fib (n a b) (if (<= n 0) a (fib (- n 1) b (+ a b))))";
+
+#[test]
+fn test_disasm_defun_shows_tail_call_without_defining_it() {
+    let mut repl = Repl::new();
+    let listing = repl.disassemble_source(FIB).unwrap();
+    assert!(listing.contains("=== Function: fib (n a b) ==="), "{}", listing);
+    assert!(listing.contains("TailCall(\"fib\", 3)  ; tail call"), "{}", listing);
+
+    // Nothing was compiled into the session
+    assert!(repl.eval_source("(fib 10 0 1)", None).is_err());
+}
+
+#[test]
+fn test_disasm_fn_dumps_a_defined_function() {
+    let mut repl = Repl::new();
+    repl.eval_source(FIB, None).unwrap();
+
+    let listing = repl.disassemble_function("fib").unwrap();
+    assert!(listing.starts_with("=== Function: fib (n a b) ===\n"), "{}", listing);
+    assert!(listing.contains("TailCall"), "{}", listing);
+    assert!(listing.contains("; -> "), "{}", listing);
+
+    // :disasm with a bare defined name shows the same listing
+    assert_eq!(repl.disassemble_source("fib").unwrap(), listing);
+    assert!(repl.disassemble_function("no-such-fn").unwrap_err().contains("Unknown function"));
+}
+
+#[test]
+fn test_disasm_expression_can_call_session_functions() {
+    let mut repl = Repl::new();
+    repl.eval_source(FIB, None).unwrap();
+
+    let listing = repl.disassemble_source("(fib 10 0 1)").unwrap();
+    assert!(listing.contains("Call(\"fib\", 3)  ; call"), "{}", listing);
+    assert!(!listing.contains("=== Function: fib"), "{}", listing);
+    assert!(repl.disassemble_source("(if)").is_err());
+}
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("repl-save-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_saved_session_loads_into_a_fresh_repl() {
+    let mut repl = Repl::new();
+    repl.eval_source(FIB, None).unwrap();
+    repl.eval_source("(def offset 100)", None).unwrap();
+    repl.eval_source("(def add-offset (lambda (x) (+ x offset)))", None).unwrap();
+
+    let path = temp_path("session.vmc");
+    repl.save_bytecode(&path).unwrap();
+
+    let mut fresh = Repl::new();
+    fresh.load_file(&path).unwrap();
+    assert_eq!(fresh.eval_source("(fib 10 0 1)", None).unwrap(), Some(Value::Integer(55)));
+    assert_eq!(fresh.eval_source("offset", None).unwrap(), Some(Value::Integer(100)));
+    assert_eq!(fresh.eval_source("(let ((f add-offset)) (f 5))", None).unwrap(), Some(Value::Integer(105)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_compiled_bytecode_file_runs_its_main() {
+    let exprs = lisp_bytecode_vm::parser::Parser::new("(defun sq (x) (* x x)) (sq 7)").parse_all().unwrap();
+    let (functions, main) = lisp_bytecode_vm::Compiler::new().compile_program(&exprs).unwrap();
+    let path = temp_path("prog.vmc");
+    lisp_bytecode_vm::bytecode::save_bytecode_file(&path, &functions, &main).unwrap();
+
+    let mut repl = Repl::new();
+    assert_eq!(repl.load_file(&path).unwrap(), Some(Value::Integer(49)));
+    assert_eq!(repl.eval_source("(sq 3)", None).unwrap(), Some(Value::Integer(9)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_a_corrupt_bytecode_file() {
+    let path = temp_path("junk.vmc");
+    std::fs::write(&path, "(+ 1 2)").unwrap();
+
+    let err = Repl::new().load_file(&path).unwrap_err();
+    assert!(err.contains("bad magic number"), "{}", err);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_runtime_error_box_lists_the_call_stack_innermost_first() {
+    let mut repl = Repl::new();
+    repl.eval_source("(defun c () (car 5))", None).unwrap();
+    repl.eval_source("(defun b () (+ 1 (c)))", None).unwrap();
+    repl.eval_source("(defun a () (+ 1 (b)))", None).unwrap();
+
+    let err = repl.eval_source("(a)", None).unwrap_err();
+    assert!(err.contains("├─ Call Stack"), "{}", err);
+    let c = err.find("#0: c").unwrap();
+    let b = err.find("#1: b").unwrap();
+    let a = err.find("#2: a").unwrap();
+    assert!(c < b && b < a, "{}", err);
+}
@@ -0,0 +1,61 @@
+// Tests for the native reverse, take and drop builtins (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_reverse() {
+    assert_eq!(run_code("(reverse '(1 2 3))").unwrap(), ints(&[3, 2, 1]));
+    assert_eq!(run_code("(reverse '())").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_take() {
+    assert_eq!(run_code("(take 2 '(1 2 3 4))").unwrap(), ints(&[1, 2]));
+    assert_eq!(run_code("(take 0 '(1 2))").unwrap(), ints(&[]));
+    assert_eq!(run_code("(take 10 '(1 2))").unwrap(), ints(&[1, 2]));
+}
+
+#[test]
+fn test_drop() {
+    assert_eq!(run_code("(drop 2 '(1 2 3 4))").unwrap(), ints(&[3, 4]));
+    assert_eq!(run_code("(drop 0 '(1 2))").unwrap(), ints(&[1, 2]));
+    assert_eq!(run_code("(drop 10 '(1 2))").unwrap(), ints(&[]));
+}
+
+#[test]
+fn test_take_and_drop_split_a_list() {
+    let source = "(let ((xs '(1 2 3 4 5))) (append (take 2 xs) (drop 2 xs)))";
+    assert_eq!(run_code(source).unwrap(), ints(&[1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn test_negative_count_is_error() {
+    let err = run_code("(take -1 '(1 2))").unwrap_err();
+    assert!(err.contains("'take' count cannot be negative"), "{}", err);
+    let err = run_code("(drop -1 '(1 2))").unwrap_err();
+    assert!(err.contains("'drop' count cannot be negative"), "{}", err);
+}
+
+#[test]
+fn test_type_errors() {
+    let err = run_code("(reverse 5)").unwrap_err();
+    assert!(err.contains("'reverse' expects a list"), "{}", err);
+    let err = run_code("(take '(1 2) 1)").unwrap_err();
+    assert!(err.contains("'take' expects an integer and a list"), "{}", err);
+}
+
+#[test]
+fn test_as_first_class_values() {
+    let source = "(list (apply take (list 1 '(7 8))) (map reverse '((1 2) (3 4))) (reduce (lambda (acc f) (f 1 acc)) '(1 2 3) (list drop take)))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::list_from_vec(vec![
+            ints(&[7]),
+            Value::list_from_vec(vec![ints(&[2, 1]), ints(&[4, 3])]),
+            ints(&[2]),
+        ])
+    );
+}
@@ -0,0 +1,129 @@
+// Tests for set!, def-mutable, and incf/decf (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::ints;
+
+fn run_code(source: &str) -> Result<Value, String> {
+    common::run_code_with(source, |vm| vm.verify_bytecode = true)
+}
+
+#[test]
+fn test_set_let_local_in_do_block() {
+    let source = "(let ((x 1))
+                    (do (set! x (+ x 10))
+                        (set! x (* x 2))
+                        x))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(22));
+}
+
+#[test]
+fn test_set_returns_new_value() {
+    assert_eq!(run_code("(let ((x 1)) (+ 100 (set! x 5)))").unwrap(), Value::Integer(105));
+}
+
+#[test]
+fn test_set_inner_local_leaves_outer_bindings() {
+    let source = "(let ((a 1) (b 2))
+                    (do (let ((c 3)) (set! b (+ b c)))
+                        (list a b)))";
+    assert_eq!(run_code(source).unwrap(), ints(&[1, 5]));
+}
+
+#[test]
+fn test_set_inside_function_and_loop() {
+    let source = "(defun sum-to (n)
+                    (let ((total 0))
+                      (loop ((i 1))
+                        (if (> i n)
+                            total
+                            (do (set! total (+ total i))
+                                (recur (+ i 1)))))))
+                  (sum-to 10)";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(55));
+}
+
+#[test]
+fn test_set_mutable_global() {
+    let source = "(def-mutable counter 0)
+                  (defun tick () (set! counter (+ counter 1)))
+                  (tick) (tick) (tick)
+                  counter";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(3));
+}
+
+#[test]
+fn test_incf_decf() {
+    let source = "(def-mutable n 10)
+                  (let ((x 0))
+                    (do (incf x) (incf x 5) (decf x 2)
+                        (decf n) (incf n 100)
+                        (list x n)))";
+    assert_eq!(run_code(source).unwrap(), ints(&[4, 109]));
+}
+
+#[test]
+fn test_set_immutable_def_is_error() {
+    let err = run_code("(def k 1) (set! k 2)").unwrap_err();
+    assert!(err.contains("Cannot set! 'k'") && err.contains("immutable"), "{}", err);
+    assert!(run_code("(def k 1) (incf k)").unwrap_err().contains("immutable"));
+}
+
+#[test]
+fn test_set_unbound_is_error() {
+    assert!(run_code("(set! missing 1)").unwrap_err().contains("not bound"));
+}
+
+#[test]
+fn test_set_parameter_or_capture_is_error() {
+    assert!(run_code("(defun f (a) (set! a 1))").unwrap_err().contains("parameter"));
+    assert!(run_code("(let ((x 1)) (lambda () (set! x 2)))").unwrap_err().contains("captured"));
+}
+
+#[test]
+fn test_set_after_closure_captures_local_is_error() {
+    // The closure would hold a copy and go on returning 1
+    let err = run_code("(let ((x 1)) (let ((f (lambda () x))) (set! x 5) (f)))").unwrap_err();
+    assert!(err.contains("Cannot set! 'x' - a closure has captured it"), "unexpected error: {}", err);
+    let err = run_code("(let ((x 1)) (let ((f (match-lambda (_ x)))) (incf x) (f 0)))").unwrap_err();
+    assert!(err.contains("Cannot set! 'x'"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_capturing_an_assigned_local_is_error() {
+    // In a loop the set! would run again after the capture
+    let err = run_code("(let ((x 1)) (set! x 5) (lambda () x))").unwrap_err();
+    assert!(err.contains("Cannot capture 'x' in a closure - it is assigned with set!"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_set_and_capture_of_separate_bindings() {
+    // A later binding reusing the slot, or the name, is a different variable
+    let source = "(do (let ((x 1)) ((lambda () x)))
+                      (let ((x 2)) (set! x 3) x))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(3));
+    let source = "(let ((x 1))
+                    (let ((f (lambda () x)))
+                      (let ((x 10)) (set! x 20) (+ x (f)))))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(21));
+    // set! on one local and capture of another is fine
+    let source = "(let ((x 1) (y 2)) (set! x 5) ((lambda () y)))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(2));
+    // As is set! inside a closure on its own local
+    let source = "(let ((x 1)) ((lambda () (let ((y x)) (set! y 7) y))))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_set_bad_forms() {
+    assert!(run_code("(let ((x 1)) (set! x))").unwrap_err().contains("set! expects"));
+    assert!(run_code("(set! 1 2)").unwrap_err().contains("variable name"));
+    assert!(run_code("(let ((x 1)) (incf x 1 2))").unwrap_err().contains("incf expects"));
+    assert!(run_code("(def-mutable a)").unwrap_err().contains("(def-mutable name value)"));
+}
+
+#[test]
+fn test_def_mutable_cannot_be_redefined() {
+    assert!(run_code("(def-mutable a 1) (def-mutable a 2)").unwrap_err().contains("Cannot redefine"));
+}
@@ -0,0 +1,82 @@
+// Tests for the native sort builtin (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::{run_code, ints};
+
+#[test]
+fn test_sort_ascending_and_descending() {
+    assert_eq!(run_code("(sort '(3 1 2) <)").unwrap(), ints(&[1, 2, 3]));
+    assert_eq!(run_code("(sort '(3 1 2) >)").unwrap(), ints(&[3, 2, 1]));
+}
+
+#[test]
+fn test_sort_default_numeric_order() {
+    assert_eq!(run_code("(sort '(3 1 2))").unwrap(), ints(&[1, 2, 3]));
+    assert_eq!(
+        run_code("(sort '(3 1.5 2) '())").unwrap(),
+        Value::list_from_vec(vec![Value::Float(1.5), Value::Integer(2), Value::Integer(3)])
+    );
+}
+
+#[test]
+fn test_sort_empty_and_singleton() {
+    assert_eq!(run_code("(sort '() <)").unwrap(), ints(&[]));
+    assert_eq!(run_code("(sort '(7))").unwrap(), ints(&[7]));
+}
+
+#[test]
+fn test_sort_is_stable() {
+    let source = "(map (lambda (p) (car (cdr p))) (sort '((2 1) (1 2) (2 3) (1 4)) (lambda (a b) (< (car a) (car b)))))";
+    assert_eq!(run_code(source).unwrap(), ints(&[2, 4, 1, 3]));
+}
+
+#[test]
+fn test_sort_closure_comparator() {
+    let source = r#"
+        (defun sort-around (pivot xs)
+          (let ((dist (lambda (n) (if (< n pivot) (- pivot n) (- n pivot)))))
+            (sort xs (lambda (a b) (< (dist a) (dist b))))))
+        (sort-around 10 '(1 12 7 10 20))
+    "#;
+    assert_eq!(run_code(source).unwrap(), ints(&[10, 12, 7, 1, 20]));
+}
+
+#[test]
+fn test_sort_leaves_input_untouched() {
+    let source = "(let ((xs '(3 1 2))) (list (sort xs) xs))";
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::list_from_vec(vec![ints(&[1, 2, 3]), ints(&[3, 1, 2])])
+    );
+}
+
+#[test]
+fn test_sort_through_apply() {
+    assert_eq!(run_code("(apply sort (list '(2 3 1) >))").unwrap(), ints(&[3, 2, 1]));
+}
+
+#[test]
+fn test_sort_non_list_is_type_error() {
+    let err = run_code("(sort 5 <)").unwrap_err();
+    assert!(err.contains("'sort' expects a list"), "{}", err);
+}
+
+#[test]
+fn test_sort_comparator_must_return_boolean() {
+    let err = run_code("(sort '(2 1) (lambda (a b) 1))").unwrap_err();
+    assert!(err.contains("comparator must return a boolean"), "{}", err);
+}
+
+#[test]
+fn test_sort_default_order_needs_numbers() {
+    let err = run_code("(sort '(\"b\" \"a\"))").unwrap_err();
+    assert!(err.contains("sort"), "{}", err);
+}
+
+#[test]
+fn test_sort_arity_is_compile_error() {
+    assert!(run_code("(sort)").is_err());
+    assert!(run_code("(sort '(1) < >)").is_err());
+}
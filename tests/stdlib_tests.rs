@@ -429,6 +429,25 @@ fn test_null_predicate() {
     assert_eq!(get_bool_result(&vm), false);
 }
 
+#[test]
+fn test_null_predicate_non_lists() {
+    // null? never errors: anything that isn't the empty list is not null
+    for source in ["(null? false)", "(null? 0)", "(null? \"\")", "(null? (vector))", "(null? 'a)"] {
+        let vm = compile_and_run(source);
+        assert!(!get_bool_result(&vm), "Failed for: {}", source);
+    }
+
+    // Also when called through the builtin function value
+    let vm = compile_and_run("(list (apply null? '(())) (apply null? '(false)))");
+    assert_eq!(get_list_result(&vm), vec![Value::Boolean(true), Value::Boolean(false)]);
+}
+
+#[test]
+fn test_list_predicate_empty_list() {
+    let vm = compile_and_run("(list (list? '()) (list? false) (null? (cdr '(1))))");
+    assert_eq!(get_list_result(&vm), vec![Value::Boolean(true), Value::Boolean(false), Value::Boolean(true)]);
+}
+
 #[test]
 fn test_list_length_with_filter() {
     let source = r#"
@@ -455,3 +474,57 @@ fn test_list_length_with_filter() {
     let vm = compile_and_run(source);
     assert_eq!(get_int_result(&vm), 5); // 5 even numbers in range 1-10
 }
+
+// ============================================================================
+// Multi-List map / for-each Tests (stdlib.lisp)
+// ============================================================================
+
+#[test]
+fn test_map_single_list_from_stdlib() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (map (lambda (x) (* x 2)) '(1 2 3))
+    "#;
+    let vm = compile_and_run(source);
+    assert_eq!(get_list_result(&vm), vec![Value::Integer(2), Value::Integer(4), Value::Integer(6)]);
+}
+
+#[test]
+fn test_map_two_lists_stops_at_shortest() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (map + '(1 2 3) '(10 20))
+    "#;
+    let vm = compile_and_run(source);
+    assert_eq!(get_list_result(&vm), vec![Value::Integer(11), Value::Integer(22)]);
+}
+
+#[test]
+fn test_map_three_lists() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (map (lambda (a b c) (+ a (* b c))) '(1 2) '(3 4) '(5 6))
+    "#;
+    let vm = compile_and_run(source);
+    assert_eq!(get_list_result(&vm), vec![Value::Integer(16), Value::Integer(26)]);
+}
+
+#[test]
+fn test_map_multiple_lists_with_empty_list() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (map + '() '(1 2))
+    "#;
+    let vm = compile_and_run(source);
+    assert!(get_list_result(&vm).is_empty());
+}
+
+#[test]
+fn test_for_each_multiple_lists() {
+    let source = r#"
+        (load "stdlib.lisp")
+        (for-each (lambda (a b) (+ a b)) '(1 2 3) '(4 5))
+    "#;
+    let vm = compile_and_run(source);
+    assert!(get_list_result(&vm).is_empty());
+}
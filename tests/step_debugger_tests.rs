@@ -0,0 +1,116 @@
+// Tests for single-stepping the VM and pausing at breakpoints (no stdlib loaded)
+
+use lisp_bytecode_vm::*;
+
+fn load(source: &str) -> VM {
+    let exprs = parser::Parser::new(source).parse_all().unwrap();
+    let (functions, main_bytecode) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main_bytecode;
+    vm
+}
+
+#[test]
+fn test_stepping_through_addition() {
+    let mut vm = load("(+ 1 2)");
+
+    let mut steps = Vec::new();
+    while !vm.halted {
+        steps.push(vm.step().unwrap());
+    }
+
+    let instructions: Vec<_> = steps.iter().map(|s| s.instruction.clone()).collect();
+    assert_eq!(instructions, vec![
+        Instruction::Push(Value::Integer(1)),
+        Instruction::Push(Value::Integer(2)),
+        Instruction::Add,
+        Instruction::Halt,
+    ]);
+    assert_eq!(steps.iter().map(|s| s.ip).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    assert_eq!(steps[1].stack_top, vec![Value::Integer(1), Value::Integer(2)]);
+    assert_eq!(steps[2].stack_top, vec![Value::Integer(3)]);
+    assert_eq!(steps[2].instruction_pointer, 3);
+    assert!(steps.iter().all(|s| s.function == "<main>" && s.depth == 0));
+}
+
+#[test]
+fn test_step_after_halt_is_an_error() {
+    let mut vm = load("1");
+    while !vm.halted {
+        vm.step().unwrap();
+    }
+    assert!(vm.step().unwrap_err().message.contains("halted"));
+}
+
+#[test]
+fn test_stack_top_shows_at_most_three_values() {
+    let mut vm = load("(list 1 2 3 4)");
+    let mut widest = 0;
+    while !vm.halted {
+        widest = widest.max(vm.step().unwrap().stack_top.len());
+    }
+    assert_eq!(widest, 3);
+}
+
+#[test]
+fn test_run_with_hook_sees_every_instruction_and_finishes() {
+    let mut vm = load("(defun square (x) (* x x)) (square 4)");
+    let mut seen = Vec::new();
+
+    let stop = vm.run_with_hook(|step| seen.push((step.function.clone(), step.instruction.clone()))).unwrap();
+
+    assert_eq!(stop, StopReason::Halted);
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(16)));
+    assert!(seen.contains(&("square".to_string(), Instruction::Mul)));
+    assert_eq!(seen.last().unwrap().1, Instruction::Halt);
+}
+
+#[test]
+fn test_breakpoint_pauses_before_entering_and_resumes() {
+    let mut vm = load("(defun square (x) (* x x)) (+ (square 3) (square 4))");
+    vm.set_breakpoint("square");
+
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Breakpoint("square".to_string()));
+    // Paused on the call itself, with its argument ready
+    assert!(matches!(&vm.current_bytecode[vm.instruction_pointer], Instruction::Call(name, 1) if name == "square"));
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(3)));
+
+    // Stepping enters the function
+    vm.step().unwrap();
+    assert_eq!(vm.step().unwrap().function, "square");
+
+    // The second call pauses again, then the program finishes
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Breakpoint("square".to_string()));
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(4)));
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Halted);
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(25)));
+}
+
+#[test]
+fn test_breakpoint_on_function_value_call() {
+    let mut vm = load("(defun twice (x) (* 2 x)) (let ((f twice)) (f 5))");
+    vm.set_breakpoint("twice");
+
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Breakpoint("twice".to_string()));
+    assert!(vm.clear_breakpoint("twice"));
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Halted);
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(10)));
+}
+
+#[test]
+fn test_stepping_reports_errors_and_honours_try() {
+    let mut vm = load("(car 5)");
+    let err = loop {
+        match vm.step() {
+            Ok(_) => continue,
+            Err(err) => break err,
+        }
+    };
+    assert!(err.message.contains("car"), "{}", err.message);
+
+    let mut vm = load("(try (car 5) (catch e 42))");
+    assert_eq!(vm.run_with_hook(|_| {}).unwrap(), StopReason::Halted);
+    assert_eq!(vm.value_stack.last(), Some(&Value::Integer(42)));
+}
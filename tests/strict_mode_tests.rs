@@ -0,0 +1,117 @@
+use lisp_bytecode_vm::{Compiler, VM, Value, parser::Parser, vm::builtins::BUILTIN_NAMES, vm::errors::CompileError};
+
+/// Compile with strict mode toggled on both the compiler and the VM
+fn compile(source: &str, strict: bool) -> Result<VM, CompileError> {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    compiler.set_strict_mode(strict);
+    let (functions, main) = compiler.compile_program(&exprs)?;
+
+    let mut vm = VM::new();
+    vm.strict_mode = strict;
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+    Ok(vm)
+}
+
+fn run(source: &str, strict: bool) -> Result<Value, String> {
+    let mut vm = compile(source, strict).map_err(|e| e.message)?;
+    vm.run_to_value().map_err(|e| e.message)
+}
+
+// ==================== Unknown functions ====================
+
+#[test]
+fn test_unknown_function_is_runtime_error_by_default() {
+    assert!(compile("(frobnicate 1)", false).is_ok());
+    let err = run("(frobnicate 1)", false).unwrap_err();
+    assert!(err.contains("Undefined function 'frobnicate'"), "got: {}", err);
+}
+
+#[test]
+fn test_unknown_function_is_compile_error_in_strict_mode() {
+    let err = compile("(def x 1)\n(frobnicate x)", true).err().unwrap();
+    assert_eq!(err.message, "Unknown function 'frobnicate' (strict mode)");
+    assert_eq!(err.location.line, 2);
+}
+
+#[test]
+fn test_strict_mode_suggests_similar_function() {
+    let err = compile("(defun square (x) (* x x)) (sqare 3)", true).err().unwrap();
+    assert!(err.suggestion.unwrap().contains("'square'"));
+}
+
+#[test]
+fn test_strict_mode_unknown_call_inside_function_body() {
+    let err = compile("(defun f (x) (helper x))", true).err().unwrap();
+    assert!(err.message.contains("'helper'"));
+}
+
+#[test]
+fn test_strict_mode_allows_forward_references_and_builtins() {
+    let source = r#"
+        (defun even-len (xs) (if (null? xs) true (odd-len (cdr xs))))
+        (defun odd-len (xs) (if (null? xs) false (even-len (cdr xs))))
+        (list (even-len (list 1 2)) (string-length "abc") (int->float 2))
+    "#;
+    assert_eq!(run(source, true).unwrap(), run(source, false).unwrap());
+}
+
+#[test]
+fn test_builtin_names_list_every_vm_function() {
+    let vm = VM::new();
+    let mut registered: Vec<&str> = vm.functions.keys().map(String::as_str).collect();
+    registered.sort();
+    let mut listed = BUILTIN_NAMES.to_vec();
+    listed.sort();
+    assert_eq!(registered, listed);
+}
+
+#[test]
+fn test_strict_mode_knows_builtins_the_compiler_does_not_inline() {
+    assert_eq!(run("(sqrt 16.0)", true).unwrap(), Value::Float(4.0));
+    assert_eq!(run("(map abs (list -1 2))", true).unwrap(), run("(list 1 2)", true).unwrap());
+}
+
+#[test]
+fn test_strict_mode_allows_calling_closures() {
+    let source = "(defun apply-twice (f x) (f (f x))) (apply-twice (lambda (n) (+ n 1)) 5)";
+    assert_eq!(run(source, true).unwrap(), Value::Integer(7));
+}
+
+// ==================== Mixed arithmetic ====================
+
+#[test]
+fn test_mixed_arithmetic_promotes_by_default() {
+    assert_eq!(run("(+ 1 2.5)", false).unwrap(), Value::Float(3.5));
+}
+
+#[test]
+fn test_mixed_arithmetic_rejected_in_strict_mode() {
+    for (source, op) in [("(+ 1 2.5)", "+"), ("(- 2.5 1)", "-"), ("(* 2 0.5)", "*"), ("(/ 1.0 2)", "/"), ("(% 7 2.0)", "%")] {
+        let err = run(source, true).unwrap_err();
+        assert!(err.contains(&format!("'{}' cannot mix", op)), "{}: {}", source, err);
+    }
+}
+
+#[test]
+fn test_strict_mode_explicit_conversion() {
+    assert_eq!(run("(+ (int->float 1) 2.5)", true).unwrap(), Value::Float(3.5));
+    assert_eq!(run("(+ 1 (float->int 2.5))", true).unwrap(), Value::Integer(3));
+    assert_eq!(run("(* 2 3)", true).unwrap(), Value::Integer(6));
+}
+
+#[test]
+fn test_strict_mode_keeps_mixed_comparisons() {
+    assert_eq!(run("(< 1 2.5)", true).unwrap(), Value::Boolean(true));
+}
+
+// ==================== Conditionals ====================
+
+#[test]
+fn test_conditionals_require_booleans_in_both_modes() {
+    for strict in [false, true] {
+        let err = run("(if 1 2 3)", strict).unwrap_err();
+        assert!(err.contains("conditional expects boolean"), "got: {}", err);
+    }
+}
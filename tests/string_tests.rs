@@ -0,0 +1,250 @@
+// Tests for string builtins that go beyond the basic operations
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn string(s: &str) -> Value {
+    Value::string(s)
+}
+
+// ============================================================
+// Char Iteration Tests
+// ============================================================
+
+#[test]
+fn test_string_map_with_closure() {
+    let result = run_code(r#"(string-map (lambda (c) (if (== c "a") "A" c)) "banana")"#).unwrap();
+    assert_eq!(result, string("bAnAnA"));
+}
+
+#[test]
+fn test_string_map_with_named_function() {
+    assert_eq!(run_code(r#"(string-map string-upcase "abc")"#).unwrap(), string("ABC"));
+}
+
+#[test]
+fn test_string_map_can_expand_or_drop_chars() {
+    let result = run_code(r#"(string-map (lambda (c) (if (== c "-") "" (string-append c c))) "a-b")"#).unwrap();
+    assert_eq!(result, string("aabb"));
+}
+
+#[test]
+fn test_string_map_passes_one_char_strings() {
+    let result = run_code(r#"(string-map (lambda (c) (string-append "[" c "]")) "héllo")"#).unwrap();
+    assert_eq!(result, string("[h][é][l][l][o]"));
+}
+
+#[test]
+fn test_string_map_requires_string_results() {
+    let err = run_code(r#"(string-map (lambda (c) 1) "ab")"#).unwrap_err();
+    assert!(err.contains("'string-map' function must return a string, got integer"), "got: {}", err);
+}
+
+#[test]
+fn test_string_filter() {
+    let result = run_code(r#"
+        (defun vowel? (c) (string-contains? "aeiou" c))
+        (string-filter (lambda (c) (if (vowel? c) false true)) "education")
+    "#).unwrap();
+    assert_eq!(result, string("dctn"));
+}
+
+#[test]
+fn test_string_filter_empty_string() {
+    assert_eq!(run_code(r#"(string-filter (lambda (c) true) "")"#).unwrap(), string(""));
+}
+
+#[test]
+fn test_string_filter_requires_boolean_results() {
+    let err = run_code(r#"(string-filter (lambda (c) c) "ab")"#).unwrap_err();
+    assert!(err.contains("'string-filter' predicate must return a boolean, got string"), "got: {}", err);
+}
+
+#[test]
+fn test_for_each_char_returns_empty_list() {
+    let result = run_code(r#"(for-each-char (lambda (c) (print c)) "ab")"#).unwrap();
+    assert_eq!(result, Value::List(List::Nil));
+}
+
+#[test]
+fn test_char_callbacks_inside_functions() {
+    // The callback runs nested inside the calling function's frame
+    let result = run_code(r#"
+        (defun shout (s) (string-append (string-map string-upcase s) "!"))
+        (defun shout-all (a b) (string-append (shout a) " " (shout b)))
+        (shout-all "hi" "there")
+    "#).unwrap();
+    assert_eq!(result, string("HI! THERE!"));
+}
+
+#[test]
+fn test_char_callback_errors_propagate() {
+    let err = run_code(r#"(for-each-char (lambda (c) (+ c 1)) "a")"#).unwrap_err();
+    assert!(err.contains("'+' expects two numbers"), "got: {}", err);
+}
+
+#[test]
+fn test_char_iteration_argument_types() {
+    let err = run_code(r#"(string-map "abc" string-upcase)"#).unwrap_err();
+    assert!(err.contains("'string-map' expects a function and a string, got string and function"), "got: {}", err);
+}
+
+// ============================================================
+// string->list / list->string Round Trip
+// ============================================================
+
+// Each entry stresses a different way a "character" can span more than one
+// byte or more than one code point
+const TRICKY_STRINGS: &[&str] = &[
+    "",
+    "a",
+    "plain ascii",
+    "é",                       // precomposed
+    "e\u{301}",                // e + combining acute accent
+    "Z\u{351}\u{36b}\u{343}",  // stacked combining marks
+    "日本語",
+    "שלום עולם",               // right-to-left
+    "👍🏽",                     // emoji + skin tone modifier
+    "👩\u{200d}👩\u{200d}👧",  // zero-width-joiner family
+    "🇧🇷🇯🇵",                     // regional indicator flags
+    "𝔘𝔫𝔦𝔠𝔬𝔡𝔢",                 // astral plane letters
+    "\u{10ffff}\u{0}",         // highest scalar value and NUL
+    "\u{feff}bom",
+    "tab\tnew\nline\r\n",
+    "quote\" back\\slash",
+];
+
+fn round_trip(vm: &mut VM, text: &str) -> Value {
+    let chars = vm.call_function("string->list", vec![string(text)]).unwrap();
+    vm.call_function("list->string", vec![chars]).unwrap()
+}
+
+#[test]
+fn test_string_list_round_trip_is_exact() {
+    let mut vm = VM::new();
+    for text in TRICKY_STRINGS {
+        assert_eq!(round_trip(&mut vm, text), string(text), "round trip changed {:?}", text);
+        // Applying it again changes nothing either
+        let once = round_trip(&mut vm, text);
+        if let Value::String(s) = once {
+            assert_eq!(round_trip(&mut vm, &s), string(text));
+        }
+    }
+}
+
+#[test]
+fn test_string_to_list_yields_one_element_per_char() {
+    let mut vm = VM::new();
+    for text in TRICKY_STRINGS {
+        let expected: Vec<Value> = text.chars().map(|c| string(&c.to_string())).collect();
+        let chars = vm.call_function("string->list", vec![string(text)]).unwrap();
+        assert_eq!(chars, Value::List(List::from_vec(expected)), "split of {:?}", text);
+    }
+}
+
+#[test]
+fn test_string_list_round_trip_from_source() {
+    // The lexer has no escape sequences, so only strings without quotes or
+    // backslashes can be written as literals
+    for text in TRICKY_STRINGS.iter().filter(|s| !s.contains('"') && !s.contains('\\')) {
+        let source = format!("(list->string (string->list \"{}\"))", text);
+        assert_eq!(run_code(&source).unwrap(), string(text), "round trip changed {:?}", text);
+    }
+}
+
+// ============================================================
+// String Search Tests
+// ============================================================
+
+#[test]
+fn test_string_index_of() {
+    assert_eq!(run_code(r#"(string-index-of "hello" "ll")"#).unwrap(), Value::Integer(2));
+    assert_eq!(run_code(r#"(string-index-of "hello" "h")"#).unwrap(), Value::Integer(0));
+    assert_eq!(run_code(r#"(string-index-of "hello" "xyz")"#).unwrap(), Value::Integer(-1));
+    // First match wins; the empty string is found at the start
+    assert_eq!(run_code(r#"(string-index-of "abcabc" "c")"#).unwrap(), Value::Integer(2));
+    assert_eq!(run_code(r#"(string-index-of "hello" "")"#).unwrap(), Value::Integer(0));
+}
+
+#[test]
+fn test_string_index_of_counts_chars_not_bytes() {
+    assert_eq!(run_code(r#"(string-index-of "héllo wörld" "wö")"#).unwrap(), Value::Integer(6));
+    // The index is usable with substring directly
+    let source = r#"
+        (let ((s "naïve café") (i (string-index-of "naïve café" "café")))
+          (substring s i (string-length s)))
+    "#;
+    assert_eq!(run_code(source).unwrap(), string("café"));
+}
+
+#[test]
+fn test_string_contains() {
+    assert_eq!(run_code(r#"(string-contains? "hello" "ell")"#).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(r#"(string-contains? "hello" "elk")"#).unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_string_prefix_and_suffix() {
+    // Same (string, affix) argument order as string-starts-with?/string-ends-with?
+    assert_eq!(run_code(r#"(string-prefix? "hello" "he")"#).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(r#"(string-prefix? "hello" "lo")"#).unwrap(), Value::Boolean(false));
+    assert_eq!(run_code(r#"(string-suffix? "hello" "lo")"#).unwrap(), Value::Boolean(true));
+    assert_eq!(run_code(r#"(string-suffix? "hello" "he")"#).unwrap(), Value::Boolean(false));
+    assert_eq!(run_code(r#"(string-prefix? "hi" "high")"#).unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn test_string_search_as_values() {
+    let source = r#"(list (apply string-index-of (list "hello" "lo")) (apply string-suffix? (list "a.txt" ".txt")))"#;
+    assert_eq!(
+        run_code(source).unwrap(),
+        Value::List(List::from_vec(vec![Value::Integer(3), Value::Boolean(true)]))
+    );
+}
+
+#[test]
+fn test_string_search_errors() {
+    assert_eq!(
+        run_code(r#"(string-index-of "hello" 1)"#).unwrap_err(),
+        "Type error: 'string-index-of' expects two strings, got string and integer"
+    );
+    assert!(run_code(r#"(string-prefix? "hello")"#).unwrap_err()
+        .contains("string-prefix? expects exactly 2 arguments"));
+}
+
+// ============================================================
+// string-ref Tests
+// ============================================================
+
+#[test]
+fn test_string_ref() {
+    assert_eq!(run_code(r#"(string-ref "hello" 0)"#).unwrap(), string("h"));
+    assert_eq!(run_code(r#"(string-ref "hello" 4)"#).unwrap(), string("o"));
+}
+
+#[test]
+fn test_string_ref_indexes_by_char() {
+    assert_eq!(run_code(r#"(string-ref "héllo→" 1)"#).unwrap(), string("é"));
+    assert_eq!(run_code(r#"(string-ref "héllo→" 5)"#).unwrap(), string("→"));
+    // string-length and substring agree with string-ref on char positions
+    assert_eq!(run_code(r#"(string-length "héllo→")"#).unwrap(), Value::Integer(6));
+    assert_eq!(run_code(r#"(substring "héllo→" 4 6)"#).unwrap(), string("o→"));
+}
+
+#[test]
+fn test_string_ref_as_value() {
+    let result = run_code(r#"(map (lambda (i) (string-ref "abc" i)) '(2 0))"#).unwrap();
+    assert_eq!(result, Value::list_from_vec(vec![string("c"), string("a")]));
+}
+
+#[test]
+fn test_string_ref_errors() {
+    let err = run_code(r#"(string-ref "héllo" 5)"#).unwrap_err();
+    assert!(err.contains("index 5 out of bounds for string of length 5"), "{}", err);
+    let err = run_code(r#"(string-ref "abc" -1)"#).unwrap_err();
+    assert!(err.contains("index -1 out of bounds"), "{}", err);
+    let err = run_code(r#"(string-ref 'abc 0)"#).unwrap_err();
+    assert!(err.contains("'string-ref' expects a string and an integer"), "{}", err);
+}
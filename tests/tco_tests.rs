@@ -275,3 +275,272 @@ fn test_multiple_tail_recursive_functions() {
         _ => panic!("Expected integer result"),
     }
 }
+
+// ============================================================
+// Tail Position in when/unless/and/or
+// ============================================================
+
+/// Helper to run source one instruction at a time, returning the VM and
+/// the deepest call stack seen along the way
+fn run_tracking_depth(source: &str) -> (VM, usize) {
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let mut compiler = Compiler::new();
+    let (functions, main) = compiler.compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main;
+
+    let mut max_depth = 0;
+    while !vm.halted {
+        vm.execute_one_instruction().unwrap();
+        max_depth = max_depth.max(vm.call_stack.len());
+    }
+    (vm, max_depth)
+}
+
+/// Helper to check that a function never calls itself with a plain Call
+fn self_calls_are_tail_calls(vm: &VM, function_name: &str) -> bool {
+    vm.functions[function_name].iter().all(|instr| match instr {
+        Instruction::Call(name, _) => name != function_name,
+        _ => true,
+    })
+}
+
+#[test]
+fn test_tail_call_inside_when() {
+    let source = r#"
+        (defun drain (n)
+          (when (> n 0)
+            (drain (- n 1))))
+        (drain 100000)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert!(function_uses_tailcall(&vm, "drain"));
+    assert!(self_calls_are_tail_calls(&vm, "drain"));
+    assert_eq!(max_depth, 1, "recursion inside when should not grow the call stack");
+    // The final, not-taken when yields false
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Boolean(false)));
+}
+
+#[test]
+fn test_tail_call_inside_unless() {
+    let source = r#"
+        (defun drain (n)
+          (unless (<= n 0)
+            (drain (- n 1))))
+        (drain 100000)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert!(self_calls_are_tail_calls(&vm, "drain"));
+    assert_eq!(max_depth, 1, "recursion inside unless should not grow the call stack");
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Boolean(false)));
+}
+
+#[test]
+fn test_when_false_branch_result_in_tail_position() {
+    // The not-taken branch's false is the function's return value
+    let source = r#"
+        (defun step (n)
+          (when (> n 0)
+            (step (- n 1))))
+        (if (step 3) 1 2)
+    "#;
+
+    let (vm, _) = run_tracking_depth(source);
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Integer(2)));
+}
+
+#[test]
+fn test_when_taken_branch_returns_call_result() {
+    let source = r#"
+        (defun find-zero (n)
+          (if (== n 0)
+            'found
+            (when (> n 0)
+              (find-zero (- n 1)))))
+        (find-zero 50000)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert_eq!(max_depth, 1);
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::symbol("found")));
+}
+
+#[test]
+fn test_tail_call_inside_and() {
+    let source = r#"
+        (defun all-positive? (n)
+          (or (== n 0)
+              (and (> n 0) (all-positive? (- n 1)))))
+        (all-positive? 100000)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert!(self_calls_are_tail_calls(&vm, "all-positive?"));
+    assert_eq!(max_depth, 1, "recursion in the last operand of and/or should not grow the call stack");
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Boolean(true)));
+}
+
+#[test]
+fn test_tail_call_inside_or() {
+    let source = r#"
+        (defun never-reaches? (n)
+          (and (> n 0)
+               (or (== n -1) (never-reaches? (- n 1)))))
+        (never-reaches? 100000)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert!(self_calls_are_tail_calls(&vm, "never-reaches?"));
+    assert_eq!(max_depth, 1);
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Boolean(false)));
+}
+
+#[test]
+fn test_when_not_in_tail_position_uses_call() {
+    // (+ 1 (when ...)) must come back to add, so the inner call is not a tail call
+    let source = r#"
+        (defun depth (n)
+          (if (== n 0)
+            0
+            (+ 1 (when true (depth (- n 1))))))
+        (depth 10)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert!(!self_calls_are_tail_calls(&vm, "depth"));
+    assert_eq!(max_depth, 11);
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Integer(10)));
+}
+
+// ============================================================
+// Tail Calls Through Closures
+// ============================================================
+
+/// Helper to check that main and every function call closures in tail
+/// position with TailCallClosure
+fn closure_calls_in(bytecode: &[Instruction]) -> (usize, usize) {
+    let mut calls = 0;
+    let mut tail_calls = 0;
+    for instr in bytecode {
+        match instr {
+            Instruction::CallClosure(_) => calls += 1,
+            Instruction::TailCallClosure(_) => tail_calls += 1,
+            Instruction::MakeClosure(_, body, _) | Instruction::MakeVariadicClosure(_, _, body, _) => {
+                let (c, t) = closure_calls_in(body);
+                calls += c;
+                tail_calls += t;
+            }
+            _ => {}
+        }
+    }
+    (calls, tail_calls)
+}
+
+#[test]
+fn test_mutual_recursion_through_closures_runs_in_constant_stack() {
+    // CPS-style even?/odd?: each closure is handed the other and calls it in tail position
+    let source = r#"
+        (defun parity (n)
+          (let ((is-even (lambda (n self other)
+                           (if (== n 0) true (other (- n 1) other self))))
+                (is-odd (lambda (n self other)
+                          (if (== n 0) false (other (- n 1) other self)))))
+            (is-even n is-even is-odd)))
+        (parity 100001)
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    let (calls, tail_calls) = closure_calls_in(&vm.functions["parity"]);
+    assert_eq!(calls, 0, "every closure call in parity is in tail position");
+    assert_eq!(tail_calls, 3);
+    assert_eq!(max_depth, 1, "closure tail calls should not grow the call stack");
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Boolean(false)));
+}
+
+#[test]
+fn test_continuation_passing_loop_runs_in_constant_stack() {
+    // Each step wraps the continuation in a new closure; unwinding the chain
+    // at the end is a run of closure tail calls
+    let source = r#"
+        (defun count-up (n k)
+          (if (== n 0)
+            (k 0)
+            (count-up (- n 1) (lambda (acc) (k (+ acc 1))))))
+        (count-up 10000 (lambda (acc) acc))
+    "#;
+
+    let (vm, max_depth) = run_tracking_depth(source);
+    assert_eq!(max_depth, 1);
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Integer(10000)));
+}
+
+#[test]
+fn test_computed_operator_in_tail_position() {
+    // ((f) x) in tail position: (f) itself is not a tail call, the outer call is
+    let source = r#"
+        (defun make-adder (n) (lambda (x) (+ x n)))
+        (defun add-ten (x) ((make-adder 10) x))
+        (add-ten 5)
+    "#;
+
+    let vm = compile_and_run(source);
+    let add_ten = &vm.functions["add-ten"];
+    assert!(add_ten.iter().any(|i| matches!(i, Instruction::Call(name, 1) if name == "make-adder")));
+    assert!(!function_uses_tailcall(&vm, "add-ten"));
+    assert_eq!(closure_calls_in(add_ten), (0, 1));
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Integer(15)));
+}
+
+#[test]
+fn test_closure_call_not_in_tail_position_uses_call_closure() {
+    let source = r#"
+        (defun twice (f x) (+ 1 (f (f x))))
+        (twice (lambda (y) (* y 2)) 3)
+    "#;
+
+    let vm = compile_and_run(source);
+    assert_eq!(closure_calls_in(&vm.functions["twice"]), (2, 0));
+    assert_eq!(vm.value_stack.last(), Some(&lisp_bytecode_vm::Value::Integer(13)));
+}
+
+#[test]
+fn test_operands_of_builtins_in_tail_position_are_not_tail_calls() {
+    // Each body ends in a builtin whose operands include a call: the call has
+    // to return to the builtin, so it must not replace the frame
+    let cases = [
+        ("(== (double x) 4)", "Boolean(true)"),
+        ("(< (double x) 5)", "Boolean(true)"),
+        ("(list (double x) 9)", "List"),
+        ("(vector (double x) 9)", "Vector"),
+        ("(hash-map \"k\" (double x))", "HashMap"),
+        ("(quasiquote (a (unquote (double x))))", "List"),
+    ];
+    for (body, expected) in cases {
+        let source = format!("(defun double (x) (* x 2)) (defun g (x) {}) (g 2)", body);
+        let vm = compile_and_run(&source);
+        assert!(!function_uses_tailcall(&vm, "g"), "{} should not tail call", body);
+        let top = format!("{:?}", vm.value_stack.last().unwrap());
+        assert!(top.starts_with(expected), "{} gave {}", body, top);
+    }
+}
+
+#[test]
+fn test_closure_calls_inside_list_in_tail_position() {
+    let source = r#"
+        (defun both (f a b) (list (f a) (f b)))
+        (both (lambda (n) (* n 10)) 1 2)
+    "#;
+    let vm = compile_and_run(source);
+    assert_eq!(closure_calls_in(&vm.functions["both"]), (2, 0));
+    assert_eq!(
+        vm.value_stack.last(),
+        Some(&lisp_bytecode_vm::Value::List(lisp_bytecode_vm::List::from_vec(vec![
+            lisp_bytecode_vm::Value::Integer(10),
+            lisp_bytecode_vm::Value::Integer(20),
+        ])))
+    );
+}
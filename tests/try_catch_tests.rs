@@ -0,0 +1,152 @@
+// Tests for try/catch recovery from runtime errors (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+
+fn run_code(source: &str) -> Result<Value, String> {
+    common::run_code_with(source, |vm| vm.verify_bytecode = true)
+}
+
+fn string(s: &str) -> Value {
+    Value::String(std::sync::Arc::new(s.to_string()))
+}
+
+#[test]
+fn test_try_catches_division_by_zero() {
+    assert_eq!(run_code("(try (/ 10 0) (catch e e))").unwrap(), string("Division by zero"));
+}
+
+#[test]
+fn test_try_catches_car_of_empty_list() {
+    let result = run_code("(try (car '()) (catch e (list 'caught e)))").unwrap();
+    let expected = run_code("(list 'caught \"'car' cannot take the first element of an empty list\")").unwrap();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_try_without_error_returns_value() {
+    assert_eq!(run_code("(+ 1 (try (* 2 3) (catch e 0)))").unwrap(), Value::Integer(7));
+}
+
+#[test]
+fn test_try_unwinds_nested_calls() {
+    let source = "(defun inner (x) (/ 100 x))
+                  (defun outer (x) (+ 1 (inner x)))
+                  (defun safe (x) (try (outer x) (catch e -1)))
+                  (list (safe 4) (safe 0))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(26 -1)").unwrap());
+}
+
+#[test]
+fn test_try_keeps_surrounding_locals() {
+    let source = "(let ((a 1) (b 2))
+                    (let ((c (try (car '()) (catch e 10))))
+                      (+ a b c)))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(13));
+}
+
+#[test]
+fn test_try_handler_sequence_and_closure_capture() {
+    let source = "(let ((get-message (try (/ 1 0) (catch e 'ignored (lambda () e)))))
+                    (get-message))";
+    assert_eq!(run_code(source).unwrap(), string("Division by zero"));
+}
+
+#[test]
+fn test_nested_try_inner_handles_first() {
+    let source = "(try (+ 1 (try (/ 1 0) (catch e 5))) (catch e 100))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(6));
+}
+
+#[test]
+fn test_error_in_handler_reaches_outer_try() {
+    let source = "(try (try (/ 1 0) (catch e (car '()))) (catch e 'outer))";
+    assert_eq!(run_code(source).unwrap(), run_code("'outer").unwrap());
+}
+
+#[test]
+fn test_try_inside_callback() {
+    let source = "(map (lambda (x) (try (/ 12 x) (catch e 0))) '(1 0 3))";
+    assert_eq!(run_code(source).unwrap(), run_code("'(12 0 4)").unwrap());
+}
+
+#[test]
+fn test_try_around_callback_error() {
+    let source = "(try (map (lambda (x) (/ 12 x)) '(1 0 3)) (catch e 'failed))";
+    assert_eq!(run_code(source).unwrap(), run_code("'failed").unwrap());
+}
+
+#[test]
+fn test_try_in_loop_recovers_each_iteration() {
+    let source = "(loop ((i 0) (acc 0))
+                    (if (== i 4)
+                        acc
+                        (recur (+ i 1) (+ acc (try (/ 12 (- i 2)) (catch e 100))))))";
+    // 12/-2 + 12/-1 + 100 + 12/1
+    assert_eq!(run_code(source).unwrap(), Value::Integer(94));
+}
+
+#[test]
+fn test_uncaught_error_unchanged() {
+    assert_eq!(run_code("(/ 1 0)").unwrap_err(), "Division by zero");
+    assert_eq!(run_code("(try (/ 1 0) (catch e (car '())))").unwrap_err(),
+               run_code("(car '())").unwrap_err());
+}
+
+#[test]
+fn test_vm_reusable_after_caught_error() {
+    let mut parser = parser::Parser::new("(defun f (x) (try (/ 1 x) (catch e -1)))");
+    let exprs = parser.parse_all().unwrap();
+    let (functions, main_bytecode) = Compiler::new().compile_program(&exprs).unwrap();
+
+    let mut vm = VM::new();
+    vm.functions.extend(functions);
+    vm.current_bytecode = main_bytecode;
+    vm.run().unwrap();
+
+    assert_eq!(vm.call_function("f", vec![Value::Integer(0)]).unwrap(), Value::Integer(-1));
+    assert_eq!(vm.call_function("f", vec![Value::Integer(1)]).unwrap(), Value::Integer(1));
+    assert!(vm.handlers.is_empty());
+}
+
+#[test]
+fn test_try_rejects_bad_forms() {
+    assert!(run_code("(try (/ 1 0))").unwrap_err().contains("try expects"));
+    assert!(run_code("(try (/ 1 0) (rescue e 0))").unwrap_err().contains("catch var handler"));
+    assert!(run_code("(try (/ 1 0) (catch 1 0))").unwrap_err().contains("catch variable"));
+    assert!(run_code("(try (/ 1 0) (catch e))").unwrap_err().contains("catch var handler"));
+}
+
+#[test]
+fn test_try_with_eval() {
+    let source = "(list (try (eval \"(car '())\") (catch e 1))
+                        (eval \"(try (car '()) (catch e 2))\")
+                        3)";
+    assert_eq!(run_code(source).unwrap(), run_code("'(1 2 3)").unwrap());
+}
+
+#[test]
+fn test_raise_caught_by_handler() {
+    assert_eq!(run_code("(try (raise \"boom\") (catch e e))").unwrap(), string("boom"));
+}
+
+#[test]
+fn test_raise_binds_the_value_itself() {
+    let source = "(defun check (n) (if (< n 0) (raise (list 'negative n)) n))
+                  (try (+ (check 1) (check -5)) (catch e (car (cdr e))))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(-5));
+}
+
+#[test]
+fn test_raise_as_function_value() {
+    let source = "(try (map raise '(7 8)) (catch e (+ e 1)))";
+    assert_eq!(run_code(source).unwrap(), Value::Integer(8));
+}
+
+#[test]
+fn test_uncaught_raise_reports_display_string() {
+    assert_eq!(run_code("(raise \"boom\")").unwrap_err(), "boom");
+    assert_eq!(run_code("(raise '(code 42))").unwrap_err(), "(code 42)");
+    assert!(run_code("(raise)").unwrap_err().contains("raise expects exactly 1 argument"));
+}
@@ -1,4 +1,4 @@
-use lisp_bytecode_vm::{Compiler, VM, parser::Parser, Value};
+use lisp_bytecode_vm::{Compiler, Instruction, VM, parser::Parser, Value};
 
 fn compile_and_run(source: &str) -> Result<String, String> {
     let mut parser = Parser::new(source);
@@ -25,6 +25,7 @@ fn compile_and_run(source: &str) -> Result<String, String> {
 fn format_value(value: &Value) -> String {
     match value {
         Value::Integer(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
         Value::Float(f) => {
             if f.fract() == 0.0 && !f.is_nan() && !f.is_infinite() {
                 format!("{:.1}", f)
@@ -277,3 +278,126 @@ fn test_variadic_zero_required_no_args() {
     let result = compile_and_run(source).unwrap();
     assert_eq!(result.trim(), "()");
 }
+
+// ==================== Variadic Builtin Tests ====================
+
+#[test]
+fn test_string_append_many_arguments() {
+    let source = r#"(string-append "a" "b" "c" "d")"#;
+    assert_eq!(compile_and_run(source).unwrap(), "\"abcd\"");
+}
+
+#[test]
+fn test_string_append_two_arguments() {
+    let source = r#"(defun greet (name) (string-append "hi " name)) (greet "bob")"#;
+    assert_eq!(compile_and_run(source).unwrap(), "\"hi bob\"");
+}
+
+#[test]
+fn test_string_append_zero_and_one_arguments() {
+    assert_eq!(compile_and_run("(string-append)").unwrap(), "\"\"");
+    assert_eq!(compile_and_run(r#"(string-append "a")"#).unwrap(), "\"a\"");
+    let err = compile_and_run("(string-append 1)").unwrap_err();
+    assert!(err.contains("'string-append' expects strings"), "got: {}", err);
+}
+
+#[test]
+fn test_string_append_as_a_value() {
+    assert_eq!(compile_and_run("(apply string-append (list))").unwrap(), "\"\"");
+    assert_eq!(compile_and_run(r#"(apply string-append (list "a"))"#).unwrap(), "\"a\"");
+    assert_eq!(compile_and_run(r#"(apply string-append (list "a" "b" "c"))"#).unwrap(), "\"abc\"");
+    let source = r#"(map string-append (list "a" "b") (list "x" "y") (list "1" "2"))"#;
+    assert_eq!(compile_and_run(source).unwrap(), r#"("ax1" "by2")"#);
+    let source = r#"(let ((parts (list "a" "b" "c"))) (string-append ...parts "d"))"#;
+    assert_eq!(compile_and_run(source).unwrap(), "\"abcd\"");
+}
+
+#[test]
+fn test_string_append_type_error_in_any_position() {
+    let err = compile_and_run(r#"(string-append "a" "b" 3)"#).unwrap_err();
+    assert!(err.contains("Runtime error"), "got: {}", err);
+}
+
+#[test]
+fn test_append_many_lists() {
+    let source = "(append (list 1) (list) (list 2 3) (list 4))";
+    assert_eq!(compile_and_run(source).unwrap(), "(1 2 3 4)");
+}
+
+#[test]
+fn test_append_zero_and_one_arguments() {
+    assert_eq!(compile_and_run("(append)").unwrap(), "()");
+    assert_eq!(compile_and_run("(append (list 1))").unwrap(), "(1)");
+    let err = compile_and_run("(append 1)").unwrap_err();
+    assert!(err.contains("'append' expects lists"), "got: {}", err);
+}
+
+#[test]
+fn test_append_as_a_value() {
+    assert_eq!(compile_and_run("(apply append (list))").unwrap(), "()");
+    assert_eq!(compile_and_run("(apply append (list (list 1)))").unwrap(), "(1)");
+    assert_eq!(compile_and_run("(apply append (list (list 1) (list 2) (list 3)))").unwrap(), "(1 2 3)");
+    // The last list is shared as the tail, dotted or not
+    assert_eq!(compile_and_run("(cdr (cdr (cdr (apply append (list (list 1) (list 2) (cons 3 4))))))").unwrap(), "4");
+    let source = "(map append (list (list 1)) (list (list 2)) (list (list 3)))";
+    assert_eq!(compile_and_run(source).unwrap(), "((1 2 3))");
+}
+
+// ============================================================================
+// Variadic print / display
+// ============================================================================
+
+#[test]
+fn test_print_line_layout() {
+    let values = vec![
+        Value::string("x ="),
+        Value::Integer(5),
+        Value::Symbol(std::sync::Arc::new("sym".to_string())),
+        Value::List(lisp_bytecode_vm::List::from_vec(vec![Value::string("a"), Value::Float(1.0)])),
+    ];
+    // print keeps strings quoted; display writes them as plain text
+    assert_eq!(VM::format_print_line(&values, false), r#""x =" 5 sym ("a" 1.0)"#);
+    assert_eq!(VM::format_print_line(&values, true), "x = 5 sym (a 1.0)");
+    assert_eq!(VM::format_print_line(&[], false), "");
+}
+
+#[test]
+fn test_print_and_display_return_last_argument() {
+    assert_eq!(compile_and_run(r#"(def x 5) (print "x =" x "y =" (+ x 1))"#).unwrap(), "6");
+    assert_eq!(compile_and_run(r#"(display "total:" (list 1 2))"#).unwrap(), "(1 2)");
+    assert_eq!(compile_and_run(r#"(display "done")"#).unwrap(), "\"done\"");
+    assert_eq!(compile_and_run("(print 7)").unwrap(), "7");
+}
+
+#[test]
+fn test_print_and_display_without_arguments() {
+    assert_eq!(compile_and_run("(print)").unwrap(), "()");
+    assert_eq!(compile_and_run("(display)").unwrap(), "()");
+}
+
+#[test]
+fn test_print_and_display_through_apply() {
+    assert_eq!(compile_and_run("(apply print (list 1 2 3))").unwrap(), "3");
+    assert_eq!(compile_and_run(r#"(apply display (list "a" "b"))"#).unwrap(), "\"b\"");
+}
+
+#[test]
+fn test_print_arguments_are_not_tail_calls() {
+    // In tail position, the call inside print must still return to print;
+    // a tail call would skip the write
+    let source = r#"
+        (defun double (x) (* x 2))
+        (defun show (x) (print "double:" (double x)))
+        (defun show-one (x) (print (double x)))
+        (list (show 4) (show-one 5))
+    "#;
+    assert_eq!(compile_and_run(source).unwrap(), "(8 10)");
+
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let program = Compiler::new().compile_to_program(&exprs).unwrap();
+    for name in ["show", "show-one"] {
+        let calls_double = |i: &Instruction| matches!(i, Instruction::Call(f, 1) if f == "double");
+        assert!(program.functions[name].iter().any(calls_double), "{} should call double normally", name);
+        assert!(!program.functions[name].iter().any(|i| matches!(i, Instruction::TailCall(..))));
+    }
+}
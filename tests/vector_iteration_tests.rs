@@ -0,0 +1,60 @@
+// Tests for vector-map and vector-for-each (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+fn int_vector(values: &[i64]) -> Value {
+    Value::Vector(values.iter().map(|&n| Value::Integer(n)).collect::<Vec<_>>().into())
+}
+
+#[test]
+fn test_vector_map_squares() {
+    let source = "(vector-map (lambda (x) (* x x)) (vector 1 2 3))";
+    assert_eq!(run_code(source).unwrap(), int_vector(&[1, 4, 9]));
+}
+
+#[test]
+fn test_vector_map_named_function_and_builtin() {
+    let source = "(defun double (x) (* 2 x))
+                  (list (vector-map double (vector 1 2 3)) (vector-map neg (vector 4 5)))";
+    let expected = Value::List(List::from_vec(vec![int_vector(&[2, 4, 6]), int_vector(&[-4, -5])]));
+    assert_eq!(run_code(source).unwrap(), expected);
+}
+
+#[test]
+fn test_vector_map_closure_captures_and_empty() {
+    let source = "(let ((offset 10)) (vector-map (lambda (x) (+ x offset)) (vector 1 2)))";
+    assert_eq!(run_code(source).unwrap(), int_vector(&[11, 12]));
+    assert_eq!(run_code("(vector-map inc (vector))").unwrap(), int_vector(&[]));
+}
+
+#[test]
+fn test_vector_for_each_accumulates() {
+    let source = "(def-mutable seen (hash-map))
+                  (def-mutable total 0)
+                  (vector-for-each
+                    (lambda (word)
+                      (do (set! seen (hashmap-set seen word (string-length word)))
+                          (set! total (+ total (string-length word)))))
+                    (vector \"a\" \"bcd\" \"ef\"))
+                  (list total (hashmap-get seen \"bcd\"))";
+    assert_eq!(run_code(source).unwrap(), Value::List(List::from_vec(vec![Value::Integer(6), Value::Integer(3)])));
+}
+
+#[test]
+fn test_vector_for_each_returns_empty_list() {
+    assert_eq!(run_code("(vector-for-each inc (vector 1 2))").unwrap(), Value::List(List::Nil));
+}
+
+#[test]
+fn test_vector_iteration_type_errors() {
+    assert!(run_code("(vector-map inc '(1 2))").unwrap_err().contains("'vector-map' expects a function and a vector"));
+    assert!(run_code("(vector-for-each 1 (vector 1))").unwrap_err().contains("'vector-for-each' expects a function and a vector"));
+}
+
+#[test]
+fn test_vector_map_error_in_callback_propagates() {
+    assert_eq!(run_code("(vector-map (lambda (x) (/ 1 x)) (vector 1 0))").unwrap_err(), "Division by zero");
+}
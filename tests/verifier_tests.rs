@@ -0,0 +1,121 @@
+use lisp_bytecode_vm::{parser::Parser, Compiler, Instruction, Value, VM};
+use lisp_bytecode_vm::vm::verifier::{verify_function, verify_program, Arity};
+use std::collections::HashMap;
+
+fn verify_main(main: Vec<Instruction>) -> Result<(), String> {
+    verify_program(&HashMap::new(), &main, 0, &HashMap::new()).map_err(|e| e.message)
+}
+
+#[test]
+fn test_compiled_programs_pass_verification() {
+    let source = r#"
+        (defun fact (n) (if (<= n 1) 1 (* n (fact (- n 1)))))
+        (defun sum (first . rest) (+ first (length rest)))
+        (defun describe ((0) "zero") ((n) "other"))
+        (defun make-adder (n) (lambda (x) (+ x n)))
+        (defun window (w &key (h 1) d) (list w h d))
+        (let ((a 1) (b 2)) (+ a b))
+        (loop ((i 0) (acc '())) (if (< i 3) (recur (+ i 1) (cons i acc)) acc))
+        ((make-adder 1) (fact 5))
+    "#;
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let program = Compiler::new().compile_to_program(&exprs).unwrap();
+    assert_eq!(program.verify().map_err(|e| e.message), Ok(()));
+}
+
+#[test]
+fn test_program_records_arity_for_optional_and_rest_params() {
+    let source = r#"
+        (defun greet (name &optional (greeting "hi")) (string-append greeting name))
+        (defun tally (a &optional (b 0) . more) (+ a b (length more)))
+    "#;
+    let exprs = Parser::new(source).parse_all().unwrap();
+    let program = Compiler::new().compile_to_program(&exprs).unwrap();
+    assert_eq!(program.arities.get("greet"), Some(&2));
+    assert_eq!(program.arities.get("tally"), Some(&3));
+    assert_eq!(program.verify().map_err(|e| e.message), Ok(()));
+}
+
+#[test]
+fn test_jump_target_out_of_range() {
+    let err = verify_main(vec![
+        Instruction::Push(Value::Boolean(true)),
+        Instruction::JmpIfFalse(7),
+        Instruction::Halt,
+    ]).unwrap_err();
+    assert_eq!(err, "Bytecode verification failed in 'main' at 1: jump target 7 is out of range (3 instructions)");
+}
+
+#[test]
+fn test_load_arg_beyond_closure_arity() {
+    let closure_body = vec![Instruction::LoadArg(1), Instruction::Ret];
+    let err = verify_function(
+        "f",
+        &[Instruction::MakeClosure(vec!["x".to_string()], closure_body, 0), Instruction::Ret],
+        Arity::Exact(0),
+    ).unwrap_err();
+    assert_eq!(err.message, "Bytecode verification failed in 'f (closure at 0)' at 0: argument 1 is out of range (arity 1)");
+}
+
+#[test]
+fn test_load_arg_beyond_declared_params() {
+    let mut functions = HashMap::new();
+    functions.insert("g".to_string(), vec![Instruction::LoadArg(2), Instruction::Ret]);
+    let params = HashMap::from([("g".to_string(), 2)]);
+    let err = verify_program(&functions, &[Instruction::Halt], 0, &params).unwrap_err();
+    assert!(err.message.contains("in 'g' at 0: argument 2 is out of range (arity 2)"), "{}", err.message);
+
+    // Without a declared arity there is nothing to check LoadArg against
+    assert!(verify_program(&functions, &[Instruction::Halt], 0, &HashMap::new()).is_ok());
+}
+
+#[test]
+fn test_function_falling_off_the_end() {
+    // The false branch skips Ret and runs past the last instruction
+    let err = verify_function("h", &[
+        Instruction::LoadArg(0),
+        Instruction::JmpIfFalse(3),
+        Instruction::Ret,
+        Instruction::Push(Value::Integer(0)),
+    ], Arity::Exact(1)).unwrap_err();
+    assert_eq!(err.message, "Bytecode verification failed in 'h' at 3: execution can run past the last instruction; expected Ret");
+}
+
+#[test]
+fn test_insane_counts_rejected() {
+    let err = verify_main(vec![
+        Instruction::Push(Value::Integer(1)),
+        Instruction::PopN(5),
+        Instruction::Halt,
+    ]).unwrap_err();
+    assert!(err.contains("at 1: PopN(5) consumes more values"), "{}", err);
+
+    let err = verify_function("p", &[Instruction::CheckArity(3, 1), Instruction::Ret], Arity::Exact(2)).unwrap_err();
+    assert!(err.message.contains("CheckArity(3) exceeds the declared arity 2"), "{}", err.message);
+
+    let err = verify_main(vec![Instruction::Recur(1), Instruction::Halt]).unwrap_err();
+    assert!(err.contains("recur without a preceding BeginLoop"), "{}", err);
+}
+
+#[test]
+fn test_vm_verifies_before_running_when_enabled() {
+    let main = vec![
+        Instruction::Push(Value::Integer(1)),
+        Instruction::Jmp(10),
+        Instruction::Halt,
+    ];
+
+    let mut vm = VM::new();
+    vm.verify_bytecode = true;
+    vm.current_bytecode = main.clone();
+    let err = vm.run().unwrap_err();
+    assert!(err.message.starts_with("Bytecode verification failed in 'main' at 1"), "{}", err.message);
+    // Nothing ran
+    assert!(vm.value_stack.is_empty());
+
+    // Off by default: the bad jump just ends the run early
+    let mut vm = VM::new();
+    vm.current_bytecode = main;
+    assert!(vm.run().is_ok());
+    assert_eq!(vm.value_stack, vec![Value::Integer(1)]);
+}
@@ -125,6 +125,25 @@ fn test_vm_conditional_jump_true() {
     assert_eq!(vm.value_stack[0], Value::Integer(10));
 }
 
+#[test]
+fn test_vm_jump_if_true() {
+    for (condition, expected) in [(true, 20), (false, 10)] {
+        let mut vm = VM::new();
+        vm.current_bytecode = vec![
+            Instruction::Push(Value::Boolean(condition)),
+            Instruction::JmpIfTrue(4),
+            Instruction::Push(Value::Integer(10)),
+            Instruction::Jmp(5),
+            Instruction::Push(Value::Integer(20)),
+            Instruction::Halt,
+        ];
+
+        vm.run().unwrap();
+
+        assert_eq!(vm.value_stack, vec![Value::Integer(expected)]);
+    }
+}
+
 #[test]
 fn test_vm_conditional_jump_false() {
     let mut vm = VM::new();
@@ -0,0 +1,67 @@
+// Tests for the native zip, unzip and enumerate builtins (no stdlib loaded)
+
+mod common;
+
+use lisp_bytecode_vm::*;
+use common::run_code;
+
+#[test]
+fn test_zip_pairs_elements() {
+    assert_eq!(
+        run_code("(zip '(1 2 3) '(a b c))").unwrap(),
+        run_code("'((1 a) (2 b) (3 c))").unwrap()
+    );
+}
+
+#[test]
+fn test_zip_truncates_to_shorter_list() {
+    assert_eq!(run_code("(zip '(1 2 3) '(a))").unwrap(), run_code("'((1 a))").unwrap());
+    assert_eq!(run_code("(zip '(1) '(a b c))").unwrap(), run_code("'((1 a))").unwrap());
+}
+
+#[test]
+fn test_zip_empty() {
+    assert_eq!(run_code("(zip '() '(1 2))").unwrap(), Value::List(List::Nil));
+    assert_eq!(run_code("(zip '() '())").unwrap(), Value::List(List::Nil));
+}
+
+#[test]
+fn test_unzip_splits_pairs() {
+    assert_eq!(
+        run_code("(unzip '((1 a) (2 b) (3 c)))").unwrap(),
+        run_code("'((1 2 3) (a b c))").unwrap()
+    );
+    assert_eq!(run_code("(unzip '())").unwrap(), run_code("'(() ())").unwrap());
+}
+
+#[test]
+fn test_unzip_inverts_zip() {
+    assert_eq!(
+        run_code("(unzip (zip '(1 2) '(3 4)))").unwrap(),
+        run_code("'((1 2) (3 4))").unwrap()
+    );
+}
+
+#[test]
+fn test_enumerate_counts_from_zero() {
+    assert_eq!(
+        run_code("(enumerate '(a b c))").unwrap(),
+        run_code("'((0 a) (1 b) (2 c))").unwrap()
+    );
+    assert_eq!(run_code("(enumerate '())").unwrap(), Value::List(List::Nil));
+}
+
+#[test]
+fn test_list_helper_errors() {
+    let err = run_code("(zip '(1) 2)").unwrap_err();
+    assert!(err.contains("'zip' expects a list, got integer"), "{}", err);
+
+    let err = run_code("(unzip '((1 2) 3))").unwrap_err();
+    assert!(err.contains("'unzip' expects a list of two-element lists"), "{}", err);
+
+    let err = run_code("(unzip '((1 2 3)))").unwrap_err();
+    assert!(err.contains("'unzip' expects a list of two-element lists"), "{}", err);
+
+    let err = run_code("(enumerate 5)").unwrap_err();
+    assert!(err.contains("'enumerate' expects a list, got integer"), "{}", err);
+}